@@ -15,3 +15,143 @@ pub struct AuthWrapper<T> {
     /// The actual response data.
     pub response: T,
 }
+
+/// The minimum number of characters in a password.
+pub const MIN_PASSWORD_LEN: usize = 8;
+/// The maximum number of characters in a password.
+pub const MAX_PASSWORD_LEN: usize = 50;
+
+/// A requirement for a strong password.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub enum PasswordCriterion {
+    /// Between [`MIN_PASSWORD_LEN`] and [`MAX_PASSWORD_LEN`] characters.
+    Length,
+    /// At least one digit (0-9).
+    Digit,
+    /// At least one symbol.
+    Symbol,
+    /// At least one lowercase letter (a-z).
+    Lowercase,
+    /// At least one uppercase letter (A-Z).
+    Uppercase,
+}
+impl PasswordCriterion {
+    /// All of the criteria, in the order they should be displayed.
+    pub const ALL: [PasswordCriterion; 5] = [
+        PasswordCriterion::Length,
+        PasswordCriterion::Digit,
+        PasswordCriterion::Symbol,
+        PasswordCriterion::Lowercase,
+        PasswordCriterion::Uppercase,
+    ];
+
+    /// Gets a description of the criterion.
+    pub fn message(self) -> &'static str {
+        match self {
+            PasswordCriterion::Length => "Between 8 and 50 characters",
+            PasswordCriterion::Digit => "At least one number",
+            PasswordCriterion::Symbol => "At least one symbol",
+            PasswordCriterion::Lowercase => "At least one lowercase letter",
+            PasswordCriterion::Uppercase => "At least one uppercase letter",
+        }
+    }
+}
+
+/// Feedback on the strength of a password, listing the criteria
+/// that it fails to meet.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct PasswordStrength {
+    failed: Vec<PasswordCriterion>,
+}
+impl PasswordStrength {
+    /// Checks a password against each [`PasswordCriterion`].
+    pub fn new(password: &str) -> Self {
+        let mut upper = 0;
+        let mut lower = 0;
+        let mut numbers = 0;
+        let mut symbols = 0;
+        let mut len = 0;
+
+        for ch in password.chars() {
+            match ch {
+                'a'..='z' => lower += 1,
+                'A'..='Z' => upper += 1,
+                '0'..='9' => numbers += 1,
+                _ => symbols += 1,
+            }
+            len += 1;
+        }
+
+        let failed = PasswordCriterion::ALL
+            .into_iter()
+            .filter(|criterion| match criterion {
+                PasswordCriterion::Length => !(MIN_PASSWORD_LEN..=MAX_PASSWORD_LEN).contains(&len),
+                PasswordCriterion::Digit => numbers == 0,
+                PasswordCriterion::Symbol => symbols == 0,
+                PasswordCriterion::Lowercase => lower == 0,
+                PasswordCriterion::Uppercase => upper == 0,
+            })
+            .collect();
+
+        Self { failed }
+    }
+    /// Checks whether the password meets every criterion.
+    pub fn is_strong(&self) -> bool {
+        self.failed.is_empty()
+    }
+    /// Gets the number of criteria that the password meets.
+    pub fn score(&self) -> usize {
+        PasswordCriterion::ALL.len() - self.failed.len()
+    }
+    /// Checks whether the password meets a criterion.
+    pub fn meets(&self, criterion: PasswordCriterion) -> bool {
+        !self.failed.contains(&criterion)
+    }
+    /// Gets the criteria that the password does not meet.
+    pub fn failed(&self) -> &[PasswordCriterion] {
+        &self.failed
+    }
+    /// Gets a message for each criterion that the password does not meet.
+    pub fn messages(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.failed.iter().map(|criterion| criterion.message())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strong_password() {
+        let strength = PasswordStrength::new("Password1!");
+
+        assert!(strength.is_strong());
+        assert_eq!(strength.score(), 5);
+        assert_eq!(strength.messages().count(), 0);
+    }
+
+    #[test]
+    fn failed_criteria() {
+        let strength = PasswordStrength::new("pass");
+
+        assert!(!strength.is_strong());
+        assert_eq!(strength.score(), 1);
+        assert_eq!(
+            strength.failed(),
+            [
+                PasswordCriterion::Length,
+                PasswordCriterion::Digit,
+                PasswordCriterion::Symbol,
+                PasswordCriterion::Uppercase,
+            ]
+        );
+        assert!(strength.meets(PasswordCriterion::Lowercase));
+    }
+
+    #[test]
+    fn length() {
+        assert!(!PasswordStrength::new("Pa1!").meets(PasswordCriterion::Length));
+        assert!(PasswordStrength::new("Pa1!Pa1!").meets(PasswordCriterion::Length));
+        assert!(!PasswordStrength::new(&"Pa1!".repeat(13)).meets(PasswordCriterion::Length));
+    }
+}
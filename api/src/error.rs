@@ -7,6 +7,85 @@ use serde::{Deserialize, Serialize};
 pub struct ErrorResponse {
     /// The http status.
     pub status: String,
-    /// The reason for the error.
-    pub msg: String,
+    /// The reason for the error. Clients map this key to
+    /// a localized message.
+    pub key: ErrorKey,
+    /// The fields of the request that failed validation, if any. When
+    /// there are invalid fields, `key` is the reason for the first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldError>,
+}
+
+/// A field of a request body that is validated by the server.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Field {
+    /// The username of an account.
+    Username,
+    /// The email address of an account.
+    Email,
+    /// The password of an account.
+    Password,
+}
+
+/// The reason that a single field failed validation.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldError {
+    /// The invalid field.
+    pub field: Field,
+    /// The reason that the field is invalid.
+    pub key: ErrorKey,
+}
+
+/// Stable identifier for the reason that a request failed.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKey {
+    /// The request was malformed.
+    BadRequest,
+    /// The request body could not be deserialized.
+    InvalidRequestBody,
+    /// The request body was too large.
+    PayloadTooLarge,
+    /// The requested resource does not exist.
+    NotFound,
+    /// The auth header was not provided.
+    MissingAuthHeader,
+    /// The auth header was invalid.
+    InvalidAuthHeader,
+    /// The username or email is already in use.
+    UsernameOrEmailExists,
+    /// The username is invalid.
+    InvalidUsername,
+    /// The password is too weak.
+    InvalidPassword,
+    /// The email is invalid.
+    InvalidEmail,
+    /// The board theme does not exist.
+    InvalidTheme,
+    /// A recent request was made to reset the password.
+    ResetTimeout,
+    /// The request to reset the password has expired.
+    ResetExpired,
+    /// The request to change the email address has expired.
+    VerifyExpired,
+    /// The username or password was incorrect.
+    IncorrectCredentials,
+    /// Too many failed logins have been made, so the account is
+    /// temporarily locked.
+    AccountLocked,
+    /// The user is not authorized to make the request.
+    Unauthorized,
+    /// Must be between 2 and 4 players per game.
+    IllegalPlayerCount,
+    /// The same user was added to a game more than once.
+    DuplicatePlayer,
+    /// A play was made when it was another player's turn.
+    NotYourTurn,
+    /// The play was not legal.
+    IllegalPlay,
+    /// The daily puzzle has already been attempted.
+    PuzzleAttempted,
+    /// An error occured on the server.
+    InternalServerError,
 }
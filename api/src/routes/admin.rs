@@ -0,0 +1,39 @@
+//! API types for /admin.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of events that are stored for each live game.
+pub const MAX_GAME_EVENTS: usize = 1000;
+
+/// The source of a [`GameEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EventSource {
+    /// A message received from the user with the id.
+    Client(i32),
+    /// A message sent to every player by the server.
+    Server,
+    /// A message sent by the server to the user with the id only.
+    ToClient(i32),
+}
+
+/// A message received or sent by a live game.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameEvent {
+    /// The time that the message was received or sent.
+    pub time: DateTime<Utc>,
+    /// Where the message came from.
+    pub source: EventSource,
+    /// The debug representation of the message.
+    pub message: String,
+}
+
+/// Response for GET /api/admin/games/{id_game}/events.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameEventsResponse {
+    /// The most recent events, oldest first.
+    pub events: Vec<GameEvent>,
+    /// The number of older events that were dropped once the log
+    /// reached [`MAX_GAME_EVENTS`].
+    pub dropped_count: usize,
+}
@@ -1,7 +1,7 @@
 //! API types for /friends.
 
-use serde::{Deserialize, Serialize};
 use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
 
 /// A list of friends for a user.
 #[derive(Debug, Serialize, Deserialize)]
@@ -2,9 +2,9 @@
 
 use std::collections::HashMap;
 
-use crate::routes::leaderboard::LeaderboardRow;
-use chrono::NaiveDateTime;
-use scrabble::game::play::Play;
+use crate::routes::{leaderboard::LeaderboardRow, live::Player};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use scrabble::game::{play::Play, tile::Tile};
 use serde::{Deserialize, Serialize};
 
 /// Response from the list games route.
@@ -61,9 +61,56 @@ pub struct GameStatsResponse {
     pub is_win: bool,
 }
 
+/// Request to create a correspondence game, which is played through
+/// REST requests rather than a live websocket connection.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateGameRequest {
+    /// The usernames of the other players, who must be friends of the
+    /// user creating the game.
+    pub usernames: Vec<String>,
+}
+
+/// The state of a correspondence game, from the perspective of a player.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GameStateResponse {
+    /// The id of the game.
+    pub id_game: i32,
+    /// The tile positions.
+    pub tiles: Vec<Option<Tile>>,
+    /// Your rack tiles.
+    pub rack: Vec<Tile>,
+    /// The current scores (or the final scores if the game is over).
+    pub scores: Vec<(Player, usize)>,
+    /// The next player (None if the game is over).
+    pub next: Option<Player>,
+    /// The number of tiles remaining in the bag.
+    pub letter_bag_len: usize,
+    /// The time by which the next player must make a play. If the
+    /// deadline passes, the player passes their turn. (None if the
+    /// game is over).
+    pub deadline: Option<DateTime<Utc>>,
+}
+
 /// A single leaderboard row for the user.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct OverallStatsResponse {
     /// The row of the leaderboard.
     pub row: LeaderboardRow,
 }
+
+/// A shareable encoding of a finished game, see
+/// [`GameRecord`](scrabble::game::record::GameRecord).
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GameRecordResponse {
+    /// The encoded game. The scores are the totals of each player's
+    /// placements, without the adjustments made at the end of the game.
+    pub record: String,
+}
+
+/// The tiles on the board of a live or finished game, in the same order
+/// as [`Board`](scrabble::game::board::Board) positions.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GameBoardResponse {
+    /// The tile at each position.
+    pub tiles: Vec<Option<Tile>>,
+}
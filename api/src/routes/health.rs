@@ -0,0 +1,18 @@
+//! API types for /health and /ready.
+
+use serde::{Deserialize, Serialize};
+
+/// Response for GET /api/ready.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReadyResponse {
+    /// Whether the database responded to a query.
+    pub database: bool,
+    /// Whether the word lists are loaded.
+    pub word_list: bool,
+}
+impl ReadyResponse {
+    /// Checks whether the server is ready to handle requests.
+    pub fn is_ready(&self) -> bool {
+        self.database && self.word_list
+    }
+}
@@ -31,3 +31,26 @@ pub struct LeaderboardRow {
     /// Percent of games that the user won.
     pub win_percentage: f32,
 }
+
+/// Response from the puzzle leaderboard route.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PuzzleLeaderboardResponse {
+    /// The rows from the route.
+    pub rows: Vec<PuzzleLeaderboardRow>,
+}
+
+/// One row of the puzzle leaderboard.
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct PuzzleLeaderboardRow {
+    /// The username for the entry.
+    pub username: String,
+    /// The number of daily puzzles attempted.
+    pub puzzle_count: usize,
+    /// Average percentage of the highest score achieved.
+    pub avg_percentage: f32,
+    /// The number of consecutive days up to today (or yesterday) that
+    /// a puzzle was attempted.
+    pub current_streak: usize,
+    /// The most consecutive days that a puzzle was attempted.
+    pub best_streak: usize,
+}
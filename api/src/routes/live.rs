@@ -3,21 +3,41 @@
 use std::collections::HashMap;
 
 use crate::auth::Token;
+use chrono::{DateTime, Utc};
 use scrabble::{
+    ai::AiDifficulty,
     error::GameError,
-    game::{play::Play, tile::Tile, GameOverReason},
+    game::{challenge::ChallengeRule, letter_bag::BagSeed, play::Play, tile::Tile, GameOverReason},
+    util::pos::Pos,
 };
 use serde::{Deserialize, Serialize};
 
-/// The difficulty of an AI player.
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
-pub enum AiDifficulty {
-    /// Easy AI.
-    Easy,
-    /// Medium AI.
-    Medium,
-    /// Hard AI.
-    Hard,
+/// The shortest time limit (in seconds) that can be set for a move.
+pub const MIN_MOVE_TIMEOUT: u64 = 15;
+/// The longest time limit (in seconds) that can be set for a move.
+pub const MAX_MOVE_TIMEOUT: u64 = 600;
+/// The time (in seconds) after a placement during which it can be
+/// challenged.
+pub const CHALLENGE_WINDOW: u64 = 30;
+/// The version of the live protocol, sent in [`ClientMsg::Auth`]. Must be
+/// incremented whenever [`ClientMsg`] or [`ServerMsg`] change shape, since
+/// messages are serialized with bincode.
+pub const PROTOCOL_VERSION: u32 = 6;
+/// The number of recent messages stored for each player, which can be resent
+/// when they rejoin with [`ClientMsg::Rejoin`].
+pub const REPLAY_LIMIT: usize = 64;
+
+/// The words that the AI players in a game can play. Plays from human
+/// players are always checked against the full dictionary.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AiVocabulary {
+    /// The words are chosen by the difficulty of each AI player.
+    #[default]
+    Difficulty,
+    /// Any word in the dictionary.
+    Full,
+    /// Only common words.
+    Common,
 }
 
 /// Messages sent from the client.
@@ -36,6 +56,21 @@ pub enum ClientMsg {
         /// Whether the game is closed to friends of the user
         /// that starts the game.
         friends_only: bool,
+        /// The time limit (in seconds) for each move. Must be between
+        /// [`MIN_MOVE_TIMEOUT`] and [`MAX_MOVE_TIMEOUT`].
+        move_timeout: u64,
+        /// Whether all players can see each other's racks, for teaching.
+        /// Only allowed when `friends_only` is set.
+        open_hands: bool,
+        /// Whether words are checked when played, or when challenged.
+        challenge_rule: ChallengeRule,
+        /// Whether players can exchange tiles with [`Play::Redraw`].
+        allow_exchange: bool,
+        /// Whether the letter bag is seeded, so that players can verify
+        /// the draws once the seed is revealed in [`ServerMsg::Over`].
+        verifiable_bag: bool,
+        /// The words that the AI players can play.
+        ai_vocabulary: AiVocabulary,
     },
     /// Request to join a game.
     Join(i32),
@@ -43,8 +78,30 @@ pub enum ClientMsg {
     Chat(String),
     /// A play message.
     Play(Play),
-    /// The first message sent, authenticates the user.
-    Auth(Token),
+    /// Challenges the most recent placement. Only the player to play
+    /// can challenge, within [`CHALLENGE_WINDOW`] of the placement.
+    Challenge,
+    /// Request the full game state, sent when the client detects that
+    /// its board differs from the server.
+    Resync,
+    /// The first message sent, authenticates the user. Rejected with
+    /// [`LiveError::VersionMismatch`] if the `version` is not the
+    /// server's [`PROTOCOL_VERSION`].
+    Auth {
+        /// The [`PROTOCOL_VERSION`] of the client.
+        version: u32,
+        /// The auth token of the user.
+        token: Token,
+    },
+    /// Request to rejoin a game after the connection dropped. The messages
+    /// after `last_seq` are resent, or a [`ServerMsg::FullState`] if more
+    /// than [`REPLAY_LIMIT`] messages were missed.
+    Rejoin {
+        /// Id of the game.
+        id_game: i32,
+        /// The sequence number of the last message received.
+        last_seq: u64,
+    },
 }
 
 /// Messages sent from the server.
@@ -56,7 +113,8 @@ pub enum ServerMsg {
         player: Player,
         /// The previous board tiles.
         prev_tiles: Vec<Option<Tile>>,
-        /// The play that was made.
+        /// The play that was made. The tiles of a redraw are hidden
+        /// (sent as blanks), so only the number of tiles is known.
         play: Play,
         /// The number of tiles remaining in the bag.
         letter_bag_len: usize,
@@ -64,6 +122,38 @@ pub enum ServerMsg {
         next: Option<Player>,
         /// The current scores.
         scores: HashMap<Player, usize>,
+        /// The number of consecutive passes made by the player.
+        pass_count: usize,
+        /// The number of consecutive passes that ends the game.
+        pass_limit: usize,
+        /// The time by which the next player must make a play. (None
+        /// if the next player is not timed).
+        deadline: Option<DateTime<Utc>>,
+        /// The [`board_hash`] of the tiles after the play.
+        board_hash: u64,
+    },
+    /// The full state of the game, sent in response to [`ClientMsg::Resync`].
+    FullState {
+        /// The tile positions.
+        tiles: Vec<Option<Tile>>,
+        /// Your rack tiles.
+        rack: Vec<Tile>,
+        /// The current scores.
+        scores: HashMap<Player, usize>,
+        /// The next player (None if the game is over).
+        next: Option<Player>,
+        /// The number of tiles remaining in the bag.
+        letter_bag_len: usize,
+        /// The time by which the next player must make a play. (None
+        /// if the next player is not timed).
+        deadline: Option<DateTime<Utc>>,
+        /// The players that have joined, in the order that they play.
+        players: Vec<Player>,
+        /// The positions of the most recent placement, if it can still
+        /// be challenged.
+        last_play: Vec<Pos>,
+        /// Whether you can challenge the most recent placement.
+        can_challenge: bool,
     },
     /// The user has joined a game.
     Joined {
@@ -83,11 +173,45 @@ pub enum ServerMsg {
         next: Option<Player>,
         /// The number of tiles remaining in the bag.
         letter_bag_len: usize,
+        /// The time limit (in seconds) for each move.
+        move_timeout: u64,
+        /// The time by which the next player must make a play. (None
+        /// if the next player is not timed).
+        deadline: Option<DateTime<Utc>>,
+        /// Whether words are checked when played, or when challenged.
+        challenge_rule: ChallengeRule,
+        /// Whether players can exchange tiles with [`Play::Redraw`].
+        allow_exchange: bool,
+    },
+    /// The most recent placement has been challenged. Followed by a
+    /// [`ServerMsg::FullState`], since the placement may have been removed.
+    ChallengeResult {
+        /// The player that made the challenge.
+        challenger: Player,
+        /// The player that made the challenged placement.
+        player: Player,
+        /// The invalid words formed by the placement. If there are any, the
+        /// placement was removed and counts as a pass.
+        invalid_words: Vec<String>,
+        /// Whether the challenger lost their turn.
+        lost_turn: bool,
+    },
+    /// The game has ended.
+    Over {
+        /// The reason that the game ended.
+        reason: GameOverReason,
+        /// The breakdown of the final score for each player.
+        final_scores: HashMap<Player, FinalScore>,
+        /// The seed of the letter bag and the nonce of its commitment, if
+        /// the game has a verifiable bag.
+        seed: Option<BagSeed>,
     },
-    /// Contains the reason that the game ended.
-    Over(GameOverReason),
     /// All users have connected. The game can start.
-    Starting,
+    Starting {
+        /// The [`commitment`](BagSeed::commitment) to the seed of the
+        /// letter bag, if the game has a verifiable bag.
+        seed_commitment: Option<String>,
+    },
     /// A user has connected to the game.
     UserConnected(Player),
     /// A user has disconnected from the game.
@@ -100,10 +224,56 @@ pub enum ServerMsg {
     Chat(Player, String),
     /// The player's rack has updated.
     Rack(Vec<Tile>),
+    /// The racks of every player, only sent in an open hands game.
+    AllRacks(HashMap<Player, Vec<Tile>>),
     /// An error occured.
     Error(LiveError),
 }
 
+/// A [`ServerMsg`] along with its sequence number. The messages sent to each
+/// player in a game are numbered from 1, so that a client that rejoins is only
+/// sent the messages that it missed. Messages that are not sent by a game
+/// have a sequence number of 0.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SequencedMsg {
+    /// The sequence number of the message.
+    pub seq: u64,
+    /// The message.
+    pub msg: ServerMsg,
+}
+impl From<ServerMsg> for SequencedMsg {
+    fn from(msg: ServerMsg) -> Self {
+        Self { seq: 0, msg }
+    }
+}
+
+impl ServerMsg {
+    /// Checks whether the message contains a player's rack, in which case
+    /// it must only be sent to that player.
+    pub fn contains_rack(&self) -> bool {
+        matches!(
+            self,
+            ServerMsg::Joined { .. }
+                | ServerMsg::FullState { .. }
+                | ServerMsg::Rack(_)
+                | ServerMsg::AllRacks(_)
+        )
+    }
+}
+
+/// The breakdown of a player's final score.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct FinalScore {
+    /// The score before adjusting for the remaining tiles.
+    pub running_score: usize,
+    /// The value subtracted for the tiles remaining on the player's rack.
+    pub penalty: usize,
+    /// The value added for emptying the rack.
+    pub bonus: usize,
+    /// The final score.
+    pub score: usize,
+}
+
 /// A member of a game.
 #[derive(Debug, Serialize, Deserialize, Clone, Hash, PartialEq, Eq)]
 pub struct Player {
@@ -111,6 +281,18 @@ pub struct Player {
     pub id_player: i32,
     /// The username (or AI difficulty) of the player.
     pub username: String,
+    /// Whether the player is connected to the game. AI players and
+    /// players in correspondence games are always connected.
+    pub is_connected: bool,
+    /// The difficulty of the player if it is an AI, or `None` for users.
+    pub difficulty: Option<AiDifficulty>,
+}
+
+impl Player {
+    /// Whether the player is an AI.
+    pub fn is_ai(&self) -> bool {
+        self.difficulty.is_some()
+    }
 }
 
 /// Error from the server.
@@ -124,8 +306,68 @@ pub enum LiveError {
     ZeroPlayers,
     /// Must be between 2 and 4 players per game.
     IllegalPlayerCount,
+    /// The move timeout was outside of the allowed range.
+    IllegalMoveTimeout,
+    /// Open hands were requested for a game that is not friends only.
+    IllegalOpenHands,
+    /// There is no placement that can still be challenged.
+    ChallengeExpired,
     /// Failed to join a game.
     FailedToJoin,
+    /// The user has created the maximum number of games.
+    TooManyGames,
     /// The Auth token provided was invalid or expired.
     InvalidToken,
+    /// The client uses a different [`PROTOCOL_VERSION`] to the server.
+    VersionMismatch,
+}
+
+/// Calculates a checksum of the board tiles (using FNV-1a), so that the
+/// client can detect when its board differs from the server.
+pub fn board_hash(tiles: &[Option<Tile>]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    tiles
+        .iter()
+        .map(|tile| match tile {
+            None => 0,
+            Some(Tile::Letter(letter)) => 1 + usize::from(*letter) as u8,
+            Some(Tile::Blank(Some(letter))) => 27 + usize::from(*letter) as u8,
+            Some(Tile::Blank(None)) => 53,
+        })
+        .fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scrabble::game::tile::Letter;
+
+    #[test]
+    fn board_hash_detects_changes() {
+        let a = Letter::new('A').unwrap();
+        let mut tiles = vec![None; 225];
+        let empty = board_hash(&tiles);
+
+        tiles[112] = Some(Tile::Letter(a));
+        let letter = board_hash(&tiles);
+        tiles[112] = Some(Tile::Blank(Some(a)));
+        let blank = board_hash(&tiles);
+        tiles[112] = None;
+        tiles[113] = Some(Tile::Letter(a));
+        let moved = board_hash(&tiles);
+
+        let hashes = [empty, letter, blank, moved];
+        for (i, x) in hashes.iter().enumerate() {
+            for y in &hashes[i + 1..] {
+                assert_ne!(x, y);
+            }
+        }
+
+        // the hash only depends on the tiles.
+        assert_eq!(moved, board_hash(&tiles.clone()));
+    }
 }
@@ -1,7 +1,13 @@
 //! Module containing API types for each route.
 
+pub mod admin;
 pub mod friends;
 pub mod games;
+pub mod health;
 pub mod leaderboard;
 pub mod live;
+pub mod puzzles;
+pub mod themes;
+pub mod tiles;
 pub mod users;
+pub mod words;
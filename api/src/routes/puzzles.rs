@@ -0,0 +1,76 @@
+//! API types for /puzzles.
+
+use chrono::NaiveDate;
+use scrabble::{game::tile::Tile, util::pos::Pos};
+use serde::{Deserialize, Serialize};
+
+/// A user's attempt at a daily puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PuzzleAttempt {
+    /// The score of the user's placement.
+    pub score: usize,
+    /// The score of the highest scoring placement.
+    pub best_score: usize,
+    /// The percentage of the highest score that the user achieved.
+    pub percentage: usize,
+}
+impl PuzzleAttempt {
+    /// Creates an attempt from the score of a placement and the score of
+    /// the highest scoring placement.
+    pub fn new(score: usize, best_score: usize) -> Self {
+        let percentage = match best_score {
+            0 => 100,
+            _ => (100 * score / best_score).min(100),
+        };
+
+        Self {
+            score,
+            best_score,
+            percentage,
+        }
+    }
+}
+
+/// Response for GET /api/puzzles/daily.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyPuzzleResponse {
+    /// The date of the puzzle, which is the same for every user.
+    pub date: NaiveDate,
+    /// The board tiles.
+    pub tiles: Vec<Option<Tile>>,
+    /// The rack that the placement is made from.
+    pub rack: Vec<Tile>,
+    /// The user's attempt, if they have already made one.
+    pub attempt: Option<PuzzleAttempt>,
+}
+
+/// Request for POST /api/puzzles/daily.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PuzzleAttemptRequest {
+    /// The date of the puzzle that the placement was made for.
+    pub date: NaiveDate,
+    /// The tiles that were placed.
+    pub tile_positions: Vec<(Pos, Tile)>,
+}
+
+/// Response for POST /api/puzzles/daily.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PuzzleAttemptResponse {
+    /// The result of the attempt.
+    pub attempt: PuzzleAttempt,
+    /// The tiles of the highest scoring placement.
+    pub best_play: Vec<(Pos, Tile)>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentage() {
+        assert_eq!(100, PuzzleAttempt::new(24, 24).percentage);
+        assert_eq!(50, PuzzleAttempt::new(12, 24).percentage);
+        assert_eq!(33, PuzzleAttempt::new(8, 24).percentage);
+        assert_eq!(100, PuzzleAttempt::new(0, 0).percentage);
+    }
+}
@@ -0,0 +1,36 @@
+//! API types for /themes.
+
+use serde::{Deserialize, Serialize};
+
+/// The names of the board themes that are styled by the client.
+pub const BUILTIN_THEMES: [&str; 3] = ["classic", "dark", "high-contrast"];
+
+/// The board theme used when the user has not chosen one.
+pub const DEFAULT_THEME: &str = "classic";
+
+/// The colors of a custom board theme, which is served by the server
+/// rather than styled by the client. Each color is a CSS color.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Palette {
+    /// The name of the theme, which is stored as the user's preference.
+    pub name: String,
+    /// The color of squares without a premium.
+    pub square: String,
+    /// The color of the start square.
+    pub start: String,
+    /// The color of double letter squares.
+    pub double_letter: String,
+    /// The color of triple letter squares.
+    pub triple_letter: String,
+    /// The color of double word squares.
+    pub double_word: String,
+    /// The color of triple word squares.
+    pub triple_word: String,
+}
+
+/// Response from GET /api/themes.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ThemesResponse {
+    /// The custom board themes.
+    pub palettes: Vec<Palette>,
+}
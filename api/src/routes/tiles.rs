@@ -0,0 +1,89 @@
+//! API types for /tiles.
+
+use scrabble::game::{letter_bag::LetterBag, tile::Tile};
+use serde::{Deserialize, Serialize};
+
+/// The number of copies of a tile in the letter bag, and its score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TileCount {
+    /// The tile (blanks have no letter).
+    pub tile: Tile,
+    /// The number of copies of the tile.
+    pub count: usize,
+    /// The score of the tile.
+    pub score: usize,
+}
+
+/// Response for GET /api/tiles, the tiles in the letter bag at the
+/// start of a game.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TilesResponse {
+    /// The count and score of each tile, including blanks.
+    pub tiles: Vec<TileCount>,
+    /// The number of blank tiles.
+    pub blank_count: usize,
+}
+impl From<&LetterBag> for TilesResponse {
+    fn from(letter_bag: &LetterBag) -> Self {
+        let counts = letter_bag.clone().counts();
+
+        Self {
+            tiles: Tile::iter()
+                .map(|tile| TileCount {
+                    tile,
+                    count: counts.count(tile),
+                    score: tile.score(),
+                })
+                .collect(),
+            blank_count: counts.count(Tile::blank()),
+        }
+    }
+}
+impl TilesResponse {
+    /// Gets the total number of tiles.
+    pub fn total(&self) -> usize {
+        self.tiles.iter().map(|tile_count| tile_count.count).sum()
+    }
+    /// Finds the tiles that have not been `seen` (such as the tiles on the
+    /// board and in the player's rack), which are either in the bag or on
+    /// another player's rack. A blank with a letter is counted as a blank.
+    pub fn unseen(&self, seen: impl IntoIterator<Item = Tile>) -> Vec<TileCount> {
+        let mut tiles = self.tiles.clone();
+
+        for tile in seen {
+            let tile = match tile.is_blank() {
+                true => Tile::blank(),
+                false => tile,
+            };
+
+            if let Some(tile_count) = tiles.iter_mut().find(|t| t.tile == tile) {
+                tile_count.count = tile_count.count.saturating_sub(1);
+            }
+        }
+
+        tiles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unseen_tiles() {
+        let response = TilesResponse::from(&LetterBag::default());
+        assert_eq!(100, response.total());
+        assert_eq!(2, response.blank_count);
+
+        let z = Tile::from('z');
+        let blank_a = Tile::Blank(Some(scrabble::game::tile::Letter::new('a').unwrap()));
+        let unseen = response.unseen([z, blank_a, Tile::blank()]);
+        let count = |tile| unseen.iter().find(|t| t.tile == tile).unwrap().count;
+
+        // blanks with a letter are counted as blanks, not as the letter.
+        assert_eq!(0, count(z));
+        assert_eq!(0, count(Tile::blank()));
+        assert_eq!(9, count(Tile::from('a')));
+        assert_eq!(97, unseen.iter().map(|t| t.count).sum::<usize>());
+    }
+}
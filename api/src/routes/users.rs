@@ -11,6 +11,12 @@ pub struct UserDetails {
     pub email: String,
     /// Whether the account is private.
     pub is_private: bool,
+    /// Whether to send an email when it is the user's turn in a
+    /// correspondence game.
+    pub turn_emails: bool,
+    /// The name of the board theme.
+    #[serde(default)]
+    pub theme: String,
 }
 
 /// Data about a user that can be publically accessed.
@@ -40,6 +46,16 @@ pub struct ResetWithSecret {
     pub username: String,
 }
 
+/// Request sent to confirm a new email address, after the secret
+/// has been recieved at that address.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VerifyEmail {
+    /// Random hexadecimal secret.
+    pub secret_hex: String,
+    /// The username for the account.
+    pub username: String,
+}
+
 /// Request sent to the login endpoint.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Login {
@@ -47,6 +63,9 @@ pub struct Login {
     pub username: String,
     /// Password (plaintext).
     pub password: String,
+    /// Whether to issue a longer lived token.
+    #[serde(default)]
+    pub remember: bool,
 }
 
 /// Request sent to the signup endpoint. All fields are validated
@@ -68,7 +87,8 @@ pub struct SignUp {
 pub struct UpdateAccount {
     /// The previous password.
     pub old_password: String,
-    /// Optional new email address.
+    /// Optional new email address. The address is only changed once
+    /// the link sent to it has been followed.
     pub email: Option<String>,
     /// Optional new username.
     pub username: Option<String>,
@@ -76,6 +96,10 @@ pub struct UpdateAccount {
     pub password: Option<String>,
     /// Optionally specify Whether to make the account private.
     pub is_private: Option<bool>,
+    /// Optionally specify whether to receive emails for correspondence game turns.
+    pub turn_emails: Option<bool>,
+    /// Optional new board theme.
+    pub theme: Option<String>,
 }
 
 /// Request to delete an account.
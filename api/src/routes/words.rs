@@ -0,0 +1,23 @@
+//! API types for /words.
+
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of words that can be validated in one request.
+pub const MAX_VALIDATE_WORDS: usize = 500;
+
+/// Request for POST /api/words/validate.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidateWordsRequest {
+    /// The words to check, at most [`MAX_VALIDATE_WORDS`].
+    pub words: Vec<String>,
+}
+
+/// Response for POST /api/words/validate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidateWordsResponse {
+    /// The words that are in the server's word list.
+    pub accepted: Vec<String>,
+    /// The words that are not in the server's word list, or contain
+    /// characters other than the letters `a-z`.
+    pub rejected: Vec<String>,
+}
@@ -5,7 +5,10 @@ use sycamore_router::{HistoryIntegration, Route, Router};
 
 use crate::{
     components::{Footer, Navbar},
-    context::{provide_auth_context, use_logged_in},
+    context::{
+        provide_auth_context, provide_fsm_context, provide_lang_context, provide_palettes_context,
+        use_logged_in,
+    },
     pages::*,
 };
 
@@ -35,15 +38,29 @@ pub enum Routes {
         /// Random secret from the server.
         secret: String,
     },
+    /// Verify a new email address from email link.
+    #[to("/users/verify-email/<username>/<secret>")]
+    VerifyEmail {
+        /// User that changed their email.
+        username: String,
+        /// Random secret from the server.
+        secret: String,
+    },
     /// Create or join a live game, requires login.
     #[to("/live")]
     Live,
+    /// The daily puzzle, requires login.
+    #[to("/puzzle")]
+    Puzzle,
     /// Leaderboard page.
     #[to("/leaderboard")]
     Leaderboard,
     /// Friends leaderboard page.
     #[to("/leaderboard/friends")]
     FriendsLeaderboard,
+    /// Daily puzzle leaderboard page.
+    #[to("/leaderboard/puzzles")]
+    PuzzleLeaderboard,
     /// Game list page.
     #[to("/games")]
     GameList,
@@ -66,6 +83,12 @@ pub enum Routes {
 pub fn App<G: Html>(cx: Scope) -> View<G> {
     // Allow all components and pages to access the auth data.
     provide_auth_context(cx);
+    // Allow all components and pages to access the language preference.
+    provide_lang_context(cx);
+    // Load the word list for client-side move generation and validation.
+    provide_fsm_context(cx);
+    // Load the custom board themes.
+    provide_palettes_context(cx);
 
     // Store the navbar expanded state.
     let is_expanded = create_signal(cx, false);
@@ -139,13 +162,23 @@ fn ViewRoute<'a, G: Html>(cx: Scope<'a>, props: ViewRouteProps<'a>) -> View<G> {
                         secret: secret.clone(),
                     }
                 },
+                Routes::VerifyEmail { username, secret } => view! { cx,
+                    VerifyEmailPage {
+                        username: username.clone(),
+                        secret: secret.clone(),
+                    }
+                },
 
                 // Live game pages.
                 Routes::Live if logged_in => view! { cx, LivePage { } },
 
+                // Puzzle pages.
+                Routes::Puzzle if logged_in => view! { cx, PuzzlePage {} },
+
                 // Leaderboard pages.
                 Routes::Leaderboard => view! { cx, LeaderboardPage {} },
                 Routes::FriendsLeaderboard if logged_in => view! { cx, FriendsLeaderboardPage {} },
+                Routes::PuzzleLeaderboard => view! { cx, PuzzleLeaderboardPage {} },
 
                 // Game stats pages.
                 Routes::GameList if logged_in => view! { cx, GameListPage {} },
@@ -1,13 +1,50 @@
 use super::tile::Tile;
+use api::routes::themes::Palette;
 use scrabble::{
-    game::tile,
-    util::pos::{Pos, Premium},
+    game::{board::BonusLayout, tile},
+    util::pos::{Col, Pos, Premium, Row},
 };
 use sycamore::prelude::*;
 
-/// The class used to style squares with a bonus.
-fn square_class(pos: Pos) -> &'static str {
-    match pos.premium() {
+/// The colors of the board.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BoardTheme {
+    /// A theme styled by the stylesheet, containing the theme name.
+    Builtin(&'static str),
+    /// A custom theme from the server.
+    Custom(Palette),
+}
+impl BoardTheme {
+    /// The class of the board, which selects a built in theme.
+    fn class(&self) -> String {
+        match self {
+            BoardTheme::Builtin(name) => format!("board theme-{name}"),
+            BoardTheme::Custom(_) => String::from("board"),
+        }
+    }
+    /// The inline style of the board, which overrides the colors of the
+    /// built in theme for a custom theme.
+    fn style(&self) -> String {
+        match self {
+            BoardTheme::Builtin(_) => String::new(),
+            BoardTheme::Custom(palette) => format!(
+                "--square: {}; --start: {}; --double-letter: {}; --triple-letter: {}; \
+                --double-word: {}; --triple-word: {};",
+                palette.square,
+                palette.start,
+                palette.double_letter,
+                palette.triple_letter,
+                palette.double_word,
+                palette.triple_word,
+            ),
+        }
+    }
+}
+
+/// The class used to style squares with a bonus, and squares
+/// that were part of the last play.
+fn square_class(premium: Option<Premium>, is_last_play: bool) -> String {
+    let class = match premium {
         None => "square",
         Some(premium) => match premium {
             Premium::DoubleLetter => "square double-letter",
@@ -16,6 +53,78 @@ fn square_class(pos: Pos) -> &'static str {
             Premium::TripleWord => "square triple-word",
             Premium::Start => "square start",
         },
+    };
+
+    match is_last_play {
+        true => format!("{class} last-play"),
+        false => String::from(class),
+    }
+}
+
+/// The label shown on an empty square with a bonus.
+fn premium_label(premium: Option<Premium>) -> &'static str {
+    match premium {
+        Some(Premium::Start) => "★",
+        Some(Premium::DoubleLetter) => "2L",
+        Some(Premium::TripleLetter) => "3L",
+        Some(Premium::TripleWord) => "3W",
+        Some(Premium::DoubleWord) => "2W",
+        None => "",
+    }
+}
+
+/// The name of a premium, as read by screen readers.
+fn premium_name(premium: Premium) -> &'static str {
+    match premium {
+        Premium::Start => "start",
+        Premium::DoubleLetter => "double letter",
+        Premium::TripleLetter => "triple letter",
+        Premium::DoubleWord => "double word",
+        Premium::TripleWord => "triple word",
+    }
+}
+
+/// Describes the board row by row for screen readers, listing the
+/// placed tiles and the remaining premium squares in each row that
+/// contains a tile.
+fn describe_board(cells: &[Option<tile::Tile>], bonuses: &BonusLayout) -> String {
+    let mut rows = vec![];
+
+    for row in Row::iter() {
+        let mut letters = vec![];
+        let mut premiums = vec![];
+
+        for col in Col::iter() {
+            let pos = Pos::from((row, col));
+            let coord = pos.to_string();
+
+            match cells[usize::from(pos)] {
+                Some(tile::Tile::Letter(letter)) => letters.push(format!("{coord} {letter}")),
+                Some(tile::Tile::Blank(Some(letter))) => {
+                    letters.push(format!("{coord} {letter} (blank)"))
+                }
+                Some(tile::Tile::Blank(None)) => letters.push(format!("{coord} blank")),
+                None => {
+                    if let Some(premium) = bonuses.bonus_at(pos) {
+                        premiums.push(format!("{coord} {}", premium_name(premium)));
+                    }
+                }
+            }
+        }
+
+        // rows without tiles are skipped to keep the description short.
+        if !letters.is_empty() {
+            let mut description = format!("Row {row}: {}.", letters.join(", "));
+            if !premiums.is_empty() {
+                description.push_str(&format!(" Empty premium squares: {}.", premiums.join(", ")));
+            }
+            rows.push(description);
+        }
+    }
+
+    match rows.is_empty() {
+        true => format!("The board is empty. The start square is {}.", Pos::start()),
+        false => rows.join(" "),
     }
 }
 
@@ -26,6 +135,12 @@ pub struct BoardProps<'a, F> {
     pub on_click: F,
     /// The Option<Tile> array for the board.
     pub cells: &'a ReadSignal<Vec<Option<tile::Tile>>>,
+    /// The premium squares of the board.
+    pub bonuses: &'a BonusLayout,
+    /// The positions of the tiles placed in the last play.
+    pub last_play: &'a ReadSignal<Vec<Pos>>,
+    /// The colors of the board.
+    pub theme: &'a ReadSignal<BoardTheme>,
 }
 
 /// View the scrabble board, providing a single dimensional array containing
@@ -39,12 +154,15 @@ where
     let squares = create_memo(cx, move || {
         let cells = props.cells.get();
         let cells = cells.as_ref();
+        let last_play = props.last_play.get();
 
         View::new_fragment(
             Pos::iter()
                 .zip(cells)
                 .map(|(p, t)| (p, *t))
                 .map(|(pos, tile)| {
+                    let premium = props.bonuses.bonus_at(pos);
+                    let class = square_class(premium, last_play.contains(&pos));
                     let on_click = on_click.clone();
                     let on_click = move |_| {
                         let on_click = on_click.clone();
@@ -52,7 +170,7 @@ where
                     };
 
                     view! { cx,
-                        div(class=square_class(pos), on:click=on_click) {
+                        div(class=class, on:click=on_click) {
                             (match tile {
                                 Some(tile @ tile::Tile::Letter { .. }) => view! { cx,
                                     Tile {
@@ -70,14 +188,7 @@ where
                                 },
                                 _ => view! { cx,
                                     div(class="premium") {
-                                        (match pos.premium() {
-                                            Some(Premium::Start) => "S",
-                                            Some(Premium::DoubleLetter) => "2L",
-                                            Some(Premium::TripleLetter) => "3L",
-                                            Some(Premium::TripleWord) => "3W",
-                                            Some(Premium::DoubleWord) => "2W",
-                                            None => "",
-                                        })
+                                        (premium_label(premium))
                                     }
                                 }
                             })
@@ -88,9 +199,21 @@ where
         )
     });
 
+    // a textual description of the board, updated whenever the tiles change.
+    let description = create_memo(cx, move || {
+        describe_board(&props.cells.get(), props.bonuses)
+    });
+
+    let class = create_memo(cx, || props.theme.get().class());
+    let style = create_memo(cx, || props.theme.get().style());
+
     view! { cx,
-        div(class="board") {
+        div(class=class.get(), style=style.get()) {
             (*squares.get())
         }
+
+        div(class="is-sr-only", aria-live="polite") {
+            (description.get())
+        }
     }
 }
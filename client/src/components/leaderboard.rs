@@ -1,51 +1,110 @@
 //! Module for displaying a leaderboard.
 
 use crate::pages::format_f32;
-use api::routes::leaderboard::LeaderboardRow as ApiLeaderboardRow;
+use api::routes::leaderboard::{LeaderboardRow as ApiLeaderboardRow, PuzzleLeaderboardRow};
 use sycamore::prelude::*;
 
+/// A row type that can be displayed by the [`Leaderboard`] component.
+pub trait LeaderboardColumns: Clone + PartialEq + 'static {
+    /// The (title, abbreviation) of each column.
+    fn headers() -> Vec<(&'static str, &'static str)>;
+    /// The contents of each column for the row.
+    fn cells(&self) -> Vec<String>;
+}
+impl LeaderboardColumns for ApiLeaderboardRow {
+    fn headers() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("Username", "id"),
+            ("Average score", "score/play"),
+            ("Average word length", "wlen/play"),
+            ("Average tiles placed", "tiles/play"),
+            ("Longest word length", "longest"),
+            ("Best score", "best"),
+            ("Average score per game", "score/game"),
+            ("Average score per tile", "score/tile"),
+            ("Win percentage", "w%"),
+        ]
+    }
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.username.clone(),
+            format_f32(self.avg_score_per_play),
+            format_f32(self.avg_word_length),
+            format_f32(self.avg_tiles_per_play),
+            self.longest_word_length.to_string(),
+            self.best_word_score.to_string(),
+            format_f32(self.avg_score_per_game),
+            format_f32(self.avg_score_per_tile),
+            format!("{}%", format_f32(self.win_percentage)),
+        ]
+    }
+}
+impl LeaderboardColumns for PuzzleLeaderboardRow {
+    fn headers() -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("Username", "id"),
+            ("Puzzles attempted", "puzzles"),
+            ("Average percentage of the best score", "avg%"),
+            ("Current streak", "streak"),
+            ("Longest streak", "best streak"),
+        ]
+    }
+    fn cells(&self) -> Vec<String> {
+        vec![
+            self.username.clone(),
+            self.puzzle_count.to_string(),
+            format!("{}%", format_f32(self.avg_percentage)),
+            self.current_streak.to_string(),
+            self.best_streak.to_string(),
+        ]
+    }
+}
+
 /// Props for the leaderboard.
 #[derive(Prop)]
-pub struct Props<'a> {
+pub struct Props<'a, R: LeaderboardColumns> {
     /// The rows of the leaderboard.
-    pub rows: &'a ReadSignal<Vec<ApiLeaderboardRow>>,
+    pub rows: &'a ReadSignal<Vec<R>>,
 }
 
 /// Renders a row of a leaderboard within a table.
 #[component]
-pub fn Leaderboard<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
+pub fn Leaderboard<'a, R: LeaderboardColumns, G: Html>(
+    cx: Scope<'a>,
+    props: Props<'a, R>,
+) -> View<G> {
+    let headers = View::new_fragment(
+        R::headers()
+            .into_iter()
+            .map(|(title, abbr)| view! { cx, th {abbr(title=title) { (abbr) }} })
+            .collect(),
+    );
+
     view! { cx,
         div(class="columns is-centered") {
             table(class="table") {
                 // define the headers of the table.
                 thead {
                     tr {
-                        th {abbr(title="Username") { "id" }}
-                        th {abbr(title="Average score") { "score/play" }}
-                        th {abbr(title="Average word length") { "wlen/play" }}
-                        th {abbr(title="Average tiles placed") { "tiles/play" }}
-                        th {abbr(title="Longest word length") { "longest" }}
-                        th {abbr(title="Best score") { "best" }}
-                        th {abbr(title="Average score per game") { "score/game" }}
-                        th {abbr(title="Average score per tile") { "score/tile" }}
-                        th {abbr(title="Win percentage") { "w%" }}
+                        (headers)
                     }
                 }
                 // define the body of the table.
                 tbody {
                     Indexed {
                         iterable: props.rows,
-                        view: |cx, row| view! { cx,
-                            tr {
-                                td { (row.username) }
-                                td { (format_f32(row.avg_score_per_play)) }
-                                td { (format_f32(row.avg_word_length)) }
-                                td { (format_f32(row.avg_tiles_per_play)) }
-                                td { (row.longest_word_length) }
-                                td { (row.best_word_score) }
-                                td { (format_f32(row.avg_score_per_game)) }
-                                td { (format_f32(row.avg_score_per_tile)) }
-                                td { (format_f32(row.win_percentage)) "%" }
+                        view: |cx, row| {
+                            let cells = View::new_fragment(
+                                row.cells()
+                                    .into_iter()
+                                    .map(|cell| view! { cx, td { (cell) } })
+                                    .collect(),
+                            );
+
+                            view! { cx,
+                                tr {
+                                    (cells)
+                                }
                             }
                         }
                     }
@@ -9,14 +9,16 @@ mod footer;
 mod friends_table;
 mod leaderboard;
 mod navbar;
+mod open_hands;
 mod progress;
 mod scoreboard;
 mod separator;
 mod tile;
+mod tile_tracker;
 mod tiles;
 mod toast;
 
-pub use board::Board;
+pub use board::{Board, BoardTheme};
 pub use chat::{Chat, Msg};
 pub use counter::{Counter, FixedCounter};
 pub use error_msg::{ErrorMsg, StaticErrorMsg};
@@ -25,9 +27,11 @@ pub use footer::Footer;
 pub use friends_table::FriendsTable;
 pub use leaderboard::Leaderboard;
 pub use navbar::Navbar;
+pub use open_hands::OpenHands;
 pub use progress::Progress;
 pub use scoreboard::Scoreboard;
 pub use separator::Separator;
 pub use tile::Tile;
+pub use tile_tracker::TileTracker;
 pub use tiles::Tiles;
 pub use toast::Toast;
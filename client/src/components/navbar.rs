@@ -1,7 +1,10 @@
 //! Navbar component.
 
-use crate::context::{use_auth, use_logged_in};
-use sycamore::prelude::*;
+use crate::{
+    context::{use_auth, use_lang, use_logged_in},
+    requests::users::log_out,
+};
+use sycamore::{futures::spawn_local_scoped, prelude::*};
 
 /// Properties for the `Navbar`.
 #[derive(Prop)]
@@ -64,6 +67,9 @@ fn NavbarStart<G: Html>(cx: Scope) -> View<G> {
                     a(class="navbar-item is-primary", href="/live") {
                         "Play"
                     }
+                    a(class="navbar-item is-primary", href="/puzzle") {
+                        "Daily puzzle"
+                    }
                     a(class="navbar-item is-primary", href="/games") {
                         "Game list"
                     }
@@ -83,11 +89,15 @@ fn NavbarStart<G: Html>(cx: Scope) -> View<G> {
 #[component]
 fn NavbarEnd<G: Html>(cx: Scope) -> View<G> {
     let is_logged_in = use_logged_in(cx);
+    let lang = use_lang(cx);
 
     view! { cx,
         div(class="navbar-end") {
             div(class="navbar-item") {
                 div(class="buttons") {
+                    a(class="button is-dark", on:click=|_| lang.set(lang.get().next())) {
+                        (lang.get().next().name())
+                    }
                     (match *is_logged_in.get() {
                         true => view! { cx, NavbarEndLoggedIn {} },
                         false => view! { cx, NavbarEndLoggedOut {} },
@@ -103,11 +113,21 @@ fn NavbarEnd<G: Html>(cx: Scope) -> View<G> {
 fn NavbarEndLoggedIn<G: Html>(cx: Scope) -> View<G> {
     let auth = use_auth(cx);
 
+    // revoke the token on the server before removing it.
+    let on_log_out = move |_| {
+        spawn_local_scoped(cx, async {
+            if let Err(e) = log_out(auth).await {
+                log::error!("failed to log out: {e:?}");
+            }
+            auth.set(None);
+        });
+    };
+
     view! { cx,
         a(class="button is-light", href="/users/account") {
             "Account"
         }
-        a(class="button is-primary", href="/", on:click=|_| auth.set(None)) {
+        a(class="button is-primary", href="/", on:click=on_log_out) {
             "Log out"
         }
     }
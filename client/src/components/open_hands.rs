@@ -0,0 +1,60 @@
+//! Displays the racks of the other players in an open hands game.
+
+use super::tile::Tile;
+use api::routes::live::Player;
+use scrabble::game::tile;
+use std::collections::HashMap;
+use sycamore::prelude::*;
+
+/// Props for `OpenHands`.
+#[derive(Prop)]
+pub struct Props<'a> {
+    /// The racks of every player.
+    pub racks: &'a ReadSignal<HashMap<Player, Vec<tile::Tile>>>,
+    /// The id of the player viewing the racks, whose rack is not shown.
+    pub id_player: i32,
+}
+
+/// Shows the racks of the other players, when they are known.
+#[component]
+pub fn OpenHands<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
+    let racks = create_memo(cx, move || {
+        let mut racks = (*props.racks.get())
+            .clone()
+            .into_iter()
+            .filter(|(player, _)| player.id_player != props.id_player)
+            .collect::<Vec<_>>();
+        racks.sort_by_key(|(player, _)| player.id_player);
+
+        racks
+    });
+
+    view! { cx,
+        (match racks.get().is_empty() {
+            true => view! { cx, },
+            false => view! { cx,
+                section(class="open-hands") {
+                    (View::new_fragment(
+                        racks
+                            .get()
+                            .iter()
+                            .cloned()
+                            .map(|(player, rack)| {
+                                let tiles = View::new_fragment(
+                                    rack.into_iter()
+                                        .map(|tile| view! { cx, div { Tile { tile: tile } } })
+                                        .collect(),
+                                );
+
+                                view! { cx,
+                                    p { (player.username) }
+                                    div(class="tiles") { (tiles) }
+                                }
+                            })
+                            .collect()
+                    ))
+                }
+            },
+        })
+    }
+}
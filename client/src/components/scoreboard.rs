@@ -1,17 +1,27 @@
+use crate::components::FaIcon;
 use api::routes::live::Player;
 use std::collections::HashMap;
 use sycamore::prelude::*;
 
+/// When there are this many tiles or fewer in the bag, a warning
+/// is shown.
+const LOW_TILES: usize = 7;
+
 /// Props for `Scoreboard`.
 #[derive(Prop)]
 pub struct Props<'a> {
     /// The player scores.
     pub scores: &'a ReadSignal<HashMap<Player, usize>>,
+    /// The number of tiles remaining in the bag.
+    pub letter_bag_len: &'a ReadSignal<usize>,
 }
 
-/// A scoreboard table.
+/// A scoreboard table, which highlights the leader and shows the
+/// number of tiles remaining in the bag.
 #[component]
 pub fn Scoreboard<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
+    let is_low = create_memo(cx, || *props.letter_bag_len.get() <= LOW_TILES);
+
     view! { cx,
         section(class="scoreboard") {
             table(class="table has-text-white has-background-black is-fullwidth") {
@@ -22,20 +32,47 @@ pub fn Scoreboard<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
                 tbody {
                     ({
                         // sort by score,
-                        let scores = (*props.scores.get()).clone();
-                        let mut scores = scores
+                        let mut scores = (*props.scores.get())
+                            .clone()
                             .into_iter()
-                            .map(|(player, score)| (player.username, score))
                             .collect::<Vec<_>>();
                         scores.sort_by_key(|(_, score)| usize::MAX - score);
 
+                        // the leaders are the players with the highest score (when
+                        // a score has been made).
+                        let leading_score = scores
+                            .first()
+                            .map(|&(_, score)| score)
+                            .filter(|&score| score > 0);
+
                         View::new_fragment(
                             scores
                                 .into_iter()
-                                .map(|(username, score)| view! { cx,
-                                    tr {
-                                        td { (username) }
-                                        td { (score) }
+                                .map(|(player, score)| {
+                                    let is_ai = player.is_ai();
+                                    let Player { username, is_connected, .. } = player;
+                                    let class = match Some(score) == leading_score {
+                                        true => "is-leader",
+                                        false => "",
+                                    };
+
+                                    view! { cx,
+                                        tr(class=class) {
+                                            td {
+                                                (match is_ai {
+                                                    true => view! { cx, FaIcon { class: "fa-solid fa-robot" } },
+                                                    false => view! { cx, },
+                                                })
+                                                (username)
+                                                (match is_connected {
+                                                    true => view! { cx, },
+                                                    false => view! { cx,
+                                                        span(class="ml-2 has-text-grey") { "(disconnected)" }
+                                                    },
+                                                })
+                                            }
+                                            td { (score) }
+                                        }
                                     }
                                 })
                                 .collect()
@@ -43,6 +80,19 @@ pub fn Scoreboard<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
                     })
                 }
             }
+
+            p(class="has-text-centered") {
+                (props.letter_bag_len.get()) " tiles remaining"
+            }
+
+            (match *is_low.get() {
+                true => view! { cx,
+                    p(class="has-text-centered has-text-warning") {
+                        "The bag is nearly empty!"
+                    }
+                },
+                false => view! { cx, },
+            })
         }
     }
 }
@@ -0,0 +1,77 @@
+//! Tracks the tiles that the player has not seen.
+
+use crate::requests::tiles::distribution;
+use api::routes::tiles::TilesResponse;
+use scrabble::{game::tile::Tile, util::pos::Pos};
+use sycamore::{futures::spawn_local_scoped, prelude::*};
+
+/// Props for `TileTracker`.
+#[derive(Prop)]
+pub struct Props<'a> {
+    /// The tiles on the board.
+    pub tiles: &'a ReadSignal<Vec<Option<Tile>>>,
+    /// The player's rack.
+    pub rack: &'a ReadSignal<Vec<Tile>>,
+    /// The tiles from the rack that have been placed, but not played.
+    pub placed_tiles: &'a ReadSignal<Vec<(Pos, Tile)>>,
+}
+
+/// Shows the number of each tile that is either in the bag or on another
+/// player's rack, found by subtracting the tiles on the board and in the
+/// player's rack from the tiles at the start of the game.
+#[component]
+pub fn TileTracker<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
+    // the tiles at the start of the game, fetched once.
+    let distribution_signal = create_signal(cx, None::<TilesResponse>);
+    spawn_local_scoped(cx, async move {
+        match distribution().await {
+            Ok(response) => distribution_signal.set(Some(response)),
+            Err(e) => log::error!("failed to load the tile distribution: {e:?}"),
+        }
+    });
+
+    let unseen = create_memo(cx, move || match distribution_signal.get().as_ref() {
+        Some(response) => {
+            let tiles = props.tiles.get();
+            let rack = props.rack.get();
+            let placed_tiles = props.placed_tiles.get();
+            let seen = tiles
+                .iter()
+                .flatten()
+                .chain(rack.iter())
+                .chain(placed_tiles.iter().map(|(_, tile)| tile))
+                .copied();
+
+            response.unseen(seen)
+        }
+        None => vec![],
+    });
+
+    view! { cx,
+        (match unseen.get().is_empty() {
+            true => view! { cx, },
+            false => view! { cx,
+                section(class="tile-tracker") {
+                    p { "Unseen tiles" }
+                    p(class="is-family-monospace") {
+                        (View::new_fragment(
+                            unseen
+                                .get()
+                                .iter()
+                                .filter(|tile_count| tile_count.count > 0)
+                                .map(|tile_count| {
+                                    let glyph = match tile_count.tile {
+                                        Tile::Letter(letter) => char::from(letter),
+                                        Tile::Blank(_) => '?',
+                                    };
+                                    let text = format!("{glyph}×{} ", tile_count.count);
+                                    view! { cx, span { (text) } }
+                                })
+                                .collect()
+                        ))
+                    }
+                }
+            },
+        })
+    }
+}
@@ -1,11 +1,27 @@
 //! Provides context (data available to all components) for global
-//! theming and authorization.
+//! theming, language, authorization and the word list.
 
-use api::{auth::Token, routes::users::UserDetails};
+use crate::{
+    components::BoardTheme,
+    i18n::Lang,
+    requests::{fsm::fetch_fsm, themes::list_themes},
+};
+use api::{
+    auth::Token,
+    routes::{
+        themes::{Palette, BUILTIN_THEMES, DEFAULT_THEME},
+        users::UserDetails,
+    },
+};
+use scrabble::util::fsm::FastFsm;
 use serde::{Deserialize, Serialize};
-use sycamore::prelude::{
-    create_effect, create_memo, create_signal, provide_context_ref, use_context, ReadSignal, Scope,
-    Signal,
+use std::rc::Rc;
+use sycamore::{
+    futures::spawn_local_scoped,
+    prelude::{
+        create_effect, create_memo, create_signal, provide_context_ref, use_context, ReadSignal,
+        Scope, Signal,
+    },
 };
 
 /// Type alias for the global auth signal.
@@ -53,6 +69,120 @@ pub fn provide_auth_context(cx: Scope) -> &AuthSignal {
     auth
 }
 
+/// Type alias for the global language signal.
+pub type LangSignal = Signal<Lang>;
+
+/// Provides the language context in the scope.
+pub fn provide_lang_context(cx: Scope) -> &LangSignal {
+    let lang = provide_context_ref(cx, create_signal(cx, Lang::load()));
+
+    // store the new preference whenever the language is updated.
+    create_effect(cx, move || lang.get().store());
+
+    lang
+}
+
+/// The loading state of the word list.
+#[derive(Clone)]
+pub enum FsmState {
+    /// The word list has not been requested by a page.
+    Idle,
+    /// The word list is being fetched.
+    Loading,
+    /// The word list has loaded.
+    Loaded(Rc<FastFsm>),
+    /// The word list failed to load, contains the error message.
+    Failed(String),
+}
+
+/// Type alias for the global word list signal.
+pub type FsmSignal = Signal<FsmState>;
+
+/// Provides the word list context in the scope. The word list is large,
+/// so it is only fetched (once, in the background) when a page first
+/// calls [`use_fsm`].
+pub fn provide_fsm_context(cx: Scope) -> &FsmSignal {
+    let fsm = provide_context_ref(cx, create_signal(cx, FsmState::Idle));
+
+    // fetch in this scope rather than the page's, so that the download
+    // continues if the page is left before it finishes.
+    create_effect(cx, move || {
+        if let FsmState::Loading = *fsm.get() {
+            spawn_local_scoped(cx, async move {
+                fsm.set(match fetch_fsm().await {
+                    Ok(fast_fsm) => FsmState::Loaded(Rc::new(fast_fsm)),
+                    Err(e) => FsmState::Failed(e.to_string()),
+                });
+            });
+        }
+    });
+
+    fsm
+}
+
+/// Type alias for the global custom theme signal.
+pub type PalettesSignal = Signal<Vec<Palette>>;
+
+/// Provides the custom board themes in the scope. The themes are
+/// fetched once, in the background, and are empty until they load.
+pub fn provide_palettes_context(cx: Scope) -> &PalettesSignal {
+    let palettes = provide_context_ref(cx, create_signal(cx, vec![]));
+
+    spawn_local_scoped(cx, async move {
+        match list_themes().await {
+            Ok(response) => palettes.set(response.palettes),
+            Err(e) => log::error!("failed to load themes: {e:?}"),
+        }
+    });
+
+    palettes
+}
+
+/// Gets a signal containing the custom board themes.
+pub fn use_palettes(cx: Scope) -> &PalettesSignal {
+    use_context(cx)
+}
+
+/// Gets a signal containing the board theme chosen by the user. The
+/// default theme is used when logged out, or if the chosen theme does
+/// not exist.
+pub fn use_board_theme(cx: Scope) -> &ReadSignal<BoardTheme> {
+    let user_details = use_user_details(cx);
+    let palettes = use_palettes(cx);
+
+    create_memo(cx, || {
+        let user_details = user_details.get();
+        let name = match user_details.as_ref() {
+            Some(details) => details.theme.as_str(),
+            None => DEFAULT_THEME,
+        };
+        let palette = palettes.get().iter().find(|p| p.name == name).cloned();
+
+        match (BUILTIN_THEMES.into_iter().find(|&b| b == name), palette) {
+            (Some(builtin), _) => BoardTheme::Builtin(builtin),
+            (None, Some(palette)) => BoardTheme::Custom(palette),
+            (None, None) => BoardTheme::Builtin(DEFAULT_THEME),
+        }
+    })
+}
+
+/// Gets a signal containing the loading state of the word list, starting
+/// to fetch it if it has not been requested yet.
+pub fn use_fsm(cx: Scope) -> &FsmSignal {
+    let fsm: &FsmSignal = use_context(cx);
+
+    if let FsmState::Idle = *fsm.get_untracked() {
+        fsm.set(FsmState::Loading);
+    }
+
+    fsm
+}
+
+/// Gets a signal containing the language preference.
+pub fn use_lang(cx: Scope) -> &LangSignal {
+    use_context(cx)
+}
+
 /// Gets a signal containing the optional auth data.
 pub fn use_auth(cx: Scope) -> &AuthSignal {
     use_context(cx)
@@ -1,6 +1,8 @@
 //! Module containing the error types.
 
-use api::error::ErrorResponse;
+use crate::i18n::{Lang, Localize};
+use api::error::{ErrorResponse, Field};
+use scrabble::error::FsmError;
 use std::fmt;
 
 /// The result type for the client.
@@ -22,6 +24,8 @@ pub enum Error {
     Js(gloo_utils::errors::JsError),
     /// Error from sending or receiving a websocket message.
     WebSocket(reqwasm::websocket::WebSocketError),
+    /// Error from loading the word list.
+    Fsm(FsmError),
 }
 
 impl std::error::Error for Error {}
@@ -46,9 +50,20 @@ impl fmt::Display for Error {
                 )
             }
             Error::Api(err) => {
-                let ErrorResponse { status, msg } = err;
-                log::error!("API error ({status}): {msg}");
-                writeln!(f, "Error: {msg}")
+                let ErrorResponse {
+                    status,
+                    key,
+                    fields,
+                } = err;
+                log::error!("API error ({status}): {key:?}");
+
+                // show the reason for every invalid field.
+                match fields.is_empty() {
+                    true => writeln!(f, "Error: {}", key.localize(Lang::load())),
+                    false => fields
+                        .iter()
+                        .try_for_each(|error| writeln!(f, "{}", error.key.localize(Lang::load()))),
+                }
             }
             Error::HttpStatus(status) => {
                 log::error!("Bad response from server.");
@@ -62,6 +77,26 @@ impl fmt::Display for Error {
             }
             Error::Js(_) => writeln!(f, "WebSocket connection error"),
             Error::WebSocket(_) => writeln!(f, "WebSocket communication error"),
+            Error::Fsm(err) => {
+                log::error!("fsm error: {err:?}");
+                writeln!(
+                    f,
+                    "Failed to load the word list.\n\
+                    Try clearing your browser's cache and reloading the page."
+                )
+            }
+        }
+    }
+}
+
+impl Error {
+    /// Checks whether the server rejected `field` of the request.
+    pub fn is_invalid(&self, field: Field) -> bool {
+        match self {
+            Error::Api(ErrorResponse { fields, .. }) => {
+                fields.iter().any(|error| error.field == field)
+            }
+            _ => false,
         }
     }
 }
@@ -86,3 +121,8 @@ impl From<serde_json::Error> for Error {
         Self::SerdeJson(err)
     }
 }
+impl From<FsmError> for Error {
+    fn from(err: FsmError) -> Self {
+        Self::Fsm(err)
+    }
+}
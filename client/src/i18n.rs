@@ -0,0 +1,269 @@
+//! Localized strings for messages sent by the server. The server
+//! only sends stable keys (such as [`ErrorKey`] or [`LiveError`]), which
+//! are mapped to a message in the user's preferred [`Lang`].
+
+use api::{auth::PasswordCriterion, error::ErrorKey, routes::live::LiveError};
+use scrabble::{error::GameError, game::GameOverReason};
+use serde::{Deserialize, Serialize};
+
+/// HTML LocalStorage key for the language preference.
+const LANG_KEY: &str = "scrabble.lang";
+
+/// The languages supported by the client.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Lang {
+    /// English.
+    En,
+    /// French.
+    Fr,
+}
+
+impl Default for Lang {
+    fn default() -> Self {
+        Lang::En
+    }
+}
+
+impl Lang {
+    /// Gets the name of the language, written in that language.
+    pub fn name(self) -> &'static str {
+        match self {
+            Lang::En => "English",
+            Lang::Fr => "Français",
+        }
+    }
+    /// Gets the next language, used to cycle through languages.
+    pub fn next(self) -> Self {
+        match self {
+            Lang::En => Lang::Fr,
+            Lang::Fr => Lang::En,
+        }
+    }
+    /// Loads the language preference from LocalStorage, defaulting to English.
+    pub fn load() -> Self {
+        web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .and_then(|local_storage| local_storage.get_item(LANG_KEY).ok().flatten())
+            .and_then(|string| serde_json::from_str(&string).ok())
+            .unwrap_or_default()
+    }
+    /// Stores the language preference in LocalStorage.
+    pub fn store(self) {
+        let local_storage =
+            web_sys::window().and_then(|window| window.local_storage().ok().flatten());
+
+        if let Some(local_storage) = local_storage {
+            let serialized = serde_json::to_string(&self).unwrap();
+            local_storage.set_item(LANG_KEY, &serialized).unwrap();
+        }
+    }
+}
+
+/// Types that can be displayed to the user in a given language.
+pub trait Localize {
+    /// Gets the message for `self` in the language `lang`.
+    fn localize(&self, lang: Lang) -> &'static str;
+}
+
+impl Localize for ErrorKey {
+    fn localize(&self, lang: Lang) -> &'static str {
+        match lang {
+            Lang::En => match self {
+                ErrorKey::BadRequest => "Bad request",
+                ErrorKey::InvalidRequestBody => "Invalid request body",
+                ErrorKey::PayloadTooLarge => "The request was too large",
+                ErrorKey::NotFound => "Not found",
+                ErrorKey::MissingAuthHeader => "Missing auth header",
+                ErrorKey::InvalidAuthHeader => "Invalid auth header",
+                ErrorKey::UsernameOrEmailExists => "Username or email exists",
+                ErrorKey::InvalidUsername => "Username is invalid",
+                ErrorKey::InvalidPassword => {
+                    "Password is too weak.\n\
+                    Must contain at least one uppercase and\n\
+                    lowercase letter, number and symbol."
+                }
+                ErrorKey::InvalidEmail => "Email is invalid",
+                ErrorKey::InvalidTheme => "That board theme does not exist",
+                ErrorKey::ResetTimeout => "A recent request was made to reset the password",
+                ErrorKey::ResetExpired => "The request to reset your password has expired",
+                ErrorKey::VerifyExpired => "The request to change your email has expired",
+                ErrorKey::IncorrectCredentials => "Incorrect username or password",
+                ErrorKey::AccountLocked => "Too many failed logins, please try again later",
+                ErrorKey::Unauthorized => "Unauthorized",
+                ErrorKey::IllegalPlayerCount => "Incorrect number of players specified",
+                ErrorKey::DuplicatePlayer => "Each player can only be added once",
+                ErrorKey::NotYourTurn => "It's not your turn!",
+                ErrorKey::IllegalPlay => "That play is not allowed",
+                ErrorKey::PuzzleAttempted => "You have already attempted today's puzzle",
+                ErrorKey::InternalServerError => "Internal server error",
+            },
+            Lang::Fr => match self {
+                ErrorKey::BadRequest => "Requête invalide",
+                ErrorKey::InvalidRequestBody => "Corps de la requête invalide",
+                ErrorKey::PayloadTooLarge => "La requête est trop volumineuse",
+                ErrorKey::NotFound => "Introuvable",
+                ErrorKey::MissingAuthHeader => "En-tête d'authentification manquant",
+                ErrorKey::InvalidAuthHeader => "En-tête d'authentification invalide",
+                ErrorKey::UsernameOrEmailExists => "Le nom d'utilisateur ou l'email existe déjà",
+                ErrorKey::InvalidUsername => "Le nom d'utilisateur est invalide",
+                ErrorKey::InvalidPassword => {
+                    "Le mot de passe est trop faible.\n\
+                    Il doit contenir au moins une majuscule,\n\
+                    une minuscule, un chiffre et un symbole."
+                }
+                ErrorKey::InvalidEmail => "L'email est invalide",
+                ErrorKey::InvalidTheme => "Ce thème de plateau n'existe pas",
+                ErrorKey::ResetTimeout => {
+                    "Une demande de réinitialisation du mot de passe a été faite récemment"
+                }
+                ErrorKey::ResetExpired => "La demande de réinitialisation du mot de passe a expiré",
+                ErrorKey::VerifyExpired => "La demande de changement d'email a expiré",
+                ErrorKey::IncorrectCredentials => "Nom d'utilisateur ou mot de passe incorrect",
+                ErrorKey::AccountLocked => {
+                    "Trop de tentatives de connexion échouées, veuillez réessayer plus tard"
+                }
+                ErrorKey::Unauthorized => "Non autorisé",
+                ErrorKey::IllegalPlayerCount => "Nombre de joueurs incorrect",
+                ErrorKey::DuplicatePlayer => "Chaque joueur ne peut être ajouté qu'une fois",
+                ErrorKey::NotYourTurn => "Ce n'est pas votre tour !",
+                ErrorKey::IllegalPlay => "Ce coup n'est pas autorisé",
+                ErrorKey::PuzzleAttempted => "Vous avez déjà tenté le puzzle du jour",
+                ErrorKey::InternalServerError => "Erreur interne du serveur",
+            },
+        }
+    }
+}
+
+impl Localize for LiveError {
+    fn localize(&self, lang: Lang) -> &'static str {
+        match lang {
+            Lang::En => match self {
+                LiveError::Play(e) => e.localize(lang),
+                LiveError::NotYourTurn => "It's not your turn!",
+                LiveError::ZeroPlayers => "No players added",
+                LiveError::IllegalPlayerCount => "Incorrect number of players specified",
+                LiveError::IllegalMoveTimeout => "The move time limit is out of range",
+                LiveError::IllegalOpenHands => "Open hands are only allowed in friends only games",
+                LiveError::ChallengeExpired => "The last play can no longer be challenged",
+                LiveError::FailedToJoin => "Failed to join",
+                LiveError::TooManyGames => "You have created too many games",
+                LiveError::InvalidToken => "Provided token was invalid. Try logging in again.",
+                LiveError::VersionMismatch => "The game has been updated. Reload the page to play.",
+            },
+            Lang::Fr => match self {
+                LiveError::Play(e) => e.localize(lang),
+                LiveError::NotYourTurn => "Ce n'est pas votre tour !",
+                LiveError::ZeroPlayers => "Aucun joueur ajouté",
+                LiveError::IllegalPlayerCount => "Nombre de joueurs incorrect",
+                LiveError::IllegalMoveTimeout => "La limite de temps par coup est hors limites",
+                LiveError::IllegalOpenHands => {
+                    "Les chevalets visibles ne sont autorisés qu'entre amis"
+                }
+                LiveError::ChallengeExpired => "Le dernier coup ne peut plus être contesté",
+                LiveError::FailedToJoin => "Impossible de rejoindre la partie",
+                LiveError::TooManyGames => "Vous avez créé trop de parties",
+                LiveError::InvalidToken => "Le jeton fourni est invalide. Reconnectez-vous.",
+                LiveError::VersionMismatch => {
+                    "Le jeu a été mis à jour. Rechargez la page pour jouer."
+                }
+            },
+        }
+    }
+}
+
+impl Localize for GameError {
+    fn localize(&self, lang: Lang) -> &'static str {
+        match lang {
+            Lang::En => match self {
+                GameError::NotEnoughLetters => "There are not enough letters in the bag to redraw",
+                GameError::NotInRack => "One or more placed tiles were not in the rack",
+                GameError::Over => "The game is over so no futher plays can be made",
+                GameError::InvalidWord => "A word was not in the dictionary",
+                GameError::PlacementCount => "At least 1 and no more than 7 tiles can be placed",
+                GameError::CoincedentTiles => "Tiles were placed over existing tiles",
+                GameError::DuplicatePosition => "Multiple tiles were placed on the same square",
+                GameError::RedrawCount => {
+                    "At least 1 and up to the number of tiles on the rack can be redrawn"
+                }
+                GameError::MustIntersectStart => "A tile must be placed on the start square",
+                GameError::WordsNeedTwoLetters => "Words need at least 2 letters",
+                GameError::NotConnected => "Not connected",
+                GameError::NoCommonLine => "Placed tiles must share a common row or column",
+                GameError::MissingLetter => {
+                    "A blank tile placed on the board did not specify a letter"
+                }
+                GameError::WordTooShort => "A word was shorter than the minimum length",
+                GameError::NothingToChallenge => "There is no play to challenge",
+                GameError::InvalidPosition => "The game can not start from that position",
+                GameError::ExchangeNotAllowed => "Exchanging tiles is not allowed in this game",
+            },
+            Lang::Fr => match self {
+                GameError::NotEnoughLetters => {
+                    "Il n'y a pas assez de lettres dans le sac pour échanger"
+                }
+                GameError::NotInRack => {
+                    "Une ou plusieurs lettres posées n'étaient pas sur le chevalet"
+                }
+                GameError::Over => "La partie est terminée, aucun coup ne peut être joué",
+                GameError::InvalidWord => "Un mot n'est pas dans le dictionnaire",
+                GameError::PlacementCount => "Il faut poser entre 1 et 7 lettres",
+                GameError::CoincedentTiles => {
+                    "Des lettres ont été posées sur des lettres existantes"
+                }
+                GameError::DuplicatePosition => "Plusieurs lettres ont été posées sur la même case",
+                GameError::RedrawCount => {
+                    "Il faut échanger entre 1 lettre et le nombre de lettres du chevalet"
+                }
+                GameError::MustIntersectStart => "Une lettre doit être posée sur la case centrale",
+                GameError::WordsNeedTwoLetters => "Les mots doivent contenir au moins 2 lettres",
+                GameError::NotConnected => "Non connecté",
+                GameError::NoCommonLine => {
+                    "Les lettres posées doivent être sur la même ligne ou colonne"
+                }
+                GameError::MissingLetter => {
+                    "Une lettre blanche posée sur le plateau n'a pas de lettre"
+                }
+                GameError::WordTooShort => "Un mot est plus court que la longueur minimale",
+                GameError::NothingToChallenge => "Il n'y a aucun coup à contester",
+                GameError::InvalidPosition => {
+                    "La partie ne peut pas commencer à partir de cette position"
+                }
+                GameError::ExchangeNotAllowed => {
+                    "Les échanges de lettres ne sont pas autorisés dans cette partie"
+                }
+            },
+        }
+    }
+}
+
+impl Localize for GameOverReason {
+    fn localize(&self, lang: Lang) -> &'static str {
+        match lang {
+            Lang::En => match self {
+                GameOverReason::TwoPasses => "A player has passed twice",
+                GameOverReason::EmptyRack => "A player has emptied their rack",
+                GameOverReason::Abandoned => "The game was abandoned",
+            },
+            Lang::Fr => match self {
+                GameOverReason::TwoPasses => "Un joueur a passé deux fois",
+                GameOverReason::EmptyRack => "Un joueur a vidé son chevalet",
+                GameOverReason::Abandoned => "La partie a été abandonnée",
+            },
+        }
+    }
+}
+
+impl Localize for PasswordCriterion {
+    fn localize(&self, lang: Lang) -> &'static str {
+        match lang {
+            Lang::En => self.message(),
+            Lang::Fr => match self {
+                PasswordCriterion::Length => "Entre 8 et 50 caractères",
+                PasswordCriterion::Digit => "Au moins un chiffre",
+                PasswordCriterion::Symbol => "Au moins un symbole",
+                PasswordCriterion::Lowercase => "Au moins une lettre minuscule",
+                PasswordCriterion::Uppercase => "Au moins une lettre majuscule",
+            },
+        }
+    }
+}
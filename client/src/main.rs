@@ -10,6 +10,7 @@ mod app;
 mod components;
 mod context;
 mod error;
+mod i18n;
 mod pages;
 mod requests;
 
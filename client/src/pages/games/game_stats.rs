@@ -1,13 +1,13 @@
 //! Implementation of the [`GameStatsPage`].
 
 use crate::{
-    components::StaticErrorMsg,
+    components::{ErrorMsg, StaticErrorMsg},
     context::use_auth,
     pages::{format_bool, format_datetime, format_f32},
-    requests::games::stats,
+    requests::games::{record, stats},
 };
 use api::routes::games::{GameMetadata, GameStatsResponse};
-use sycamore::{prelude::*, suspense::Suspense};
+use sycamore::{futures::spawn_local_scoped, prelude::*, suspense::Suspense};
 
 /// Props for `GameStatsPage`.
 #[derive(Prop)]
@@ -62,10 +62,10 @@ fn ViewStats<G: Html>(cx: Scope<'_>, response: GameStatsResponse) -> View<G> {
     let GameStatsResponse {
         meta:
             GameMetadata {
+                id_game,
                 start_time,
                 end_time,
                 is_over,
-                ..
             },
         avg_score_per_play,
         avg_word_length,
@@ -132,5 +132,55 @@ fn ViewStats<G: Html>(cx: Scope<'_>, response: GameStatsResponse) -> View<G> {
                 }
             }
         }
+
+        (match is_over {
+            true => view! { cx, ExportGame { id_game } },
+            false => view! { cx, },
+        })
+    }
+}
+
+/// Props for `ExportGame`.
+#[derive(Prop)]
+struct ExportGameProps {
+    /// The game id.
+    id_game: i32,
+}
+
+/// Component that fetches the shareable encoding of a finished game, which
+/// is shown so that it can be copied.
+#[component]
+fn ExportGame<G: Html>(cx: Scope, props: ExportGameProps) -> View<G> {
+    let auth = use_auth(cx);
+    let encoded = create_signal(cx, None::<String>);
+    let err = create_signal(cx, None);
+
+    let on_export = move |_| {
+        spawn_local_scoped(cx, async move {
+            match record(auth, props.id_game).await {
+                Ok(response) => encoded.set(Some(response.record)),
+                Err(e) => err.set(Some(e)),
+            }
+        });
+    };
+
+    view! { cx,
+        hr
+
+        h1(class="h1 is-size-5") { "Share" }
+
+        button(class="button is-primary", on:click=on_export) { "Copy game" }
+
+        ErrorMsg { err }
+
+        (match encoded.get().as_ref() {
+            Some(encoded) => {
+                let encoded = encoded.clone();
+                view! { cx,
+                    textarea(class="textarea mt-3", readonly=true, value=encoded)
+                }
+            }
+            None => view! { cx, },
+        })
     }
 }
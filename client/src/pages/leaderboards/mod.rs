@@ -1,5 +1,7 @@
 mod friends_leaderboard;
 mod leaderboard;
+mod puzzle_leaderboard;
 
 pub use friends_leaderboard::FriendsLeaderboardPage;
 pub use leaderboard::LeaderboardPage;
+pub use puzzle_leaderboard::PuzzleLeaderboardPage;
@@ -0,0 +1,48 @@
+//! Implementation of the [`PuzzleLeaderboardPage`].
+
+use crate::{
+    components::{Leaderboard, StaticErrorMsg},
+    requests::leaderboard::puzzle_leaderboard,
+};
+use sycamore::{prelude::*, suspense::Suspense};
+
+/// Page for the daily puzzle leaderboard.
+#[component]
+pub fn PuzzleLeaderboardPage<G: Html>(cx: Scope) -> View<G> {
+    view! { cx,
+        div(class="page") {
+            section(class="is-centered") {
+                div {
+                    div(class="m-3 has-text-centered") {
+                        h1(class="h1 is-size-5") { "Puzzle Leaderboard" }
+                    }
+
+                    Suspense {
+                        fallback: view! { cx, p { "loading" } },
+                        FetchPuzzleLeaderboard {}
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Component that makes an API request to display the leaderboard.
+#[component]
+async fn FetchPuzzleLeaderboard<G: Html>(cx: Scope<'_>) -> View<G> {
+    match puzzle_leaderboard(15, 0).await {
+        Ok(response) => {
+            let rows = create_signal(cx, response.rows);
+            view! { cx,
+                Leaderboard {
+                    rows: rows,
+                }
+            }
+        }
+        Err(e) => view! { cx,
+            StaticErrorMsg {
+                err: e,
+            }
+        },
+    }
+}
@@ -1,7 +1,11 @@
-use crate::components::Msg;
-use api::routes::live::{LiveError, Player, ServerMsg};
+use crate::{
+    components::Msg,
+    i18n::{Lang, Localize},
+};
+use api::routes::live::{board_hash, FinalScore, LiveError, Player, ServerMsg};
+use chrono::{DateTime, Utc};
 use scrabble::{
-    game::{play::Play, tile::Tile, GameOverReason},
+    game::{challenge::ChallengeRule, play::Play, tile::Tile},
     util::pos::Pos,
 };
 use std::collections::HashMap;
@@ -39,23 +43,62 @@ pub struct PlayingState {
     pub id_game: i32,
     pub id_player: i32,
     pub capacity: usize,
+    pub move_timeout: u64,
+    pub challenge_rule: ChallengeRule,
+    pub allow_exchange: bool,
 
     // -- shared state --
     pub tiles: RcSignal<Vec<Option<Tile>>>,
     pub scores: RcSignal<HashMap<Player, usize>>,
     pub next: RcSignal<Option<Player>>,
     pub letter_bag_len: RcSignal<usize>,
+    /// The racks of every player, which are only known in an open
+    /// hands game.
+    pub all_racks: RcSignal<HashMap<Player, Vec<Tile>>>,
+    pub deadline: RcSignal<Option<DateTime<Utc>>>,
     pub is_started: RcSignal<bool>,
     pub is_over: RcSignal<bool>,
+    /// Set when the local board differs from the server, until the
+    /// full state is received.
+    pub is_desynced: RcSignal<bool>,
+    /// Set when the most recent play was a placement by another player,
+    /// which can be challenged.
+    pub can_challenge: RcSignal<bool>,
+    /// The commitment to the seed of the letter bag, sent when the game
+    /// starts, which is checked against the seed revealed when it ends.
+    pub seed_commitment: RcSignal<Option<String>>,
 
     // -- local state --
     pub messages: RcSignal<Vec<Msg>>,
     pub rack: RcSignal<Vec<Tile>>,
     pub placed_tiles: RcSignal<Vec<(Pos, Tile)>>,
+    pub last_play: RcSignal<Vec<Pos>>,
     pub redraw_tiles: RcSignal<Vec<Tile>>,
     pub show_rules_modal: RcSignal<bool>,
 }
 
+/// Describes how the final score of a player was calculated, e.g.
+/// "running score 312, minus 8 for leftover tiles = 304".
+fn score_breakdown(player: &Player, final_score: &FinalScore) -> String {
+    let FinalScore {
+        running_score,
+        penalty,
+        bonus,
+        score,
+    } = final_score;
+
+    let mut breakdown = format!("{}: running score {running_score}", player.username);
+    if *penalty > 0 {
+        breakdown.push_str(&format!(", minus {penalty} for leftover tiles"));
+    }
+    if *bonus > 0 {
+        breakdown.push_str(&format!(", plus {bonus} for emptying the rack"));
+    }
+    breakdown.push_str(&format!(" = {score}"));
+
+    breakdown
+}
+
 impl AppState {
     /// Calculates the next state from the previous state and a message.
     pub fn reduce(&self, msg: ServerMsg) -> Self {
@@ -71,10 +114,14 @@ impl AppState {
             ServerMsg::Error(e) => {
                 log::error!("failed to join/create: {e:?}");
                 connected.toast.set(Some(String::from(match e {
-                    LiveError::ZeroPlayers => "No players added",
-                    LiveError::IllegalPlayerCount => "Incorrect number of players specified",
-                    LiveError::FailedToJoin => "Failed to join",
-                    LiveError::InvalidToken => "Provided token was invalid. Try logging in again.",
+                    LiveError::ZeroPlayers
+                    | LiveError::IllegalPlayerCount
+                    | LiveError::IllegalMoveTimeout
+                    | LiveError::IllegalOpenHands
+                    | LiveError::FailedToJoin
+                    | LiveError::TooManyGames
+                    | LiveError::InvalidToken
+                    | LiveError::VersionMismatch => e.localize(Lang::load()),
                     _ => "Unexpected message",
                 })));
             }
@@ -87,6 +134,10 @@ impl AppState {
                 scores,
                 next,
                 letter_bag_len,
+                move_timeout,
+                deadline,
+                challenge_rule,
+                allow_exchange,
             } => {
                 let is_started = scores.len() >= capacity;
                 let status = match is_started {
@@ -104,6 +155,7 @@ impl AppState {
                         ),
                     }]),
                     placed_tiles: create_rc_signal(vec![]),
+                    last_play: create_rc_signal(vec![]),
                     redraw_tiles: create_rc_signal(vec![]),
                     show_rules_modal: create_rc_signal(true),
 
@@ -111,13 +163,21 @@ impl AppState {
                     id_game,
                     id_player,
                     capacity,
+                    move_timeout,
+                    challenge_rule,
+                    allow_exchange,
                     tiles: create_rc_signal(tiles),
                     rack: create_rc_signal(rack),
                     scores: create_rc_signal(scores),
                     next: create_rc_signal(next),
                     letter_bag_len: create_rc_signal(letter_bag_len),
+                    all_racks: create_rc_signal(HashMap::new()),
+                    deadline: create_rc_signal(deadline),
                     is_started: create_rc_signal(is_started),
                     is_over: create_rc_signal(false),
+                    is_desynced: create_rc_signal(false),
+                    can_challenge: create_rc_signal(false),
+                    seed_commitment: create_rc_signal(None),
                 }));
             }
             msg => log::error!("unexpected message: {msg:?}"),
@@ -136,14 +196,21 @@ impl AppState {
                 letter_bag_len,
                 next,
                 scores,
+                pass_count,
+                pass_limit,
+                deadline,
+                board_hash: expected_hash,
             } => {
                 self.add_server_msg(format!(
                     "{} has made a play ({}). {}",
                     player.username,
-                    match play {
-                        Play::Pass => "Passed",
-                        Play::Redraw(..) => "Redraw tiles",
-                        Play::Place(..) => "Placed tiles",
+                    match &play {
+                        Play::Pass => "Passed".to_string(),
+                        Play::Redraw(tiles) => match tiles.len() {
+                            1 => "Exchanged 1 tile".to_string(),
+                            count => format!("Exchanged {count} tiles"),
+                        },
+                        Play::Place(..) => "Placed tiles".to_string(),
                     },
                     match &next {
                         Some(player) => format!("It's {} next!", player.username),
@@ -151,19 +218,125 @@ impl AppState {
                     }
                 ));
 
+                // warn players when the game is close to ending by passes.
+                if pass_count > 0 && next.is_some() {
+                    let remaining = pass_limit.saturating_sub(pass_count);
+                    self.add_server_msg(format!(
+                        "Game will end if {} passes {} more time{}.",
+                        player.username,
+                        remaining,
+                        if remaining == 1 { "" } else { "s" },
+                    ));
+                }
+
                 // find the next set of tiles.
-                let mut tiles = prev_tiles;
+                let mut tiles = prev_tiles.clone();
                 if let Play::Place(tile_positions) = &play {
                     for (pos, tile) in tile_positions {
                         tiles[usize::from(*pos)] = Some(*tile);
                     }
                 }
 
+                // find the positions that changed so they can be highlighted.
+                let last_play = Pos::iter()
+                    .filter(|&pos| prev_tiles[usize::from(pos)] != tiles[usize::from(pos)])
+                    .collect();
+                playing.last_play.set(last_play);
+
+                // check that the board matches the server, otherwise request
+                // the full state.
+                if board_hash(&tiles) != expected_hash && !*playing.is_desynced.get() {
+                    log::error!("board is out of sync with the server");
+                    playing.is_desynced.set(true);
+                }
+
+                // a placement by another player can be challenged until the
+                // next play is made.
+                playing.can_challenge.set(
+                    !playing.challenge_rule.is_off()
+                        && matches!(play, Play::Place(_))
+                        && player.id_player != playing.id_player,
+                );
+
                 playing.letter_bag_len.set(letter_bag_len);
+                playing.deadline.set(deadline);
                 playing.tiles.set(tiles);
                 playing.next.set(next);
                 playing.scores.set(scores);
             }
+            ServerMsg::FullState {
+                tiles,
+                rack,
+                scores,
+                next,
+                letter_bag_len,
+                deadline,
+                players,
+                last_play,
+                can_challenge,
+            } => {
+                log::info!("resynced with the server");
+                self.add_server_msg(String::from("Resynced with the server"));
+
+                // rebuild the state from scratch, only keeping the chat
+                // messages and the fixed details of the game. Any tiles that
+                // were being placed or redrawn are discarded.
+                return AppState::Playing(Box::new(PlayingState {
+                    // -- local state --
+                    messages: playing.messages.clone(),
+                    placed_tiles: create_rc_signal(vec![]),
+                    last_play: create_rc_signal(last_play),
+                    redraw_tiles: create_rc_signal(vec![]),
+                    show_rules_modal: playing.show_rules_modal.clone(),
+
+                    // -- shared state --
+                    id_game: playing.id_game,
+                    id_player: playing.id_player,
+                    capacity: playing.capacity,
+                    move_timeout: playing.move_timeout,
+                    challenge_rule: playing.challenge_rule,
+                    allow_exchange: playing.allow_exchange,
+                    tiles: create_rc_signal(tiles),
+                    rack: create_rc_signal(rack),
+                    scores: create_rc_signal(scores),
+                    is_over: create_rc_signal(next.is_none()),
+                    next: create_rc_signal(next),
+                    letter_bag_len: create_rc_signal(letter_bag_len),
+                    all_racks: playing.all_racks.clone(),
+                    deadline: create_rc_signal(deadline),
+                    is_started: create_rc_signal(players.len() >= playing.capacity),
+                    is_desynced: create_rc_signal(false),
+                    can_challenge: create_rc_signal(can_challenge),
+                    seed_commitment: playing.seed_commitment.clone(),
+                }));
+            }
+            ServerMsg::ChallengeResult {
+                challenger,
+                player,
+                invalid_words,
+                lost_turn,
+            } => {
+                playing.can_challenge.set(false);
+
+                self.add_server_msg(match invalid_words.is_empty() {
+                    true => format!(
+                        "{} challenged the play by {}, but every word was valid.",
+                        challenger.username, player.username
+                    ),
+                    false => {
+                        format!(
+                        "{} challenged the play by {}: {} {} not valid, so the tiles were removed.",
+                        challenger.username,
+                        player.username,
+                        invalid_words.join(", "),
+                        if invalid_words.len() == 1 { "is" } else { "are" },
+                    )
+                    }
+                });
+                if lost_turn {
+                    self.add_server_msg(format!("{} loses their turn.", challenger.username));
+                }
+            }
             ServerMsg::UserConnected(player) => {
                 self.add_server_msg(format!("{} has joined", player.username));
             }
@@ -183,6 +356,9 @@ impl AppState {
                     content: msg,
                 });
             }
+            ServerMsg::AllRacks(racks) => {
+                playing.all_racks.set(racks);
+            }
             ServerMsg::Rack(mut new_rack) => {
                 // try to rebuild the previous rack.
                 let mut prev_rack = (*playing.rack.get()).clone();
@@ -230,26 +406,53 @@ impl AppState {
                 }
 
                 match e {
-                    LiveError::Play(e) => self.add_server_msg(format!("Illegal play: {e}")),
-                    LiveError::NotYourTurn => {
-                        self.add_server_msg(String::from("It's not your turn!"))
+                    LiveError::Play(_) => {
+                        self.add_server_msg(format!("Illegal play: {}", e.localize(Lang::load())))
+                    }
+                    LiveError::NotYourTurn | LiveError::ChallengeExpired => {
+                        self.add_server_msg(String::from(e.localize(Lang::load())))
                     }
                     _ => (),
                 }
             }
-            ServerMsg::Over(reason) => {
+            ServerMsg::Over {
+                reason,
+                final_scores,
+                seed,
+            } => {
                 playing.is_over.set(true);
-                self.add_server_msg(format!(
-                    "Game over: {}.",
-                    match reason {
-                        GameOverReason::TwoPasses => "A player has passed twice",
-                        GameOverReason::EmptyRack => "A player has emptied their rack",
-                    }
-                ))
+                self.add_server_msg(format!("Game over: {}.", reason.localize(Lang::load())));
+
+                // explain how the final score was calculated for each player.
+                for (player, final_score) in &final_scores {
+                    self.add_server_msg(score_breakdown(player, final_score));
+                }
+
+                playing.scores.set(
+                    final_scores
+                        .into_iter()
+                        .map(|(p, s)| (p, s.score))
+                        .collect(),
+                );
+
+                // check the revealed seed against the commitment.
+                if let Some(seed) = seed {
+                    let status = match playing.seed_commitment.get().as_ref() {
+                        Some(commitment) if *commitment == seed.commitment() => "verified",
+                        Some(_) => "does not match the commitment",
+                        None => "no commitment was received",
+                    };
+                    self.add_server_msg(format!("The letter bag seed was {seed} ({status})."));
+                }
             }
-            ServerMsg::Starting => {
+            ServerMsg::Starting { seed_commitment } => {
                 playing.is_started.set(true);
 
+                if let Some(commitment) = &seed_commitment {
+                    self.add_server_msg(format!("The letter bag seed commitment is {commitment}."));
+                }
+                playing.seed_commitment.set(seed_commitment);
+
                 match playing.next.get().as_ref() {
                     Some(Player { username, .. }) => {
                         self.add_server_msg(format!("The game is starting. It's {username} next.",))
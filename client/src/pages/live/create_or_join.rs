@@ -1,7 +1,8 @@
 //! Component that handles game creation.
 
 use crate::components::{Counter, FixedCounter, Toast};
-use api::routes::live::{AiDifficulty, ClientMsg};
+use api::routes::live::{AiVocabulary, ClientMsg};
+use scrabble::{ai::AiDifficulty, game::challenge::ChallengeRule};
 use sycamore::prelude::*;
 use tokio::sync::mpsc;
 
@@ -81,7 +82,7 @@ pub fn CreateOrJoin<G: Html>(cx: Scope, props: Props) -> View<G> {
 
                         h1 { "Creating a game" }
                         p {
-                            "When creating a game there are four options to configure:"
+                            "When creating a game there are five options to configure:"
                             ul {
                                 li {
                                     code { "Player count" } "The total number of players in the game (this includes
@@ -98,6 +99,10 @@ pub fn CreateOrJoin<G: Html>(cx: Scope, props: Props) -> View<G> {
                                     code { "Friends only" } "If this field is checked, only users that you have
                                     added as friends can join the game."
                                 }
+                                li {
+                                    code { "Move time limit" } "The time each player has to make a play. Players
+                                    that run out of time are replaced by an Ai player."
+                                }
                             }
                         }
                         p {
@@ -145,7 +150,13 @@ fn CreateTab<G: Html>(cx: Scope, props: Props) -> View<G> {
     let player_count = create_signal(cx, 2);
     let ai_count = create_signal(cx, 0);
     let ai_difficulty = create_signal(cx, AiDifficulty::Medium);
+    let ai_vocabulary = create_signal(cx, AiVocabulary::Difficulty);
     let friends_only = create_signal(cx, true);
+    let open_hands = create_signal(cx, false);
+    let allow_exchange = create_signal(cx, true);
+    let verifiable_bag = create_signal(cx, false);
+    let challenge_rule = create_signal(cx, ChallengeRule::Off);
+    let move_timeout = create_signal(cx, 60);
 
     // the maximum number of ai players.
     let ai_count_max = create_memo(cx, || *player_count.get() - 1);
@@ -159,12 +170,45 @@ fn CreateTab<G: Html>(cx: Scope, props: Props) -> View<G> {
     };
     let ai_btn_on_click = |difficulty| move |_| ai_difficulty.set(difficulty);
 
+    // the class signal for a particular ai vocabulary button.
+    let vocabulary_btn_class = move |vocabulary| {
+        create_memo(cx, move || match *ai_vocabulary.get() == vocabulary {
+            true => "button is-small is-primary",
+            false => "button is-small",
+        })
+    };
+    let vocabulary_btn_on_click = |vocabulary| move |_| ai_vocabulary.set(vocabulary);
+
+    // the class signal for a particular move timeout button.
+    let timeout_btn_class = move |timeout| {
+        create_memo(cx, move || match *move_timeout.get() == timeout {
+            true => "button is-small is-primary",
+            false => "button is-small",
+        })
+    };
+    let timeout_btn_on_click = |timeout| move |_| move_timeout.set(timeout);
+
+    // the class signal for a particular challenge rule button.
+    let challenge_btn_class = move |rule| {
+        create_memo(cx, move || match *challenge_rule.get() == rule {
+            true => "button is-small is-primary",
+            false => "button is-small",
+        })
+    };
+    let challenge_btn_on_click = |rule| move |_| challenge_rule.set(rule);
+
     // called when the create button is clicked.
     let on_create = move |_| {
         let player_count = *player_count.get();
         let ai_count = *ai_count.get();
         let ai_difficulty = *ai_difficulty.get();
+        let ai_vocabulary = *ai_vocabulary.get();
         let friends_only = *friends_only.get();
+        let open_hands = *open_hands.get();
+        let allow_exchange = *allow_exchange.get();
+        let verifiable_bag = *verifiable_bag.get();
+        let challenge_rule = *challenge_rule.get();
+        let move_timeout = *move_timeout.get();
 
         props
             .ws_write
@@ -175,6 +219,12 @@ fn CreateTab<G: Html>(cx: Scope, props: Props) -> View<G> {
                 // the number of human players, so subtract `ai_count`.
                 player_count: player_count - ai_count,
                 friends_only,
+                move_timeout,
+                open_hands,
+                challenge_rule,
+                allow_exchange,
+                verifiable_bag,
+                ai_vocabulary,
             })
             .unwrap();
     };
@@ -224,6 +274,66 @@ fn CreateTab<G: Html>(cx: Scope, props: Props) -> View<G> {
             }
         }
 
+        label(class="label") { "Ai words" }
+        div(class="buttons is-centered") {
+            a(
+                class=vocabulary_btn_class(AiVocabulary::Difficulty),
+                on:click=vocabulary_btn_on_click(AiVocabulary::Difficulty),
+            ) {
+                "By difficulty"
+            }
+            a(
+                class=vocabulary_btn_class(AiVocabulary::Full),
+                on:click=vocabulary_btn_on_click(AiVocabulary::Full),
+            ) {
+                "All words"
+            }
+            a(
+                class=vocabulary_btn_class(AiVocabulary::Common),
+                on:click=vocabulary_btn_on_click(AiVocabulary::Common),
+            ) {
+                "Common words"
+            }
+        }
+
+        label(class="label") { "Move time limit" }
+        div(class="buttons is-centered") {
+            a(class=timeout_btn_class(30), on:click=timeout_btn_on_click(30)) {
+                "30s"
+            }
+            a(class=timeout_btn_class(60), on:click=timeout_btn_on_click(60)) {
+                "1m"
+            }
+            a(class=timeout_btn_class(120), on:click=timeout_btn_on_click(120)) {
+                "2m"
+            }
+            a(class=timeout_btn_class(300), on:click=timeout_btn_on_click(300)) {
+                "5m"
+            }
+        }
+
+        label(class="label") { "Challenges" }
+        div(class="buttons is-centered") {
+            a(
+                class=challenge_btn_class(ChallengeRule::Off),
+                on:click=challenge_btn_on_click(ChallengeRule::Off),
+            ) {
+                "Off"
+            }
+            a(
+                class=challenge_btn_class(ChallengeRule::Single),
+                on:click=challenge_btn_on_click(ChallengeRule::Single),
+            ) {
+                "Lose turn"
+            }
+            a(
+                class=challenge_btn_class(ChallengeRule::Free),
+                on:click=challenge_btn_on_click(ChallengeRule::Free),
+            ) {
+                "Free"
+            }
+        }
+
         div(class="field") {
             label(class="label") {
                 input(type="checkbox", bind:checked=friends_only)
@@ -231,6 +341,27 @@ fn CreateTab<G: Html>(cx: Scope, props: Props) -> View<G> {
             }
         }
 
+        div(class="field") {
+            label(class="label") {
+                input(type="checkbox", bind:checked=open_hands)
+                " Open hands? (everyone can see all racks)"
+            }
+        }
+
+        div(class="field") {
+            label(class="label") {
+                input(type="checkbox", bind:checked=allow_exchange)
+                " Allow exchanges? (redraw tiles from the bag)"
+            }
+        }
+
+        div(class="field") {
+            label(class="label") {
+                input(type="checkbox", bind:checked=verifiable_bag)
+                " Verifiable letter bag? (the seed is revealed at the end)"
+            }
+        }
+
         hr
 
         button(class="button is-primary", on:click=on_create) {
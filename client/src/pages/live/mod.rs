@@ -6,8 +6,8 @@ use crate::{
     pages::live::app_state::AppState,
     requests::live::{connect_and_authenticate, to_msg},
 };
-use api::routes::live::{ClientMsg, LiveError, ServerMsg};
-use futures::{SinkExt, StreamExt};
+use api::routes::live::{ClientMsg, LiveError, SequencedMsg, ServerMsg};
+use futures::{lock::Mutex, SinkExt, StreamExt};
 use gloo_timers::future::TimeoutFuture;
 use reqwasm::websocket::{futures::WebSocket, Message};
 use sycamore::{futures::spawn_local_scoped, prelude::*, suspense::Suspense};
@@ -21,6 +21,12 @@ use create_or_join::CreateOrJoin;
 use playing::Playing;
 use sycamore_router::navigate;
 
+/// The number of times that the connection is reopened to rejoin a game,
+/// without receiving any messages, before giving up.
+const MAX_REJOIN_ATTEMPTS: usize = 3;
+/// The number of milliseconds waited before reopening the connection.
+const REJOIN_DELAY: u32 = 1000;
+
 /// Page for playing live games.
 #[component]
 pub fn LivePage<G: Html>(cx: Scope) -> View<G> {
@@ -100,8 +106,10 @@ fn setup(cx: Scope, ws: WebSocket) -> Setup {
     // `AppMsg` to incrementally update the state (dispatch).
     let (state, dispatch) = create_reducer(cx, AppState::default(), AppState::reduce);
 
-    // split the websocket into a read/write pair.
-    let (mut socket_write, mut socket_read) = ws.split();
+    // split the websocket into a read/write pair. The write half is replaced
+    // when the connection is reopened to rejoin a game.
+    let (socket_write, mut socket_read) = ws.split();
+    let socket_write = create_ref(cx, Mutex::new(socket_write));
 
     // create a queue that forwards messages sent to `ws_write` to the server.
     let (ws_write, mut ws_read) = mpsc::unbounded_channel();
@@ -114,38 +122,80 @@ fn setup(cx: Scope, ws: WebSocket) -> Setup {
     // to forward messages to the dispatch queue (writes to `dispatch_write`).
     spawn_local_scoped(cx, async move {
         let auth = use_auth(cx);
-
-        // read from `socket_read`.
-        while let Some(msg) = socket_read.next().await {
-            match msg {
-                // If a message is received, parse it as a `ServerMsg`.
-                Ok(Message::Bytes(bytes)) => {
-                    match bincode::deserialize::<ServerMsg>(&bytes) {
-                        // Forward the message to the dispatch queue.
-                        Ok(msg) => {
-                            log::info!("message recieved: {msg:?}");
-
-                            match msg {
-                                // If the message content states that the user's token
-                                // has expired, log the user out.
-                                ServerMsg::Error(LiveError::InvalidToken) => {
-                                    auth.set(None);
-                                    navigate("/live");
+        let token = use_token(cx);
+        // the sequence number of the last message received from the game.
+        let mut last_seq = 0;
+        let mut rejoin_attempts = 0;
+
+        loop {
+            // read from `socket_read`.
+            while let Some(msg) = socket_read.next().await {
+                match msg {
+                    // If a message is received, parse it as a `SequencedMsg`.
+                    Ok(Message::Bytes(bytes)) => {
+                        match bincode::deserialize::<SequencedMsg>(&bytes) {
+                            // Forward the message to the dispatch queue.
+                            Ok(SequencedMsg { seq, msg }) => {
+                                log::info!("message recieved ({seq}): {msg:?}");
+                                rejoin_attempts = 0;
+
+                                // skip messages that were resent after rejoining
+                                // but had already been received.
+                                if seq != 0 && seq <= last_seq {
+                                    continue;
+                                }
+                                last_seq = last_seq.max(seq);
+
+                                match msg {
+                                    // If the message content states that the user's token
+                                    // has expired, log the user out.
+                                    ServerMsg::Error(LiveError::InvalidToken) => {
+                                        auth.set(None);
+                                        navigate("/live");
+                                    }
+                                    msg => dispatch_write.send(msg).unwrap(),
                                 }
-                                msg => dispatch_write.send(msg).unwrap(),
                             }
+                            Err(e) => log::error!("failed to deserialize: {e:?}"),
                         }
-                        Err(e) => log::error!("failed to deserialize: {e:?}"),
+                    }
+                    // Only binary messages should be received.
+                    Ok(Message::Text(txt)) => {
+                        log::error!("text message received: {txt:?}");
+                    }
+                    Err(e) => {
+                        log::error!("websocket error: {e:?}");
+                        break;
                     }
                 }
-                // Only binary messages should be received.
-                Ok(Message::Text(txt)) => {
-                    log::error!("text message received: {txt:?}");
-                }
-                Err(e) => {
-                    log::error!("websocket error: {e:?}");
-                    break;
+            }
+
+            // If a game is being played, reopen the connection and rejoin the
+            // game, so that the server resends the messages that were missed.
+            let id_game = match state.get().as_ref() {
+                AppState::Playing(playing) if !*playing.is_over.get() => playing.id_game,
+                _ => break,
+            };
+            let token = match token.get().as_ref() {
+                Some(token) if rejoin_attempts < MAX_REJOIN_ATTEMPTS => token.clone(),
+                _ => break,
+            };
+            rejoin_attempts += 1;
+            TimeoutFuture::new(REJOIN_DELAY).await;
+
+            log::info!("rejoining game {id_game} after message {last_seq}");
+            match connect_and_authenticate(token).await {
+                Ok(ws) => {
+                    let (mut write, read) = ws.split();
+                    let rejoin = ClientMsg::Rejoin { id_game, last_seq };
+
+                    if let Err(e) = write.send(to_msg(&rejoin)).await {
+                        log::error!("failed to send message: {e:?}");
+                    }
+                    *socket_write.lock().await = write;
+                    socket_read = read;
                 }
+                Err(e) => log::error!("failed to reconnect: {e:?}"),
             }
         }
 
@@ -161,7 +211,7 @@ fn setup(cx: Scope, ws: WebSocket) -> Setup {
         while let Some(msg) = ws_read.recv().await {
             log::info!("sending message: {msg:?}");
 
-            if let Err(e) = socket_write.send(to_msg(&msg)).await {
+            if let Err(e) = socket_write.lock().await.send(to_msg(&msg)).await {
                 log::error!("failed to send message: {e:?}");
             }
         }
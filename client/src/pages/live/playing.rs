@@ -1,13 +1,19 @@
 use crate::{
-    components::{Board, Chat, Scoreboard, Tiles},
+    components::{Board, Chat, OpenHands, Scoreboard, TileTracker, Tiles},
+    context::use_board_theme,
     pages::live::app_state::AppState,
 };
 use api::routes::live::{ClientMsg, Player};
+use chrono::Utc;
+use gloo_timers::future::TimeoutFuture;
+use rand::seq::SliceRandom;
 use scrabble::game::{
+    board::{self, BonusLayout},
     play::Play,
     tile::{Letter, Tile},
+    GameConfig,
 };
-use sycamore::{prelude::*, rt::JsCast};
+use sycamore::{futures::spawn_local_scoped, prelude::*, rt::JsCast};
 use tokio::sync::mpsc;
 use web_sys::{Event, KeyboardEvent};
 
@@ -41,16 +47,23 @@ pub fn Playing<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
     let tiles = create_ref(cx, state.tiles.clone());
     let local_rack = create_ref(cx, state.rack.clone());
     let placed_tiles = create_ref(cx, state.placed_tiles.clone());
+    let last_play = create_ref(cx, state.last_play.clone());
     let redraw_tiles = create_ref(cx, state.redraw_tiles.clone());
     let messages = create_ref(cx, state.messages.clone());
     let scores = create_ref(cx, state.scores.clone());
+    let all_racks = create_ref(cx, state.all_racks.clone());
+    let id_player = state.id_player;
+    let allow_exchange = state.allow_exchange;
     let letter_bag_remaining = create_ref(cx, state.letter_bag_len.clone());
     let show_rules_modal = create_ref(cx, state.show_rules_modal.clone());
+    let deadline = create_ref(cx, state.deadline.clone());
+    let can_challenge = create_ref(cx, state.can_challenge.clone());
 
     // whether the game has started.
     let is_started = create_ref(cx, state.is_started.clone());
     let is_over = create_ref(cx, state.is_over.clone());
-    let next = state.next.clone();
+    let is_desynced = create_ref(cx, state.is_desynced.clone());
+    let next = create_ref(cx, state.next.clone());
 
     // whether it is the connected player's turn.
     let is_my_turn = create_memo(cx, move || {
@@ -61,6 +74,36 @@ pub fn Playing<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
             && matches!(next.as_ref(), Some(Player { id_player, .. }) if *id_player == state.id_player)
     });
 
+    // -- RESYNC --
+    // request the full state when the board differs from the server.
+    create_effect(cx, move || {
+        if *is_desynced.get() {
+            ws_write.send(ClientMsg::Resync).unwrap();
+        }
+    });
+
+    // -- COUNTDOWN --
+    // the number of seconds until the next player must make a play. The
+    // server sends a `Timeout` message when the time has actually run out.
+    let remaining = create_signal(cx, None);
+    spawn_local_scoped(cx, async move {
+        loop {
+            let seconds =
+                (*deadline.get()).map(|deadline| (deadline - Utc::now()).num_seconds().max(0));
+            remaining.set(seconds);
+
+            TimeoutFuture::new(1000).await;
+        }
+    });
+    let countdown = create_memo(cx, || match (*is_over.get(), *remaining.get()) {
+        (false, Some(0)) => String::from("Time running out!"),
+        (false, Some(seconds)) => match next.get().as_ref() {
+            Some(Player { username, .. }) => format!("{username} has {seconds}s to play"),
+            None => String::new(),
+        },
+        _ => String::new(),
+    });
+
     // -- TABS --
     let active_tab = create_signal(cx, ControlTab::Place);
     let tab_class = |tab| {
@@ -77,8 +120,13 @@ pub fn Playing<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
     let local_tiles = create_signal(cx, vec![]);
     create_effect(cx, || local_tiles.set((*tiles.get()).clone()));
     let selected_tile = create_ref(cx, create_rc_signal(None));
+    let bonuses = create_ref(cx, BonusLayout::default());
+    let theme = use_board_theme(cx);
 
+    // the rack index and board position of a blank that needs a letter. The
+    // index is `None` when changing the letter of a blank on the board.
     let blank_tile = create_signal(cx, None);
+    let is_redesignating = create_memo(cx, || matches!(*blank_tile.get(), Some((None, _))));
     let show_modal = create_memo(cx, || blank_tile.get().is_some());
     let modal_class = create_memo(cx, || match *show_modal.get() {
         true => "modal is-active",
@@ -88,6 +136,34 @@ pub fn Playing<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
 
     // -- STATE FOR PLAYS --
     let redraw_selected = create_signal(cx, None);
+    // the server rejects a redraw of more tiles than remain in the bag.
+    let can_redraw = create_memo(cx, || {
+        redraw_tiles.get().len() <= *letter_bag_remaining.get()
+    });
+    // whether the positions of the placed tiles are legal. The words are
+    // only checked by the server.
+    let can_place = create_memo(cx, || {
+        let board = board::Board::try_from((*tiles.get()).clone());
+
+        board.is_ok_and(|board| {
+            board
+                .precheck_placement(&placed_tiles.get(), &GameConfig::default())
+                .is_ok()
+        })
+    });
+
+    // whether the staged tiles connect to the existing tiles, so that a
+    // hint can be shown before the placement is complete.
+    let is_connected = create_memo(cx, || {
+        let board = board::Board::try_from((*tiles.get()).clone());
+        let staged = placed_tiles
+            .get()
+            .iter()
+            .map(|&(pos, _)| pos)
+            .collect::<Vec<_>>();
+
+        board.is_ok_and(|board| board.would_connect(&staged))
+    });
 
     // -- CALLBACKS --
     // called when a chat message is sent.
@@ -117,20 +193,25 @@ pub fn Playing<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
                             }
                             // if the tile is blank, show a modal to determine which letter
                             // it should be.
-                            Tile::Blank(_) => blank_tile.set(Some((idx, pos))),
+                            Tile::Blank(_) => blank_tile.set(Some((Some(idx), pos))),
                         }
                     }
                 } else {
                     // if no tile is selected, and the position clicked was
-                    // newly placed, return that tile to the rack.
+                    // newly placed, return that tile to the rack. A blank
+                    // reopens the modal so that its letter can be changed.
                     let placed = placed_tiles.get();
                     let newly_placed = placed.iter().find(|(p, _)| *p == pos);
 
-                    if let Some((_, tile)) = newly_placed {
-                        let mut local_tiles = local_tiles.modify();
-                        local_tiles[usize::from(pos)] = None;
-                        placed_tiles.modify().retain(|(p, _)| *p != pos);
-                        local_rack.modify().push(*tile);
+                    match newly_placed {
+                        Some((_, Tile::Blank(_))) => blank_tile.set(Some((None, pos))),
+                        Some((_, tile)) => {
+                            let mut local_tiles = local_tiles.modify();
+                            local_tiles[usize::from(pos)] = None;
+                            placed_tiles.modify().retain(|(p, _)| *p != pos);
+                            local_rack.modify().push(*tile);
+                        }
+                        None => {}
                     }
                 }
             }
@@ -174,6 +255,13 @@ pub fn Playing<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
             local_rack.push(tile);
         }
     };
+    // called when the shuffle button is clicked. Only reorders the
+    // local rack, so nothing is sent to the server.
+    let on_shuffle = |_| {
+        // the selected index would no longer refer to the same tile.
+        selected_tile.set(None);
+        local_rack.modify().shuffle(&mut rand::thread_rng());
+    };
     // called when a tile to redraw is clicked.
     let on_redraw_tile_clicked = |idx, tile| {
         redraw_tiles.modify().remove(idx);
@@ -191,19 +279,48 @@ pub fn Playing<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
                     let tile = Tile::Blank(Some(letter));
                     let (idx, pos) = (*blank_tile.get()).unwrap();
                     blank_tile.set(None);
+                    modal_letter.set(String::new());
 
                     let mut local_tiles = local_tiles.modify();
-                    local_rack.modify().remove(idx);
                     local_tiles[usize::from(pos)] = Some(tile);
-                    placed_tiles.modify().push((pos, tile));
+
+                    match idx {
+                        // the blank is placed from the rack.
+                        Some(idx) => {
+                            local_rack.modify().remove(idx);
+                            placed_tiles.modify().push((pos, tile));
+                        }
+                        // the blank is already on the board, so only its letter
+                        // is changed.
+                        None => {
+                            for placed in placed_tiles.modify().iter_mut() {
+                                if placed.0 == pos {
+                                    placed.1 = tile;
+                                }
+                            }
+                        }
+                    }
                 }
             }
         }
     };
+    // called when a blank on the board is returned to the rack from the modal.
+    let on_return_blank = |_| {
+        if let Some((None, pos)) = *blank_tile.get() {
+            local_tiles.modify()[usize::from(pos)] = None;
+            placed_tiles.modify().retain(|(p, _)| *p != pos);
+            local_rack.modify().push(Tile::Blank(None));
+        }
+        blank_tile.set(None);
+    };
     // called when the user clicks the pass button.
     let on_pass = move |_| {
         ws_write.send(ClientMsg::Play(Play::Pass)).unwrap();
     };
+    // called when the user clicks the challenge button.
+    let on_challenge = move |_| {
+        ws_write.send(ClientMsg::Challenge).unwrap();
+    };
     // called when the user clicks the redraw button.
     let on_redraw = move |_| {
         let tiles = (*redraw_tiles.get()).clone();
@@ -279,9 +396,8 @@ pub fn Playing<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
                         h1 { "Playing" }
                         h2 { "Premium squares" }
                         p {
-                            "Hovering over (or clicking on mobile devices) a square will show
-                            the premium for that square (if any). Premium squares are coloured
-                            on the board."
+                            "Premium squares are coloured on the board and labelled with their
+                            premium. The start square is marked with a star."
 
                             table {
                                 thead {
@@ -319,7 +435,8 @@ pub fn Playing<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
                         p {
                             "When you have a blank tile in your rack, you can place it on the board
                             as you would any other tile. After placing the tile, a dialogue box
-                            will ask for the letter that you wish the tile to represent."
+                            will ask for the letter that you wish the tile to represent. To change
+                            the letter before making the play, click the blank tile again."
                         }
                         p {
                             "When you see a blank tile on the board, you can hover (or tap on mobile)
@@ -330,7 +447,8 @@ pub fn Playing<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
                         p {
                             "To reorder tiles, simply click on one tile in rack to select it, then
                             click another to swap the pair. (tiles cannot be reordered whilst in
-                            the redraw tab)."
+                            the redraw tab). The " code { "Shuffle" } " button randomly reorders
+                            your rack, which can help when looking for words."
                         }
 
                         h2 { "Making a play" }
@@ -354,7 +472,8 @@ pub fn Playing<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
                             all tiles from your rack into the redraw area. Clicking tiles in
                             the redraw area will return them to your rack. If you wish to select
                             specific tiles to redraw, click on tiles in your rack and they will move
-                            to the redraw area."
+                            to the redraw area. You cannot redraw more tiles than remain in the
+                            letter bag."
                         }
                         p {
                             "Once you have selected the tiles you wish to redraw, clicking the "
@@ -367,6 +486,15 @@ pub fn Playing<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
                             after two consecutive passes from any player, the game will end."
                         }
 
+                        h2 { "Challenges" }
+                        p {
+                            "If the game allows challenges, words are not checked when they are played.
+                            Instead, at the start of your turn you can click the " code { "Challenge the last play" }
+                            " button. If any word in the last play is not valid, its tiles are removed and
+                            that player's turn counts as a pass. Otherwise, depending on the rules of the game,
+                            you may lose your turn. A play can only be challenged shortly after it is made."
+                        }
+
                         h2 { "Illegal plays" }
                         p {
                             "If you make an illegal play, your tiles will return to your rack. A message
@@ -424,6 +552,16 @@ pub fn Playing<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
                             )
                         }
                     }
+                    (match *is_redesignating.get() {
+                        true => view! { cx,
+                            div(class="field") {
+                                button(class="button", on:click=on_return_blank) {
+                                    "Return to rack"
+                                }
+                            }
+                        },
+                        false => view! { cx, },
+                    })
                 }
             }
             button(class="modal-close is-large", on:click=|_| blank_tile.set(None))
@@ -433,6 +571,9 @@ pub fn Playing<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
             Board {
                 on_click: on_square_clicked,
                 cells: local_tiles,
+                bonuses: bonuses,
+                last_play: last_play,
+                theme: theme,
             }
 
             div(class="rack") {
@@ -442,6 +583,12 @@ pub fn Playing<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
                     selected: selected_tile,
                 }
 
+                div(class="has-text-centered") {
+                    button(class="button is-small is-dark", on:click=on_shuffle) {
+                        "Shuffle"
+                    }
+                }
+
                 p(class="pb-4 has-text-centered has-text-white") {
                     (match *is_started.get() {
                         false => view! { cx, "Waiting for players" },
@@ -453,15 +600,35 @@ pub fn Playing<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
                         }
                     })
                 }
+
+                p(class="pb-4 has-text-centered has-text-white") {
+                    (countdown.get())
+                }
             }
 
             (match *is_my_turn.get() {
                 false => view! { cx, },
                 true => view! { cx,
                     div(class="controls") {
+                        (match *can_challenge.get() {
+                            false => view! { cx, },
+                            true => view! { cx,
+                                div(class="buttons is-centered") {
+                                    button(class="button is-warning", on:click=on_challenge) {
+                                        "Challenge the last play"
+                                    }
+                                }
+                            },
+                        })
+
                         div(class="tabs is-centered") {
                             ul {
-                                li(class=(redraw_class.get()), on:click=|_| active_tab.set(ControlTab::Redraw)) { a { "Redraw" } }
+                                (match allow_exchange {
+                                    true => view! { cx,
+                                        li(class=(redraw_class.get()), on:click=|_| active_tab.set(ControlTab::Redraw)) { a { "Redraw" } }
+                                    },
+                                    false => view! { cx, },
+                                })
                                 li(class=(place_class.get()), on:click=|_| active_tab.set(ControlTab::Place)) { a { "Place" } }
                                 li(class=(pass_class.get()), on:click=|_| active_tab.set(ControlTab::Pass)) { a { "Pass" } }
                             }
@@ -474,7 +641,7 @@ pub fn Playing<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
                                         0 => view! { cx,
                                             p { "Select tiles from your rack to redraw" }
 
-                                            button(class="button mt-4 is-dark", on:click=on_redraw_all) {
+                                            button(class="button mt-4 is-dark", on:click=on_redraw_all, disabled=*letter_bag_remaining.get() == 0) {
                                                 "Select all tiles"
                                             }
                                         },
@@ -487,7 +654,17 @@ pub fn Playing<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
                                                 }
                                             }
 
-                                            button(class="button is-dark", on:click=on_redraw) {
+                                            (match *can_redraw.get() {
+                                                true => view! { cx, },
+                                                false => view! { cx,
+                                                    p(class="mb-4") {
+                                                        "There are only " (letter_bag_remaining.get()) " tiles in the bag, so
+                                                        no more than " (letter_bag_remaining.get()) " tiles can be redrawn."
+                                                    }
+                                                },
+                                            })
+
+                                            button(class="button is-dark", on:click=on_redraw, disabled=!*can_redraw.get()) {
                                                 "Redraw these tiles"
                                             }
                                         }
@@ -504,10 +681,19 @@ pub fn Playing<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
                                                     "Recall these tiles"
                                                 }
 
-                                                button(class="button is-dark", on:click=on_place) {
+                                                button(class="button is-dark", on:click=on_place, disabled=!*can_place.get()) {
                                                     "Place these tiles"
                                                 }
                                             }
+
+                                            (match *is_connected.get() {
+                                                true => view! { cx, },
+                                                false => view! { cx,
+                                                    p(class="help is-danger") {
+                                                        "These tiles are not connected to the existing tiles"
+                                                    }
+                                                },
+                                            })
                                         }
                                     })
                                 },
@@ -524,6 +710,18 @@ pub fn Playing<'a, G: Html>(cx: Scope<'a>, props: Props<'a>) -> View<G> {
 
             Scoreboard {
                 scores: scores,
+                letter_bag_len: letter_bag_remaining,
+            }
+
+            OpenHands {
+                racks: all_racks,
+                id_player: id_player,
+            }
+
+            TileTracker {
+                tiles: tiles,
+                rack: local_rack,
+                placed_tiles: placed_tiles,
             }
 
             Chat {
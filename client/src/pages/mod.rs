@@ -6,16 +6,20 @@ mod invalid_state;
 mod leaderboards;
 mod live;
 mod not_found;
+mod puzzle;
 mod users;
 
 use chrono::NaiveDateTime;
 pub use friends::FriendsPage;
 pub use games::{GameListPage, GameStatsPage};
 pub use invalid_state::InvalidStatePage;
-pub use leaderboards::{FriendsLeaderboardPage, LeaderboardPage};
+pub use leaderboards::{FriendsLeaderboardPage, LeaderboardPage, PuzzleLeaderboardPage};
 pub use live::LivePage;
 pub use not_found::NotFoundPage;
-pub use users::{AccountPage, LoginPage, ResetPasswordPage, ResetWithSecretPage, SignUpPage};
+pub use puzzle::PuzzlePage;
+pub use users::{
+    AccountPage, LoginPage, ResetPasswordPage, ResetWithSecretPage, SignUpPage, VerifyEmailPage,
+};
 
 /// Formats a NaiveDateTime.
 pub fn format_datetime(dt: NaiveDateTime) -> String {
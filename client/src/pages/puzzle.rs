@@ -0,0 +1,276 @@
+//! Implementation of the [`PuzzlePage`].
+
+use crate::{
+    components::{Board, ErrorMsg, StaticErrorMsg, Tiles},
+    context::{use_auth, use_board_theme},
+    requests::puzzles,
+};
+use api::routes::puzzles::{DailyPuzzleResponse, PuzzleAttempt, PuzzleAttemptRequest};
+use scrabble::game::{
+    board::{self, BonusLayout},
+    tile::{Letter, Tile},
+    GameConfig,
+};
+use sycamore::{futures::spawn_local_scoped, prelude::*, rt::JsCast, suspense::Suspense};
+use web_sys::{Event, KeyboardEvent};
+
+/// Page for the daily puzzle, where a single placement is made and
+/// compared to the highest scoring placement.
+#[component]
+pub fn PuzzlePage<G: Html>(cx: Scope) -> View<G> {
+    view! { cx,
+        div(class="page") {
+            section(class="has-text-centered p-3") {
+                h1(class="h1 is-size-5") { "Daily puzzle" }
+                p { "Find the highest scoring play. You only get one attempt!" }
+                a(href="/leaderboard/puzzles") { "Puzzle leaderboard" }
+            }
+
+            Suspense {
+                fallback: view! { cx, p(class="has-text-centered") { "Loading puzzle" } },
+                FetchPuzzle {}
+            }
+        }
+    }
+}
+
+/// Component that makes an API request for today's puzzle.
+#[component]
+async fn FetchPuzzle<G: Html>(cx: Scope<'_>) -> View<G> {
+    let auth = use_auth(cx);
+
+    match puzzles::daily(auth).await {
+        Ok(response) => view! { cx,
+            Puzzle(response)
+        },
+        Err(e) => view! { cx,
+            StaticErrorMsg {
+                err: e,
+            }
+        },
+    }
+}
+
+/// Displays the result of an attempt.
+fn attempt_message(attempt: PuzzleAttempt) -> String {
+    format!(
+        "You scored {} out of a possible {} ({}%)",
+        attempt.score, attempt.best_score, attempt.percentage
+    )
+}
+
+/// Component for making a placement from the puzzle position.
+#[component]
+fn Puzzle<G: Html>(cx: Scope<'_>, response: DailyPuzzleResponse) -> View<G> {
+    let auth = use_auth(cx);
+    let theme = use_board_theme(cx);
+    let bonuses = create_ref(cx, BonusLayout::default());
+    let date = response.date;
+
+    let puzzle_tiles = create_ref(cx, response.tiles);
+    let tiles = create_signal(cx, puzzle_tiles.clone());
+    let local_rack = create_signal(cx, response.rack);
+    let placed_tiles = create_signal(cx, vec![]);
+    let selected_tile = create_signal(cx, None);
+    let last_play = create_signal(cx, vec![]);
+    let attempt = create_signal(cx, response.attempt);
+    let is_loading = create_signal(cx, false);
+    let err = create_signal(cx, None);
+
+    // the rack index and board position of a blank that needs a letter.
+    let blank_tile = create_signal(cx, None);
+    let modal_class = create_memo(cx, || match blank_tile.get().is_some() {
+        true => "modal is-active",
+        false => "modal",
+    });
+    let modal_letter = create_signal(cx, String::new());
+
+    // whether the positions of the placed tiles are legal. The words are
+    // only checked by the server.
+    let can_place = create_memo(cx, || {
+        let board = board::Board::try_from(puzzle_tiles.clone());
+
+        board.is_ok_and(|board| {
+            board
+                .precheck_placement(&placed_tiles.get(), &GameConfig::default())
+                .is_ok()
+        })
+    });
+
+    // called when a board square is clicked.
+    let on_square_clicked = |pos| {
+        // the puzzle can only be attempted once.
+        if attempt.get().is_some() {
+            return;
+        }
+
+        if let Some((idx, tile)) = *selected_tile.get() {
+            selected_tile.set(None);
+
+            // if the board position is empty, place the tile.
+            let mut tiles = tiles.modify();
+            if tiles[usize::from(pos)].is_none() {
+                match tile {
+                    Tile::Letter(_) => {
+                        tiles[usize::from(pos)] = Some(tile);
+                        local_rack.modify().remove(idx);
+                        placed_tiles.modify().push((pos, tile));
+                    }
+                    // a blank needs a letter before it is placed.
+                    Tile::Blank(_) => blank_tile.set(Some((idx, pos))),
+                }
+            }
+        } else {
+            // if no tile is selected, and the position clicked was newly
+            // placed, return that tile to the rack.
+            let placed = placed_tiles.get();
+            if let Some(&(_, tile)) = placed.iter().find(|(p, _)| *p == pos) {
+                tiles.modify()[usize::from(pos)] = None;
+                placed_tiles.modify().retain(|(p, _)| *p != pos);
+                local_rack.modify().push(match tile {
+                    Tile::Blank(_) => Tile::Blank(None),
+                    tile => tile,
+                });
+            }
+        }
+    };
+    // called when a rack tile is clicked.
+    let on_rack_tile_clicked = |idx, tile| match *selected_tile.get() {
+        // two tiles on the rack were clicked, so swap them.
+        Some((i, _)) => {
+            selected_tile.set(None);
+            local_rack.modify().swap(idx, i);
+        }
+        None => selected_tile.set(Some((idx, tile))),
+    };
+    // called when a key is pressed in the blank modal.
+    let on_modal_keydown = |evt: Event| {
+        let keyboard_event: KeyboardEvent = evt.unchecked_into();
+        let key = keyboard_event.key();
+
+        if key.len() == 1 {
+            if let Some(letter) = key.chars().next().and_then(Letter::new) {
+                let tile = Tile::Blank(Some(letter));
+                let (idx, pos) = (*blank_tile.get()).unwrap();
+                blank_tile.set(None);
+                modal_letter.set(String::new());
+
+                tiles.modify()[usize::from(pos)] = Some(tile);
+                local_rack.modify().remove(idx);
+                placed_tiles.modify().push((pos, tile));
+            }
+        }
+    };
+    // called when the recall button is clicked.
+    let on_recall = |_| {
+        let mut tiles = tiles.modify();
+        let mut local_rack = local_rack.modify();
+
+        for (pos, tile) in placed_tiles.modify().drain(..) {
+            tiles[usize::from(pos)] = None;
+            local_rack.push(match tile {
+                Tile::Blank(_) => Tile::Blank(None),
+                tile => tile,
+            });
+        }
+    };
+    // called when the place button is clicked.
+    let on_place = move |_| {
+        is_loading.set(true);
+        err.set(None);
+
+        spawn_local_scoped(cx, async move {
+            let req = PuzzleAttemptRequest {
+                date,
+                tile_positions: (*placed_tiles.get()).clone(),
+            };
+
+            match puzzles::attempt(auth, &req).await {
+                Ok(response) => {
+                    // show the highest scoring placement on the board.
+                    let mut board = puzzle_tiles.clone();
+                    for &(pos, tile) in &response.best_play {
+                        board[usize::from(pos)] = Some(tile);
+                    }
+                    tiles.set(board);
+                    last_play.set(response.best_play.iter().map(|&(pos, _)| pos).collect());
+                    attempt.set(Some(response.attempt));
+                }
+                Err(e) => err.set(Some(e)),
+            }
+
+            is_loading.set(false);
+        });
+    };
+
+    view! { cx,
+        // This modal will display when a blank tile is placed on the board.
+        div(class=(modal_class.get())) {
+            div(class="modal-background")
+            div(class="modal-content") {
+                div(class="box") {
+                    div(class="field") {
+                        label(class="label") {
+                            "Enter a letter for the blank tile"
+                        }
+                        div(class="control") {
+                            input(
+                                class="input",
+                                type="text",
+                                maxlength="1",
+                                placeholder="Letter",
+                                bind:value=modal_letter,
+                                on:keydown=on_modal_keydown,
+                            )
+                        }
+                    }
+                }
+            }
+            button(class="modal-close is-large", on:click=|_| blank_tile.set(None))
+        }
+
+        div(class="live") {
+            Board {
+                on_click: on_square_clicked,
+                cells: tiles,
+                bonuses: bonuses,
+                last_play: last_play,
+                theme: theme,
+            }
+
+            div(class="rack") {
+                Tiles {
+                    on_click: on_rack_tile_clicked,
+                    tiles: local_rack,
+                    selected: selected_tile,
+                }
+
+                (match *attempt.get() {
+                    Some(attempt) => view! { cx,
+                        p(class="pb-4 has-text-centered has-text-white") {
+                            (attempt_message(attempt))
+                        }
+                    },
+                    None => view! { cx,
+                        div(class="buttons is-centered") {
+                            button(class="button is-dark", on:click=on_recall) {
+                                "Recall"
+                            }
+                            button(
+                                class="button is-success",
+                                disabled=!*can_place.get() || *is_loading.get(),
+                                on:click=on_place,
+                            ) {
+                                "Place"
+                            }
+                        }
+                    },
+                })
+
+                ErrorMsg {
+                    err: err,
+                }
+            }
+        }
+    }
+}
@@ -2,10 +2,13 @@
 
 use crate::{
     components::{ErrorMsg, Progress},
-    context::{set_user_details, use_auth, use_user_details},
+    context::{set_user_details, use_auth, use_palettes, use_user_details},
     requests::users::{delete, update},
 };
-use api::routes::users::{DeleteAccount, UpdateAccount, UserDetails};
+use api::routes::{
+    themes::{BUILTIN_THEMES, DEFAULT_THEME},
+    users::{DeleteAccount, UpdateAccount, UserDetails},
+};
 use sycamore::{futures::spawn_local_scoped, prelude::*};
 use sycamore_router::navigate;
 
@@ -22,8 +25,17 @@ pub fn AccountPage<G: Html>(cx: Scope) -> View<G> {
 
     // create signals for the inputs.
     let username = create_signal(cx, details.username);
+    let details_email = create_ref(cx, details.email.clone());
     let email = create_signal(cx, details.email);
     let is_private = create_signal(cx, details.is_private);
+    let turn_emails = create_signal(cx, details.turn_emails);
+    let theme = create_signal(
+        cx,
+        match details.theme.is_empty() {
+            true => String::from(DEFAULT_THEME),
+            false => details.theme,
+        },
+    );
     let password = create_signal(cx, String::new());
     let curr_password = create_signal(cx, String::new());
 
@@ -73,6 +85,8 @@ pub fn AccountPage<G: Html>(cx: Scope) -> View<G> {
                     false => Some((*password.get()).clone()),
                 },
                 is_private: Some(*is_private.get()),
+                turn_emails: Some(*turn_emails.get()),
+                theme: Some((*theme.get()).clone()),
             };
 
             match update(auth, &req).await {
@@ -82,8 +96,12 @@ pub fn AccountPage<G: Html>(cx: Scope) -> View<G> {
                         auth,
                         UserDetails {
                             username: (*username.get()).clone(),
-                            email: (*email.get()).clone(),
+                            // the email is only changed once the new
+                            // address has been verified.
+                            email: details_email.clone(),
                             is_private: *is_private.get(),
+                            turn_emails: *turn_emails.get(),
+                            theme: (*theme.get()).clone(),
                         },
                     );
                     navigate("/users/account");
@@ -96,6 +114,31 @@ pub fn AccountPage<G: Html>(cx: Scope) -> View<G> {
         });
     };
 
+    // the names of the built in and custom board themes.
+    let palettes = use_palettes(cx);
+    let theme_options = create_memo(cx, move || {
+        let custom = palettes.get();
+        let names = BUILTIN_THEMES
+            .into_iter()
+            .map(String::from)
+            .chain(custom.iter().map(|palette| palette.name.clone()));
+
+        View::new_fragment(
+            names
+                .map(|name| {
+                    let selected = name == *theme.get_untracked();
+                    let value = name.clone();
+
+                    view! { cx,
+                        option(value=value, selected=selected) {
+                            (name)
+                        }
+                    }
+                })
+                .collect(),
+        )
+    });
+
     view! { cx,
         div(class="page") {
             section(class="is-fullheight is-flex is-centered is-vcentered columns") {
@@ -127,6 +170,26 @@ pub fn AccountPage<G: Html>(cx: Scope) -> View<G> {
                         }
                     }
 
+                    div(class="field") {
+                        label(class="label") {
+                            "Board theme"
+                        }
+                        div(class="control") {
+                            div(class="select") {
+                                select(bind:value=theme) {
+                                    (*theme_options.get())
+                                }
+                            }
+                        }
+                    }
+
+                    div(class="field") {
+                        label(class="checkbox") {
+                            input(type="checkbox", bind:checked=turn_emails)
+                            "  Email me when it's my turn in a correspondence game"
+                        }
+                    }
+
                     hr
 
                     div(class="field") {
@@ -17,6 +17,7 @@ pub fn LoginPage<G: Html>(cx: Scope) -> View<G> {
     // create signals for the inputs.
     let username = create_signal(cx, String::new());
     let password = create_signal(cx, String::new());
+    let remember = create_signal(cx, false);
 
     // state signals
     let is_loading = create_signal(cx, false);
@@ -33,6 +34,7 @@ pub fn LoginPage<G: Html>(cx: Scope) -> View<G> {
             let req = Login {
                 username: (*username.get()).clone(),
                 password: (*password.get()).clone(),
+                remember: *remember.get(),
             };
 
             match login(&req).await {
@@ -82,6 +84,13 @@ pub fn LoginPage<G: Html>(cx: Scope) -> View<G> {
                         }
                     }
 
+                    div(class="field") {
+                        label(class="checkbox") {
+                            input(type="checkbox", bind:checked=remember)
+                            "  Remember me?"
+                        }
+                    }
+
                     button(on:click=on_log_in, disabled=*is_loading.get(), class="button is-primary") {
                         "Log in"
                     }
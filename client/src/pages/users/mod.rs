@@ -3,9 +3,11 @@ mod login;
 mod reset_password;
 mod reset_with_secret;
 mod sign_up;
+mod verify_email;
 
 pub use account::AccountPage;
 pub use login::LoginPage;
 pub use reset_password::ResetPasswordPage;
 pub use reset_with_secret::ResetWithSecretPage;
 pub use sign_up::SignUpPage;
+pub use verify_email::VerifyEmailPage;
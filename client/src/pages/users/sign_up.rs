@@ -2,16 +2,23 @@
 
 use crate::{
     components::{ErrorMsg, Progress},
-    context::{use_auth, AuthCtx},
+    context::{use_auth, use_lang, AuthCtx},
+    error::Error,
+    i18n::Localize,
     requests::users::sign_up,
 };
-use api::routes::users::SignUp;
+use api::{
+    auth::{PasswordCriterion, PasswordStrength},
+    error::Field,
+    routes::users::SignUp,
+};
 use sycamore::{futures::spawn_local_scoped, prelude::*};
 use sycamore_router::navigate;
 
 #[component]
 pub fn SignUpPage<G: Html>(cx: Scope) -> View<G> {
     let auth = use_auth(cx);
+    let lang = use_lang(cx);
 
     // input signals
     let username = create_signal(cx, String::new());
@@ -19,9 +26,18 @@ pub fn SignUpPage<G: Html>(cx: Scope) -> View<G> {
     let password = create_signal(cx, String::new());
     let is_private = create_signal(cx, false);
 
+    // check the password as it is typed.
+    let strength = create_memo(cx, || PasswordStrength::new(&password.get()));
+
     // state signals
     let is_loading = create_signal(cx, false);
-    let err = create_signal(cx, None);
+    let err = create_signal(cx, None::<Error>);
+
+    // highlights the fields that the server rejected.
+    let input_class = move |field| match err.get().as_ref() {
+        Some(e) if e.is_invalid(field) => "input is-danger",
+        _ => "input",
+    };
 
     // called when a user clicks the signup button.
     let on_sign_up = move |_| {
@@ -71,7 +87,7 @@ pub fn SignUpPage<G: Html>(cx: Scope) -> View<G> {
                             "Username"
                         }
                         div(class="control") {
-                            input(type="text", class="input", placeholder="username", bind:value=username)
+                            input(type="text", class=input_class(Field::Username), placeholder="username", bind:value=username)
                         }
                     }
 
@@ -80,7 +96,7 @@ pub fn SignUpPage<G: Html>(cx: Scope) -> View<G> {
                             "Email"
                         }
                         div(class="control") {
-                            input(type="email", class="input", placeholder="you@example.com", bind:value=email)
+                            input(type="email", class=input_class(Field::Email), placeholder="you@example.com", bind:value=email)
                         }
                     }
 
@@ -89,7 +105,23 @@ pub fn SignUpPage<G: Html>(cx: Scope) -> View<G> {
                             "Password"
                         }
                         div(class="control") {
-                            input(type="password", class="input", placeholder="**********", bind:value=password)
+                            input(type="password", class=input_class(Field::Password), placeholder="**********", bind:value=password)
+                        }
+                        ul(class="help") {
+                            (View::new_fragment(
+                                PasswordCriterion::ALL
+                                    .into_iter()
+                                    .map(|criterion| {
+                                        let class = match strength.get().meets(criterion) {
+                                            true => "has-text-success",
+                                            false => "has-text-danger",
+                                        };
+                                        let message = criterion.localize(*lang.get());
+
+                                        view! { cx, li(class=class) { (message) } }
+                                    })
+                                    .collect()
+                            ))
                         }
                     }
 
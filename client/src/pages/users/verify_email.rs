@@ -0,0 +1,83 @@
+//! Implementation of the [`VerifyEmailPage`].
+
+use crate::{
+    components::{ErrorMsg, Progress},
+    context::{use_auth, AuthCtx},
+    requests::users::verify_email,
+};
+use api::routes::users::VerifyEmail;
+use sycamore::{futures::spawn_local_scoped, prelude::*};
+use sycamore_router::navigate;
+
+/// Props for `VerifyEmailPage`.
+#[derive(Prop)]
+pub struct Props {
+    /// The username of the account.
+    pub username: String,
+    /// The secret to verify the email address.
+    pub secret: String,
+}
+
+/// Page for confirming a new email address.
+#[component]
+pub fn VerifyEmailPage<G: Html>(cx: Scope, props: Props) -> View<G> {
+    let auth = use_auth(cx);
+
+    // state signals
+    let props = create_signal(cx, props);
+    let is_loading = create_signal(cx, false);
+    let err = create_signal(cx, None);
+
+    // called when the user confirms their email address.
+    let on_verify = move |_| {
+        log::trace!("verifying email");
+
+        is_loading.set(true);
+        err.set(None);
+
+        spawn_local_scoped(cx, async {
+            let props = props.get();
+            let req = VerifyEmail {
+                secret_hex: props.secret.clone(),
+                username: props.username.clone(),
+            };
+
+            match verify_email(&req).await {
+                Ok((token, user_details)) => {
+                    auth.set(Some(AuthCtx {
+                        token,
+                        user_details,
+                    }));
+                    navigate("/users/account");
+                }
+                Err(e) => {
+                    is_loading.set(false);
+                    err.set(Some(e))
+                }
+            }
+        });
+    };
+
+    view! { cx,
+        div(class="page") {
+            section(class="is-fullheight is-centered is-vcentered is-flex columns") {
+                div(class="box") {
+                    h1 { "Verify email" }
+
+                    hr
+
+                    button(on:click=on_verify, disabled=*is_loading.get(), class="button is-primary") {
+                        "Confirm new email"
+                    }
+
+                    Progress {
+                        is_visible: is_loading,
+                    }
+                    ErrorMsg {
+                        err: err,
+                    }
+                }
+            }
+        }
+    }
+}
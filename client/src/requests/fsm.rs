@@ -0,0 +1,20 @@
+use crate::error::{Error, Result};
+use reqwasm::http::Request;
+use scrabble::util::fsm::FastFsm;
+
+/// The path to the word list, which is copied to the site root by trunk.
+const FSM_URL: &str = "/fast_fsm.bin";
+
+/// Fetches and deserializes the word list.
+pub async fn fetch_fsm() -> Result<FastFsm> {
+    let response = Request::get(FSM_URL).send().await?;
+
+    match response.status() {
+        200 => {
+            let bytes = response.binary().await?;
+
+            Ok(FastFsm::from_bytes(&bytes)?)
+        }
+        status => Err(Error::HttpStatus(status)),
+    }
+}
@@ -23,3 +23,13 @@ pub async fn stats(auth_signal: &AuthSignal, id_game: i32) -> Result<GameStatsRe
 pub async fn overall_stats(auth_signal: &AuthSignal) -> Result<OverallStatsResponse> {
     req_no_body("/games/stats", Method::GET, Some(auth_signal)).await
 }
+
+/// GET /api/games/{game id}/record [+Auth]
+pub async fn record(auth_signal: &AuthSignal, id_game: i32) -> Result<GameRecordResponse> {
+    req_no_body(
+        &format!("/games/{id_game}/record"),
+        Method::GET,
+        Some(auth_signal),
+    )
+    .await
+}
@@ -18,3 +18,13 @@ pub async fn overall_leaderboard(count: usize, offset: usize) -> Result<Leaderbo
 pub async fn friends_leaderboard(auth_signal: &AuthSignal) -> Result<LeaderboardResponse> {
     req_no_body("/leaderboard/friends", Method::GET, Some(auth_signal)).await
 }
+
+/// GET /api/leaderboard/puzzles
+pub async fn puzzle_leaderboard(limit: usize, offset: usize) -> Result<PuzzleLeaderboardResponse> {
+    req_no_body(
+        &format!("/leaderboard/puzzles?limit={limit}&offset={offset}"),
+        Method::GET,
+        None,
+    )
+    .await
+}
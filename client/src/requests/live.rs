@@ -1,5 +1,8 @@
 use crate::{error::Result, requests::API_HOST};
-use api::{auth::Token, routes::live::ClientMsg};
+use api::{
+    auth::Token,
+    routes::live::{ClientMsg, PROTOCOL_VERSION},
+};
 use futures::SinkExt;
 use reqwasm::websocket::{futures::WebSocket, Message};
 
@@ -10,7 +13,11 @@ pub async fn connect_and_authenticate(token: Token) -> Result<WebSocket> {
     let mut ws = WebSocket::open(&url)?;
 
     // Send a `ClientMsg::Auth` to authenticate the connection.
-    ws.send(to_msg(&ClientMsg::Auth(token))).await?;
+    let auth = ClientMsg::Auth {
+        version: PROTOCOL_VERSION,
+        token,
+    };
+    ws.send(to_msg(&auth)).await?;
 
     Ok(ws)
 }
@@ -9,9 +9,13 @@ use reqwasm::http::{Method, Request};
 use serde::{de::DeserializeOwned, Serialize};
 
 pub mod friends;
+pub mod fsm;
 pub mod games;
 pub mod leaderboard;
 pub mod live;
+pub mod puzzles;
+pub mod themes;
+pub mod tiles;
 pub mod users;
 
 /// The domain name and path to the API, excluding the protocol.
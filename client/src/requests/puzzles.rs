@@ -0,0 +1,22 @@
+//! Convenience methods for the puzzles api route.
+
+use crate::{
+    context::AuthSignal,
+    error::Result,
+    requests::{req_no_body, req_std},
+};
+use api::routes::puzzles::*;
+use reqwasm::http::Method;
+
+/// GET /api/puzzles/daily [+Auth]
+pub async fn daily(auth_signal: &AuthSignal) -> Result<DailyPuzzleResponse> {
+    req_no_body("/puzzles/daily", Method::GET, Some(auth_signal)).await
+}
+
+/// POST /api/puzzles/daily [+Auth]
+pub async fn attempt(
+    auth_signal: &AuthSignal,
+    req: &PuzzleAttemptRequest,
+) -> Result<PuzzleAttemptResponse> {
+    req_std("/puzzles/daily", Method::POST, Some(req), Some(auth_signal)).await
+}
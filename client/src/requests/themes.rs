@@ -0,0 +1,10 @@
+//! Convenience methods for the themes api route.
+
+use crate::{error::Result, requests::req_no_body};
+use api::routes::themes::*;
+use reqwasm::http::Method;
+
+/// GET /api/themes
+pub async fn list_themes() -> Result<ThemesResponse> {
+    req_no_body("/themes", Method::GET, None).await
+}
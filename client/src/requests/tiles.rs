@@ -0,0 +1,10 @@
+//! Convenience methods for the tiles api route.
+
+use crate::{error::Result, requests::req_no_body};
+use api::routes::tiles::*;
+use reqwasm::http::Method;
+
+/// GET /api/tiles
+pub async fn distribution() -> Result<TilesResponse> {
+    req_no_body("/tiles", Method::GET, None).await
+}
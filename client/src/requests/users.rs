@@ -3,7 +3,7 @@
 use crate::{
     context::AuthSignal,
     error::Result,
-    requests::{req_std, request},
+    requests::{req_no_body, req_std, request},
 };
 use api::{auth::Token, routes::users::*};
 use reqwasm::http::Method;
@@ -14,6 +14,11 @@ pub async fn login(req: &Login) -> Result<(Token, UserDetails)> {
     Ok((token.unwrap(), user_details))
 }
 
+/// POST /api/users/logout [+Auth]
+pub async fn log_out(auth_signal: &AuthSignal) -> Result<()> {
+    req_no_body("/users/logout", Method::POST, Some(auth_signal)).await
+}
+
 /// POST /api/users
 pub async fn sign_up(req: &SignUp) -> Result<(Token, UserDetails)> {
     let (token, user_details) = request("/users", Method::POST, Some(req), None).await?;
@@ -44,3 +49,10 @@ pub async fn reset_with_secret(req: &ResetWithSecret) -> Result<(Token, UserDeta
         request("/users/reset-password", Method::PUT, Some(req), None).await?;
     Ok((token.unwrap(), user_details))
 }
+
+/// PUT /api/users/verify-email
+pub async fn verify_email(req: &VerifyEmail) -> Result<(Token, UserDetails)> {
+    let (token, user_details) =
+        request("/users/verify-email", Method::PUT, Some(req), None).await?;
+    Ok((token.unwrap(), user_details))
+}
@@ -1,6 +1,6 @@
 use scrabble::{
     ai::Ai,
-    game::{Game, GameStatus},
+    game::{board::BonusLayout, Game, GameStatus},
     util::fsm::FastFsm,
 };
 use std::{fs::File, io::BufReader};
@@ -17,7 +17,7 @@ fn main() {
 
     // Let the AI play itself until the game is over.
     while let GameStatus::ToPlay(_) = game.status() {
-        let play = ai.next_play(&fsm, &game);
+        let play = ai.next_play(&fsm, &game).unwrap();
 
         if let Err(e) = game.make_play(&play, &fsm) {
             eprintln!("err: {e}");
@@ -26,7 +26,8 @@ fn main() {
     }
 
     // Display the final board state.
-    println!("{}", game.board());
+    let bonuses = BonusLayout::default();
+    println!("{}", game.board().display_with_bonuses(&bonuses));
     println!("{:#?}", game.status());
 
     if let GameStatus::Over(game_over) = game.status() {
@@ -0,0 +1,69 @@
+use scrabble::{
+    ai::Ai,
+    game::{play::Play, Game, GameStatus},
+    util::fsm::FastFsm,
+};
+use std::{
+    fs::File,
+    io::BufReader,
+    time::{Duration, Instant},
+};
+
+/// The number of games to simulate for each method.
+const GAME_COUNT: usize = 200;
+
+/// Plays a game between AI players. If `trusted` is set, placements are made
+/// with `Game::apply_generated`, otherwise with `Game::make_play`. Returns the
+/// number of plays and the time spent making them (excluding move generation).
+fn simulate(ai: &Ai, fsm: &FastFsm, trusted: bool) -> (usize, Duration) {
+    let mut game = Game::new(4);
+    let mut play_count = 0;
+    let mut elapsed = Duration::ZERO;
+
+    while let GameStatus::ToPlay(to_play) = *game.status() {
+        let gen_play = ai.next_generated(fsm, &game).unwrap();
+        let start = Instant::now();
+
+        match gen_play {
+            Some(gen_play) if trusted => game.apply_generated(gen_play).unwrap(),
+            Some(gen_play) => game.make_play(&Play::from(gen_play), fsm).unwrap(),
+            None => {
+                let rack = game.player(to_play).rack();
+                let play = Ai::fallback_play(rack, game.letter_bag_len(), game.config());
+                game.make_play(&play, fsm).unwrap();
+            }
+        }
+
+        elapsed += start.elapsed();
+        play_count += 1;
+    }
+
+    (play_count, elapsed)
+}
+
+fn main() {
+    // Open the FSM file.
+    let file = File::open("../server/data/fast_fsm.bin").unwrap();
+    let rdr = BufReader::new(file);
+    let fsm: FastFsm = bincode::deserialize_from(rdr).unwrap();
+
+    let ai = Ai::hard();
+
+    for (name, trusted) in [("make_play", false), ("apply_generated", true)] {
+        let start = Instant::now();
+        let (play_count, elapsed) = (0..GAME_COUNT)
+            .map(|_| simulate(&ai, &fsm, trusted))
+            .fold((0, Duration::ZERO), |(count, total), (c, t)| {
+                (count + c, total + t)
+            });
+        let total = start.elapsed();
+
+        println!(":: {name}");
+        println!("   {GAME_COUNT} games, {play_count} plays in {total:?}");
+        println!(
+            "   {:?} making plays ({:?} per play)",
+            elapsed,
+            elapsed / play_count as u32
+        );
+    }
+}
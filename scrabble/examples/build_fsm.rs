@@ -1,21 +1,40 @@
-use scrabble::util::fsm::{FastFsm, FsmBuilder, SmallFsm};
+use scrabble::{
+    ai::opening::OPENINGS,
+    util::fsm::{FastFsm, FsmBuilder, SmallFsm},
+};
 use std::{
+    collections::HashSet,
     fs::File,
     io::{BufRead, BufReader},
 };
 
+/// The word frequency list that the common words are taken from, with a
+/// word and its count on each line (most frequent first).
+const FREQUENCY_LIST: &str = "../server/data/en_50k.txt";
+/// The number of the most frequent words to keep as common words.
+const COMMON_WORD_COUNT: usize = 8000;
+
+/// Reads a list of words, with one word on each line.
+fn read_words(path: &str) -> Vec<String> {
+    let word_file = File::open(path).unwrap();
+    let reader = BufReader::new(word_file);
+
+    // remove whitespace from each line.
+    reader
+        .lines()
+        .flatten()
+        .map(|line| line.trim().to_string())
+        .collect()
+}
+
 fn main() {
     // open the list of words
-    let word_file = File::open("../server/data/words.txt").unwrap();
-    let reader = BufReader::new(word_file);
-    let mut builder = FsmBuilder::default();
+    let lines = read_words("../server/data/words.txt");
 
     println!(":: Building finite state machine");
 
-    // remove whitespace from each line then insert into the builder.
-    for line in reader.lines().flatten() {
-        builder.insert(line.trim());
-    }
+    // The word list is not guaranteed to be sorted, so let the builder sort it.
+    let builder = FsmBuilder::from_unsorted_iter(lines.iter().map(String::as_str));
 
     // build a FastFsm and write to a file.
     println!(":: Writing file 'fast_fsm.bin'");
@@ -28,4 +47,35 @@ fn main() {
     let small_fsm = SmallFsm::from(fast_fsm);
     let bytes = bincode::serialize(&small_fsm).unwrap();
     std::fs::write("small_fsm.bin", &bytes).unwrap();
+
+    // find the most frequent words, only keeping words that are in the
+    // full list, since plays are validated against the full list. The
+    // opening book words are always included.
+    let frequencies = match File::open(FREQUENCY_LIST) {
+        Ok(file) => BufReader::new(file),
+        Err(_) => {
+            println!(":: Skipping common words, '{FREQUENCY_LIST}' was not found");
+            return;
+        }
+    };
+    println!(":: Writing file 'common_words.txt'");
+    let words: HashSet<_> = lines.iter().map(String::as_str).collect();
+    let mut common = frequencies
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| Some(line.split_whitespace().next()?.to_lowercase()))
+        .filter(|word| words.contains(word.as_str()))
+        .take(COMMON_WORD_COUNT)
+        .chain(OPENINGS.iter().map(|word| word.to_string()))
+        .collect::<Vec<_>>();
+    common.sort();
+    common.dedup();
+    std::fs::write("common_words.txt", common.join("\n") + "\n").unwrap();
+
+    // build a FastFsm from the common words.
+    println!(":: Writing file 'common_fsm.bin'");
+    let builder = FsmBuilder::from_sorted_iter(common.iter().map(String::as_str)).unwrap();
+    let common_fsm: FastFsm = builder.build();
+    let bytes = bincode::serialize(&common_fsm).unwrap();
+    std::fs::write("common_fsm.bin", &bytes).unwrap();
 }
@@ -0,0 +1,262 @@
+use scrabble::{
+    ai::Ai,
+    error::{GameError, GameResult},
+    game::{
+        alphabet::Alphabet,
+        board::{Board, COLS},
+        play::Play,
+        tile::Letter,
+        Game,
+    },
+    util::{
+        bitboard::BitBoard,
+        fsm::{FastFsm, Fsm, StateId},
+        scoring,
+        words::{Word, WordsExt},
+    },
+};
+use std::{
+    fs::File,
+    io::BufReader,
+    time::{Duration, Instant},
+};
+
+/// The number of games to simulate.
+const GAME_COUNT: usize = 20;
+/// The number of times that the words of each placement are validated.
+const ITERATIONS: usize = 1000;
+
+/// A placement, as the board after the tiles were placed and the positions
+/// of the new tiles in the horizontal and vertical grids.
+struct Placement {
+    board: Board,
+    new_h: BitBoard,
+    new_v: BitBoard,
+}
+
+/// The letters of a word, with the state reached after each letter. A word
+/// can have at most [`COLS`] letters.
+#[derive(Debug, Clone, Copy, Default)]
+struct Path {
+    steps: [(Letter, StateId); COLS],
+    len: usize,
+}
+impl Path {
+    /// Gets the letters and states in the path.
+    fn steps(&self) -> &[(Letter, StateId)] {
+        &self.steps[..self.len]
+    }
+    /// Adds a letter and the state reached after it to the path.
+    fn push(&mut self, letter: Letter, state: StateId) {
+        self.steps[self.len] = (letter, state);
+        self.len += 1;
+    }
+}
+
+/// The states reached while validating the words formed by a play, so that
+/// a word sharing a prefix with an earlier word only traverses the fsm from
+/// the end of the shared prefix. A new cache should be used for each play.
+///
+/// Traversing a [`FastFsm`] is cheap, and the words formed by a play rarely
+/// share a prefix, so validating each word with [`scoring::score`] is usually
+/// faster, as this benchmark shows.
+#[derive(Debug, Default)]
+struct PrefixCache {
+    /// The path of each valid word.
+    paths: Vec<Path>,
+}
+impl PrefixCache {
+    /// Finds the longest cached prefix of `word`.
+    fn longest_prefix(&self, word: &Path) -> Path {
+        let mut prefix = Path::default();
+
+        for path in &self.paths {
+            let len = path
+                .steps()
+                .iter()
+                .zip(word.steps())
+                .take_while(|((a, _), (b, _))| a == b)
+                .count();
+
+            if len > prefix.len {
+                prefix = Path {
+                    steps: path.steps,
+                    len,
+                };
+            }
+        }
+
+        prefix
+    }
+}
+
+/// Validates a word and finds its score in the same way as [`scoring::score`],
+/// but resumes the traversal from the longest prefix that is shared with a
+/// word that was previously validated with `cache`.
+fn score_cached<'a>(
+    word: Word<'_>,
+    new: &BitBoard,
+    fsm: &impl Fsm<'a>,
+    alphabet: &Alphabet,
+    cache: &mut PrefixCache,
+) -> GameResult<usize> {
+    let mut letters = Path::default();
+    for (_, tile) in word.clone() {
+        letters.push(tile.letter()?, StateId::default());
+    }
+
+    let mut path = cache.longest_prefix(&letters);
+    let mut curr_state = match path.steps().last() {
+        Some(&(_, state)) => state,
+        None => fsm.initial_state(),
+    };
+
+    for &(letter, _) in &letters.steps()[path.len..] {
+        curr_state = fsm
+            .traverse_from(curr_state, letter)
+            .ok_or(GameError::InvalidWord)?;
+        path.push(letter, curr_state);
+    }
+
+    match fsm.is_terminal(curr_state) {
+        true => {
+            cache.paths.push(path);
+            Ok(scoring::score_unchecked(word, new, alphabet))
+        }
+        false => Err(GameError::InvalidWord),
+    }
+}
+
+/// Plays a game between AI players, returning every placement. Later
+/// placements are made on increasingly dense boards.
+fn placements(ai: &Ai, fsm: &FastFsm) -> Vec<Placement> {
+    let mut game = Game::new(4);
+    let mut placements = vec![];
+
+    while let Some(rack) = game.to_play_rack() {
+        let play = match ai.next_generated(fsm, &game).unwrap() {
+            Some(gen_play) => Play::from(gen_play),
+            None => Ai::fallback_play(rack, game.letter_bag_len(), game.config()),
+        };
+        game.make_play(&play, fsm).unwrap();
+
+        if let Play::Place(tile_positions) = play {
+            placements.push(Placement {
+                board: game.board().clone(),
+                new_h: tile_positions.iter().map(|&(pos, _)| pos).collect(),
+                new_v: tile_positions
+                    .iter()
+                    .map(|&(pos, _)| pos.swap_rc())
+                    .collect(),
+            });
+        }
+    }
+
+    placements
+}
+
+/// Validates the words formed by `placement` `ITERATIONS` times. If `cached`
+/// is set, the words share a [`PrefixCache`], otherwise each word is validated
+/// separately (as in `Board::make_placement`). Returns the total score (so
+/// that both methods can be compared) and the time taken.
+fn validate(placement: &Placement, fsm: &FastFsm, cached: bool) -> (usize, Duration) {
+    let Placement {
+        board,
+        new_h,
+        new_v,
+    } = placement;
+    let alphabet = Alphabet::default();
+    let start = Instant::now();
+    let mut total = 0;
+
+    for _ in 0..ITERATIONS {
+        let mut cache = PrefixCache::default();
+
+        for (grid, new) in [(board.grid_h(), new_h), (board.grid_v(), new_v)] {
+            let words = grid.occ().word_boundaries().intersecting(*new).words(grid);
+
+            for word in words {
+                let score = match cached {
+                    true => score_cached(word, new, fsm, &alphabet, &mut cache),
+                    false => scoring::score(word, new, fsm, &alphabet),
+                };
+                total += score.unwrap();
+            }
+        }
+    }
+
+    (total, start.elapsed())
+}
+
+fn main() {
+    // Open the FSM file.
+    let file = File::open("../server/data/fast_fsm.bin").unwrap();
+    let rdr = BufReader::new(file);
+    let fsm: FastFsm = bincode::deserialize_from(rdr).unwrap();
+
+    let ai = Ai::hard();
+    let placements = (0..GAME_COUNT)
+        .flat_map(|_| placements(&ai, &fsm))
+        .collect::<Vec<_>>();
+
+    for (name, cached) in [("per word", false), ("prefix cache", true)] {
+        let (total, elapsed) = placements
+            .iter()
+            .map(|placement| validate(placement, &fsm, cached))
+            .fold((0, Duration::ZERO), |(total, elapsed), (t, e)| {
+                (total + t, elapsed + e)
+            });
+
+        println!(":: {name}");
+        println!(
+            "   {} placements validated {ITERATIONS} times in {elapsed:?}",
+            placements.len()
+        );
+        println!("   total score {total}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scrabble::{
+        game::board::BoardBuilder,
+        util::{fsm::FsmBuilder, pos::Pos},
+    };
+
+    #[test]
+    fn same_scores() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["cab", "cat", "cats"])
+            .unwrap()
+            .build();
+        let mut builder = BoardBuilder::default();
+        for (row, word) in [(0, "cat"), (2, "cats"), (4, "cab"), (6, "ca"), (8, "cot")] {
+            builder = builder.place(Play::horizontal(Pos::from((row, 0))).letters(word));
+        }
+        let board = builder.build();
+        let grid = board.grid_h();
+        let new = *grid.occ();
+
+        // every word gives the same result with and without the cache, even
+        // though later words share a prefix with earlier ones.
+        let alphabet = Alphabet::default();
+        let mut cache = PrefixCache::default();
+        let words = grid.occ().word_boundaries().words(grid);
+        let results = words
+            .map(|word| {
+                let expected = scoring::score(word.clone(), &new, &fsm, &alphabet);
+                let actual = score_cached(word, &new, &fsm, &alphabet, &mut cache);
+                (expected, actual)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(5, results.len());
+        for (expected, actual) in results {
+            match (expected, actual) {
+                (Ok(expected), Ok(actual)) => assert_eq!(expected, actual),
+                (Err(GameError::InvalidWord), Err(GameError::InvalidWord)) => (),
+                results => panic!("results differ: {results:?}"),
+            }
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Fuzzes [`Board::make_placement`] with a sequence of random placements.
+//!
+//! The input is read as a list of placements. Each placement starts with a
+//! byte giving the tile count (modulo 9, so that invalid counts are also
+//! tried), followed by a `(pos, tile)` byte pair for each tile:
+//! * `pos` is the board position (modulo 225).
+//! * `tile` (modulo 64) is a letter for `0..=25`, a blank with a letter for
+//!   `26..=51`, and a blank without a letter otherwise.
+//!
+//! Run with the seed corpus (from the `scrabble` directory) using:
+//! ```text
+//! mkdir -p fuzz/corpus/make_placement
+//! cargo fuzz run make_placement fuzz/corpus/make_placement fuzz/seeds/make_placement
+//! ```
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use scrabble::{
+    game::{
+        board::Board,
+        tile::{Letter, Tile},
+        GameConfig,
+    },
+    util::{fsm::FastFsm, pos::Pos},
+};
+use std::sync::OnceLock;
+
+/// The word list that is bundled with the server.
+static FSM: OnceLock<FastFsm> = OnceLock::new();
+
+/// Decodes a tile from a byte.
+fn tile(byte: u8) -> Tile {
+    match byte % 64 {
+        letter @ 0..=25 => Tile::Letter(Letter::from(letter as usize)),
+        letter @ 26..=51 => Tile::Blank(Some(Letter::from(letter as usize - 26))),
+        _ => Tile::Blank(None),
+    }
+}
+
+/// Decodes the next placement, returning `None` at the end of the input.
+fn next_placement(data: &mut &[u8]) -> Option<Vec<(Pos, Tile)>> {
+    let (&count, rest) = data.split_first()?;
+    let count = usize::from(count % 9);
+    let len = rest.len().min(2 * count);
+    let (tiles, rest) = rest.split_at(len);
+
+    *data = rest;
+
+    Some(
+        tiles
+            .chunks_exact(2)
+            .map(|pair| (Pos::from(usize::from(pair[0])), tile(pair[1])))
+            .collect(),
+    )
+}
+
+/// Checks that the horizontal and vertical grids agree with each other
+/// and with their occupancy bitboards.
+fn assert_consistent(board: &Board) {
+    let grid_h = board.grid_h();
+    let grid_v = board.grid_v();
+
+    for pos in Pos::iter() {
+        let tile = grid_h[pos];
+
+        assert_eq!(tile, grid_v[pos.swap_rc()], "grids disagree at {pos}");
+        assert_eq!(
+            tile.is_some(),
+            grid_h.occ().is_set(pos),
+            "grid_h occ at {pos}"
+        );
+        assert_eq!(
+            tile.is_some(),
+            grid_v.occ().is_set(pos.swap_rc()),
+            "grid_v occ at {pos}"
+        );
+    }
+}
+
+/// Gets the tiles of the board, used to compare boards.
+fn tiles(board: &Board) -> Vec<Option<Tile>> {
+    Vec::from(board.clone())
+}
+
+fuzz_target!(|data: &[u8]| {
+    let fsm = FSM.get_or_init(|| {
+        let bytes = include_bytes!("../../../server/data/fast_fsm.bin");
+        FastFsm::from_bytes(bytes).unwrap()
+    });
+    let config = GameConfig::default();
+
+    let mut data = data;
+    let mut board = Board::default();
+
+    while let Some(tile_positions) = next_placement(&mut data) {
+        let before = tiles(&board);
+
+        match board.make_placement(&tile_positions, fsm, &config) {
+            Ok(score) => {
+                assert_consistent(&board);
+
+                // undoing the placement should restore the previous board.
+                board.undo_placement(&tile_positions);
+                assert_consistent(&board);
+                assert_eq!(before, tiles(&board), "undo did not restore the board");
+
+                // the same placement should then succeed with the same score.
+                let replayed = board.make_placement(&tile_positions, fsm, &config);
+                assert_eq!(Some(score), replayed.ok(), "replay changed the result");
+            }
+            Err(_) => {
+                // a failed placement should leave the board unchanged.
+                assert_consistent(&board);
+                assert_eq!(before, tiles(&board), "failed placement changed the board");
+            }
+        }
+    }
+});
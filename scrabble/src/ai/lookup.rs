@@ -1,8 +1,14 @@
 //! Precalculates the scores for vertical (perpendicular) words
-//! with a single letter placed in each column.
+//! with a single letter placed in each column, and finds the words
+//! that can be formed from a rack.
 
 use crate::{
-    game::{board::CELLS, tile::Tile},
+    game::{
+        alphabet::Alphabet,
+        board::CELLS,
+        rack::{Rack, RACK_SIZE},
+        tile::Tile,
+    },
     util::{
         bitboard::BitBoard,
         fsm::{Fsm, StateId},
@@ -24,25 +30,40 @@ pub struct Lookup {
 }
 impl Lookup {
     /// Creates a lookup for the perpendicular direction (to the `grid`).
-    /// `counts` are the frequencies of each tile on the rack.
-    pub fn new<'a, F: Fsm<'a>>(fsm: &'a F, counts: &TileCounts, grid: &Grid) -> Self {
+    /// `counts` are the frequencies of each tile on the rack, and tiles are
+    /// scored using `alphabet`.
+    pub fn new<'a, F: Fsm<'a>>(
+        fsm: &'a F,
+        counts: &TileCounts,
+        grid: &Grid,
+        alphabet: &Alphabet,
+    ) -> Self {
         let mut lookup = Lookup {
             above_or_below: grid.occ().above_or_below(),
             lookup: (0..CELLS).map(|_| HashMap::new()).collect(),
         };
 
-        lookup.init(fsm, counts, grid);
+        lookup.init(fsm, counts, grid, alphabet);
 
         lookup
     }
 
     /// Initializes the lookup table. This method is called by `Lookup::new`.
-    fn init<'a, F: Fsm<'a>>(&mut self, fsm: &'a F, counts: &TileCounts, grid: &Grid) {
+    fn init<'a, F: Fsm<'a>>(
+        &mut self,
+        fsm: &'a F,
+        counts: &TileCounts,
+        grid: &Grid,
+        alphabet: &Alphabet,
+    ) {
         // Each column can be considered seperately. Considering
         // columns seperately also means that fewer fsm traversals
         // are required, as these words are vertical.
         for col in Col::iter() {
-            let mut state = fsm.initial_state();
+            // `None` if the tiles above are not a prefix in the fsm. This
+            // only happens when the fsm is a subset of the word list used
+            // to validate plays.
+            let mut state = Some(fsm.initial_state());
             let mut score = 0;
 
             // Go down the rows for the current column
@@ -50,25 +71,24 @@ impl Lookup {
                 match grid[pos] {
                     // already a tile at this position, update score and state.
                     Some(tile @ Tile::Letter(letter) | tile @ Tile::Blank(Some(letter))) => {
-                        // since the tile has already been placed, the path should be in the fsm.
-                        state = fsm.traverse_from(state, letter).expect("a valid word");
+                        state = state.and_then(|state| fsm.traverse_from(state, letter));
                         // add the tile score but do not apply any premiums.
-                        score += tile.score();
+                        score += alphabet.score(tile);
                     }
                     _ => {
                         // if the position is not directly above or below
                         // an existing square then words placed there ignore
                         // the map for that square.
-                        if self.above_or_below.is_set(pos) {
+                        if let (true, Some(state)) = (self.above_or_below.is_set(pos), state) {
                             // iterate through letters that could be placed here.
                             for (letter, next_state) in fsm.transitions(state) {
                                 // a blank or standard tile could be placed for each letter.
                                 for tile in [Tile::Letter(letter), Tile::Blank(Some(letter))] {
                                     // check whether the tile is in the player's rack.
                                     if counts.any(tile) {
-                                        if let Some((tile, score)) =
-                                            self.score(grid, fsm, tile, score, pos, next_state)
-                                        {
+                                        if let Some((tile, score)) = Self::score(
+                                            grid, fsm, alphabet, tile, score, pos, next_state,
+                                        ) {
                                             // update the lookup table.
                                             self.lookup[usize::from(pos)].insert(tile, score);
                                         }
@@ -78,7 +98,7 @@ impl Lookup {
                         }
 
                         // reset the state and values as there is a break in the column.
-                        state = fsm.initial_state();
+                        state = Some(fsm.initial_state());
                         score = 0;
                     }
                 }
@@ -88,16 +108,16 @@ impl Lookup {
 
     /// Finds the scores for a vertical word from a position.
     fn score<'a, F: Fsm<'a>>(
-        &mut self,
         grid: &Grid,
         fsm: &F,
+        alphabet: &Alphabet,
         tile: Tile,
         score: usize,
         pos: Pos,
         state: StateId,
     ) -> Option<(Tile, usize)> {
         let (tile_m, multiplier) = pos.premium_multipliers();
-        let mut score = score + tile_m * tile.score();
+        let mut score = score + tile_m * alphabet.score(tile);
         let mut state = state;
 
         // keep following the word down the board until:
@@ -108,7 +128,7 @@ impl Lookup {
             match grid[pos] {
                 Some(tile @ Tile::Letter(letter) | tile @ Tile::Blank(Some(letter))) => {
                     // these tiles are already placed so premium does not apply
-                    score += tile.score();
+                    score += alphabet.score(tile);
                     state = fsm.traverse_from(state, letter)?;
                 }
                 _ => break,
@@ -136,3 +156,99 @@ impl Lookup {
         self.above_or_below.is_set(pos)
     }
 }
+
+/// Finds every word that uses all the tiles of a full rack (a bingo),
+/// in alphabetical order. A blank can stand for any letter. Returns an
+/// empty list if the rack has fewer than [`RACK_SIZE`] tiles.
+pub fn find_bingos<'a, F: Fsm<'a>>(fsm: &'a F, rack: &Rack) -> Vec<String> {
+    let mut bingos = vec![];
+
+    if rack.len() == RACK_SIZE {
+        let mut counts = *rack.tile_counts();
+        let mut word = String::with_capacity(RACK_SIZE);
+        find_bingos_from(
+            fsm,
+            fsm.initial_state(),
+            &mut counts,
+            &mut word,
+            &mut bingos,
+        );
+    }
+
+    // a word can be formed with either a letter or a blank, so may
+    // have been found more than once.
+    bingos.sort();
+    bingos.dedup();
+
+    bingos
+}
+
+/// Recursively extends `word` with the tiles remaining in `counts`, adding
+/// it to `bingos` once every tile has been used.
+fn find_bingos_from<'a, F: Fsm<'a>>(
+    fsm: &'a F,
+    state: StateId,
+    counts: &mut TileCounts,
+    word: &mut String,
+    bingos: &mut Vec<String>,
+) {
+    if counts.is_empty() {
+        if fsm.is_terminal(state) {
+            bingos.push(word.clone());
+        }
+        return;
+    }
+
+    for (letter, next_state) in fsm.transitions(state) {
+        for tile in [Tile::Letter(letter), Tile::Blank(None)] {
+            if counts.any(tile) {
+                counts.remove_one(tile);
+                word.push(char::from(letter));
+
+                find_bingos_from(fsm, next_state, counts, word, bingos);
+
+                word.pop();
+                counts.insert_one(tile);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::fsm::{FastFsm, FsmBuilder};
+
+    /// Creates a rack from letters and a number of blanks.
+    fn rack(letters: &str, blank_count: usize) -> Rack {
+        let blanks = (0..blank_count).map(|_| Tile::blank());
+        let tiles: Vec<_> = letters.chars().map(Tile::from).chain(blanks).collect();
+
+        Rack::with_tiles(&tiles)
+    }
+
+    #[test]
+    fn find_bingos() {
+        let fsm: FastFsm = FsmBuilder::from_unsorted_iter([
+            "retains", "stainer", "nastier", "retina", "ratines", "anestri", "stearin",
+        ])
+        .build();
+
+        // every anagram of the rack is found, but not shorter words.
+        let bingos = super::find_bingos(&fsm, &rack("aeinrst", 0));
+        assert_eq!(
+            vec!["ANESTRI", "NASTIER", "RATINES", "RETAINS", "STAINER", "STEARIN"],
+            bingos
+        );
+
+        // a blank stands for the missing letter, with each word found once.
+        let bingos = super::find_bingos(&fsm, &rack("aenrst", 1));
+        assert_eq!(6, bingos.len());
+        let bingos = super::find_bingos(&fsm, &rack("inrst", 2));
+        assert_eq!(6, bingos.len());
+
+        // a rack that is not full, or has no anagrams, has no bingos.
+        assert!(super::find_bingos(&fsm, &rack("aeinrs", 0)).is_empty());
+        assert!(super::find_bingos(&fsm, &rack("aeinrsz", 0)).is_empty());
+    }
+}
@@ -2,13 +2,26 @@
 
 use crate::{
     ai::movegen::GeneratedPlay,
-    game::{board::Board, play::Play, rack::Rack, Game, GameStatus},
-    util::{self, fsm::Fsm},
+    error::{GameError, GameResult},
+    game::{board::Board, play::Play, rack::Rack, Game, GameConfig},
+    util::{
+        self,
+        bitboard::BitBoard,
+        fsm::Fsm,
+        pos::{Pos, Premium},
+    },
 };
-use rand::Rng;
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    rngs::StdRng,
+    Rng, SeedableRng,
+};
+use serde::{Deserialize, Serialize};
+use std::{f32::consts::TAU, fmt};
 
 pub mod lookup;
 pub mod movegen;
+pub mod opening;
 
 /// The weighting of the proportional length difference in the score.
 const LEN_WEIGHT: f32 = 0.8;
@@ -18,17 +31,149 @@ const TILES_WEIGHT: f32 = 0.2;
 const SCORE_WEIGHT: f32 = 1.0;
 /// The weighting of the cross word count in the final score calculation.
 const CROSS_WORD_WEIGHT: f32 = 0.8;
+/// The weighting of the cross word count used by the defensive personality.
+const DEFENSIVE_CROSS_WORD_WEIGHT: f32 = 2.0;
+/// The weighting of the premium squares opened to the opponent in the final
+/// score calculation, used by the hard difficulty.
+pub const DEFENSE_WEIGHT: f32 = 1.0;
+/// The weighting of the premium squares opened to the opponent, used by
+/// the defensive personality.
+pub const DEFENSIVE_DEFENSE_WEIGHT: f32 = 3.0;
+/// A preferred score that is higher than any play can score, so that the
+/// highest scoring play is always preferred. (`usize::MAX` loses precision
+/// when the difference is converted to an `f32`).
+const MAX_PREFERRED_SCORE: usize = 2000;
 /// If there are no plays, tiles with fewer than
 const REDRAW_LIMIT: usize = 8;
 
+/// The words that an [`Ai`] will play.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Vocabulary {
+    /// Any word in the dictionary.
+    Full,
+    /// Only common words. Plays are generated from a smaller fsm containing a
+    /// subset of the dictionary, but are still validated against the full
+    /// dictionary.
+    Common,
+}
+
+/// The distribution of the random noise applied to the score of each play.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Noise {
+    /// Uniformly distributed in the range `-1..1`.
+    Uniform,
+    /// Normally distributed with a mean of 0 and standard deviation of 1.
+    /// Small amounts of noise are most likely, but larger amounts are
+    /// still possible.
+    Gaussian,
+}
+
+impl Noise {
+    /// Samples a value from the distribution.
+    fn sample<R: Rng>(self, rng: &mut R) -> f32 {
+        match self {
+            Noise::Uniform => rng.gen_range(-1.0..1.0),
+            // use the Box-Muller transform (`u1` is in the range `(0, 1]`
+            // to avoid taking the log of zero).
+            Noise::Gaussian => {
+                let u1 = 1.0 - rng.gen::<f32>();
+                let u2 = rng.gen::<f32>();
+
+                (-2.0 * u1.ln()).sqrt() * (TAU * u2).cos()
+            }
+        }
+    }
+}
+
+/// The difficulty of an AI player, which can be converted to an [`Ai`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AiDifficulty {
+    /// The [`Ai::easy`] preset.
+    Easy,
+    /// The [`Ai::medium`] preset.
+    Medium,
+    /// The [`Ai::hard`] preset.
+    Hard,
+    /// A difficulty chosen by the player.
+    Custom(AiParams),
+}
+impl fmt::Display for AiDifficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AiDifficulty::Easy => write!(f, "easy"),
+            AiDifficulty::Medium => write!(f, "medium"),
+            AiDifficulty::Hard => write!(f, "hard"),
+            AiDifficulty::Custom(_) => write!(f, "custom"),
+        }
+    }
+}
+impl From<AiDifficulty> for Ai {
+    fn from(difficulty: AiDifficulty) -> Self {
+        match difficulty {
+            AiDifficulty::Easy => Ai::easy(),
+            AiDifficulty::Medium => Ai::medium(),
+            AiDifficulty::Hard => Ai::hard(),
+            AiDifficulty::Custom(params) => Ai::from(params),
+        }
+    }
+}
+
+/// The settings of a [`AiDifficulty::Custom`] difficulty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AiParams {
+    /// The uncertainty of the score of each play, as a percentage
+    /// (at most 100).
+    pub random_percent: u8,
+    /// The number of best plays to choose between (at least 1).
+    pub top_k: usize,
+    /// The ideal score for each play.
+    pub preferred_score: usize,
+    /// If set, makes words of this length more likely to be chosen.
+    pub preferred_len: Option<usize>,
+    /// If set, makes plays containing this number of tiles more likely to be chosen.
+    pub preferred_tiles: Option<usize>,
+    /// If set, makes plays forming this number of perpendicular words more
+    /// likely to be chosen.
+    pub preferred_cross_words: Option<usize>,
+    /// The words that the Ai will play.
+    pub vocabulary: Vocabulary,
+}
+impl From<AiParams> for Ai {
+    fn from(params: AiParams) -> Self {
+        Self {
+            random_factor: f32::from(params.random_percent.min(100)) / 100.0,
+            noise: Noise::Gaussian,
+            top_k: params.top_k.max(1),
+            seed: None,
+            preferred_score: params.preferred_score,
+            preferred_len: params.preferred_len,
+            preferred_tiles: params.preferred_tiles,
+            preferred_cross_words: params.preferred_cross_words,
+            cross_word_weight: CROSS_WORD_WEIGHT,
+            vocabulary: params.vocabulary,
+            opening_book: false,
+            defense_weight: 0.0,
+        }
+    }
+}
+
 /// An Ai implementation that can play at varying difficulty levels.
 #[derive(Debug)]
 pub struct Ai {
     /// A number for randomising final move choices. The final score is:
-    ///     `score` * (1 + (`rand(-1..1)` * `random_factor`)).
+    ///     `score` * (1 + (`noise` * `random_factor`)).
     /// The `random_factor` can be thought of as an uncertainty for the final
     /// score.
     random_factor: f32,
+    /// The distribution that the noise is sampled from.
+    noise: Noise,
+    /// The number of best plays to choose between. The play is chosen
+    /// randomly from these, with better plays being more likely (set to 1
+    /// to always choose the best play).
+    top_k: usize,
+    /// If set, the random choices are seeded so that the same play is
+    /// always chosen for a position.
+    seed: Option<u64>,
     /// The ideal score for each play (set to infinity to maximise).
     preferred_score: usize,
     /// If set, makes words of this length more likely to be chosen.
@@ -37,6 +182,15 @@ pub struct Ai {
     preferred_tiles: Option<usize>,
     /// The range of the number of perpendicular words that can be formed in a play.
     preferred_cross_words: Option<usize>,
+    /// The weighting of the difference from `preferred_cross_words`.
+    cross_word_weight: f32,
+    /// The words that the Ai will play.
+    vocabulary: Vocabulary,
+    /// Whether the first play of the game is chosen from an opening book.
+    opening_book: bool,
+    /// The weighting of the premium squares that a play makes available to
+    /// the next player (zero to ignore them).
+    defense_weight: f32,
 }
 
 impl Default for Ai {
@@ -44,10 +198,17 @@ impl Default for Ai {
         // The default implementation chooses the highest scored play.
         Self {
             random_factor: 0.0,
+            noise: Noise::Uniform,
+            top_k: 1,
+            seed: None,
             preferred_score: usize::MAX,
             preferred_len: None,
             preferred_tiles: None,
             preferred_cross_words: None,
+            cross_word_weight: CROSS_WORD_WEIGHT,
+            vocabulary: Vocabulary::Full,
+            opening_book: false,
+            defense_weight: 0.0,
         }
     }
 }
@@ -56,30 +217,96 @@ impl Ai {
     pub fn easy() -> Self {
         Self {
             random_factor: 0.2,
+            noise: Noise::Gaussian,
+            top_k: 3,
+            seed: None,
             preferred_score: 17,
             preferred_len: Some(15),
             preferred_tiles: Some(6),
             preferred_cross_words: Some(0),
+            cross_word_weight: CROSS_WORD_WEIGHT,
+            vocabulary: Vocabulary::Common,
+            opening_book: true,
+            defense_weight: 0.0,
         }
     }
     /// A preset medium difficulty.
     pub fn medium() -> Self {
         Self {
             random_factor: 0.1,
+            noise: Noise::Gaussian,
+            top_k: 2,
+            seed: None,
             preferred_score: 28,
             preferred_len: Some(8),
             preferred_tiles: Some(5),
             preferred_cross_words: Some(1),
+            cross_word_weight: CROSS_WORD_WEIGHT,
+            vocabulary: Vocabulary::Common,
+            opening_book: true,
+            defense_weight: 0.0,
         }
     }
     /// A preset hard difficulty.
     pub fn hard() -> Self {
         Self {
             random_factor: 0.05,
+            noise: Noise::Uniform,
+            top_k: 1,
+            seed: None,
             preferred_score: 50,
             preferred_len: None,
             preferred_tiles: None,
             preferred_cross_words: None,
+            cross_word_weight: CROSS_WORD_WEIGHT,
+            vocabulary: Vocabulary::Full,
+            opening_book: false,
+            defense_weight: DEFENSE_WEIGHT,
+        }
+    }
+    /// A defensive personality, which keeps the board closed. Plays are
+    /// chosen by:
+    /// * a preferred score of 40, slightly below the hard difficulty.
+    /// * a `defense_weight` of [`DEFENSIVE_DEFENSE_WEIGHT`], so that each
+    ///   premium square opened to the next player costs more than a few
+    ///   points of score.
+    /// * a preference for 2 cross words with a high weighting, since plays
+    ///   that overlap existing words fill the gaps between them.
+    pub fn defensive() -> Self {
+        Self {
+            random_factor: 0.05,
+            noise: Noise::Uniform,
+            top_k: 1,
+            seed: None,
+            preferred_score: 40,
+            preferred_len: None,
+            preferred_tiles: None,
+            preferred_cross_words: Some(2),
+            cross_word_weight: DEFENSIVE_CROSS_WORD_WEIGHT,
+            vocabulary: Vocabulary::Full,
+            opening_book: false,
+            defense_weight: DEFENSIVE_DEFENSE_WEIGHT,
+        }
+    }
+    /// An aggressive personality, which opens the board. Plays are chosen by
+    /// score alone: the preferred score is higher than any play can score,
+    /// the squares opened to the next player are ignored, and there are no
+    /// length, tile count or cross word preferences. (The tiles kept on the
+    /// rack are never considered).
+    pub fn aggressive() -> Self {
+        Self {
+            random_factor: 0.05,
+            noise: Noise::Uniform,
+            top_k: 1,
+            seed: None,
+            preferred_score: MAX_PREFERRED_SCORE,
+            preferred_len: None,
+            preferred_tiles: None,
+            preferred_cross_words: None,
+            cross_word_weight: CROSS_WORD_WEIGHT,
+            vocabulary: Vocabulary::Full,
+            opening_book: false,
+            defense_weight: 0.0,
         }
     }
     /// A setting that always chooses the longest word.
@@ -93,6 +320,59 @@ impl Ai {
     pub fn highest_scoring() -> Self {
         Ai::default()
     }
+    /// Sets the words that the Ai will play.
+    pub fn with_vocabulary(self, vocabulary: Vocabulary) -> Self {
+        Self { vocabulary, ..self }
+    }
+    /// Sets whether the first play of the game is chosen from an
+    /// opening book.
+    pub fn with_opening_book(self, opening_book: bool) -> Self {
+        Self {
+            opening_book,
+            ..self
+        }
+    }
+    /// Sets the distribution of the noise applied to the score of each play.
+    pub fn with_noise(self, noise: Noise) -> Self {
+        Self { noise, ..self }
+    }
+    /// Sets the number of best plays that the Ai chooses between.
+    pub fn with_top_k(self, top_k: usize) -> Self {
+        Self {
+            top_k: top_k.max(1),
+            ..self
+        }
+    }
+    /// Seeds the random choices made by the Ai, so that the same play is
+    /// always chosen for a given position.
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self {
+            seed: Some(seed),
+            ..self
+        }
+    }
+    /// Sets the weighting of the premium squares that a play makes
+    /// available to the next player.
+    pub fn with_defense_weight(self, defense_weight: f32) -> Self {
+        Self {
+            defense_weight,
+            ..self
+        }
+    }
+
+    /// Gets the words that the Ai will play.
+    pub fn vocabulary(&self) -> Vocabulary {
+        self.vocabulary
+    }
+    /// Chooses the fsm that plays should be generated from: `common` if the
+    /// Ai only plays common words, otherwise `full`. The plays should still
+    /// be validated against `full`.
+    pub fn generation_fsm<'f, F>(&self, full: &'f F, common: &'f F) -> &'f F {
+        match self.vocabulary {
+            Vocabulary::Full => full,
+            Vocabulary::Common => common,
+        }
+    }
 
     /// Chooses a play based on the position and ai settings.
     pub fn select_play<'a, F: Fsm<'a>>(
@@ -101,57 +381,97 @@ impl Ai {
         board: &Board,
         rack: &Rack,
         letter_bag_len: usize,
+        config: &GameConfig,
     ) -> Play {
+        match self.select_generated(fsm, board, rack, config) {
+            // If there is a play, return it.
+            Some(gen_play) => Play::from(gen_play),
+            // Otherwise pass or redraw.
+            None => Self::fallback_play(rack, letter_bag_len, config),
+        }
+    }
+    /// Chooses a placement based on the position and ai settings, returning
+    /// `None` if no placement is possible. Plays are scored using the
+    /// alphabet of `config`, and opening plays also use its other rules.
+    pub fn select_generated<'a, F: Fsm<'a>>(
+        &self,
+        fsm: &'a F,
+        board: &Board,
+        rack: &Rack,
+        config: &GameConfig,
+    ) -> Option<GeneratedPlay> {
+        let mut rng = match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        // Check the opening book before generating every play.
+        if self.opening_book {
+            if let Some(gen_play) = opening::select(board, rack, fsm, config, &mut rng) {
+                return Some(gen_play);
+            }
+        }
+
         // Find an initial list of plays.
         let mut plays = vec![];
-        movegen::gen(board, rack, fsm, &mut plays);
+        movegen::gen(board, rack, fsm, &config.alphabet, &mut plays);
 
-        // The score should be minimised, so find the play with
-        // lowest score.
-        let best_play = plays
+        let scored = plays
             .into_iter()
-            .min_by(|a, b| {
-                let score_a = self.score(a);
-                let score_b = self.score(b);
+            .map(|gen_play| (self.score(board, &gen_play, &mut rng), gen_play))
+            .collect();
 
-                score_a.partial_cmp(&score_b).unwrap()
-            })
-            .map(Play::from);
+        choose(scored, self.top_k, &mut rng)
+    }
+    /// Chooses a play when no placement is possible. Passes if `config`
+    /// does not allow exchanges.
+    pub fn fallback_play(rack: &Rack, letter_bag_len: usize, config: &GameConfig) -> Play {
+        // If the rack has fewer than 7 tiles or exchanges are not
+        // allowed, always pass.
+        if rack.len() < 7 || !config.allow_exchange {
+            return Play::Pass;
+        }
 
-        // Return the play with highest final score.
-        match best_play {
-            // If there is a play, return it.
-            Some(play) => play,
-            // If the rack has fewer than 7 tiles, always pass.
-            None if rack.len() < 7 => Play::Pass,
-            // Only redraw if we have all 7 tiles.
-            None => {
-                // Find all rack tiles below the limit (taking no more than are left in
-                // the bag).
-                let redraw = rack
-                    .tiles()
-                    .filter(|tile| tile.score() <= REDRAW_LIMIT)
-                    .take(letter_bag_len)
-                    .collect::<Vec<_>>();
-
-                // Pass if there are no tiles to redraw.
-                match redraw.len() {
-                    0 => Play::Pass,
-                    _ => Play::Redraw(redraw),
-                }
-            }
+        // Only redraw if we have all 7 tiles. Find all rack tiles below
+        // the limit (taking no more than are left in the bag).
+        let redraw = rack
+            .tiles()
+            .filter(|&tile| config.alphabet.score(tile) <= REDRAW_LIMIT)
+            .take(letter_bag_len)
+            .collect::<Vec<_>>();
+
+        // Pass if there are no tiles to redraw.
+        match redraw.len() {
+            0 => Play::Pass,
+            _ => Play::Redraw(redraw),
         }
     }
     /// Chooses a play for the next player in the game based on
-    /// the board position and `difficulty`.
-    pub fn next_play<'a, F: Fsm<'a>>(&self, fsm: &'a F, game: &Game) -> Play {
-        let &to_play = match game.status() {
-            GameStatus::ToPlay(to_play) => to_play,
-            GameStatus::Over(_) => panic!("game is over"),
-        };
-        let rack = game.player(to_play).rack();
+    /// the board position and `difficulty`. Returns [`GameError::Over`]
+    /// if the game is over.
+    pub fn next_play<'a, F: Fsm<'a>>(&self, fsm: &'a F, game: &Game) -> GameResult<Play> {
+        let rack = game.to_play_rack().ok_or(GameError::Over)?;
+
+        Ok(self.select_play(
+            fsm,
+            game.board(),
+            rack,
+            game.letter_bag_len(),
+            game.config(),
+        ))
+    }
+    /// Chooses a placement for the next player in the game, which can be
+    /// applied with [`Game::apply_generated`]. Returns `None` if no
+    /// placement is possible, in which case [`Ai::fallback_play`] should
+    /// be made instead, or [`GameError::Over`] if the game is over.
+    pub fn next_generated<'a, F: Fsm<'a>>(
+        &self,
+        fsm: &'a F,
+        game: &Game,
+    ) -> GameResult<Option<GeneratedPlay>> {
+        let rack = game.to_play_rack().ok_or(GameError::Over)?;
 
-        self.select_play(fsm, game.board(), rack, game.letter_bag_len())
+        Ok(self.select_generated(fsm, game.board(), rack, game.config()))
     }
 
     /// Takes into account:
@@ -159,38 +479,383 @@ impl Ai {
     /// * `preferred_len`
     /// * `preferred_tiles`
     /// * `preferred_cross_words`
+    /// * `defense_weight`
     /// and the score of the word to calculate a new score, which
     /// should be minimised.
-    fn score(&self, gen_play: &GeneratedPlay) -> f32 {
+    fn score<R: Rng>(&self, board: &Board, gen_play: &GeneratedPlay, rng: &mut R) -> f32 {
         // find the difference between the word length and preferred length
         let len_score = self
             .preferred_len
-            .map(|pl| util::abs_diff(pl, gen_play.len))
+            .map(|pl| util::abs_diff(pl, gen_play.word_len()))
             .map(|diff| LEN_WEIGHT * (diff as f32))
             .unwrap_or(0.0);
         // find the difference between the tile count and preferred count
         let tiles_score = self
             .preferred_tiles
-            .map(|pt| util::abs_diff(pt, gen_play.tile_positions.len()))
+            .map(|pt| util::abs_diff(pt, gen_play.tile_positions().len()))
             .map(|diff| TILES_WEIGHT * (diff as f32))
             .unwrap_or(0.0);
         // find the difference between the cross word count and preferred count.
         let cross_word_score = self
             .preferred_cross_words
-            .map(|pcw| util::abs_diff(pcw, gen_play.cross_count))
-            .map(|diff| CROSS_WORD_WEIGHT * (diff as f32))
+            .map(|pcw| util::abs_diff(pcw, gen_play.cross_count()))
+            .map(|diff| self.cross_word_weight * (diff as f32))
             .unwrap_or(0.0);
         // find the difference between the actual score and the preferred score.
-        let score_diff = util::abs_diff(self.preferred_score, gen_play.score) as f32;
+        let score_diff = util::abs_diff(self.preferred_score, gen_play.score()) as f32;
+        // penalise plays that give the next player access to premium squares.
+        let defense_score = match self.defense_weight > 0.0 {
+            true => self.defense_weight * opened_premiums(board, gen_play),
+            false => 0.0,
+        };
 
         // smaller is better for `len_diff`, `tiles_diff` and `score_diff`, so
         // combine them by taking the reciprocal of each. (1.0 is added to each
         // to avoid a zero division error). Each reciprocal is multiplied by a
         // constant weight factor to fine tune the evaulation function.
-        let combined_score = cross_word_score + len_score + tiles_score + SCORE_WEIGHT * score_diff;
+        let combined_score =
+            cross_word_score + len_score + tiles_score + defense_score + SCORE_WEIGHT * score_diff;
 
         // apply a final random factor to the score.
-        let multiplier = rand::thread_rng().gen_range(-1.0..1.0);
+        let multiplier = self.noise.sample(rng);
         combined_score * (1.0 + multiplier * self.random_factor)
     }
 }
+
+/// Chooses a play from the `top_k` plays with the lowest score. The score
+/// should be minimised, so the chance of choosing each play is proportional
+/// to the reciprocal of its score.
+fn choose<R: Rng>(
+    mut scored: Vec<(f32, GeneratedPlay)>,
+    top_k: usize,
+    rng: &mut R,
+) -> Option<GeneratedPlay> {
+    scored.sort_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+    scored.truncate(top_k.max(1));
+
+    // (1.0 is added to avoid a zero division error).
+    let weights = scored.iter().map(|(score, _)| 1.0 / (1.0 + score.max(0.0)));
+    let idx = WeightedIndex::new(weights).ok()?.sample(rng);
+
+    Some(scored.swap_remove(idx).1)
+}
+
+/// Finds the total value of the empty premium squares that are adjacent to
+/// the play, but were not adjacent to any tile before the play. These squares
+/// can only be used by the next player because of the play.
+fn opened_premiums(board: &Board, gen_play: &GeneratedPlay) -> f32 {
+    let &occ = board.grid_h().occ();
+    let new: BitBoard = gen_play
+        .tile_positions()
+        .iter()
+        .map(|&(pos, _)| pos)
+        .collect();
+    let opened = (occ | new).neighbours() & !occ.neighbours() & !occ;
+
+    opened
+        .into_iter()
+        .filter_map(|pos: Pos| pos.premium())
+        .map(|premium| match premium {
+            Premium::DoubleLetter => 1.0,
+            Premium::TripleLetter => 2.0,
+            Premium::DoubleWord | Premium::Start => 3.0,
+            Premium::TripleWord => 6.0,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game::{
+            board::BoardBuilder, letter_bag::LetterBag, rack::Rack, tile::Tile, GameStatus,
+            PlayerNum,
+        },
+        util::fsm::{FastFsm, FsmBuilder},
+    };
+
+    /// Creates a play from `(row, col)` pairs.
+    fn gen_play(positions: &[(usize, usize)], score: usize) -> GeneratedPlay {
+        GeneratedPlay::new(
+            positions
+                .iter()
+                .map(|&rc| (Pos::from(rc), Tile::from('e')))
+                .collect(),
+            score,
+            0,
+            positions.len() + 3,
+        )
+    }
+
+    #[test]
+    fn prefers_defensive_play() {
+        // "CAT" from H8 to J8.
+        let board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::start()).letters("cat"))
+            .build();
+
+        // extending "CAT" to N8 opens the triple word at O8 (and two
+        // double letters), but scores slightly more than a play before
+        // "CAT", which only opens two double letters.
+        let open = gen_play(&[(7, 10), (7, 11), (7, 12), (7, 13)], 20);
+        let closed = gen_play(&[(7, 6)], 19);
+        assert_eq!(8.0, opened_premiums(&board, &open));
+        assert_eq!(2.0, opened_premiums(&board, &closed));
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let ai = Ai {
+            random_factor: 0.0,
+            ..Ai::hard()
+        };
+        assert!(ai.score(&board, &closed, &mut rng) < ai.score(&board, &open, &mut rng));
+
+        let ai = ai.with_defense_weight(0.0);
+        assert!(ai.score(&board, &open, &mut rng) < ai.score(&board, &closed, &mut rng));
+    }
+
+    #[test]
+    fn personalities() {
+        // "CAT" from L8 to N8, with the triple word at O8 open, and "ON"
+        // from M10 to N10.
+        let board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::from((7, 11))).letters("cat"))
+            .place(Play::horizontal(Pos::from((9, 12))).letters("on"))
+            .build();
+        let play = |positions: &[(usize, usize)], score, cross_count| {
+            let tile_positions = positions
+                .iter()
+                .map(|&rc| (Pos::from(rc), Tile::from('e')))
+                .collect();
+            GeneratedPlay::new(tile_positions, score, cross_count, positions.len())
+        };
+
+        // a play down column O closes the triple word, forming cross words
+        // with "CAT" and "ON". A play down column K scores more, forming a
+        // cross word with "CAT", but leaves the triple word open and opens a
+        // double word and a triple letter.
+        let close = play(&[(6, 14), (7, 14), (8, 14), (9, 14)], 30, 2);
+        let open = play(&[(3, 10), (4, 10), (5, 10), (6, 10), (7, 10)], 44, 1);
+        assert_eq!(0.0, opened_premiums(&board, &close));
+        assert_eq!(5.0, opened_premiums(&board, &open));
+
+        // without noise, the defensive score is the difference from the
+        // preferred score (40) and cross word count (2), and the value of
+        // the opened premiums.
+        let mut rng = StdRng::seed_from_u64(0);
+        let exact = Ai {
+            random_factor: 0.0,
+            ..Ai::defensive()
+        };
+        assert_eq!(10.0, exact.score(&board, &close, &mut rng));
+        assert_eq!(
+            4.0 + DEFENSIVE_CROSS_WORD_WEIGHT + DEFENSIVE_DEFENSE_WEIGHT * 5.0,
+            exact.score(&board, &open, &mut rng)
+        );
+
+        // the defensive personality closes the triple word, whereas the
+        // aggressive personality takes the points (which would be lost to
+        // rounding with a preferred score of `usize::MAX`).
+        let chosen = |ai: Ai| {
+            let mut rng = StdRng::seed_from_u64(0);
+            let scored = [&close, &open]
+                .into_iter()
+                .map(|gen_play| {
+                    let tile_positions = gen_play.tile_positions().to_vec();
+                    (ai.score(&board, gen_play, &mut rng), tile_positions)
+                })
+                .min_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap());
+            scored.unwrap().1
+        };
+        assert_eq!(close.tile_positions(), chosen(Ai::defensive()));
+        assert_eq!(open.tile_positions(), chosen(Ai::aggressive()));
+    }
+
+    #[test]
+    fn gaussian_noise() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let samples: Vec<_> = (0..10_000)
+            .map(|_| Noise::Gaussian.sample(&mut rng))
+            .collect();
+
+        let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+        let variance =
+            samples.iter().map(|x| (x - mean).powi(2)).sum::<f32>() / samples.len() as f32;
+        assert!(mean.abs() < 0.05);
+        assert!((variance - 1.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn chooses_from_top_k() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let scored = |scores: &[usize]| {
+            scores
+                .iter()
+                .map(|&score| (score as f32, gen_play(&[], score)))
+                .collect::<Vec<_>>()
+        };
+
+        // only the best play can be chosen.
+        for _ in 0..20 {
+            let gen_play = choose(scored(&[4, 1, 3, 2]), 1, &mut rng).unwrap();
+            assert_eq!(1, gen_play.score());
+        }
+
+        // every play in the top 2 is chosen at some point.
+        let mut counts = [0; 5];
+        for _ in 0..200 {
+            let gen_play = choose(scored(&[4, 1, 3, 2]), 2, &mut rng).unwrap();
+            counts[gen_play.score()] += 1;
+        }
+        assert_eq!(0, counts[3] + counts[4]);
+        assert!(counts[1] > counts[2] && counts[2] > 0);
+
+        assert!(choose(vec![], 3, &mut rng).is_none());
+    }
+
+    #[test]
+    fn custom_difficulty() {
+        // the presets are unchanged.
+        let hard = Ai::from(AiDifficulty::Hard);
+        assert_eq!(format!("{:?}", Ai::hard()), format!("{hard:?}"));
+
+        // out of range settings are clamped.
+        let difficulty = AiDifficulty::Custom(AiParams {
+            random_percent: 250,
+            top_k: 0,
+            preferred_score: 20,
+            preferred_len: Some(4),
+            preferred_tiles: None,
+            preferred_cross_words: None,
+            vocabulary: Vocabulary::Common,
+        });
+        let ai = Ai::from(difficulty);
+        assert_eq!(1.0, ai.random_factor);
+        assert_eq!(1, ai.top_k);
+        assert_eq!(Some(4), ai.preferred_len);
+        assert_eq!(Vocabulary::Common, ai.vocabulary());
+        assert_eq!("custom", difficulty.to_string());
+    }
+
+    #[test]
+    fn seeded_choice() {
+        let fsm: FastFsm =
+            FsmBuilder::from_unsorted_iter(["at", "cat", "act", "tac", "ta", "eat", "tea", "ate"])
+                .build();
+        let rack = Rack::with_str("aceqtxz");
+        let ai = Ai::easy().with_opening_book(false).with_seed(7);
+        let config = GameConfig::default();
+
+        let first = ai
+            .select_generated(&fsm, &Board::default(), &rack, &config)
+            .unwrap();
+        for _ in 0..10 {
+            let gen_play = ai
+                .select_generated(&fsm, &Board::default(), &rack, &config)
+                .unwrap();
+            assert_eq!(first.tile_positions(), gen_play.tile_positions());
+        }
+    }
+
+    #[test]
+    fn seeded_opening() {
+        let fsm = FastFsm::bundled();
+        let rack = Rack::with_str("aeilrst");
+        let config = GameConfig::default();
+
+        for ai in [Ai::easy(), Ai::medium()] {
+            let ai = ai.with_seed(3);
+            let first = ai
+                .select_generated(fsm, &Board::default(), &rack, &config)
+                .unwrap();
+            for _ in 0..10 {
+                let gen_play = ai
+                    .select_generated(fsm, &Board::default(), &rack, &config)
+                    .unwrap();
+                assert_eq!(first.tile_positions(), gen_play.tile_positions());
+            }
+        }
+    }
+
+    #[test]
+    fn fallback_without_exchange() {
+        let rack = Rack::with_str("aeiorst");
+        let config = GameConfig::default();
+        assert!(matches!(
+            Ai::fallback_play(&rack, 100, &config),
+            Play::Redraw(tiles) if tiles.len() == 7
+        ));
+
+        // the ai passes instead of exchanging when it is not allowed.
+        let config = GameConfig {
+            allow_exchange: false,
+            ..config
+        };
+        assert!(matches!(Ai::fallback_play(&rack, 100, &config), Play::Pass));
+    }
+
+    #[test]
+    fn next_play_when_over() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["cat"]).unwrap().build();
+        let ai = Ai::default();
+        let mut game = Game::new(2);
+
+        // end the game by passing.
+        while let GameStatus::ToPlay(_) = game.status() {
+            assert!(ai.next_play(&fsm, &game).is_ok());
+            game.make_play(&Play::Pass, &fsm).unwrap();
+        }
+
+        assert!(matches!(ai.next_play(&fsm, &game), Err(GameError::Over)));
+        assert!(matches!(
+            ai.next_generated(&fsm, &game),
+            Err(GameError::Over)
+        ));
+    }
+
+    #[test]
+    fn generation_vocabulary() {
+        let full: FastFsm = FsmBuilder::from_sorted_iter(["cat", "qat"])
+            .unwrap()
+            .build();
+        let common: FastFsm = FsmBuilder::from_sorted_iter(["cat"]).unwrap().build();
+        let new_game = || {
+            let racks = vec![Rack::with_str("cqat"), Rack::with_str("cqat")];
+            Game::from_position(
+                Board::default(),
+                racks,
+                LetterBag::default(),
+                PlayerNum::first(),
+            )
+            .unwrap()
+        };
+
+        // "QAT" is closest to the preferred score, so an ai with the full
+        // dictionary plays it.
+        let ai = Ai::hard().with_defense_weight(0.0).with_seed(0);
+        let fsm = ai.generation_fsm(&full, &common);
+        let play = ai.next_play(fsm, &new_game()).unwrap();
+        assert!(
+            matches!(&play, Play::Place(tiles) if tiles.iter().any(|&(_, tile)| tile == Tile::from('q')))
+        );
+
+        // an ai restricted to common words plays "CAT" instead.
+        let ai = ai.with_vocabulary(Vocabulary::Common);
+        let fsm = ai.generation_fsm(&full, &common);
+        let play = ai.next_play(fsm, &new_game()).unwrap();
+        assert!(
+            matches!(&play, Play::Place(tiles) if tiles.iter().all(|&(_, tile)| tile != Tile::from('q')))
+        );
+
+        // but a human can still play "QAT", which is validated against the
+        // full dictionary.
+        let mut game = new_game();
+        let qat = Play::Place(
+            Play::horizontal(Pos::start())
+                .letters("qat")
+                .tile_positions(game.board()),
+        );
+        game.make_play(&qat, &full).unwrap();
+        assert_eq!(24, game.player(PlayerNum::first()).score());
+    }
+}
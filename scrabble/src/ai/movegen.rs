@@ -1,9 +1,20 @@
 //! Move generator implementation. Use the top level function [`gen`]
 //! to get a list of scored plays.
+//!
+//! Plays are generated one row at a time (the vertical plays are found
+//! by running the same generator on the transposed grid). Rather than
+//! anchoring on a single square, generation starts from every square
+//! returned by [`util::possible_starts_h`]: any empty square up to
+//! `rack_len - 1` squares west of a tile or a square adjacent to a tile,
+//! and the first square of each existing word. From each start the row
+//! is traversed eastwards, so existing tiles can be extended at either
+//! end (e.g. "CAT" to "CATS" or "SCAT") or used in the middle of a play.
+//! A word is only accepted once the square after it is empty.
 
 use crate::{
     ai::lookup::Lookup,
     game::{
+        alphabet::Alphabet,
         board::Board,
         play::Play,
         rack::Rack,
@@ -19,25 +30,37 @@ use crate::{
     },
 };
 
-/// Adds all moves for the board position to `plays`. (Clears `plays` first).
-pub fn gen<'a>(board: &Board, rack: &Rack, fsm: &'a impl Fsm<'a>, plays: &mut Vec<GeneratedPlay>) {
+/// Adds all moves for the board position to `plays`, scored using the tile
+/// scores of `alphabet`. (Clears `plays` first).
+pub fn gen<'a>(
+    board: &Board,
+    rack: &Rack,
+    fsm: &'a impl Fsm<'a>,
+    alphabet: &Alphabet,
+    plays: &mut Vec<GeneratedPlay>,
+) {
     plays.clear();
-    MoveGen::new(rack, board.grid_v(), fsm).gen(plays);
-    MoveGen::new(rack, board.grid_h(), fsm).gen(plays);
+    MoveGen::new(rack, board.grid_v(), fsm, alphabet).gen(plays);
+    MoveGen::new(rack, board.grid_h(), fsm, alphabet).gen(plays);
 }
 
 /// Stores a generated play and details that can be used to
 /// score the play.
+///
+/// Can only be created by the move generator (or the opening book), so that
+/// a [`GeneratedPlay`] can be applied with
+/// [`Game::apply_generated`](crate::game::Game::apply_generated) without the
+/// words being checked again.
 #[derive(Debug)]
 pub struct GeneratedPlay {
     /// The play (tile positions).
-    pub tile_positions: Vec<(Pos, Tile)>,
+    tile_positions: Vec<(Pos, Tile)>,
     /// The total score of the play.
-    pub score: usize,
+    score: usize,
     /// The number of perpendicular words formed.
-    pub cross_count: usize,
+    cross_count: usize,
     /// The length of the primary word.
-    pub len: usize,
+    len: usize,
 }
 impl From<GeneratedPlay> for Play {
     fn from(gen_play: GeneratedPlay) -> Self {
@@ -45,6 +68,36 @@ impl From<GeneratedPlay> for Play {
     }
 }
 impl GeneratedPlay {
+    /// Creates a [`GeneratedPlay`].
+    pub(crate) fn new(
+        tile_positions: Vec<(Pos, Tile)>,
+        score: usize,
+        cross_count: usize,
+        len: usize,
+    ) -> Self {
+        Self {
+            tile_positions,
+            score,
+            cross_count,
+            len,
+        }
+    }
+    /// Gets the tile positions of the play.
+    pub fn tile_positions(&self) -> &[(Pos, Tile)] {
+        &self.tile_positions
+    }
+    /// Gets the total score of the play.
+    pub fn score(&self) -> usize {
+        self.score
+    }
+    /// Gets the number of perpendicular words formed.
+    pub fn cross_count(&self) -> usize {
+        self.cross_count
+    }
+    /// Gets the length of the primary word.
+    pub fn word_len(&self) -> usize {
+        self.len
+    }
     /// Converts to a [`Play`].
     pub fn play(self) -> Play {
         Play::from(self)
@@ -56,6 +109,7 @@ impl GeneratedPlay {
 struct WordState {
     state: StateId,
     score: usize,
+    cross_score: usize,
     cross_count: usize,
     len: usize,
     multiplier: usize,
@@ -67,10 +121,10 @@ struct WordState {
 struct MoveGen<'a, 'b, F> {
     fsm: &'a F,
     grid: &'b Grid,
+    alphabet: &'b Alphabet,
     lookup: Lookup,
 
     occ: BitBoard,
-    illegal_ends: BitBoard,
     neighbours: BitBoard,
 
     stack: Vec<(Pos, Tile)>,
@@ -82,10 +136,10 @@ where
     F: Fsm<'a>,
 {
     /// Creates a new [`MoveGen`].
-    pub fn new(rack: &Rack, grid: &'b Grid, fsm: &'a F) -> Self {
+    pub fn new(rack: &Rack, grid: &'b Grid, fsm: &'a F, alphabet: &'b Alphabet) -> Self {
         let &occ = grid.occ();
         let &counts = rack.tile_counts();
-        let lookup = Lookup::new(fsm, &counts, grid);
+        let lookup = Lookup::new(fsm, &counts, grid, alphabet);
 
         let mut neighbours = occ.neighbours();
         neighbours.set(Pos::start());
@@ -93,10 +147,10 @@ where
         Self {
             fsm,
             grid,
+            alphabet,
             lookup,
 
             occ,
-            illegal_ends: occ.west(),
             neighbours,
 
             stack: vec![],
@@ -112,6 +166,7 @@ where
                 WordState {
                     state: self.fsm.initial_state(),
                     score: 0,
+                    cross_score: 0,
                     cross_count: 0,
                     len: 0,
                     multiplier: 1,
@@ -122,7 +177,7 @@ where
     }
     /// Recursively traverses possible moves and adds them to the list.
     fn gen_recursive(&mut self, plays: &mut Vec<GeneratedPlay>, pos: Option<Pos>, ws: WordState) {
-        self.check_position(plays, &ws);
+        self.check_position(plays, pos, &ws);
 
         if let Some(pos) = pos {
             let next_pos = pos.dir(Direction::East);
@@ -156,7 +211,8 @@ where
                 next_pos,
                 WordState {
                     state: next_state,
-                    score: ws.score + tile.score(),
+                    score: ws.score + self.alphabet.score(tile),
+                    cross_score: ws.cross_score,
                     len: ws.len + 1,
                     cross_count: ws.cross_count,
                     multiplier: ws.multiplier,
@@ -189,7 +245,10 @@ where
                             next_pos,
                             WordState {
                                 state: next_state,
-                                score: ws.score + tile_m * tile.score() + perpendicular_score,
+                                score: ws.score + tile_m * self.alphabet.score(tile),
+                                // perpendicular words are not affected by the
+                                // word multiplier of the main word.
+                                cross_score: ws.cross_score + perpendicular_score,
                                 // increment the cross count if a perpendicular
                                 // word with a non zero score is placed.
                                 cross_count: ws.cross_count
@@ -209,14 +268,20 @@ where
     }
     /// Checks whether a point in the recursive stack is a valid move,
     /// and if so adds it to the list.
-    fn check_position(&self, plays: &mut Vec<GeneratedPlay>, ws: &WordState) {
+    /// `next_pos` is the square directly after the end of the word.
+    fn check_position(
+        &self,
+        plays: &mut Vec<GeneratedPlay>,
+        next_pos: Option<Pos>,
+        ws: &WordState,
+    ) {
         // check that the word is connected and is valid.
         if ws.connected && self.fsm.is_terminal(ws.state) {
-            // check that the final stack item does not have
-            // a disallowed end position & that stack is not empty.
+            // check that the stack is not empty.
             if let Some(&(pos, _)) = self.stack.last() {
-                // prevents illegal plays if the end of a word is adjacent to a tile.
-                if !self.illegal_ends.is_set(pos) {
+                // prevents illegal plays if the square after the word is occupied,
+                // as the word would then continue into that tile.
+                if !next_pos.is_some_and(|next_pos| self.occ.is_set(next_pos)) {
                     // prevents doubled up moves from horizontal and vertical generation.
                     // if there is only one tile, it must not be adjacent.
                     if self.stack.len() > 1 || !self.lookup.is_above_or_below(pos) {
@@ -240,9 +305,201 @@ where
                 // maps the position back to the horizontal coordinate.
                 .map(|&(pos, tile)| (self.grid.map_pos(pos), tile))
                 .collect(),
-            score: ws.score * ws.multiplier + all_tiles_bonus,
+            score: ws.score * ws.multiplier + ws.cross_score + all_tiles_bonus,
             cross_count: 0,
             len: ws.len,
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game::{board::BoardBuilder, GameConfig},
+        util::fsm::{FastFsm, FsmBuilder},
+    };
+    use proptest::{prelude::*, sample::Index};
+
+    /// A strategy for a rack of 1 to 7 random tiles (including blanks).
+    fn rack() -> impl Strategy<Value = Rack> {
+        prop::collection::vec((0..27usize).prop_map(Tile::from), 1..=7)
+            .prop_map(|tiles| Rack::with_tiles(&tiles))
+    }
+
+    /// A strategy for a reachable board position, found by replaying a
+    /// random sequence of generated plays from random racks.
+    fn board() -> impl Strategy<Value = Board> {
+        prop::collection::vec((rack(), any::<Index>()), 0..12).prop_map(|steps| {
+            let fsm = FastFsm::bundled();
+            let config = GameConfig::default();
+            let mut board = Board::default();
+            let mut plays = vec![];
+
+            for (rack, index) in steps {
+                gen(&board, &rack, fsm, &Alphabet::default(), &mut plays);
+
+                if !plays.is_empty() {
+                    let play = &plays[index.index(plays.len())];
+                    board
+                        .make_placement(&play.tile_positions, fsm, &config)
+                        .unwrap();
+                }
+            }
+
+            board
+        })
+    }
+
+    /// Generates all plays for a rack of "S" on a board containing
+    /// "CAT" from the start square.
+    fn gen_cat_plays() -> Vec<GeneratedPlay> {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["at", "cat", "cats", "scat", "ta"])
+            .unwrap()
+            .build();
+
+        let board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::start()).letters("cat"))
+            .build();
+        let rack = Rack::with_str("s");
+
+        let mut plays = vec![];
+        gen(&board, &rack, &fsm, &Alphabet::default(), &mut plays);
+        plays
+    }
+
+    fn contains_single(plays: &[GeneratedPlay], pos: Option<Pos>) -> bool {
+        let pos = pos.unwrap();
+        plays
+            .iter()
+            .any(|p| p.tile_positions == [(pos, Tile::Letter(Letter::new('s').unwrap()))])
+    }
+
+    #[test]
+    fn multiple_blanks() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["cats"]).unwrap().build();
+
+        let board = Board::default();
+        let rack = Rack::with_tiles(&[Tile::Blank(None); 4]);
+
+        let mut plays = vec![];
+        gen(&board, &rack, &fsm, &Alphabet::default(), &mut plays);
+
+        // every play should use all four blanks to spell "cats".
+        assert!(!plays.is_empty());
+        for play in &plays {
+            let blanks = play
+                .tile_positions
+                .iter()
+                .filter(|(_, tile)| tile.is_blank())
+                .count();
+
+            assert_eq!(blanks, 4);
+            assert_eq!(play.score, 0);
+        }
+    }
+
+    #[test]
+    fn extends_word_at_end() {
+        let plays = gen_cat_plays();
+        let end = Pos::start()
+            .dir(Direction::East)
+            .and_then(|p| p.dir(Direction::East));
+
+        // CATS
+        assert!(contains_single(
+            &plays,
+            end.and_then(|p| p.dir(Direction::East))
+        ));
+    }
+
+    #[test]
+    fn extends_word_at_start() {
+        let plays = gen_cat_plays();
+
+        // SCAT
+        assert!(contains_single(&plays, Pos::start().dir(Direction::West)));
+    }
+
+    #[test]
+    fn subset_fsm() {
+        // the board contains a word that is not in the smaller fsm, which
+        // can happen when an ai uses a subset of the dictionary.
+        let subset: FastFsm = FsmBuilder::from_sorted_iter(["as", "at", "ta"])
+            .unwrap()
+            .build();
+        let mut board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::start()).letters("cat"))
+            .build();
+        let rack = Rack::with_str("s");
+
+        let mut plays = vec![];
+        gen(&board, &rack, &subset, &Alphabet::default(), &mut plays);
+
+        // all plays only form words from the subset, so "cats" and "scat"
+        // are not generated.
+        assert!(!plays.is_empty());
+        for play in plays {
+            let score = board.make_placement(&play.tile_positions, &subset, &GameConfig::default());
+            assert_eq!(score.ok(), Some(play.score));
+            board.undo_placement(&play.tile_positions);
+        }
+    }
+
+    #[test]
+    fn alphabet_scores() {
+        // every letter scores 10 points.
+        let glyphs: Vec<_> = Letter::iter()
+            .map(|letter| char::from(letter).to_string())
+            .collect();
+        let alphabet = Alphabet::new(glyphs.iter().map(|glyph| (glyph.as_str(), 10))).unwrap();
+        let config = GameConfig {
+            alphabet: alphabet.clone(),
+            ..GameConfig::default()
+        };
+
+        let fsm = FastFsm::bundled();
+        let mut board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::start()).letters("cat"))
+            .build();
+        let rack = Rack::with_str("aeirst");
+
+        let mut plays = vec![];
+        gen(&board, &rack, fsm, &alphabet, &mut plays);
+
+        // the plays are scored with the alphabet, in the same way as the board.
+        assert!(!plays.is_empty());
+        for play in plays {
+            assert_eq!(0, play.score % 10);
+            let score = board.make_placement(&play.tile_positions, fsm, &config);
+            assert_eq!(score.ok(), Some(play.score));
+            board.undo_placement(&play.tile_positions);
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        /// Every generated play should pass the same checks as
+        /// [`Game::make_play`](crate::game::Game::make_play): the tiles are in
+        /// the rack and the placement is accepted by the board with the same
+        /// score.
+        #[test]
+        fn generates_legal_plays(board in board(), rack in rack()) {
+            let fsm = FastFsm::bundled();
+            let config = GameConfig::default();
+
+            let mut plays = vec![];
+            gen(&board, &rack, fsm, &Alphabet::default(), &mut plays);
+
+            for play in plays {
+                let tiles = play.tile_positions.iter().map(|&(_, tile)| tile);
+                prop_assert!(rack.contains(tiles), "{:?} not in rack", play);
+
+                let mut board = board.clone();
+                let score = board.make_placement(&play.tile_positions, fsm, &config);
+                prop_assert_eq!(score.ok(), Some(play.score), "{:?}\n{}", play, board);
+            }
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! A small opening book, used by the [`Ai`](super::Ai) to make varied but
+//! sensible first plays.
+
+use crate::{
+    ai::movegen::GeneratedPlay,
+    game::{
+        board::Board,
+        rack::Rack,
+        tile::{Letter, Tile},
+        GameConfig,
+    },
+    util::{
+        fsm::Fsm,
+        pos::{Direction, Pos},
+    },
+};
+use rand::{seq::SliceRandom, Rng};
+use std::iter;
+
+/// Opening words, made of common letters so that a rack is likely
+/// to contain at least one of them. These are included in the common
+/// word list, so that the book is also used with
+/// [`Vocabulary::Common`](super::Vocabulary::Common).
+pub const OPENINGS: &[&str] = &[
+    "alert", "alike", "alone", "arise", "aside", "atone", "irate", "later", "learn", "least",
+    "noise", "notes", "oaten", "ocean", "rails", "raise", "rates", "ratio", "reins", "rinse",
+    "risen", "roast", "route", "saint", "satin", "siren", "slate", "snare", "stain", "stare",
+    "steal", "stone", "store", "tails", "tears", "tenor", "tiles", "toner", "train", "trail",
+    "unite", "aliens", "antler", "denial", "listen", "rental", "salute", "senior", "silent",
+    "stolen", "strain", "tinsel", "toenail", "trains", "treason", "retains",
+];
+
+/// Chooses an opening play from the book for the first play of the game,
+/// returning `None` if the board is not empty or no opening word can be
+/// made from the rack. A random word is chosen from those that can be
+/// made (using `rng`), which is then placed horizontally in the position
+/// that scores the most under `config`.
+pub fn select<'a, F: Fsm<'a>, R: Rng>(
+    board: &Board,
+    rack: &Rack,
+    fsm: &'a F,
+    config: &GameConfig,
+    rng: &mut R,
+) -> Option<GeneratedPlay> {
+    if !board.grid_h().occ().is_zero() {
+        return None;
+    }
+
+    let mut openings = OPENINGS
+        .iter()
+        .filter(|word| fsm.accepts(**word))
+        .filter_map(|word| tiles(word, rack))
+        .collect::<Vec<_>>();
+    openings.shuffle(rng);
+
+    let tiles = openings.pop()?;
+    let len = tiles.len();
+
+    // try each position that covers the start square.
+    (0..len)
+        .flat_map(|offset| Pos::start().offset(Direction::West, offset))
+        .flat_map(|first| {
+            let tile_positions = first
+                .project(Direction::East)
+                .zip(tiles.iter().copied())
+                .collect::<Vec<_>>();
+            let score = board
+                .clone()
+                .make_placement(&tile_positions, fsm, config)
+                .ok()?;
+
+            Some(GeneratedPlay::new(tile_positions, score, 0, len))
+        })
+        .max_by_key(GeneratedPlay::score)
+}
+
+/// Finds the tiles from the rack that spell `word`, using blanks
+/// only for letters that are not on the rack.
+fn tiles(word: &str, rack: &Rack) -> Option<Vec<Tile>> {
+    let mut rack = rack.clone();
+
+    word.chars()
+        .map(|ch| {
+            let letter = Letter::new(ch)?;
+            let tile = [Tile::Letter(letter), Tile::Blank(None)]
+                .into_iter()
+                .find(|&tile| rack.contains(iter::once(tile)))?;
+            rack.remove(iter::once(tile));
+
+            Some(match tile {
+                Tile::Blank(_) => Tile::Blank(Some(letter)),
+                tile => tile,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game::{board::BoardBuilder, play::Play},
+        util::fsm::{FastFsm, FsmBuilder},
+    };
+    use rand::{rngs::StdRng, SeedableRng};
+
+    /// Creates an fsm containing some of the opening words.
+    fn fsm() -> FastFsm {
+        FsmBuilder::from_unsorted_iter(["rates", "stare", "tears", "zebra"]).build()
+    }
+
+    #[test]
+    fn uses_rack_tiles() {
+        let stare: Vec<_> = Rack::with_str("stare").tiles().collect();
+        let found = tiles("stare", &Rack::with_str("zestar")).unwrap();
+        assert_eq!(stare, Rack::with_tiles(&found).tiles().collect::<Vec<_>>());
+
+        // a blank is only used for the missing "t".
+        let rack = Rack::with_tiles(&[
+            Tile::from('s'),
+            Tile::from('a'),
+            Tile::from('r'),
+            Tile::from('e'),
+            Tile::blank(),
+        ]);
+        let found = tiles("stare", &rack).unwrap();
+        assert_eq!(Tile::Blank(Letter::new('t')), found[1]);
+
+        assert_eq!(None, tiles("stare", &Rack::with_str("starz")));
+    }
+
+    #[test]
+    fn selects_opening() {
+        let fsm = fsm();
+        let rack = Rack::with_str("aerstxq");
+        let plain = GameConfig {
+            premiums: false,
+            ..GameConfig::default()
+        };
+
+        for config in [GameConfig::default(), plain] {
+            let mut rng = StdRng::seed_from_u64(0);
+            let gen_play = select(&Board::default(), &rack, &fsm, &config, &mut rng).unwrap();
+
+            // the play is legal, correctly scored and uses the rack tiles.
+            let tiles = gen_play.tile_positions().iter().map(|&(_, tile)| tile);
+            assert!(rack.contains(tiles));
+            let mut board = Board::default();
+            let score = board.make_placement(gen_play.tile_positions(), &fsm, &config);
+            assert_eq!(Some(gen_play.score()), score.ok());
+        }
+    }
+
+    #[test]
+    fn openings_are_words() {
+        let fsm = FastFsm::bundled();
+        assert!(OPENINGS.iter().all(|word| fsm.accepts(*word)));
+    }
+
+    #[test]
+    fn only_when_possible() {
+        let fsm = fsm();
+
+        // no words in the book can be made.
+        assert!(select(
+            &Board::default(),
+            &Rack::with_str("zebraqq"),
+            &fsm,
+            &GameConfig::default(),
+            &mut StdRng::seed_from_u64(0)
+        )
+        .is_none());
+
+        // the board is not empty.
+        let board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::start()).letters("tears"))
+            .build();
+        assert!(select(
+            &board,
+            &Rack::with_str("aerstxq"),
+            &fsm,
+            &GameConfig::default(),
+            &mut StdRng::seed_from_u64(0)
+        )
+        .is_none());
+    }
+}
@@ -6,6 +6,15 @@ use std::{error::Error, fmt};
 /// The [`Result`] type for the [`game`](super::game) module.
 pub type GameResult<T> = std::result::Result<T, GameError>;
 
+/// The [`Result`] type for the [`fsm`](super::util::fsm) module.
+pub type FsmResult<T> = std::result::Result<T, FsmError>;
+
+/// The [`Result`] type for the [`record`](super::game::record) module.
+pub type RecordResult<T> = std::result::Result<T, RecordError>;
+
+/// The [`Result`] type for the [`alphabet`](super::game::alphabet) module.
+pub type AlphabetResult<T> = std::result::Result<T, AlphabetError>;
+
 /// The error type for the game module.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum GameError {
@@ -35,6 +44,16 @@ pub enum GameError {
     NoCommonLine,
     /// A blank tile placed on the board did not specify a letter.
     MissingLetter,
+    /// A word was shorter than the minimum length allowed by the
+    /// [`GameConfig`](crate::game::GameConfig).
+    WordTooShort,
+    /// There is no placement that can be challenged.
+    NothingToChallenge,
+    /// A game can not be started from the provided position.
+    InvalidPosition,
+    /// Exchanging tiles is disabled by the
+    /// [`GameConfig`](crate::game::GameConfig).
+    ExchangeNotAllowed,
 }
 
 impl Error for GameError {}
@@ -59,6 +78,87 @@ impl fmt::Display for GameError {
                 GameError::NoCommonLine => "Placed tiles must share a common row or column",
                 GameError::MissingLetter =>
                     "A blank tile placed on the board did not specify a letter",
+                GameError::WordTooShort => "A word was shorter than the minimum length",
+                GameError::NothingToChallenge => "There is no placement to challenge",
+                GameError::InvalidPosition => "The game can not start from that position",
+                GameError::ExchangeNotAllowed => "Exchanging tiles is not allowed in this game",
+            }
+        )
+    }
+}
+
+/// The error type for the fsm module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsmError {
+    /// A sequence was inserted into an [`FsmBuilder`](super::util::fsm::FsmBuilder)
+    /// before a sequence that precedes it alphabetically.
+    OutOfOrder,
+    /// The bytes provided could not be deserialized as a finite state machine.
+    Deserialize,
+}
+
+impl Error for FsmError {}
+impl fmt::Display for FsmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                FsmError::OutOfOrder => "Sequences must be inserted in alphabetical order",
+                FsmError::Deserialize => "Failed to deserialize the finite state machine",
+            }
+        )
+    }
+}
+
+/// The error type for the record module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordError {
+    /// The record was made by an unsupported version of the encoding.
+    UnsupportedVersion,
+    /// The record could not be decoded.
+    Malformed,
+}
+
+impl Error for RecordError {}
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                RecordError::UnsupportedVersion => "The game record version is not supported",
+                RecordError::Malformed => "The game record could not be read",
+            }
+        )
+    }
+}
+
+/// The error type for the alphabet module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlphabetError {
+    /// The alphabet has no letters.
+    NoLetters,
+    /// The alphabet has more than [`Letter::COUNT`](super::game::tile::Letter::COUNT)
+    /// letters.
+    TooManyLetters,
+    /// A glyph was empty.
+    EmptyGlyph,
+    /// The same glyph was used for more than one letter.
+    DuplicateGlyph,
+}
+
+impl Error for AlphabetError {}
+impl fmt::Display for AlphabetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                AlphabetError::NoLetters => "An alphabet needs at least one letter",
+                AlphabetError::TooManyLetters => "The alphabet has too many letters",
+                AlphabetError::EmptyGlyph => "A letter was written as an empty string",
+                AlphabetError::DuplicateGlyph => "Letters must be written differently",
             }
         )
     }
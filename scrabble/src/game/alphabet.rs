@@ -0,0 +1,223 @@
+//! Alphabets, which map the [`Letter`]s used by the engine to the glyphs
+//! printed on the tiles.
+//!
+//! A glyph can be an accented letter or made of several characters (such
+//! as "CH" in Spanish), so words are parsed into letters with
+//! [`Alphabet::parse`] rather than one letter per `char`. The parsed letters
+//! are an [`FsmSequence`](crate::util::fsm::FsmSequence), so can be used to
+//! build and traverse a finite state machine.
+//!
+//! A game is played with the alphabet in its
+//! [`GameConfig`](super::GameConfig), which gives the tile scores used by
+//! the board, the move generator and the final scores. [`Letter::new`] and
+//! the [`Display`](std::fmt::Display) impl of [`Letter`] always use `A..=Z`,
+//! so use [`Alphabet::glyph`] to show a letter to a player.
+
+use crate::{
+    error::{AlphabetError, AlphabetResult},
+    game::tile::{Letter, Tile},
+};
+use serde::{Deserialize, Serialize};
+
+/// An ordered list of glyphs, each with a score. The letter with index
+/// `i` is written as the `i`th glyph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Alphabet {
+    glyphs: Vec<String>,
+    scores: Vec<usize>,
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Alphabet::english()
+    }
+}
+
+impl Alphabet {
+    /// Creates an alphabet from `(glyph, score)` pairs in letter order.
+    /// Glyphs are stored in uppercase, and must be unique and not empty.
+    /// There can be at most [`Letter::COUNT`] glyphs.
+    pub fn new<'a>(letters: impl IntoIterator<Item = (&'a str, usize)>) -> AlphabetResult<Self> {
+        let mut glyphs = Vec::new();
+        let mut scores = Vec::new();
+
+        for (glyph, score) in letters {
+            let glyph = glyph.to_uppercase();
+
+            if glyph.is_empty() {
+                return Err(AlphabetError::EmptyGlyph);
+            }
+            if glyphs.contains(&glyph) {
+                return Err(AlphabetError::DuplicateGlyph);
+            }
+
+            glyphs.push(glyph);
+            scores.push(score);
+        }
+
+        match glyphs.len() {
+            0 => Err(AlphabetError::NoLetters),
+            len if len > Letter::COUNT => Err(AlphabetError::TooManyLetters),
+            _ => Ok(Self { glyphs, scores }),
+        }
+    }
+    /// The English alphabet `A..=Z`, with the standard tile scores.
+    pub fn english() -> Self {
+        Self {
+            glyphs: Letter::iter()
+                .map(|letter| char::from(letter).to_string())
+                .collect(),
+            scores: Letter::iter()
+                .map(|letter| Tile::Letter(letter).score())
+                .collect(),
+        }
+    }
+    /// Gets the number of letters in the alphabet.
+    pub fn len(&self) -> usize {
+        self.glyphs.len()
+    }
+    /// Checks whether the alphabet has no letters (which is never the
+    /// case for an alphabet created with [`Alphabet::new`]).
+    pub fn is_empty(&self) -> bool {
+        self.glyphs.is_empty()
+    }
+    /// Returns an iterator over the letters of the alphabet.
+    pub fn letters(&self) -> impl Iterator<Item = Letter> {
+        (0..self.len()).map(Letter::from)
+    }
+    /// Gets the glyph of a letter, or [`None`] if the letter is not in
+    /// the alphabet.
+    pub fn glyph(&self, letter: Letter) -> Option<&str> {
+        self.glyphs.get(usize::from(letter)).map(String::as_str)
+    }
+    /// Gets the score of a tile. Blanks, and letters that are not in the
+    /// alphabet, have zero score.
+    pub fn score(&self, tile: Tile) -> usize {
+        match tile {
+            Tile::Letter(letter) => self.scores.get(usize::from(letter)).copied(),
+            Tile::Blank(_) => None,
+        }
+        .unwrap_or(0)
+    }
+    /// Splits a word into letters, ignoring case. The longest glyph that
+    /// matches is taken at each position, so when "CH" is a glyph, "CHE"
+    /// is parsed as "CH", "E". Returns [`None`] if part of the word is not
+    /// in the alphabet.
+    pub fn parse(&self, word: &str) -> Option<Vec<Letter>> {
+        let word = word.to_uppercase();
+        let mut rest = word.as_str();
+        let mut letters = Vec::new();
+
+        while !rest.is_empty() {
+            let (idx, glyph) = self
+                .glyphs
+                .iter()
+                .enumerate()
+                .filter(|(_, glyph)| rest.starts_with(glyph.as_str()))
+                .max_by_key(|(_, glyph)| glyph.len())?;
+
+            letters.push(Letter::from(idx));
+            rest = &rest[glyph.len()..];
+        }
+
+        Some(letters)
+    }
+    /// Writes letters as a word, where letters that are not in the
+    /// alphabet are written as `?`.
+    pub fn format(&self, letters: impl IntoIterator<Item = Letter>) -> String {
+        letters
+            .into_iter()
+            .map(|letter| self.glyph(letter).unwrap_or("?"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::fsm::{FastFsm, Fsm, FsmBuilder};
+
+    /// Creates part of the Spanish alphabet, which has digraphs.
+    fn spanish() -> Alphabet {
+        Alphabet::new([
+            ("a", 1),
+            ("c", 3),
+            ("ch", 5),
+            ("e", 1),
+            ("l", 1),
+            ("ll", 8),
+            ("m", 3),
+            ("ñ", 8),
+            ("o", 1),
+            ("u", 1),
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn english() {
+        let english = Alphabet::default();
+        assert_eq!(26, english.len());
+
+        for letter in Letter::iter() {
+            assert_eq!(
+                Some(char::from(letter).to_string().as_str()),
+                english.glyph(letter)
+            );
+            assert_eq!(
+                Tile::Letter(letter).score(),
+                english.score(Tile::Letter(letter))
+            );
+        }
+        assert_eq!(0, english.score(Tile::blank()));
+
+        let letters = english.parse("Quiz").unwrap();
+        assert_eq!("QUIZ", english.format(letters));
+        assert_eq!(None, english.parse("naïve"));
+    }
+
+    #[test]
+    fn digraphs() {
+        let spanish = spanish();
+
+        // the longest glyph is matched first.
+        let letters = spanish.parse("Chulla").unwrap();
+        assert_eq!(4, letters.len());
+        assert_eq!("CHULLA", spanish.format(letters.iter().copied()));
+        assert_eq!(Some("CH"), spanish.glyph(letters[0]));
+        assert_eq!(Some("LL"), spanish.glyph(letters[2]));
+        assert_eq!(8, spanish.score(Tile::Letter(letters[2])));
+
+        // accented letters are their own glyph.
+        let letters = spanish.parse("caña").unwrap();
+        assert_eq!(Some("Ñ"), spanish.glyph(letters[2]));
+        assert_eq!(None, spanish.parse("cana"));
+    }
+
+    #[test]
+    fn fsm() {
+        let spanish = spanish();
+        let fsm: FastFsm = FsmBuilder::from_unsorted_iter(
+            ["llama", "coche", "mucho"].map(|word| spanish.parse(word).unwrap()),
+        )
+        .build();
+
+        assert!(fsm.accepts(spanish.parse("coche").unwrap().as_slice()));
+        assert!(fsm.accepts(spanish.parse("LLAMA").unwrap().as_slice()));
+        assert!(!fsm.accepts(spanish.parse("mucha").unwrap().as_slice()));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(Err(AlphabetError::NoLetters), Alphabet::new([]));
+        assert_eq!(Err(AlphabetError::EmptyGlyph), Alphabet::new([("", 1)]));
+        assert_eq!(
+            Err(AlphabetError::DuplicateGlyph),
+            Alphabet::new([("ch", 5), ("CH", 5)])
+        );
+
+        let glyphs: Vec<_> = (0..=Letter::COUNT).map(|idx| format!("x{idx}")).collect();
+        let result = Alphabet::new(glyphs.iter().map(|glyph| (glyph.as_str(), 1)));
+        assert_eq!(Err(AlphabetError::TooManyLetters), result);
+    }
+}
@@ -2,17 +2,18 @@
 
 use crate::{
     error::{GameError, GameResult},
-    game::{play::PlaceBuilder, tile::Tile},
+    game::{play::PlaceBuilder, tile::Tile, GameConfig},
     util::{
         self,
         bitboard::{BitBoard, Bits},
         fsm::Fsm,
         grid::Grid,
-        pos::{Direction, Pos},
-        scoring,
-        words::WordsExt,
+        pos::{Direction, Pos, Premium},
+        scoring::{self, ScoreBreakdown},
+        words::{Word, WordsExt},
     },
 };
+use serde::{Deserialize, Serialize};
 use std::{fmt, ops::Index};
 
 /// The number of rows on the board.
@@ -41,6 +42,51 @@ impl BoardBuilder {
     }
 }
 
+/// The premium square for each position on the board.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BonusLayout {
+    bonuses: Vec<Option<Premium>>,
+}
+impl Default for BonusLayout {
+    fn default() -> Self {
+        Self {
+            bonuses: Pos::iter().map(|pos| pos.premium()).collect(),
+        }
+    }
+}
+impl BonusLayout {
+    /// Gets the optional premium at `pos`.
+    pub fn bonus_at(&self, pos: Pos) -> Option<Premium> {
+        self.bonuses[usize::from(pos)]
+    }
+}
+
+/// Displays a [`Board`] with the premium squares shown in the empty
+/// cells, like a physical board. Created by [`Board::display_with_bonuses`].
+#[derive(Clone, Copy, Debug)]
+pub struct BoardDisplay<'a> {
+    board: &'a Board,
+    bonuses: &'a BonusLayout,
+}
+impl fmt::Display for BoardDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        util::write_grid(f, |pos| match self.board[pos] {
+            Some(tile) => format!("{}", tile),
+            None => match self.bonuses.bonus_at(pos) {
+                Some(Premium::DoubleLetter) => "2L ",
+                Some(Premium::TripleLetter) => "3L ",
+                Some(Premium::DoubleWord) => "2W ",
+                Some(Premium::TripleWord) => "3W ",
+                Some(Premium::Start) => " * ",
+                None => " . ",
+            }
+            .to_string(),
+        })?;
+
+        writeln!(f)
+    }
+}
+
 /// Used to iterate over board tiles.
 pub struct BoardIter<'a> {
     grid_h: &'a Grid,
@@ -63,7 +109,10 @@ impl<'a> Iterator for BoardIter<'a> {
 /// Represents the 15 x 15 scrabble board, storing the location of
 /// tiles, and allowing [`Play`](super::play::Play)s to be made
 /// and validated.
-#[derive(Clone, Debug)]
+///
+/// Only the tiles are serialized, as both grids can be rebuilt from them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(into = "Vec<Option<Tile>>", try_from = "Vec<Option<Tile>>")]
 pub struct Board {
     grid_h: Grid,
     grid_v: Grid,
@@ -76,6 +125,27 @@ impl Default for Board {
         }
     }
 }
+impl From<Board> for Vec<Option<Tile>> {
+    fn from(board: Board) -> Self {
+        board.grid_h.tiles().to_vec()
+    }
+}
+impl TryFrom<Vec<Option<Tile>>> for Board {
+    type Error = &'static str;
+
+    fn try_from(tiles: Vec<Option<Tile>>) -> Result<Self, Self::Error> {
+        if tiles.len() != CELLS {
+            return Err("Expected a tile for every square on the board");
+        }
+
+        let mut board = Board::default();
+        for (pos, tile) in Pos::iter().zip(tiles) {
+            board.set(pos, tile);
+        }
+
+        Ok(board)
+    }
+}
 impl fmt::Display for Board {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "{}", self.grid_h)
@@ -90,15 +160,19 @@ impl<T: Into<Pos>> Index<T> for Board {
 }
 impl Board {
     /// Computes the combined score for horizontal and vertical words, adding
-    /// the 50 point bonus where appropriate. If an invalid word is encountered,
-    /// returns an error.
-    fn score_and_validate<'a>(
+    /// the 50 point bonus where appropriate. Each word is scored by `score_word`,
+    /// which can reject the word. If a word shorter than the minimum length in
+    /// `config` is encountered, returns an error.
+    fn score_and_validate(
         &self,
         new_h: BitBoard,
         new_v: BitBoard,
-        fsm: &impl Fsm<'a>,
+        config: &GameConfig,
+        score_word: impl Fn(Word<'_>, &BitBoard) -> GameResult<usize>,
     ) -> GameResult<usize> {
         let mut score = 0;
+        let premiums_h = config.premium_squares(new_h);
+        let premiums_v = config.premium_squares(new_v);
 
         // find and score the horizontal words.
         let occ_h = self.grid_h.occ();
@@ -107,7 +181,10 @@ impl Board {
             .intersecting(new_h)
             .words(&self.grid_h);
         for word in words_h {
-            score += scoring::score(word, &new_h, fsm)?;
+            if word.len() < config.min_word_len {
+                return Err(GameError::WordTooShort);
+            }
+            score += score_word(word, &premiums_h)?;
         }
 
         // find and score the vertical words.
@@ -117,14 +194,25 @@ impl Board {
             .intersecting(new_v)
             .words(&self.grid_v);
         for word in words_v {
-            score += scoring::score(word, &new_v, fsm)?;
+            if word.len() < config.min_word_len {
+                return Err(GameError::WordTooShort);
+            }
+            score += score_word(word, &premiums_v)?;
         }
 
-        // If the bitcount for `new_h` is 7, add a 50 point bonus.
-        match new_h.bit_count() {
-            7 => Ok(score + 50),
-            _ => Ok(score),
-        }
+        // add the bonus if all 7 tiles were placed.
+        Ok(score + config.bonus_for(new_h.bit_count()))
+    }
+    /// Checks that the horizontal and vertical grids contain the same
+    /// tiles, and that the occupancy of each grid matches its tiles.
+    pub fn is_consistent(&self) -> bool {
+        Pos::iter().all(|pos| {
+            let tile = self.grid_h[pos];
+
+            tile == self.grid_v[pos.swap_rc()]
+                && self.grid_h.occ().is_set(pos) == tile.is_some()
+                && self.grid_v.occ().is_set(pos.swap_rc()) == tile.is_some()
+        })
     }
     /// Sets the tile at `pos`.
     fn set(&mut self, pos: Pos, tile: impl Into<Option<Tile>>) {
@@ -133,11 +221,28 @@ impl Board {
         self.grid_h.set(pos, tile);
         self.grid_v.set(pos.swap_rc(), tile);
     }
-    /// Gets the board occupancy.
+    /// Gets the optional premium at `pos`.
+    pub fn bonus_at(&self, pos: Pos) -> Option<Premium> {
+        pos.premium()
+    }
+    /// Gets a value that displays the board with the premium squares
+    /// from `bonuses` shown in the empty cells.
+    pub fn display_with_bonuses<'a>(&'a self, bonuses: &'a BonusLayout) -> BoardDisplay<'a> {
+        BoardDisplay {
+            board: self,
+            bonuses,
+        }
+    }
+    /// Gets the horizontal grid, which has the same layout as the board.
+    /// Horizontal words on the board are horizontal words in this grid.
     pub fn grid_h(&self) -> &Grid {
         &self.grid_h
     }
-    /// Gets the rotated board occupancy.
+    /// Gets the vertical grid, in which the row and column of every position
+    /// are swapped (see [`Pos::swap_rc`]). Vertical words on the board are
+    /// horizontal words in this grid, so the same code can find and score words
+    /// in both directions. Use [`Grid::map_pos`] to convert a position back to
+    /// board coordinates.
     pub fn grid_v(&self) -> &Grid {
         &self.grid_v
     }
@@ -148,6 +253,200 @@ impl Board {
             occ: Bits::from(*self.grid_h.occ()),
         }
     }
+    /// Finds every complete word on the board, in both orientations, along
+    /// with the board positions of its tiles (from left to right, or from
+    /// top to bottom).
+    pub fn find_words(&self) -> Vec<(String, Vec<Pos>)> {
+        [&self.grid_h, &self.grid_v]
+            .into_iter()
+            .flat_map(|grid| {
+                grid.word_boundaries()
+                    .words_with_positions(grid)
+                    .map(|(word, positions)| (word.to_string(), positions))
+            })
+            .collect()
+    }
+    /// Finds the tile positions of every occurrence of `word` on the board.
+    pub fn find_word(&self, word: &str) -> Vec<Vec<Pos>> {
+        self.find_words()
+            .into_iter()
+            .filter(|(w, _)| w.eq_ignore_ascii_case(word))
+            .map(|(_, positions)| positions)
+            .collect()
+    }
+    /// Finds the squares on which a horizontal or vertical word could start
+    /// when placing up to `rack_len` tiles (see [`util::possible_starts_h`]).
+    /// Unlike anchors, these include empty squares up to `rack_len - 1`
+    /// before a tile or the start square, and tiles that begin a word. Both
+    /// bitboards are in board coordinates, so a vertical start is the top
+    /// square of a vertical word. There are no starts when `rack_len` is zero.
+    pub fn word_starts(&self, rack_len: usize) -> (BitBoard, BitBoard) {
+        if rack_len == 0 {
+            return (BitBoard::default(), BitBoard::default());
+        }
+
+        let starts_h = util::possible_starts_h(*self.grid_h.occ(), rack_len);
+        let starts_v = util::possible_starts_h(*self.grid_v.occ(), rack_len)
+            .into_iter()
+            .map(|pos| self.grid_v.map_pos(pos))
+            .collect();
+
+        (starts_h, starts_v)
+    }
+    /// Places all tiles in `tile_positions` on the board without any
+    /// validation. Only used for plays from the move generator, which are
+    /// already known to be valid.
+    pub(crate) fn place_unchecked(&mut self, tile_positions: &[(Pos, Tile)]) {
+        for &(pos, tile) in tile_positions {
+            self.set(pos, tile);
+        }
+    }
+    /// Finds every word that would be formed by placing `tile_positions`,
+    /// without modifying the board. Each word is returned with whether it is
+    /// valid (in the word list and no shorter than the minimum length in
+    /// `config`) and its score if valid, not including the 50 point bonus.
+    ///
+    /// Unlike [`Board::make_placement`], the tile positions are not checked,
+    /// so this can be used to highlight invalid words while tiles are still
+    /// being placed. Tiles placed over existing tiles are ignored, and blanks
+    /// without a letter are shown as `'?'`.
+    pub fn analyze_placement<'a>(
+        &self,
+        tile_positions: &[(Pos, Tile)],
+        fsm: &impl Fsm<'a>,
+        config: &GameConfig,
+    ) -> Vec<(String, bool, usize)> {
+        let mut board = self.clone();
+        let mut new_h = BitBoard::default();
+        let mut new_v = BitBoard::default();
+
+        for &(pos, tile) in tile_positions {
+            if board[pos].is_none() {
+                board.set(pos, tile);
+                new_h.set(pos);
+                new_v.set(pos.swap_rc());
+            }
+        }
+
+        [(&board.grid_h, new_h), (&board.grid_v, new_v)]
+            .into_iter()
+            .flat_map(|(grid, new)| {
+                grid.occ()
+                    .word_boundaries()
+                    .intersecting(new)
+                    .words(grid)
+                    .map(move |word| Self::analyze_word(word, &new, fsm, config))
+            })
+            .collect()
+    }
+    /// Finds the word that placing `tile` at `pos` would form perpendicular
+    /// to a play in `dir`, such as the vertical word formed by one tile of a
+    /// horizontal play. The word is returned with whether it is valid and its
+    /// score, in the same way as [`Board::analyze_placement`].
+    ///
+    /// Returns [`None`] if `pos` is occupied, or if the tile has no
+    /// neighbours in the perpendicular direction, so no word is formed.
+    pub fn cross_word_at<'a>(
+        &self,
+        pos: Pos,
+        tile: Tile,
+        dir: Direction,
+        fsm: &impl Fsm<'a>,
+        config: &GameConfig,
+    ) -> Option<(String, bool, usize)> {
+        if self[pos].is_some() {
+            return None;
+        }
+
+        let mut board = self.clone();
+        board.set(pos, tile);
+
+        // the cross-word of a horizontal play is read from the vertical grid.
+        let (grid, pos) = match dir {
+            Direction::East | Direction::West => (&board.grid_v, pos.swap_rc()),
+            Direction::North | Direction::South => (&board.grid_h, pos),
+        };
+        let new = BitBoard::from_iter([pos]);
+
+        let word = grid
+            .occ()
+            .word_boundaries()
+            .intersecting(new)
+            .words(grid)
+            .next()?;
+
+        Some(Self::analyze_word(word, &new, fsm, config))
+    }
+    /// Gets the text of `word`, whether it is valid and its score if valid.
+    /// Blanks without a letter are shown as `'?'`.
+    fn analyze_word<'a>(
+        word: Word<'_>,
+        new: &BitBoard,
+        fsm: &impl Fsm<'a>,
+        config: &GameConfig,
+    ) -> (String, bool, usize) {
+        let text = word
+            .clone()
+            .map(|(_, tile)| match tile.letter() {
+                Ok(letter) => char::from(letter),
+                Err(_) => '?',
+            })
+            .collect();
+
+        match word.len() >= config.min_word_len {
+            true => {
+                match scoring::score(word, &config.premium_squares(*new), fsm, &config.alphabet) {
+                    Ok(score) => (text, true, score),
+                    Err(_) => (text, false, 0),
+                }
+            }
+            false => (text, false, 0),
+        }
+    }
+    /// Finds the score of placing `tile_positions` broken down by word,
+    /// without modifying the board. The placement is validated in the same
+    /// way as [`Board::make_placement`], and the total of the breakdown is
+    /// the score that it returns.
+    pub fn score_breakdown<'a>(
+        &self,
+        tile_positions: &[(Pos, Tile)],
+        fsm: &impl Fsm<'a>,
+        config: &GameConfig,
+    ) -> GameResult<ScoreBreakdown> {
+        let mut board = self.clone();
+        board.make_placement(tile_positions, fsm, config)?;
+
+        let new_h: BitBoard = tile_positions.iter().map(|&(pos, _)| pos).collect();
+        let new_v: BitBoard = tile_positions
+            .iter()
+            .map(|&(pos, _)| pos.swap_rc())
+            .collect();
+
+        // the main word contains the most new tiles, so is listed first.
+        let mut words = [(&board.grid_h, new_h), (&board.grid_v, new_v)]
+            .into_iter()
+            .flat_map(|(grid, new)| {
+                grid.occ()
+                    .word_boundaries()
+                    .intersecting(new)
+                    .words(grid)
+                    .map(move |word| {
+                        let new_count = word.clone().filter(|&(pos, _)| new.is_set(pos)).count();
+                        let premiums = config.premium_squares(new);
+                        (
+                            new_count,
+                            scoring::word_score(word, &premiums, &config.alphabet),
+                        )
+                    })
+            })
+            .collect::<Vec<_>>();
+        words.sort_by_key(|&(new_count, _)| usize::MAX - new_count);
+
+        Ok(ScoreBreakdown {
+            words: words.into_iter().map(|(_, word)| word).collect(),
+            bingo: config.bonus_for(tile_positions.len()),
+        })
+    }
     /// Removes all tiles in `tile_positions` from the board.
     pub fn undo_placement(&mut self, tile_positions: &[(Pos, Tile)]) {
         for &(pos, _) in tile_positions {
@@ -161,7 +460,68 @@ impl Board {
         &mut self,
         tile_positions: &[(Pos, Tile)],
         fsm: &impl Fsm<'a>,
+        config: &GameConfig,
+    ) -> GameResult<usize> {
+        self.place_and_score(tile_positions, config, |word, new| {
+            scoring::score(word, new, fsm, &config.alphabet)
+        })
+    }
+    /// Performs a [`Play::Place`](super::play::Play::Place) in the same way
+    /// as [`Board::make_placement`], except that the words are not checked
+    /// against a word list. The positions of the tiles and the length of
+    /// each word are still checked. Used when words are checked later by a
+    /// challenge, rather than when they are played.
+    pub fn make_unvalidated_placement(
+        &mut self,
+        tile_positions: &[(Pos, Tile)],
+        config: &GameConfig,
     ) -> GameResult<usize> {
+        // every blank must still be given a letter.
+        for (_, tile) in tile_positions {
+            tile.letter()?;
+        }
+
+        self.place_and_score(tile_positions, config, |word, new| {
+            Ok(scoring::score_unchecked(word, new, &config.alphabet))
+        })
+    }
+    /// Checks the positions of a placement without checking the words, so
+    /// that obviously illegal placements can be found before the word list
+    /// is available. Checks the tile count, that the tiles share a line and
+    /// do not overlap, and that they connect to existing tiles (or cover the
+    /// start square). A placement that passes can still be rejected by
+    /// [`Board::make_placement`].
+    pub fn precheck_placement(
+        &self,
+        tile_positions: &[(Pos, Tile)],
+        config: &GameConfig,
+    ) -> GameResult<()> {
+        self.validate_positions(tile_positions, config).map(drop)
+    }
+    /// Checks whether a partial placement is connected to the existing
+    /// tiles (or to the start square on an empty board), so that a hint
+    /// can be shown while tiles are being staged. Staged positions that
+    /// are already occupied are ignored.
+    pub fn would_connect(&self, staged: &[Pos]) -> bool {
+        let &occ_h = self.grid_h.occ();
+        let mut connected = occ_h;
+
+        // the first placement connects through the start square.
+        if occ_h.is_zero() {
+            connected.set(Pos::start());
+        }
+
+        let new = staged.iter().copied().collect::<BitBoard>() & !connected;
+        util::is_connected(connected, new)
+    }
+    /// Validates the positions of a placement, returning the new tiles
+    /// for horizontal words and for vertical words (with the row and
+    /// column swapped).
+    fn validate_positions(
+        &self,
+        tile_positions: &[(Pos, Tile)],
+        config: &GameConfig,
+    ) -> GameResult<(BitBoard, BitBoard)> {
         // check the tile count
         if !(1..=7).contains(&tile_positions.len()) {
             return Err(GameError::PlacementCount);
@@ -176,7 +536,7 @@ impl Board {
 
         // new tiles for horizontal words
         let mut new_h = BitBoard::default();
-        // new tiles for vertical words: rotated 90deg anticlockwise
+        // new tiles for vertical words: row and column swapped
         let mut new_v = BitBoard::default();
 
         for &(pos_h, _) in tile_positions {
@@ -200,7 +560,20 @@ impl Board {
 
         // perform tile placement validation
         let &occ_h = self.grid_h.occ();
-        util::validate_occ_h(occ_h, new_h)?;
+        util::validate_occ_h(occ_h, new_h, config)?;
+
+        Ok((new_h, new_v))
+    }
+    /// Validates the positions of a placement, then places the tiles and
+    /// scores each word with `score_word`. If any word is rejected, the
+    /// tiles are removed again.
+    fn place_and_score(
+        &mut self,
+        tile_positions: &[(Pos, Tile)],
+        config: &GameConfig,
+        score_word: impl Fn(Word<'_>, &BitBoard) -> GameResult<usize>,
+    ) -> GameResult<usize> {
+        let (new_h, new_v) = self.validate_positions(tile_positions, config)?;
 
         // Tiles positions have now been validated: place the tiles on the board.
         // Word validation requires that these tiles are present. If an invalid
@@ -210,7 +583,7 @@ impl Board {
         }
 
         // checks that words are valid then returns the score
-        match self.score_and_validate(new_h, new_v, fsm) {
+        match self.score_and_validate(new_h, new_v, config, score_word) {
             // everything was ok, update the bitboards.
             Ok(score) => Ok(score),
             // error occured, reverse the state change
@@ -221,3 +594,717 @@ impl Board {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ai::movegen,
+        game::{alphabet::Alphabet, play::Play, rack::Rack, tile::Letter},
+        util::fsm::{FastFsm, FsmBuilder},
+    };
+
+    /// Parses a standard scrabble coordinate ("8H" for a horizontal word,
+    /// "H8" for a vertical word) into a builder for the word.
+    fn parse_coord(coord: &str) -> PlaceBuilder {
+        let (horizontal, letter, number) = match coord.split_at(1) {
+            (letter, number) if letter.chars().all(|ch| ch.is_ascii_alphabetic()) => {
+                (false, letter, number)
+            }
+            _ => {
+                let (number, letter) = coord.split_at(coord.len() - 1);
+                (true, letter, number)
+            }
+        };
+
+        let col = usize::from(letter.as_bytes()[0].to_ascii_uppercase() - b'A');
+        let row = number.parse::<usize>().unwrap() - 1;
+        let start = Pos::from((row, col));
+
+        match horizontal {
+            true => Play::horizontal(start),
+            false => Play::vertical(start),
+        }
+    }
+
+    /// Parses a word from a scoring fixture. Uppercase letters are tiles,
+    /// lowercase letters are blanks and bracketed letters must already be
+    /// on `board`.
+    fn parse_play(board: &Board, coord: &str, word: &str) -> Vec<(Pos, Tile)> {
+        let mut builder = parse_coord(coord);
+        let mut existing = vec![];
+        let mut is_existing = false;
+
+        for ch in word.chars() {
+            match ch {
+                '(' => is_existing = true,
+                ')' => is_existing = false,
+                _ => {
+                    let letter = Letter::new(ch).unwrap();
+                    existing.push(is_existing);
+                    builder = match ch.is_ascii_uppercase() {
+                        true => builder.letter(letter),
+                        false => builder.blank(letter),
+                    };
+                }
+            }
+        }
+
+        // check that the bracketed letters match the board.
+        let tile_positions = builder.tile_positions(board);
+        let placed = existing.iter().filter(|&&is_existing| !is_existing).count();
+        assert_eq!(placed, tile_positions.len(), "{coord} {word}: wrong tiles");
+
+        tile_positions
+    }
+
+    #[test]
+    fn scoring_fixtures() {
+        let fsm = FastFsm::bundled();
+        let config = GameConfig::default();
+        let fixtures = include_str!("../../fixtures/scoring.txt");
+
+        let mut board = Board::default();
+        for line in fixtures.lines().map(str::trim) {
+            // a blank line starts a new game.
+            if line.is_empty() {
+                board = Board::default();
+                continue;
+            }
+            if line.starts_with('#') {
+                continue;
+            }
+
+            let mut parts = line.split_whitespace();
+            let (coord, word, score) = match (parts.next(), parts.next(), parts.next()) {
+                (Some(coord), Some(word), Some(score)) => (coord, word, score),
+                _ => panic!("invalid fixture: {line}"),
+            };
+
+            let tile_positions = parse_play(&board, coord, word);
+            let result = board.make_placement(&tile_positions, fsm, &config);
+
+            assert_eq!(Some(score.parse().unwrap()), result.ok(), "{line}\n{board}");
+        }
+    }
+
+    /// Places "AT" horizontally from `start` on `board`.
+    fn place_at(board: &mut Board, start: Pos, config: &GameConfig) -> GameResult<usize> {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["at"]).unwrap().build();
+        let tile_positions = Play::horizontal(start).letters("at").tile_positions(board);
+
+        board.make_placement(&tile_positions, &fsm, config)
+    }
+
+    #[test]
+    fn used_premium_not_reapplied() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["at", "cat", "ta"])
+            .unwrap()
+            .build();
+        // the board is built without covering the start square.
+        let config = GameConfig {
+            require_center_start: false,
+            ..GameConfig::default()
+        };
+
+        // "CAT" along the top row, with the "A" on a triple word square.
+        let triple_word = Pos::from((0, 7));
+        assert_eq!(Some(Premium::TripleWord), triple_word.premium());
+        let mut board = BoardBuilder::default()
+            .place(Play::horizontal(triple_word.dir(Direction::West).unwrap()).letters("cat"))
+            .build();
+
+        // "AT" runs down from the existing "A", but the triple word square
+        // was used by an earlier play.
+        let tile_positions = Play::vertical(triple_word)
+            .letters("at")
+            .tile_positions(&board);
+        assert_eq!(1, tile_positions.len());
+
+        // the move generator should agree with the board.
+        let mut plays = vec![];
+        movegen::gen(
+            &board,
+            &Rack::with_str("t"),
+            &fsm,
+            &Alphabet::default(),
+            &mut plays,
+        );
+        let gen_play = plays
+            .iter()
+            .find(|play| play.tile_positions() == tile_positions)
+            .unwrap();
+        assert_eq!(2, gen_play.score());
+
+        let result = board.make_placement(&tile_positions, &fsm, &config);
+        assert!(matches!(result, Ok(2)));
+    }
+
+    #[test]
+    fn plain_scoring() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["cat", "letters"])
+            .unwrap()
+            .build();
+        let standard = GameConfig {
+            require_center_start: false,
+            ..GameConfig::default()
+        };
+        let plain = GameConfig {
+            premiums: false,
+            ..standard.clone()
+        };
+
+        // "CAT" along the top row, with the "A" on a triple word square, only
+        // scores the face value of its tiles.
+        let triple_word = Pos::from((0, 7));
+        let cat = Play::horizontal(triple_word.dir(Direction::West).unwrap())
+            .letters("cat")
+            .tile_positions(&Board::default());
+        assert!(matches!(
+            Board::default().make_placement(&cat, &fsm, &standard),
+            Ok(15)
+        ));
+        assert!(matches!(
+            Board::default().make_placement(&cat, &fsm, &plain),
+            Ok(5)
+        ));
+        let breakdown = Board::default()
+            .score_breakdown(&cat, &fsm, &plain)
+            .unwrap();
+        assert!(breakdown.words[0].premiums.is_empty());
+        assert_eq!(5, breakdown.total());
+
+        // the bingo bonus can be disabled separately.
+        let letters = Play::horizontal(Pos::start())
+            .letters("letters")
+            .tile_positions(&Board::default());
+        assert!(matches!(
+            Board::default().make_placement(&letters, &fsm, &plain),
+            Ok(57)
+        ));
+        let no_bonus = GameConfig {
+            bingo_bonus: false,
+            ..plain
+        };
+        assert!(matches!(
+            Board::default().make_placement(&letters, &fsm, &no_bonus),
+            Ok(7)
+        ));
+    }
+
+    #[test]
+    fn display_with_bonuses() {
+        let board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::start()).letters("cat"))
+            .build();
+        let bonuses = BonusLayout::default();
+        let display = board.display_with_bonuses(&bonuses).to_string();
+        let rows: Vec<_> = display.lines().collect();
+
+        // the first row of squares starts and ends with a triple word.
+        assert!(rows[1].starts_with(" 1 3W  .  . 2L "));
+        assert!(rows[1].ends_with("3W  1 "));
+        // tiles are shown in place of the premium squares.
+        assert!(rows[8].contains(" C  A  T "));
+        assert!(!display.contains(" * "));
+
+        // the plain display is unchanged.
+        assert!(!board.to_string().contains("3W"));
+        assert!(Board::default()
+            .display_with_bonuses(&bonuses)
+            .to_string()
+            .contains(" * "));
+    }
+
+    #[test]
+    fn analyze_placement() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["at", "cat", "cats", "ta"])
+            .unwrap()
+            .build();
+        let config = GameConfig::default();
+        let board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::start()).letters("cat"))
+            .build();
+
+        // "S" forms "CATS", and "Z" below the "T" forms "TZ".
+        let end = Pos::start().offset(Direction::East, 3).unwrap();
+        let below_t = Pos::start().offset(Direction::East, 2).unwrap();
+        let below_t = below_t.dir(Direction::South).unwrap();
+        let tile_positions = [(end, Tile::from('s')), (below_t, Tile::from('z'))];
+
+        assert_eq!(
+            vec![
+                (String::from("CATS"), true, 6),
+                (String::from("TZ"), false, 0)
+            ],
+            board.analyze_placement(&tile_positions, &fsm, &config)
+        );
+
+        // the board is not modified.
+        assert!(board[end].is_none());
+        assert!(board[below_t].is_none());
+
+        // short words are invalid when a longer minimum is configured.
+        let config = GameConfig {
+            min_word_len: 5,
+            ..GameConfig::default()
+        };
+        assert_eq!(
+            vec![(String::from("CATS"), false, 0)],
+            board.analyze_placement(&tile_positions[..1], &fsm, &config)
+        );
+    }
+
+    #[test]
+    fn cross_word_at() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["at", "cat", "cats", "ta"])
+            .unwrap()
+            .build();
+        let config = GameConfig::default();
+        let board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::start()).letters("cat"))
+            .build();
+        let end = Pos::start().offset(Direction::East, 3).unwrap();
+        let below_t = Pos::start().offset(Direction::East, 2).unwrap();
+        let below_t = below_t.dir(Direction::South).unwrap();
+
+        // a horizontal play below the "T" forms a vertical word.
+        let a = Tile::from('a');
+        assert_eq!(
+            board
+                .analyze_placement(&[(below_t, a)], &fsm, &config)
+                .pop(),
+            board.cross_word_at(below_t, a, Direction::East, &fsm, &config)
+        );
+        assert_eq!(
+            Some((String::from("TZ"), false, 0)),
+            board.cross_word_at(below_t, Tile::from('z'), Direction::West, &fsm, &config)
+        );
+
+        // a vertical play after the "T" forms a horizontal word.
+        assert_eq!(
+            Some((String::from("CATS"), true, 6)),
+            board.cross_word_at(end, Tile::from('s'), Direction::South, &fsm, &config)
+        );
+
+        // no word is formed without a perpendicular neighbour, or on an
+        // occupied square.
+        assert_eq!(
+            None,
+            board.cross_word_at(below_t, a, Direction::South, &fsm, &config)
+        );
+        assert_eq!(
+            None,
+            board.cross_word_at(end, a, Direction::East, &fsm, &config)
+        );
+        assert_eq!(
+            None,
+            board.cross_word_at(Pos::start(), a, Direction::North, &fsm, &config)
+        );
+        assert!(board[below_t].is_none());
+    }
+
+    #[test]
+    fn score_breakdown() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["at", "cat", "cats", "ta", "tas"])
+            .unwrap()
+            .build();
+        let config = GameConfig::default();
+        let board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::start()).letters("cat"))
+            .build();
+
+        // extending "CAT" to "CATS" covers no premium squares.
+        let t = Pos::start().offset(Direction::East, 2).unwrap();
+        let s = Pos::start().offset(Direction::East, 3).unwrap();
+        let tile_positions = [(s, Tile::from('s'))];
+        let breakdown = board
+            .score_breakdown(&tile_positions, &fsm, &config)
+            .unwrap();
+        assert_eq!("CATS 6 = 6", breakdown.to_string());
+
+        let a = t.dir(Direction::South).unwrap();
+        let tile_positions = [
+            (a, Tile::from('a')),
+            (a.dir(Direction::South).unwrap(), Tile::from('s')),
+        ];
+        let breakdown = board
+            .score_breakdown(&tile_positions, &fsm, &config)
+            .unwrap();
+
+        // "TAS" is played down from the "T" of "CAT".
+        let mut after = board.clone();
+        let score = after
+            .make_placement(&tile_positions, &fsm, &config)
+            .unwrap();
+        assert_eq!(score, breakdown.total());
+        assert_eq!(1, breakdown.words.len());
+        assert_eq!("TAS", breakdown.words[0].word);
+
+        // invalid placements are rejected, and the board is not modified.
+        let tile_positions = [(a, Tile::from('z'))];
+        assert!(board
+            .score_breakdown(&tile_positions, &fsm, &config)
+            .is_err());
+        assert!(board[a].is_none());
+    }
+
+    #[test]
+    fn score_breakdown_display() {
+        let breakdown = ScoreBreakdown {
+            words: vec![
+                scoring::WordScore {
+                    word: String::from("HELLO"),
+                    premiums: vec![(Tile::from('h'), Premium::DoubleLetter)],
+                    score: 8,
+                },
+                scoring::WordScore {
+                    word: String::from("ZA"),
+                    premiums: vec![],
+                    score: 11,
+                },
+            ],
+            bingo: 50,
+        };
+
+        assert_eq!(69, breakdown.total());
+        assert_eq!(
+            "HELLO 8 (H on DL) + ZA 11 + 50 bingo = 69",
+            breakdown.to_string()
+        );
+    }
+
+    #[test]
+    fn parallel_play() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["at", "ta"]).unwrap().build();
+        let config = GameConfig::default();
+        let mut board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::start()).letters("at"))
+            .build();
+
+        // "TA" directly below "AT" forms "TA" and the cross-words "AT"
+        // and "TA". The "A" is on a double letter square, which counts
+        // towards both "TA"s, and the start square was already used.
+        let below = Pos::start().dir(Direction::South).unwrap();
+        let tile_positions = Play::horizontal(below).letters("ta").tile_positions(&board);
+        assert_eq!(2, tile_positions.len());
+        assert_eq!(
+            vec![
+                (String::from("TA"), true, 3),
+                (String::from("AT"), true, 2),
+                (String::from("TA"), true, 3)
+            ],
+            board.analyze_placement(&tile_positions, &fsm, &config)
+        );
+
+        let result = board.make_placement(&tile_positions, &fsm, &config);
+        assert!(matches!(result, Ok(8)));
+        assert_eq!(2, board.find_word("ta").len());
+        assert_eq!(2, board.find_word("at").len());
+    }
+
+    #[test]
+    fn redesignated_blank() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["at", "ta"]).unwrap().build();
+        let config = GameConfig::default();
+        let mut board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::start()).letters("at"))
+            .build();
+        let before = Vec::from(board.clone());
+
+        // a blank below the "A" is designated as "Z" and then changed to
+        // "T". Only the final designation is validated and stored.
+        let below = Pos::start().dir(Direction::South).unwrap();
+        let blank = |ch| vec![(below, Tile::Blank(Letter::new(ch)))];
+
+        let result = board.make_placement(&blank('z'), &fsm, &config);
+        assert!(matches!(result, Err(GameError::InvalidWord)));
+        assert_eq!(before, Vec::from(board.clone()));
+
+        // the blank scores nothing, whichever letter it is.
+        let result = board.make_placement(&blank('t'), &fsm, &config);
+        assert!(matches!(result, Ok(1)));
+        assert_eq!(Some(Tile::Blank(Letter::new('t'))), board[below]);
+    }
+
+    #[test]
+    fn parallel_play_invalid_cross_word() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["at"]).unwrap().build();
+        let config = GameConfig::default();
+        let mut board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::start()).letters("at"))
+            .build();
+        let before = Vec::from(board.clone());
+
+        // "AT" below and one square right of "AT" is a valid word, but forms
+        // the invalid cross-word "TA".
+        let below_t = Pos::start().offset(Direction::East, 1).unwrap();
+        let below_t = below_t.dir(Direction::South).unwrap();
+        let tile_positions = Play::horizontal(below_t)
+            .letters("at")
+            .tile_positions(&board);
+
+        let result = board.make_placement(&tile_positions, &fsm, &config);
+        assert!(matches!(result, Err(GameError::InvalidWord)));
+        assert_eq!(before, Vec::from(board.clone()));
+
+        // the play is allowed once "TA" is a word.
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["at", "ta"]).unwrap().build();
+        let result = board.make_placement(&tile_positions, &fsm, &config);
+        assert!(matches!(result, Ok(6)));
+    }
+
+    /// Creates a bitboard from `(row, col)` pairs.
+    fn bitboard(positions: &[(usize, usize)]) -> BitBoard {
+        positions.iter().map(|&rc| Pos::from(rc)).collect()
+    }
+
+    #[test]
+    fn word_starts_empty_board() {
+        let board = Board::default();
+
+        // only the start square is a word start for a single tile.
+        let start = bitboard(&[(7, 7)]);
+        assert_eq!((start, start), board.word_starts(1));
+
+        // longer words can start before the start square.
+        let (starts_h, starts_v) = board.word_starts(3);
+        assert_eq!(bitboard(&[(7, 5), (7, 6), (7, 7)]), starts_h);
+        assert_eq!(bitboard(&[(5, 7), (6, 7), (7, 7)]), starts_v);
+
+        assert_eq!(
+            (BitBoard::default(), BitBoard::default()),
+            board.word_starts(0)
+        );
+    }
+
+    #[test]
+    fn word_starts_mid_game() {
+        // "CAT" from H8 to J8.
+        let board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::start()).letters("cat"))
+            .build();
+        let (starts_h, starts_v) = board.word_starts(1);
+
+        // the squares above, below and before "CAT", and the "C" itself.
+        let expected_h = bitboard(&[
+            (6, 7),
+            (6, 8),
+            (6, 9),
+            (7, 6),
+            (7, 7),
+            (8, 7),
+            (8, 8),
+            (8, 9),
+        ]);
+        assert_eq!(expected_h, starts_h);
+
+        // the squares above, before and after "CAT", and every tile of "CAT"
+        // (as none have a tile above).
+        let expected_v = bitboard(&[
+            (6, 7),
+            (6, 8),
+            (6, 9),
+            (7, 6),
+            (7, 7),
+            (7, 8),
+            (7, 9),
+            (7, 10),
+        ]);
+        assert_eq!(expected_v, starts_v);
+
+        // the start square is no longer a word start once it is covered by a
+        // word that does not start there.
+        let board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::from((7, 6))).letters("cat"))
+            .build();
+        let (starts_h, _) = board.word_starts(7);
+        assert!(!starts_h.is_set(Pos::start()));
+        assert!(starts_h.is_set(Pos::from((7, 0))));
+    }
+
+    #[test]
+    fn min_word_len() {
+        // two letter words are allowed by default (double word score).
+        let config = GameConfig::default();
+        let result = place_at(&mut Board::default(), Pos::start(), &config);
+        assert!(matches!(result, Ok(4)));
+
+        // but can be restricted.
+        let config = GameConfig {
+            min_word_len: 3,
+            ..GameConfig::default()
+        };
+        let result = place_at(&mut Board::default(), Pos::start(), &config);
+        assert!(matches!(result, Err(GameError::WordTooShort)));
+    }
+
+    #[test]
+    fn consistency() {
+        let mut board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::start()).letters("cat"))
+            .build();
+        assert!(board.is_consistent());
+
+        // a tile that is only in one grid.
+        board.grid_v.set(Pos::from(0), Some(Tile::from('a')));
+        assert!(!board.is_consistent());
+    }
+
+    #[test]
+    fn would_connect() {
+        let mut board = Board::default();
+        let row = |start: Pos, len| start.project(Direction::East).take(len).collect::<Vec<_>>();
+
+        // the first placement must reach the start square.
+        assert!(board.would_connect(&[]));
+        assert!(board.would_connect(&row(Pos::start(), 2)));
+        assert!(!board.would_connect(&row(Pos::from(0), 2)));
+
+        // later placements must touch an existing tile, even if the tiles
+        // between are not placed yet.
+        board.place_unchecked(
+            &Play::horizontal(Pos::start())
+                .letters("cat")
+                .tile_positions(&board),
+        );
+        let below = Pos::start().dir(Direction::South).unwrap();
+        assert!(board.would_connect(&[below]));
+        assert!(board.would_connect(&row(Pos::start(), 4)));
+        assert!(!board.would_connect(&row(Pos::from(0), 2)));
+        assert!(!board.would_connect(&[below, Pos::from(0)]));
+    }
+
+    #[test]
+    fn precheck_placement() {
+        let config = GameConfig::default();
+        let mut board = Board::default();
+        let empty = Board::default();
+        let tiles = |start, word| Play::horizontal(start).letters(word).tile_positions(&empty);
+
+        // the words are not checked.
+        let opening = tiles(Pos::start(), "zzx");
+        assert!(board.precheck_placement(&opening, &config).is_ok());
+
+        // but the positions are.
+        let result = board.precheck_placement(&tiles(Pos::from(0), "at"), &config);
+        assert!(matches!(result, Err(GameError::MustIntersectStart)));
+        let result = board.precheck_placement(&[], &config);
+        assert!(matches!(result, Err(GameError::PlacementCount)));
+        let scattered = vec![
+            (Pos::start(), Tile::from('a')),
+            (Pos::from(0), Tile::from('t')),
+        ];
+        let result = board.precheck_placement(&scattered, &config);
+        assert!(matches!(result, Err(GameError::NoCommonLine)));
+
+        // the result matches a real placement for the positions.
+        board.place_unchecked(&opening);
+        let result = board.precheck_placement(&opening, &config);
+        assert!(matches!(result, Err(GameError::CoincedentTiles)));
+        let result = board.precheck_placement(&tiles(Pos::from(0), "at"), &config);
+        assert!(matches!(result, Err(GameError::NotConnected)));
+    }
+
+    #[test]
+    fn off_center_opening() {
+        let corner = Pos::from(0);
+
+        // the standard rule requires the start square.
+        let config = GameConfig::default();
+        let result = place_at(&mut Board::default(), corner, &config);
+        assert!(matches!(result, Err(GameError::MustIntersectStart)));
+
+        // the relaxed rule allows any opening.
+        let config = GameConfig {
+            require_center_start: false,
+            ..GameConfig::default()
+        };
+        let mut board = Board::default();
+        assert!(place_at(&mut board, corner, &config).is_ok());
+
+        // but later plays must still be connected.
+        let result = place_at(&mut board, Pos::start(), &config);
+        assert!(matches!(result, Err(GameError::NotConnected)));
+    }
+
+    #[test]
+    fn find_words() {
+        let below_start = Pos::start().offset(Direction::South, 1).unwrap();
+        let board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::start()).letters("cat"))
+            .place(Play::vertical(below_start).letters("ow"))
+            .build();
+
+        let cat = (0..3)
+            .map(|i| Pos::start().offset(Direction::East, i).unwrap())
+            .collect::<Vec<_>>();
+        let cow = (0..3)
+            .map(|i| Pos::start().offset(Direction::South, i).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![
+                (String::from("CAT"), cat),
+                (String::from("COW"), cow.clone())
+            ],
+            board.find_words()
+        );
+
+        // every position of a vertical word holds one of its tiles.
+        let found = board.find_word("cow");
+        assert_eq!(vec![cow], found);
+        let letters = found[0]
+            .iter()
+            .map(|&pos| char::from(board[pos].unwrap().letter().unwrap()))
+            .collect::<String>();
+        assert_eq!("COW", letters);
+        assert!(board.find_word("dog").is_empty());
+    }
+
+    #[test]
+    fn vertical_grid() {
+        let start = Pos::start();
+        let board = BoardBuilder::default()
+            .place(Play::vertical(start).letters("cow"))
+            .build();
+
+        // the vertical word is horizontal in `grid_v`.
+        let mut boundaries = board.grid_v().word_boundaries();
+        let boundary = boundaries.next().unwrap();
+        assert!(boundaries.next().is_none());
+        assert!(board.grid_h().word_boundaries().next().is_none());
+
+        let positions: Vec<_> = boundary.into_iter().collect();
+        for pair in positions.windows(2) {
+            assert_eq!(pair[0].row(), pair[1].row());
+        }
+
+        // positions are mapped back onto the column of the board.
+        for (idx, pos) in positions.into_iter().enumerate() {
+            let board_pos = board.grid_v().map_pos(pos);
+
+            assert_eq!(pos.swap_rc(), board_pos);
+            assert_eq!(start.offset(Direction::South, idx), Some(board_pos));
+            assert_eq!(board.grid_v()[pos], board[board_pos]);
+        }
+    }
+
+    #[test]
+    fn serde_roundtrip() {
+        let board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::start()).letters("cat"))
+            .place(Play::vertical(Pos::start()).letters("cow"))
+            .build();
+
+        let bytes = bincode::serialize(&board).unwrap();
+        let deserialized: Board = bincode::deserialize(&bytes).unwrap();
+
+        assert_eq!(board.grid_h().tiles(), deserialized.grid_h().tiles());
+        assert_eq!(board.grid_v().tiles(), deserialized.grid_v().tiles());
+        assert!(bincode::deserialize::<Board>(
+            &bincode::serialize(&vec![None::<Tile>; 3]).unwrap()
+        )
+        .is_err());
+    }
+}
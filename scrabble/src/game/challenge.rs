@@ -0,0 +1,47 @@
+//! Models challenges, where words are checked after they are played
+//! rather than when the play is made.
+
+use crate::game::PlayerNum;
+use serde::{Deserialize, Serialize};
+
+/// The rule used for challenging the words formed by a placement.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChallengeRule {
+    /// Words are checked against the word list when they are played, so
+    /// a placement with an invalid word is never made.
+    #[default]
+    Off,
+    /// Words are only checked when a placement is challenged. If every
+    /// word is valid, the challenger loses their turn.
+    Single,
+    /// Words are only checked when a placement is challenged, and an
+    /// unsuccessful challenge has no penalty.
+    Free,
+}
+impl ChallengeRule {
+    /// Checks whether words are checked when they are played (rather than
+    /// when they are challenged).
+    pub fn is_off(self) -> bool {
+        self == ChallengeRule::Off
+    }
+}
+
+/// The result of challenging a placement.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChallengeOutcome {
+    /// The player that made the challenged placement.
+    pub player_num: PlayerNum,
+    /// The invalid words formed by the placement. If there are any, the
+    /// challenge was successful and the placement was removed from the board.
+    pub invalid_words: Vec<String>,
+    /// Whether the challenger lost their turn, which happens when the
+    /// challenge was unsuccessful under [`ChallengeRule::Single`].
+    pub lost_turn: bool,
+}
+impl ChallengeOutcome {
+    /// Checks whether the challenge was successful (the placement
+    /// contained an invalid word).
+    pub fn is_successful(&self) -> bool {
+        !self.invalid_words.is_empty()
+    }
+}
@@ -0,0 +1,187 @@
+//! A turn loop for games between human and Ai players, without a socket,
+//! database or runtime. Mirrors the live game on the server (where Ai plays
+//! are made until a connected user has to play), so that full games with
+//! mixed participants can be tested deterministically.
+
+use crate::{
+    ai::Ai,
+    error::{GameError, GameResult},
+    game::{play::Play, Game, PlayerNum},
+    util::fsm::Fsm,
+};
+use std::collections::HashMap;
+
+/// The result of a single [`Harness::step`].
+#[derive(Debug)]
+pub(crate) enum Step {
+    /// A play was made by a player.
+    Played(PlayerNum, Play),
+    /// It is a human player's turn, but no play was provided.
+    Waiting(PlayerNum),
+    /// The game is over.
+    Over,
+}
+
+/// Runs the turns of a [`Game`], where each player is either controlled by
+/// an [`Ai`] or makes plays that are provided to [`Harness::step`].
+#[derive(Debug)]
+pub(crate) struct Harness<'a, F> {
+    /// The game being played.
+    game: Game,
+    /// The fsm used to generate and validate plays.
+    fsm: &'a F,
+    /// The Ai controlling each player, or `None` for a human player.
+    players: HashMap<PlayerNum, Option<Ai>>,
+}
+
+impl<'a, F: Fsm<'a>> Harness<'a, F> {
+    /// Creates a harness where every player of `game` is a human.
+    pub fn new(game: Game, fsm: &'a F) -> Self {
+        let players = game
+            .player_nums()
+            .map(|player_num| (player_num, None))
+            .collect();
+
+        Self { game, fsm, players }
+    }
+    /// Sets the Ai that controls `player_num`, or makes them a human player
+    /// if `ai` is `None`. The Ai should be seeded to make its plays
+    /// deterministic.
+    pub fn set_ai(&mut self, player_num: PlayerNum, ai: Option<Ai>) {
+        self.players.insert(player_num, ai);
+    }
+    /// Checks whether `player_num` is controlled by an Ai.
+    pub fn is_ai(&self, player_num: PlayerNum) -> bool {
+        self.players.get(&player_num).is_some_and(Option::is_some)
+    }
+    /// Gets the game being played.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Makes the next play. If an Ai is to play, it chooses a play and
+    /// `human_play` is ignored. Otherwise `human_play` is made, or
+    /// [`Step::Waiting`] is returned if there is no play. An invalid human
+    /// play returns an error, and the same player is still to play.
+    pub fn step(&mut self, human_play: Option<Play>) -> GameResult<Step> {
+        let to_play = match self.game.to_play() {
+            Some(to_play) => to_play,
+            None => return Ok(Step::Over),
+        };
+
+        let play = match (self.players.get(&to_play), human_play) {
+            (Some(Some(ai)), _) => ai.next_play(self.fsm, &self.game)?,
+            (_, Some(play)) => play,
+            (_, None) => return Ok(Step::Waiting(to_play)),
+        };
+        self.game.make_play(&play, self.fsm)?;
+
+        Ok(Step::Played(to_play, play))
+    }
+    /// Makes plays for Ai players until a human player is to play or the
+    /// game ends, returning the plays that were made. (As on the server,
+    /// Ai players that can only exchange tiles will never end the game).
+    /// Returns an error if an Ai fails to make a play.
+    pub fn make_ai_plays(&mut self) -> GameResult<Vec<(PlayerNum, Play)>> {
+        let mut plays = vec![];
+
+        while let Step::Played(player_num, play) = self.step(None)? {
+            plays.push((player_num, play));
+        }
+
+        Ok(plays)
+    }
+    /// Makes a play for the human player that is to play, then makes plays
+    /// for the Ai players that follow.
+    ///
+    /// # Panics
+    /// If an Ai is to play, since the play would be ignored.
+    pub fn try_play(&mut self, play: Play) -> GameResult<Vec<(PlayerNum, Play)>> {
+        let to_play = self.game.to_play().ok_or(GameError::Over)?;
+        assert!(!self.is_ai(to_play), "an Ai is to play");
+
+        self.step(Some(play))?;
+
+        self.make_ai_plays()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game::{letter_bag::LetterBag, GameStatus},
+        util::{
+            fsm::{FastFsm, FsmBuilder},
+            pos::Pos,
+        },
+    };
+
+    /// Creates a game with a seeded letter bag.
+    fn seeded_game(player_count: usize) -> Game {
+        Game::with_letter_bag(player_count, LetterBag::default().with_seed(3))
+    }
+
+    /// Plays a full game between two seeded Ais, returning the final scores.
+    fn ai_game(fsm: &FastFsm) -> Vec<usize> {
+        let mut harness = Harness::new(seeded_game(2), fsm);
+        for player_num in PlayerNum::iter(2) {
+            harness.set_ai(player_num, Some(Ai::hard().with_seed(1)));
+        }
+
+        harness.make_ai_plays().unwrap();
+
+        match harness.game().status() {
+            GameStatus::Over(game_over) => game_over.final_scores().map(|(_, s)| s).collect(),
+            GameStatus::ToPlay(_) => panic!("the game should be over"),
+        }
+    }
+
+    #[test]
+    fn deterministic_ai_game() {
+        let fsm = FastFsm::bundled();
+
+        assert_eq!(ai_game(fsm), ai_game(fsm));
+    }
+
+    #[test]
+    fn mixed_players() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["at", "cat"]).unwrap().build();
+        let human = PlayerNum::first();
+        let ai = human.next(2);
+
+        let mut harness = Harness::new(seeded_game(2), &fsm);
+        harness.set_ai(ai, Some(Ai::default().with_seed(0)));
+        assert!(!harness.is_ai(human));
+        assert!(harness.is_ai(ai));
+
+        // without a play, the human is waited for.
+        assert!(matches!(harness.step(None), Ok(Step::Waiting(p)) if p == human));
+        assert!(harness.make_ai_plays().unwrap().is_empty());
+
+        // an invalid play is rejected, and the human is still to play.
+        let invalid = Play::Place(vec![(Pos::start(), 'q'.into())]);
+        assert!(harness.try_play(invalid).is_err());
+        assert_eq!(Some(human), harness.game().to_play());
+
+        // after the human plays, the ai plays and it is the human's turn again.
+        let ai_plays = harness.try_play(Play::Pass).unwrap();
+        assert_eq!(1, ai_plays.len());
+        assert_eq!(ai, ai_plays[0].0);
+        assert_eq!(Some(human), harness.game().to_play());
+
+        // an ai player ignores the provided play.
+        harness.set_ai(human, Some(Ai::default().with_seed(0)));
+        assert!(matches!(
+            harness.step(Some(Play::Pass)),
+            Ok(Step::Played(p, _)) if p == human
+        ));
+
+        // the game ends when both human players pass twice.
+        harness.set_ai(human, None);
+        harness.set_ai(ai, None);
+        while let Ok(Step::Played(..)) = harness.step(Some(Play::Pass)) {}
+        assert!(matches!(harness.step(None), Ok(Step::Over)));
+        assert!(harness.try_play(Play::Pass).is_err());
+    }
+}
@@ -4,15 +4,66 @@ use crate::{
     game::{rack::RACK_SIZE, tile::Tile},
     util::tile_counts::TileCounts,
 };
-use rand::Rng;
-use std::iter::once;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{fmt, iter::once};
+
+/// The number of random bytes hashed with a seed in [`BagSeed::commitment`].
+pub const NONCE_LEN: usize = 32;
+
+/// The seed of a verifiable letter bag, and a random nonce that prevents
+/// the seed from being found from its commitment by trying every seed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BagSeed {
+    /// The seed passed to [`LetterBag::with_seed`].
+    pub seed: u64,
+    /// Random bytes that are hashed with the seed.
+    pub nonce: [u8; NONCE_LEN],
+}
+impl fmt::Display for BagSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (nonce {})", self.seed, hex(&self.nonce))
+    }
+}
+impl BagSeed {
+    /// Creates a random seed and nonce.
+    pub fn random() -> Self {
+        Self {
+            seed: rand::random(),
+            nonce: rand::random(),
+        }
+    }
+    /// Commits to the seed without revealing it, as the hex encoded SHA-256
+    /// hash of the nonce followed by the little endian bytes of the seed.
+    /// Once the seed and nonce are revealed, players can check them against
+    /// the commitment and replay the draws with [`LetterBag::with_seed`].
+    pub fn commitment(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.nonce);
+        hasher.update(self.seed.to_le_bytes());
+
+        hex(&hasher.finalize())
+    }
+}
+
+/// Hex encodes `bytes`.
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
 
 /// A structure containing a finite number of tiles which can
 /// be used during the game. Since there are 27 tiles, an array
 /// with 27 elements is used to keep count.
-#[derive(Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct LetterBag {
     counts: TileCounts,
+    /// If set, tiles are drawn using this generator rather than the
+    /// thread local one, so the draw order is reproducible. The generator
+    /// is not serialized, so a deserialized bag always draws randomly.
+    #[serde(skip)]
+    rng: Option<ChaCha20Rng>,
 }
 
 impl Default for LetterBag {
@@ -30,10 +81,48 @@ impl From<[usize; 27]> for LetterBag {
     fn from(counts: [usize; 27]) -> Self {
         Self {
             counts: TileCounts::from(counts),
+            rng: None,
         }
     }
 }
 impl LetterBag {
+    /// Creates a [`LetterBag`] with the official letter counts, but
+    /// containing `blank_count` blank tiles.
+    pub fn with_blanks(blank_count: usize) -> Self {
+        let mut counts = [0; 27];
+        for (idx, tile) in Tile::iter().enumerate() {
+            counts[idx] = match tile {
+                Tile::Blank(_) => blank_count,
+                _ => Self::initial_count(tile),
+            };
+        }
+
+        Self::from(counts)
+    }
+    /// Draws tiles in a fixed order determined by `seed`, so that
+    /// tests can reproduce the contents of each rack, and players can
+    /// verify the draws once the seed is revealed. The draws use ChaCha20,
+    /// so they are the same on every platform and version of `rand`.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Some(ChaCha20Rng::seed_from_u64(seed));
+        self
+    }
+    /// Gets every tile remaining in the bag, in the order that they will be
+    /// drawn (assuming no tiles are added back). Only available in tests,
+    /// as the order is only known in advance for a seeded bag.
+    #[cfg(test)]
+    pub fn peek_remaining(&self) -> Vec<Tile> {
+        assert!(
+            self.rng.is_some(),
+            "the draw order of an unseeded bag is unknown"
+        );
+
+        let mut letter_bag = LetterBag {
+            counts: self.counts,
+            rng: self.rng.clone(),
+        };
+        std::iter::from_fn(|| letter_bag.draw()).collect()
+    }
     /// Checks whether the bag is empty.
     pub fn is_empty(&self) -> bool {
         self.counts.is_empty()
@@ -89,7 +178,10 @@ impl LetterBag {
             len => Some({
                 // Generate a random index, as though all tiles
                 // in the bag were layed out in a single array.
-                let idx = rand::thread_rng().gen_range(0..len);
+                let idx = match &mut self.rng {
+                    Some(rng) => rng.gen_range(0..len),
+                    None => rand::thread_rng().gen_range(0..len),
+                };
                 // traverse the tiles until `idx` is reached
                 let mut tile_idx = 0;
                 let mut count = self.counts.count(tile_idx);
@@ -130,7 +222,11 @@ impl LetterBag {
 
 #[cfg(test)]
 mod tests {
-    use crate::game::{letter_bag::LetterBag, rack::RACK_SIZE, tile::Tile};
+    use crate::game::{
+        letter_bag::{BagSeed, LetterBag, NONCE_LEN},
+        rack::RACK_SIZE,
+        tile::Tile,
+    };
 
     #[test]
     fn draw_limits() {
@@ -140,6 +236,13 @@ mod tests {
         assert_eq!(letter_bag.len(), 93);
     }
 
+    #[test]
+    fn blank_count() {
+        assert_eq!(LetterBag::with_blanks(0).len(), 98);
+        assert_eq!(LetterBag::with_blanks(2).len(), 100);
+        assert_eq!(LetterBag::with_blanks(4).len(), 102);
+    }
+
     #[test]
     fn empty_bag() {
         let mut letter_bag = LetterBag::default();
@@ -166,4 +269,37 @@ mod tests {
 
         assert_eq!(letter_bag.len(), len);
     }
+
+    #[test]
+    fn seeded_draw_order() {
+        let mut a = LetterBag::default().with_seed(1);
+        let mut b = LetterBag::default().with_seed(1);
+        let expected = a.peek_remaining();
+
+        assert_eq!(expected.len(), 100);
+        assert_eq!(a.draw_many(RACK_SIZE).collect::<Vec<_>>(), expected[..7]);
+        assert_eq!(b.draw_many(RACK_SIZE).collect::<Vec<_>>(), expected[..7]);
+        assert_eq!(a.peek_remaining(), expected[7..]);
+
+        // a different seed gives a different order.
+        let c = LetterBag::default().with_seed(2);
+        assert_ne!(c.peek_remaining(), expected);
+    }
+
+    #[test]
+    fn commitment() {
+        let bag_seed = |seed, nonce| BagSeed {
+            seed,
+            nonce: [nonce; NONCE_LEN],
+        };
+        let commitment = bag_seed(1, 0).commitment();
+
+        assert_eq!(64, commitment.len());
+        assert!(commitment.chars().all(|ch| ch.is_ascii_hexdigit()));
+        assert_eq!(commitment, bag_seed(1, 0).commitment());
+        assert_ne!(commitment, bag_seed(2, 0).commitment());
+
+        // the same seed with a different nonce has a different commitment.
+        assert_ne!(commitment, bag_seed(1, 1).commitment());
+    }
 }
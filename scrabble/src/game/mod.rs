@@ -8,32 +8,134 @@
 //! state for the UI.
 
 use crate::{
+    ai::movegen::GeneratedPlay,
     error::{GameError, GameResult},
-    game::{board::Board, letter_bag::LetterBag, play::Play, rack::Rack, tile::Tile},
-    util::{fsm::Fsm, pos::Pos},
+    game::{
+        alphabet::Alphabet,
+        board::Board,
+        challenge::{ChallengeOutcome, ChallengeRule},
+        letter_bag::LetterBag,
+        play::Play,
+        rack::{Rack, RACK_SIZE},
+        tile::Tile,
+    },
+    util::{bitboard::BitBoard, fsm::Fsm, pos::Pos, scoring, tile_counts::TileCounts},
 };
 use serde::{Deserialize, Serialize};
 
+pub mod alphabet;
 pub mod board;
+pub mod challenge;
+#[cfg(test)]
+pub(crate) mod harness;
 pub mod letter_bag;
 pub mod play;
+pub mod puzzle;
 pub mod rack;
+pub mod record;
+pub mod snapshot;
 pub mod tile;
 
+/// The number of consecutive passes by a player that ends the game.
+pub const PASS_LIMIT: usize = 2;
+
+/// Options for the rules of a [`Game`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameConfig {
+    /// The minimum number of letters in every word formed by a play.
+    /// Words always need at least 2 letters, so values below 2 have
+    /// no effect.
+    pub min_word_len: usize,
+    /// Whether the first play must cover the start square. If disabled,
+    /// the first play can be placed anywhere on the board.
+    pub require_center_start: bool,
+    /// Whether words are checked when they are played, or only when the
+    /// placement is challenged with [`Game::challenge`].
+    pub challenge: ChallengeRule,
+    /// How the final scores are adjusted for the tiles left on the racks.
+    pub endgame: EndgameScoring,
+    /// Whether players may exchange tiles from their rack with
+    /// [`Play::Redraw`].
+    pub allow_exchange: bool,
+    /// Whether premium squares multiply the score of the tiles and words
+    /// placed on them. Disabled for plain scoring, where every word scores
+    /// the face value of its tiles.
+    pub premiums: bool,
+    /// Whether placing every tile from the rack scores a bonus of
+    /// [`BINGO_BONUS`](scoring::BINGO_BONUS) points.
+    pub bingo_bonus: bool,
+    /// The glyphs and scores of the letters.
+    pub alphabet: Alphabet,
+}
+impl Default for GameConfig {
+    fn default() -> Self {
+        Self {
+            min_word_len: 2,
+            require_center_start: true,
+            challenge: ChallengeRule::default(),
+            endgame: EndgameScoring::default(),
+            allow_exchange: true,
+            premiums: true,
+            bingo_bonus: true,
+            alphabet: Alphabet::default(),
+        }
+    }
+}
+impl GameConfig {
+    /// Gets the squares in `new` on which premiums apply, which is none
+    /// when premiums are disabled.
+    pub(crate) fn premium_squares(&self, new: BitBoard) -> BitBoard {
+        match self.premiums {
+            true => new,
+            false => BitBoard::default(),
+        }
+    }
+    /// Gets the bonus for placing `tile_count` tiles in a single play.
+    pub(crate) fn bonus_for(&self, tile_count: usize) -> usize {
+        match (self.bingo_bonus, tile_count) {
+            (true, RACK_SIZE) => scoring::BINGO_BONUS,
+            _ => 0,
+        }
+    }
+    /// Checks whether the standard scoring is used, with premiums and the
+    /// bingo bonus.
+    fn is_standard_scoring(&self) -> bool {
+        self.premiums && self.bingo_bonus
+    }
+}
+
 /// Top level struct allowing for management of the entire
 /// game. Manages players, all state, and determines when the
 /// game is over, calculating scores and determining the winner.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Game {
     board: Board,
     letter_bag: LetterBag,
     players: Vec<Player>,
     to_play: PlayerNum,
     status: GameStatus,
+    config: GameConfig,
+    play_count: usize,
+    last_placement: Option<LastPlacement>,
+    /// Every tile in the game when it was created.
+    initial_tiles: TileCounts,
+}
+
+/// The most recent placement, which can be challenged until the
+/// next play is made.
+#[derive(Debug, Serialize, Deserialize)]
+struct LastPlacement {
+    /// The player that made the placement.
+    player_num: PlayerNum,
+    tile_positions: Vec<(Pos, Tile)>,
+    /// The state of the player before the placement was made.
+    player: Player,
+    /// The letter bag before the player's rack was refilled.
+    letter_bag: LetterBag,
 }
 
 /// Models a scrabble player.
-#[derive(Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Player {
     rack: Rack,
     score: usize,
@@ -48,10 +150,15 @@ impl Player {
     pub fn score(&self) -> usize {
         self.score
     }
+    /// Gets the number of consecutive passes made by the player.
+    /// The game ends once this reaches [`PASS_LIMIT`].
+    pub fn pass_count(&self) -> usize {
+        self.pass_count
+    }
 }
 
 /// The current state of the game.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum GameStatus {
     /// One or more players have won
     Over(GameOver),
@@ -66,37 +173,59 @@ impl GameStatus {
 }
 
 /// Stores the final scores and the outcome of the game.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GameOver {
     max_score: usize,
     scores: Vec<usize>,
+    running_scores: Vec<usize>,
+    penalties: Vec<usize>,
+    bonuses: Vec<usize>,
     reason: GameOverReason,
 }
 impl GameOver {
-    /// Computes the final scores from the game state.
-    pub fn new(reason: GameOverReason, players: &[Player], last_player: PlayerNum) -> Self {
+    /// Computes the final scores from the game state, adjusting the scores
+    /// for the tiles left on the racks (valued using `alphabet`) according
+    /// to `endgame`.
+    pub fn new(
+        reason: GameOverReason,
+        players: &[Player],
+        last_player: PlayerNum,
+        endgame: EndgameScoring,
+        alphabet: &Alphabet,
+    ) -> Self {
         let mut scores = vec![0; players.len()];
+        let mut penalties = vec![0; players.len()];
+        let mut bonuses = vec![0; players.len()];
         let mut overall_rack_sum = 0;
 
         // First calculate the initial scores for all players, as
         //     (current running total) - (sum of tiles on rack)
         for (idx, player) in players.iter().enumerate() {
-            let rack_sum = player.rack.tile_sum();
+            let rack_sum = match endgame {
+                EndgameScoring::None => 0,
+                _ => player.rack.tile_sum(alphabet),
+            };
             scores[idx] = player.score.saturating_sub(rack_sum);
+            penalties[idx] = player.score - scores[idx];
             overall_rack_sum += rack_sum;
         }
 
         // If the last player has no remaining tiles, add the sum of the
         // other players' scores.
-        if players[usize::from(last_player)].rack.is_empty() {
+        if endgame == EndgameScoring::Standard && players[usize::from(last_player)].rack.is_empty()
+        {
             // Then calculate the final score for the player that ended the game,
             // by adding `overall_rack_total` to their score.
             scores[usize::from(last_player)] += overall_rack_sum;
+            bonuses[usize::from(last_player)] = overall_rack_sum;
         }
 
         Self {
             max_score: scores.iter().max().copied().unwrap_or_default(),
             scores,
+            running_scores: players.iter().map(|player| player.score).collect(),
+            penalties,
+            bonuses,
             reason,
         }
     }
@@ -104,6 +233,21 @@ impl GameOver {
     pub fn score(&self, player_num: PlayerNum) -> usize {
         self.scores[usize::from(player_num)]
     }
+    /// Gets the score for a player before it was adjusted for
+    /// the tiles remaining on the racks.
+    pub fn running_score(&self, player_num: PlayerNum) -> usize {
+        self.running_scores[usize::from(player_num)]
+    }
+    /// Gets the value subtracted from a player's score for the
+    /// tiles remaining on their rack.
+    pub fn penalty(&self, player_num: PlayerNum) -> usize {
+        self.penalties[usize::from(player_num)]
+    }
+    /// Gets the value added to a player's score for emptying their
+    /// rack (the sum of the tiles remaining on all other racks).
+    pub fn bonus(&self, player_num: PlayerNum) -> usize {
+        self.bonuses[usize::from(player_num)]
+    }
     /// Gets the maximum score achieved.
     pub fn max_score(&self) -> usize {
         self.max_score
@@ -128,13 +272,32 @@ impl GameOver {
     }
 }
 
+/// How the final scores are adjusted for the tiles left on the racks
+/// at the end of the game.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EndgameScoring {
+    /// The value of each player's remaining tiles is subtracted from their
+    /// score. If a player emptied their rack, the total of the other racks is
+    /// added to their score.
+    #[default]
+    Standard,
+    /// The value of each player's remaining tiles is subtracted from their
+    /// score, with no bonus for emptying the rack.
+    SubtractOnly,
+    /// The scores are not adjusted.
+    None,
+}
+
 /// The reason that the game has ended.
-#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GameOverReason {
     /// A player has emptied their rack with no letters remaining in the bag.
     EmptyRack,
     /// A player has passed their turn twice in a row.
     TwoPasses,
+    /// The game was ended before it finished, as the players stopped
+    /// playing.
+    Abandoned,
 }
 
 /// Used to identify players within a [`Game`]. Since
@@ -169,8 +332,16 @@ impl From<PlayerNum> for usize {
 impl Game {
     /// Constructs a new [`Game`] from the number of players.
     pub fn new(player_count: usize) -> Self {
-        let mut letter_bag = LetterBag::default();
-
+        Self::with_letter_bag(player_count, LetterBag::default())
+    }
+    /// Constructs a new [`Game`] from the number of players, drawing
+    /// tiles from a custom `letter_bag`.
+    pub fn with_letter_bag(player_count: usize, letter_bag: LetterBag) -> Self {
+        Self::with_config(player_count, letter_bag, GameConfig::default())
+    }
+    /// Constructs a new [`Game`] from the number of players, drawing
+    /// tiles from a custom `letter_bag` and following the rules in `config`.
+    pub fn with_config(player_count: usize, mut letter_bag: LetterBag, config: GameConfig) -> Self {
         let players = (0..player_count)
             .map(|_| Player {
                 rack: Rack::new(&mut letter_bag),
@@ -179,13 +350,62 @@ impl Game {
             })
             .collect();
 
-        Self {
+        let mut game = Self {
             letter_bag,
             to_play: PlayerNum::first(),
             board: Board::default(),
             status: GameStatus::ToPlay(PlayerNum::first()),
             players,
+            config,
+            play_count: 0,
+            last_placement: None,
+            initial_tiles: TileCounts::default(),
+        };
+        game.initial_tiles = game.total_tiles_by_kind();
+        game
+    }
+    /// Constructs a [`Game`] that starts from an existing position, such
+    /// as a puzzle, with one rack per player. The scores and pass counts
+    /// start from zero, and the standard rules are used.
+    ///
+    /// Returns [`GameError::InvalidPosition`] if there are no racks, if
+    /// `to_play` is not one of the players, or if the board is inconsistent
+    /// or contains a blank without a letter.
+    pub fn from_position(
+        board: Board,
+        racks: Vec<Rack>,
+        letter_bag: LetterBag,
+        to_play: PlayerNum,
+    ) -> GameResult<Self> {
+        let is_valid = usize::from(to_play) < racks.len()
+            && board.is_consistent()
+            && board.iter().all(|(_, tile)| tile.letter().is_ok());
+        if !is_valid {
+            return Err(GameError::InvalidPosition);
         }
+
+        let players = racks
+            .into_iter()
+            .map(|rack| Player {
+                rack,
+                score: 0,
+                pass_count: 0,
+            })
+            .collect();
+
+        let mut game = Self {
+            board,
+            letter_bag,
+            players,
+            to_play,
+            status: GameStatus::ToPlay(to_play),
+            config: GameConfig::default(),
+            play_count: 0,
+            last_placement: None,
+            initial_tiles: TileCounts::default(),
+        };
+        game.initial_tiles = game.total_tiles_by_kind();
+        Ok(game)
     }
     /// Gets the next player number.
     pub fn to_play(&self) -> Option<PlayerNum> {
@@ -194,6 +414,15 @@ impl Game {
             GameStatus::ToPlay(to_play) => Some(*to_play),
         }
     }
+    /// Gets the player to play next, or [`None`] if the game is over.
+    pub fn to_play_player(&self) -> Option<&Player> {
+        self.to_play().map(|to_play| self.player(to_play))
+    }
+    /// Gets the rack of the player to play next, or [`None`] if the game
+    /// is over.
+    pub fn to_play_rack(&self) -> Option<&Rack> {
+        self.to_play_player().map(Player::rack)
+    }
     /// Gets the number of tiles left in the letter bag.
     pub fn letter_bag_len(&self) -> usize {
         self.letter_bag.len()
@@ -206,6 +435,10 @@ impl Game {
     pub fn status(&self) -> &GameStatus {
         &self.status
     }
+    /// Gets the rules that the game follows.
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
     /// Borrows the board.
     pub fn board(&self) -> &Board {
         &self.board
@@ -214,33 +447,301 @@ impl Game {
     pub fn player_count(&self) -> usize {
         self.players.len()
     }
+    /// Gets the number of plays (including passes and redraws) that have
+    /// been made.
+    pub fn play_count(&self) -> usize {
+        self.play_count
+    }
+    /// Gets the number of the current turn, starting from 1 for the first
+    /// play of the game. Once the game is over, this is one more than the
+    /// number of plays made.
+    pub fn turn_number(&self) -> usize {
+        self.play_count + 1
+    }
     /// Gets an iterator over player numbers for the game.
     pub fn player_nums(&self) -> impl Iterator<Item = PlayerNum> {
         PlayerNum::iter(self.player_count())
     }
+    /// Counts every tile in the game, in the letter bag, on the racks and
+    /// on the board. Blanks on the board are counted as blanks.
+    pub fn total_tiles_by_kind(&self) -> TileCounts {
+        let mut counts = self.letter_bag.clone().counts();
+        for player in &self.players {
+            counts.insert(player.rack.tiles());
+        }
+        counts.insert(self.board.iter().map(|(_, tile)| tile));
+        counts
+    }
+    /// Panics if any tiles have been created or destroyed since the game
+    /// was created. Checked after every play and challenge in debug builds.
+    pub fn assert_tile_conservation(&self) {
+        assert_eq!(
+            self.initial_tiles,
+            self.total_tiles_by_kind(),
+            "the tiles in the game have changed"
+        );
+    }
 
-    /// Attempts to make a [`Play`].
+    /// Attempts to make a [`Play`]. If the most recent placement ended the
+    /// game (see [`Game::is_ending`]), the play accepts it instead, so the
+    /// game ends without the play being made.
     pub fn make_play<'a, F: Fsm<'a>>(&mut self, play: &Play, fsm: &F) -> GameResult<()> {
         // Return early if the game is over.
         if self.status().is_over() {
             return Err(GameError::Over);
         }
+        if self.is_ending() {
+            return self.accept_placement();
+        }
 
         // make the play.
-        match play {
-            Play::Pass => self.pass(),
-            Play::Redraw(tiles) => self.redraw(tiles)?,
-            Play::Place(tile_positions) => self.place(fsm, tile_positions)?,
+        let last_placement = match play {
+            Play::Pass => {
+                self.pass();
+                None
+            }
+            Play::Redraw(_) if !self.config.allow_exchange => {
+                return Err(GameError::ExchangeNotAllowed);
+            }
+            Play::Redraw(tiles) => {
+                self.redraw(tiles)?;
+                None
+            }
+            Play::Place(tile_positions) => {
+                let last_placement = self.last_placement(tile_positions);
+                self.place(fsm, tile_positions)?;
+                last_placement
+            }
+        };
+
+        // a placement can only be challenged until the next play is made.
+        self.last_placement = last_placement;
+
+        // update current player & status
+        self.play_count += 1;
+        let previous = self.to_play;
+        self.to_play = self.to_play.next(self.player_count());
+        self.status = self.next_status(previous);
+
+        if cfg!(debug_assertions) {
+            self.assert_tile_conservation();
+        }
+
+        Ok(())
+    }
+
+    /// Makes a placement found by the move generator. The score and tile
+    /// positions of `gen_play` are trusted, so the words are not checked
+    /// against the word list again. This is much faster than
+    /// [`Game::make_play`] for simulating games between AI players, but any
+    /// play from an untrusted source (such as a user) must use
+    /// [`Game::make_play`] instead.
+    ///
+    /// `gen_play` must have been generated for the current board and rack
+    /// of the player to move. As with [`Game::make_play`], the play accepts
+    /// a placement that ended the game instead of being made.
+    pub fn apply_generated(&mut self, gen_play: GeneratedPlay) -> GameResult<()> {
+        // Return early if the game is over.
+        if self.status().is_over() {
+            return Err(GameError::Over);
+        }
+        if self.is_ending() {
+            return self.accept_placement();
+        }
+
+        let last_placement = self.last_placement(gen_play.tile_positions());
+        let player = &mut self.players[usize::from(self.to_play)];
+        let tile_positions = gen_play.tile_positions();
+
+        // check that the player has enough tiles.
+        if !player.rack.contains(tile_positions.iter().map(|&(_, t)| t)) {
+            return Err(GameError::NotInRack);
         }
 
+        // a play generated for a previous position may overlap existing tiles.
+        let occ_h = self.board.grid_h().occ();
+        if tile_positions.iter().any(|&(pos, _)| occ_h.is_set(pos)) {
+            return Err(GameError::CoincedentTiles);
+        }
+
+        // place the tiles without checking the words. The generated score
+        // assumes the standard scoring and that two letter words are allowed,
+        // so otherwise the play is rescored (which checks the word lengths).
+        let score = match self.config.is_standard_scoring() && self.config.min_word_len <= 2 {
+            true => {
+                self.board.place_unchecked(tile_positions);
+                gen_play.score()
+            }
+            false => self
+                .board
+                .make_unvalidated_placement(tile_positions, &self.config)?,
+        };
+
+        // update player data
+        player.pass_count = 0;
+        player.score += score;
+        player.rack.remove(tile_positions.iter().map(|(_, t)| *t));
+        player.rack.refill(&mut self.letter_bag);
+        self.last_placement = last_placement;
+
         // update current player & status
+        self.play_count += 1;
         let previous = self.to_play;
         self.to_play = self.to_play.next(self.player_count());
         self.status = self.next_status(previous);
 
+        if cfg!(debug_assertions) {
+            self.assert_tile_conservation();
+        }
+
+        Ok(())
+    }
+
+    /// Challenges the most recent placement, which can only be done before
+    /// the next play is made. The challenger is the player to play, since
+    /// they are the only player that can act before the next play.
+    ///
+    /// If any word formed by the placement is not in `fsm`, the placement
+    /// is removed and the turn of the player that made it counts as a pass.
+    /// Otherwise, under [`ChallengeRule::Single`], the challenger loses their
+    /// turn, which also counts as a pass. Either pass can end the game. If
+    /// the placement ended the game and every word is valid, the game ends.
+    pub fn challenge<'a, F: Fsm<'a>>(&mut self, fsm: &F) -> GameResult<ChallengeOutcome> {
+        // Return early if the game is over.
+        if self.status().is_over() {
+            return Err(GameError::Over);
+        }
+
+        let invalid_words = self.invalid_words(fsm);
+        let last = self
+            .last_placement
+            .take()
+            .ok_or(GameError::NothingToChallenge)?;
+        let is_final = self.players[usize::from(last.player_num)].rack.is_empty();
+
+        let lost_turn =
+            invalid_words.is_empty() && !is_final && self.config.challenge == ChallengeRule::Single;
+        if !invalid_words.is_empty() {
+            // withdraw the placement, returning any drawn tiles to the bag.
+            self.board.undo_placement(&last.tile_positions);
+            self.letter_bag = last.letter_bag;
+            self.players[usize::from(last.player_num)] = last.player;
+            self.players[usize::from(last.player_num)].pass_count += 1;
+            self.status = self.next_status(last.player_num);
+        } else if is_final {
+            // the final placement stands, so the game ends.
+            self.status = self.next_status(last.player_num);
+        } else if lost_turn {
+            // the challenger's turn is skipped.
+            self.pass();
+            self.play_count += 1;
+            let previous = self.to_play;
+            self.to_play = self.to_play.next(self.player_count());
+            self.status = self.next_status(previous);
+        }
+
+        if cfg!(debug_assertions) {
+            self.assert_tile_conservation();
+        }
+
+        Ok(ChallengeOutcome {
+            player_num: last.player_num,
+            invalid_words,
+            lost_turn,
+        })
+    }
+    /// Ends the game early, as the players have stopped playing. The scores
+    /// are not adjusted for the tiles left on the racks.
+    pub fn abandon(&mut self) -> GameResult<()> {
+        let to_play = self.to_play().ok_or(GameError::Over)?;
+
+        let game_over = GameOver::new(
+            GameOverReason::Abandoned,
+            &self.players,
+            to_play,
+            EndgameScoring::None,
+            &self.config.alphabet,
+        );
+        self.status = GameStatus::Over(game_over);
+        self.last_placement = None;
+
         Ok(())
     }
+    /// Checks whether the most recent placement can be challenged.
+    pub fn can_challenge(&self) -> bool {
+        self.last_placement.is_some() && !self.status().is_over()
+    }
+    /// Gets the player that made the most recent placement and the tiles
+    /// that they placed, if the placement can be challenged.
+    pub fn challengeable_placement(&self) -> Option<(PlayerNum, &[(Pos, Tile)])> {
+        match self.can_challenge() {
+            true => self
+                .last_placement
+                .as_ref()
+                .map(|last| (last.player_num, last.tile_positions.as_slice())),
+            false => None,
+        }
+    }
+    /// Checks whether the most recent placement emptied the player's rack,
+    /// so the game ends once the placement can no longer be challenged
+    /// (when it is accepted or unsuccessfully challenged).
+    pub fn is_ending(&self) -> bool {
+        self.can_challenge()
+            && self
+                .last_placement
+                .as_ref()
+                .is_some_and(|last| self.player(last.player_num).rack.is_empty())
+    }
+    /// Closes the challenge window of the most recent placement without
+    /// challenging it. If the placement emptied the player's rack, the
+    /// game ends.
+    pub fn accept_placement(&mut self) -> GameResult<()> {
+        // Return early if the game is over.
+        if self.status().is_over() {
+            return Err(GameError::Over);
+        }
+
+        let last = self
+            .last_placement
+            .take()
+            .ok_or(GameError::NothingToChallenge)?;
+        self.status = self.next_status(last.player_num);
+
+        Ok(())
+    }
+    /// Finds the words formed by the most recent placement that are not in
+    /// `fsm`, so would be withdrawn by a challenge. Returns no words if
+    /// there is nothing to challenge.
+    pub fn invalid_words<'a, F: Fsm<'a>>(&self, fsm: &F) -> Vec<String> {
+        let last = match &self.last_placement {
+            Some(last) if !self.status().is_over() => last,
+            _ => return vec![],
+        };
+
+        // check the words against the board before the placement was made.
+        let mut board = self.board.clone();
+        board.undo_placement(&last.tile_positions);
+        board
+            .analyze_placement(&last.tile_positions, fsm, &self.config)
+            .into_iter()
+            .filter(|(_, is_valid, _)| !is_valid)
+            .map(|(word, ..)| word)
+            .collect()
+    }
 
+    /// Stores the state needed to withdraw a placement by the player to play,
+    /// if placements can be challenged.
+    fn last_placement(&self, tile_positions: &[(Pos, Tile)]) -> Option<LastPlacement> {
+        match self.config.challenge.is_off() {
+            true => None,
+            false => Some(LastPlacement {
+                player_num: self.to_play,
+                tile_positions: tile_positions.to_vec(),
+                player: self.player(self.to_play).clone(),
+                letter_bag: self.letter_bag.clone(),
+            }),
+        }
+    }
     /// Makes a [`Play::Redraw`] play.
     fn redraw(&mut self, tiles: &[Tile]) -> GameResult<()> {
         let player = &mut self.players[usize::from(self.to_play)];
@@ -262,13 +763,27 @@ impl Game {
     fn place<'a, F: Fsm<'a>>(&mut self, fsm: &F, tile_positions: &[(Pos, Tile)]) -> GameResult<()> {
         let player = &mut self.players[usize::from(self.to_play)];
 
+        // check the tile count before the rack, since a rack can never
+        // contain more than 7 tiles.
+        if !(1..=7).contains(&tile_positions.len()) {
+            return Err(GameError::PlacementCount);
+        }
+
         // check that the player has enough tiles.
         if !player.rack.contains(tile_positions.iter().map(|&(_, t)| t)) {
             return Err(GameError::NotInRack);
         }
 
-        // attempt to make the placement
-        let score = self.board.make_placement(tile_positions, fsm)?;
+        // attempt to make the placement. When placements can be challenged,
+        // the words are only checked by a challenge.
+        let score = match self.config.challenge.is_off() {
+            true => self
+                .board
+                .make_placement(tile_positions, fsm, &self.config)?,
+            false => self
+                .board
+                .make_unvalidated_placement(tile_positions, &self.config)?,
+        };
 
         // update player data
         player.pass_count = 0;
@@ -283,14 +798,27 @@ impl Game {
     fn next_status(&self, previous: PlayerNum) -> GameStatus {
         let previous_player = &self.players[usize::from(previous)];
 
-        if previous_player.pass_count >= 2 {
+        if previous_player.pass_count >= PASS_LIMIT {
             // The game ends if the most recent player has passed twice
             // in a row.
-            let game_over = GameOver::new(GameOverReason::TwoPasses, &self.players, previous);
+            let game_over = GameOver::new(
+                GameOverReason::TwoPasses,
+                &self.players,
+                previous,
+                self.config.endgame,
+                &self.config.alphabet,
+            );
             GameStatus::Over(game_over)
-        } else if previous_player.rack.is_empty() {
-            // The game ends if the most recent player has emptied their rack.
-            let game_over = GameOver::new(GameOverReason::EmptyRack, &self.players, previous);
+        } else if previous_player.rack.is_empty() && self.last_placement.is_none() {
+            // The game ends if the most recent player has emptied their rack,
+            // once the placement can no longer be challenged.
+            let game_over = GameOver::new(
+                GameOverReason::EmptyRack,
+                &self.players,
+                previous,
+                self.config.endgame,
+                &self.config.alphabet,
+            );
             GameStatus::Over(game_over)
         } else {
             // Otherwise the game is ongoing.
@@ -298,3 +826,611 @@ impl Game {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ai::Ai,
+        game::{board::BoardBuilder, tile::Letter},
+        util::{
+            fsm::{FastFsm, FsmBuilder},
+            pos::Direction,
+        },
+    };
+
+    #[test]
+    fn play_count() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["cat"]).unwrap().build();
+        let mut game = Game::new(2);
+        assert_eq!(0, game.play_count());
+        assert_eq!(1, game.turn_number());
+
+        // an illegal play is not counted.
+        let illegal = Play::Place(vec![(Pos::from((0, 0)), Tile::from('z'))]);
+        assert!(game.make_play(&illegal, &fsm).is_err());
+        assert_eq!(0, game.play_count());
+
+        game.make_play(&Play::Pass, &fsm).unwrap();
+        assert_eq!(1, game.play_count());
+        assert_eq!(2, game.turn_number());
+
+        // the game ends once the first player has passed twice, after which
+        // no more plays are counted.
+        game.make_play(&Play::Pass, &fsm).unwrap();
+        game.make_play(&Play::Pass, &fsm).unwrap();
+        assert!(game.make_play(&Play::Pass, &fsm).is_err());
+        assert!(game.status().is_over());
+        assert_eq!(3, game.play_count());
+        assert_eq!(4, game.turn_number());
+    }
+
+    #[test]
+    fn to_play_rack() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["cat"]).unwrap().build();
+        let mut game = Game::new(2);
+        let first = PlayerNum::first();
+        assert_eq!(
+            game.player(first).rack().to_string(),
+            game.to_play_rack().unwrap().to_string()
+        );
+
+        game.make_play(&Play::Pass, &fsm).unwrap();
+        let second = first.next(2);
+        assert_eq!(
+            game.player(second).score(),
+            game.to_play_player().unwrap().score()
+        );
+        assert_eq!(
+            game.player(second).rack().to_string(),
+            game.to_play_rack().unwrap().to_string()
+        );
+
+        // there is no player to play once the game is over.
+        game.abandon().unwrap();
+        assert!(game.to_play_player().is_none());
+        assert!(game.to_play_rack().is_none());
+    }
+
+    #[test]
+    fn abandon() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["cat"]).unwrap().build();
+        let mut game = Game::new(2);
+        let tile_positions = Play::horizontal(Pos::start())
+            .letters("cat")
+            .tile_positions(game.board());
+        // replacing the rack changes the tiles in the game.
+        game.players[0].rack = Rack::with_str("CATQZZZ");
+        game.initial_tiles = game.total_tiles_by_kind();
+        game.make_play(&Play::Place(tile_positions), &fsm).unwrap();
+
+        game.abandon().unwrap();
+        assert!(!game.can_challenge());
+        match game.status() {
+            GameStatus::Over(game_over) => {
+                assert_eq!(GameOverReason::Abandoned, game_over.reason());
+                // the scores are not adjusted for the remaining tiles.
+                assert_eq!(10, game_over.score(PlayerNum::first()));
+                assert_eq!(0, game_over.penalty(PlayerNum::first()));
+            }
+            GameStatus::ToPlay(_) => panic!("expected the game to be over"),
+        }
+
+        // a finished game cannot be abandoned, or played.
+        assert!(matches!(game.abandon(), Err(GameError::Over)));
+        assert!(matches!(
+            game.make_play(&Play::Pass, &fsm),
+            Err(GameError::Over)
+        ));
+    }
+
+    #[test]
+    fn placement_count() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["cat"]).unwrap().build();
+        let mut game = Game::new(2);
+
+        let result = game.make_play(&Play::Place(vec![]), &fsm);
+        assert!(matches!(result, Err(GameError::PlacementCount)));
+
+        let tile_positions = Pos::start()
+            .project(Direction::East)
+            .take(8)
+            .map(|pos| (pos, Tile::from('a')))
+            .collect();
+        let result = game.make_play(&Play::Place(tile_positions), &fsm);
+        assert!(matches!(result, Err(GameError::PlacementCount)));
+
+        // neither play is made.
+        assert_eq!(0, game.play_count());
+    }
+
+    #[test]
+    fn allow_exchange() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["cat"]).unwrap().build();
+
+        let mut game = Game::new(2);
+        let tiles = game.players[0].rack.tiles().take(2).collect::<Vec<_>>();
+        game.make_play(&Play::Redraw(tiles), &fsm).unwrap();
+        assert_eq!(1, game.play_count());
+
+        let config = GameConfig {
+            allow_exchange: false,
+            ..GameConfig::default()
+        };
+        let mut game = Game::with_config(2, LetterBag::default(), config);
+        let tiles = game.players[0].rack.tiles().take(2).collect::<Vec<_>>();
+        let result = game.make_play(&Play::Redraw(tiles), &fsm);
+        assert!(matches!(result, Err(GameError::ExchangeNotAllowed)));
+        assert_eq!(0, game.play_count());
+
+        // other plays can still be made.
+        game.make_play(&Play::Pass, &fsm).unwrap();
+        assert_eq!(1, game.play_count());
+    }
+
+    #[test]
+    fn from_position() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["at", "cat", "cats"])
+            .unwrap()
+            .build();
+        let board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::start()).letters("cat"))
+            .build();
+        let racks = || vec![Rack::with_str("SXYZ"), Rack::with_str("ABC")];
+        let second = PlayerNum::first().next(2);
+
+        let mut game =
+            Game::from_position(board.clone(), racks(), LetterBag::default(), second).unwrap();
+        assert_eq!(Some(second), game.to_play());
+        assert!(!game.status().is_over());
+
+        // the second player can only extend the existing word with their rack.
+        let cats = Play::horizontal(Pos::start()).letters("cats");
+        let play = Play::Place(cats.tile_positions(game.board()));
+        assert!(matches!(
+            game.make_play(&play, &fsm),
+            Err(GameError::NotInRack)
+        ));
+        game.make_play(&Play::Pass, &fsm).unwrap();
+        game.make_play(&play, &fsm).unwrap();
+        assert_eq!(6, game.player(PlayerNum::first()).score());
+
+        // the player to move must be one of the players.
+        let result = Game::from_position(board, racks(), LetterBag::default(), second.next(3));
+        assert!(matches!(result, Err(GameError::InvalidPosition)));
+        let result = Game::from_position(Board::default(), vec![], LetterBag::default(), second);
+        assert!(matches!(result, Err(GameError::InvalidPosition)));
+    }
+
+    #[test]
+    fn endgame_scoring() {
+        // the first player went out, and the second player has "QZ" left.
+        let players = [
+            Player {
+                rack: Rack::with_str(""),
+                score: 100,
+                pass_count: 0,
+            },
+            Player {
+                rack: Rack::with_str("QZ"),
+                score: 90,
+                pass_count: 0,
+            },
+        ];
+        let [first, second] = [PlayerNum(0), PlayerNum(1)];
+        let alphabet = Alphabet::default();
+        let game_over = |endgame| {
+            GameOver::new(
+                GameOverReason::EmptyRack,
+                &players,
+                first,
+                endgame,
+                &alphabet,
+            )
+        };
+
+        // the standard rule moves the value of the rack to the player that went out.
+        let standard = game_over(EndgameScoring::Standard);
+        assert_eq!(120, standard.score(first));
+        assert_eq!(20, standard.bonus(first));
+        assert_eq!(70, standard.score(second));
+        assert_eq!(20, standard.penalty(second));
+
+        // the value is only subtracted.
+        let subtract_only = game_over(EndgameScoring::SubtractOnly);
+        assert_eq!(100, subtract_only.score(first));
+        assert_eq!(0, subtract_only.bonus(first));
+        assert_eq!(70, subtract_only.score(second));
+        assert_eq!(20, subtract_only.penalty(second));
+
+        // the running scores are kept.
+        let none = game_over(EndgameScoring::None);
+        assert_eq!(100, none.score(first));
+        assert_eq!(90, none.score(second));
+        assert_eq!(0, none.penalty(second));
+        assert_eq!(vec![(first, 100)], none.winners().collect::<Vec<_>>());
+    }
+
+    /// Creates a two player game using `rule`, and the first two tiles
+    /// from the first player's rack, placed from the start square.
+    fn challenge_game(rule: ChallengeRule) -> (Game, Vec<(Pos, Tile)>) {
+        let config = GameConfig {
+            challenge: rule,
+            ..GameConfig::default()
+        };
+        let game = Game::with_config(2, LetterBag::default().with_seed(0), config);
+        let tile_positions = Pos::start()
+            .project(Direction::East)
+            .zip(game.player(PlayerNum::first()).rack().tiles())
+            .map(|(pos, tile)| match tile {
+                Tile::Blank(_) => (pos, Tile::Blank(Some(Letter::new('a').unwrap()))),
+                tile => (pos, tile),
+            })
+            .take(2)
+            .collect();
+
+        (game, tile_positions)
+    }
+
+    /// Gets the word formed by placing `tile_positions` on an empty board.
+    fn formed_word(tile_positions: &[(Pos, Tile)]) -> String {
+        tile_positions
+            .iter()
+            .map(|(_, tile)| char::from(tile.letter().unwrap()).to_ascii_lowercase())
+            .collect()
+    }
+
+    #[test]
+    fn successful_challenge() {
+        let (mut game, tile_positions) = challenge_game(ChallengeRule::Single);
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["zzz"]).unwrap().build();
+        let first = PlayerNum::first();
+        let rack = game.player(first).rack().to_string();
+        let letter_bag_len = game.letter_bag_len();
+
+        // the invalid word is only rejected when challenged.
+        assert!(!game.can_challenge());
+        game.make_play(&Play::Place(tile_positions.clone()), &fsm)
+            .unwrap();
+        assert!(game.can_challenge());
+        assert_eq!(
+            Some((first, tile_positions.as_slice())),
+            game.challengeable_placement()
+        );
+        assert!(game.player(first).score() > 0);
+
+        let outcome = game.challenge(&fsm).unwrap();
+        assert!(game.challengeable_placement().is_none());
+        assert!(outcome.is_successful());
+        assert!(!outcome.lost_turn);
+        assert_eq!(first, outcome.player_num);
+        assert_eq!(
+            vec![formed_word(&tile_positions).to_uppercase()],
+            outcome.invalid_words
+        );
+
+        // the placement is withdrawn, and the challenger is still to play.
+        assert_eq!(Board::default().to_string(), game.board().to_string());
+        assert_eq!(0, game.player(first).score());
+        assert_eq!(1, game.player(first).pass_count());
+        assert_eq!(rack, game.player(first).rack().to_string());
+        assert_eq!(letter_bag_len, game.letter_bag_len());
+        assert_eq!(Some(first.next(2)), game.to_play());
+        assert_eq!(1, game.play_count());
+
+        // a placement can only be challenged once.
+        assert!(matches!(
+            game.challenge(&fsm),
+            Err(GameError::NothingToChallenge)
+        ));
+    }
+
+    #[test]
+    fn unsuccessful_challenge() {
+        for (rule, lost_turn) in [(ChallengeRule::Single, true), (ChallengeRule::Free, false)] {
+            let (mut game, tile_positions) = challenge_game(rule);
+            let word = formed_word(&tile_positions);
+            let fsm: FastFsm = FsmBuilder::from_sorted_iter([word.as_str()])
+                .unwrap()
+                .build();
+            let first = PlayerNum::first();
+
+            game.make_play(&Play::Place(tile_positions), &fsm).unwrap();
+            let score = game.player(first).score();
+
+            let outcome = game.challenge(&fsm).unwrap();
+            assert!(!outcome.is_successful());
+            assert_eq!(lost_turn, outcome.lost_turn);
+
+            // the placement stays on the board.
+            assert_eq!(score, game.player(first).score());
+            assert!(!game.board().grid_h().occ().is_zero());
+            match lost_turn {
+                true => {
+                    assert_eq!(Some(first), game.to_play());
+                    assert_eq!(2, game.play_count());
+                    assert_eq!(1, game.player(first.next(2)).pass_count());
+                }
+                false => {
+                    assert_eq!(Some(first.next(2)), game.to_play());
+                    assert_eq!(1, game.play_count());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn challenge_window() {
+        // without challenges, an invalid word is rejected when played.
+        let (mut game, tile_positions) = challenge_game(ChallengeRule::Off);
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["zzz"]).unwrap().build();
+        let result = game.make_play(&Play::Place(tile_positions), &fsm);
+        assert!(matches!(result, Err(GameError::InvalidWord)));
+        assert!(matches!(
+            game.challenge(&fsm),
+            Err(GameError::NothingToChallenge)
+        ));
+
+        // a placement can not be challenged once the next play is made.
+        let (mut game, tile_positions) = challenge_game(ChallengeRule::Free);
+        game.make_play(&Play::Place(tile_positions), &fsm).unwrap();
+        game.make_play(&Play::Pass, &fsm).unwrap();
+        assert!(!game.can_challenge());
+        assert!(matches!(
+            game.challenge(&fsm),
+            Err(GameError::NothingToChallenge)
+        ));
+    }
+
+    #[test]
+    fn final_placement_challenge() {
+        let first = PlayerNum::first();
+        let zz: FastFsm = FsmBuilder::from_sorted_iter(["zz"]).unwrap().build();
+        let cat: FastFsm = FsmBuilder::from_sorted_iter(["cat"]).unwrap().build();
+        let tile_positions = vec![
+            (Pos::start(), Tile::from('z')),
+            (Pos::from((7, 8)), Tile::from('z')),
+        ];
+        // a game where the first player empties their rack by playing "ZZ".
+        let final_game = |rule| {
+            let (mut game, _) = challenge_game(rule);
+            game.letter_bag = LetterBag::from([0; 27]);
+            game.players[0].rack = Rack::with_str("zz");
+            game.initial_tiles = game.total_tiles_by_kind();
+            game.make_play(&Play::Place(tile_positions.clone()), &cat)
+                .unwrap();
+            game
+        };
+
+        // the game only ends once the final placement can not be challenged.
+        let mut game = final_game(ChallengeRule::Single);
+        assert!(game.is_ending());
+        assert!(game.can_challenge());
+        assert_eq!(Some(first.next(2)), game.to_play());
+
+        // a successful challenge withdraws the placement.
+        let outcome = game.challenge(&cat).unwrap();
+        assert!(outcome.is_successful());
+        assert!(!game.is_ending());
+        assert_eq!(2, game.player(first).rack().len());
+        assert_eq!(Some(first.next(2)), game.to_play());
+
+        // an unsuccessful challenge ends the game, without a lost turn.
+        let mut game = final_game(ChallengeRule::Single);
+        let outcome = game.challenge(&zz).unwrap();
+        assert!(!outcome.is_successful());
+        assert!(!outcome.lost_turn);
+        match game.status() {
+            GameStatus::Over(game_over) => {
+                assert_eq!(GameOverReason::EmptyRack, game_over.reason())
+            }
+            GameStatus::ToPlay(_) => panic!("the game should be over"),
+        }
+
+        // any other play accepts the placement, and is not made.
+        let mut game = final_game(ChallengeRule::Free);
+        assert_eq!(vec![String::from("ZZ")], game.invalid_words(&cat));
+        game.make_play(&Play::Pass, &cat).unwrap();
+        assert!(game.status().is_over());
+        assert_eq!(0, game.player(first.next(2)).pass_count());
+        assert_eq!(1, game.play_count());
+        assert!(game.invalid_words(&cat).is_empty());
+        assert!(matches!(game.challenge(&zz), Err(GameError::Over)));
+    }
+
+    #[test]
+    fn apply_generated() {
+        let fsm = FastFsm::bundled();
+        let ai = Ai::default();
+        let mut game = Game::new(2);
+
+        while let GameStatus::ToPlay(to_play) = *game.status() {
+            match ai.next_generated(fsm, &game).unwrap() {
+                Some(gen_play) => {
+                    // validate the same play on a copy of the game.
+                    let mut copy: Game =
+                        bincode::deserialize(&bincode::serialize(&game).unwrap()).unwrap();
+                    let play = Play::Place(gen_play.tile_positions().to_vec());
+                    copy.make_play(&play, fsm).unwrap();
+
+                    game.apply_generated(gen_play).unwrap();
+
+                    assert_eq!(copy.player(to_play).score(), game.player(to_play).score());
+                    assert_eq!(copy.board().to_string(), game.board().to_string());
+                }
+                None => {
+                    let rack = game.player(to_play).rack();
+                    let play = Ai::fallback_play(rack, game.letter_bag_len(), game.config());
+                    game.make_play(&play, fsm).unwrap();
+                }
+            }
+        }
+    }
+
+    /// Plays a two player game twice with the same seed, once validating every
+    /// play with [`Game::make_play`] and once applying generated plays with
+    /// [`Game::apply_generated`]. Both games should be identical after every
+    /// play, including when the game ends.
+    #[test]
+    fn mirror_make_play_and_apply_generated() {
+        let fsm = FastFsm::bundled();
+        let ai = Ai::default();
+
+        for seed in 0..4 {
+            let new_game = || Game::with_letter_bag(2, LetterBag::default().with_seed(seed));
+            let mut checked = new_game();
+            let mut trusted = new_game();
+
+            while let GameStatus::ToPlay(to_play) = *checked.status() {
+                match ai.next_generated(fsm, &checked).unwrap() {
+                    Some(gen_play) => {
+                        checked.make_play(&Play::from(gen_play), fsm).unwrap();
+
+                        let gen_play = ai.next_generated(fsm, &trusted).unwrap().unwrap();
+                        trusted.apply_generated(gen_play).unwrap();
+                    }
+                    None => {
+                        let rack = checked.player(to_play).rack();
+                        let play =
+                            Ai::fallback_play(rack, checked.letter_bag_len(), checked.config());
+                        checked.make_play(&play, fsm).unwrap();
+                        trusted.make_play(&play, fsm).unwrap();
+                    }
+                }
+
+                assert_eq!(checked.to_play(), trusted.to_play());
+                assert_eq!(checked.letter_bag_len(), trusted.letter_bag_len());
+                assert_eq!(checked.board().to_string(), trusted.board().to_string());
+                for player_num in checked.player_nums() {
+                    let (a, b) = (checked.player(player_num), trusted.player(player_num));
+
+                    assert_eq!(a.score(), b.score());
+                    assert_eq!(a.pass_count(), b.pass_count());
+                    assert_eq!(a.rack().to_string(), b.rack().to_string());
+                }
+            }
+
+            // the end of game rules are applied in the same way.
+            match (checked.status(), trusted.status()) {
+                (GameStatus::Over(a), GameStatus::Over(b)) => {
+                    assert_eq!(a.reason(), b.reason());
+                    assert_eq!(
+                        a.final_scores().collect::<Vec<_>>(),
+                        b.final_scores().collect::<Vec<_>>()
+                    );
+                }
+                _ => panic!("both games should be over"),
+            }
+        }
+    }
+
+    #[test]
+    fn total_tiles_by_kind() {
+        let game = Game::new(2);
+        assert_eq!(LetterBag::default().counts(), game.total_tiles_by_kind());
+        game.assert_tile_conservation();
+
+        // a blank placed on the board is still counted as a blank.
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["cat", "cats"])
+            .unwrap()
+            .build();
+        let board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::start()).letters("cat"))
+            .build();
+        let racks = vec![Rack::with_tiles(&[Tile::blank()]), Rack::with_str("Q")];
+        let mut game =
+            Game::from_position(board, racks, LetterBag::from([0; 27]), PlayerNum::first())
+                .unwrap();
+        let initial = game.total_tiles_by_kind();
+        assert_eq!(1, initial.count(Tile::blank()));
+
+        let pos = Pos::start().project(Direction::East).nth(3).unwrap();
+        let blank = Tile::Blank(Some(Letter::new('s').unwrap()));
+        game.make_play(&Play::Place(vec![(pos, blank)]), &fsm)
+            .unwrap();
+        assert_eq!(initial, game.total_tiles_by_kind());
+        game.assert_tile_conservation();
+    }
+
+    #[test]
+    fn apply_generated_min_word_len() {
+        let config = GameConfig {
+            min_word_len: 3,
+            ..GameConfig::default()
+        };
+        let mut game = Game::with_config(2, LetterBag::default(), config);
+        game.players[0].rack = Rack::with_str("atxxxxx");
+
+        // a two letter word is generated, but is too short for the config.
+        let tile_positions = vec![
+            (Pos::start(), Tile::from('a')),
+            (Pos::from((7, 8)), Tile::from('t')),
+        ];
+        let gen_play = GeneratedPlay::new(tile_positions, 4, 0, 2);
+        assert!(matches!(
+            game.apply_generated(gen_play),
+            Err(GameError::WordTooShort)
+        ));
+
+        // the game is unchanged.
+        assert_eq!(0, game.play_count());
+        assert_eq!(7, game.player(PlayerNum::first()).rack().len());
+        assert!(game.board().grid_h().occ().is_zero());
+    }
+
+    #[test]
+    fn apply_generated_plain_scoring() {
+        let fsm = FastFsm::bundled();
+        let config = GameConfig {
+            premiums: false,
+            bingo_bonus: false,
+            ..GameConfig::default()
+        };
+        let mut game = Game::with_config(2, LetterBag::default().with_seed(0), config);
+
+        // the generated score includes the premium of the start square, so
+        // the play is rescored.
+        let gen_play = Ai::default().next_generated(fsm, &game).unwrap().unwrap();
+        let face_value: usize = gen_play
+            .tile_positions()
+            .iter()
+            .map(|(_, tile)| tile.score())
+            .sum();
+        assert_ne!(face_value, gen_play.score());
+
+        game.apply_generated(gen_play).unwrap();
+        assert_eq!(face_value, game.player(PlayerNum::first()).score());
+    }
+
+    /// Counts the tiles in the bag, on every rack and on the board.
+    fn total_tile_count(game: &Game) -> usize {
+        let rack_count: usize = game
+            .player_nums()
+            .map(|player_num| game.player(player_num).rack().len())
+            .sum();
+
+        game.letter_bag_len() + rack_count + game.board().grid_h().occ().bit_count()
+    }
+
+    #[test]
+    fn tile_count_is_conserved() {
+        let fsm = FastFsm::bundled();
+        let ai = Ai::default();
+        let initial_count = LetterBag::default().len();
+
+        for seed in 0..2 {
+            let mut game = Game::with_letter_bag(2, LetterBag::default().with_seed(seed));
+            assert_eq!(initial_count, total_tile_count(&game));
+
+            while game.to_play().is_some() {
+                let play = ai.next_play(fsm, &game).unwrap();
+                game.make_play(&play, fsm).unwrap();
+
+                assert_eq!(initial_count, total_tile_count(&game));
+                for player_num in game.player_nums() {
+                    assert!(game.player(player_num).rack().len() <= RACK_SIZE);
+                }
+            }
+        }
+    }
+}
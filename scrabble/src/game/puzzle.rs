@@ -0,0 +1,176 @@
+//! Models puzzles, where a single placement is made from a position and
+//! compared to the highest scoring placement.
+
+use crate::{
+    ai::{
+        movegen::{self, GeneratedPlay},
+        Ai,
+    },
+    error::GameResult,
+    game::{
+        alphabet::Alphabet, board::Board, letter_bag::LetterBag, play::Play, rack::Rack,
+        tile::Tile, Game, PlayerNum,
+    },
+    util::{fsm::Fsm, pos::Pos},
+};
+
+/// The number of plays that are made before the puzzle position.
+pub const PUZZLE_PLAYS: usize = 6;
+/// The number of seeds that are tried when generating a puzzle, in case
+/// a game ends early or the final position has no placement.
+const MAX_ATTEMPTS: u64 = 8;
+
+/// A position and rack, along with the highest scoring placement.
+#[derive(Clone, Debug)]
+pub struct Puzzle {
+    board: Board,
+    rack: Rack,
+    letter_bag: LetterBag,
+    best_play: Vec<(Pos, Tile)>,
+    best_score: usize,
+}
+
+impl Puzzle {
+    /// Creates a puzzle from a position, finding the highest scoring
+    /// placement. Returns `None` if no placement is possible.
+    pub fn new<'a, F: Fsm<'a>>(
+        fsm: &'a F,
+        board: Board,
+        rack: Rack,
+        letter_bag: LetterBag,
+    ) -> Option<Self> {
+        let mut plays = vec![];
+        // puzzles are played with the default (English) rules.
+        movegen::gen(&board, &rack, fsm, &Alphabet::default(), &mut plays);
+        let best = plays.into_iter().max_by_key(GeneratedPlay::score)?;
+
+        Some(Self {
+            board,
+            rack,
+            letter_bag,
+            best_score: best.score(),
+            best_play: best.tile_positions().to_vec(),
+        })
+    }
+    /// Generates a puzzle from a seed, by playing [`PUZZLE_PLAYS`] plays of a
+    /// two player game between AI players. The same seed always generates the
+    /// same puzzle (for the same word list). Returns `None` if no suitable
+    /// position was found.
+    pub fn generate<'a, F: Fsm<'a>>(fsm: &'a F, seed: u64) -> Option<Self> {
+        (0..MAX_ATTEMPTS)
+            .map(|attempt| seed.wrapping_add(attempt))
+            .find_map(|seed| Self::play_out(fsm, seed))
+    }
+    /// Attempts to generate a puzzle from a single seed.
+    fn play_out<'a, F: Fsm<'a>>(fsm: &'a F, seed: u64) -> Option<Self> {
+        let letter_bag = LetterBag::default().with_seed(seed);
+        let mut game = Game::with_letter_bag(2, letter_bag);
+        let ai = Ai::medium().with_seed(seed);
+
+        for _ in 0..PUZZLE_PLAYS {
+            match ai.next_generated(fsm, &game).ok()? {
+                Some(gen_play) => game.apply_generated(gen_play).ok()?,
+                None => {
+                    let rack = game.to_play_rack()?;
+                    let play = Ai::fallback_play(rack, game.letter_bag_len(), game.config());
+                    game.make_play(&play, fsm).ok()?;
+                }
+            }
+        }
+
+        let rack = game.to_play_rack()?.clone();
+
+        Self::new(fsm, game.board().clone(), rack, game.letter_bag)
+    }
+
+    /// Gets the board of the puzzle.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+    /// Gets the rack that the placement is made from.
+    pub fn rack(&self) -> &Rack {
+        &self.rack
+    }
+    /// Gets the tile positions of the highest scoring placement.
+    pub fn best_play(&self) -> &[(Pos, Tile)] {
+        &self.best_play
+    }
+    /// Gets the score of the highest scoring placement.
+    pub fn best_score(&self) -> usize {
+        self.best_score
+    }
+    /// Creates a single player game from the puzzle position.
+    pub fn game(&self) -> Game {
+        Game::from_position(
+            self.board.clone(),
+            vec![self.rack.clone()],
+            self.letter_bag.clone(),
+            PlayerNum::first(),
+        )
+        .expect("a puzzle position to be valid")
+    }
+    /// Scores a placement from the puzzle position, returning an error if
+    /// the placement is illegal.
+    pub fn score<'a, F: Fsm<'a>>(
+        &self,
+        fsm: &F,
+        tile_positions: Vec<(Pos, Tile)>,
+    ) -> GameResult<usize> {
+        let mut game = self.game();
+        game.make_play(&Play::Place(tile_positions), fsm)?;
+
+        Ok(game.player(PlayerNum::first()).score())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        error::GameError,
+        game::board::BoardBuilder,
+        util::fsm::{FastFsm, FsmBuilder},
+    };
+
+    #[test]
+    fn best_play() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["at", "cat", "cats", "scat"])
+            .unwrap()
+            .build();
+        let board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::start()).letters("cat"))
+            .build();
+        let puzzle = Puzzle::new(&fsm, board, Rack::with_str("S"), LetterBag::default()).unwrap();
+
+        // "SCAT" and "CATS" both score 6.
+        assert_eq!(6, puzzle.best_score());
+        assert_eq!(1, puzzle.best_play().len());
+        let best_play = puzzle.best_play().to_vec();
+        assert_eq!(6, puzzle.score(&fsm, best_play).unwrap());
+
+        // only tiles from the rack can be placed.
+        let cat = Play::vertical(Pos::start()).letters("cat");
+        let result = puzzle.score(&fsm, cat.tile_positions(puzzle.board()));
+        assert!(matches!(result, Err(GameError::NotInRack)));
+
+        // no placement is possible with an unusable rack.
+        let board = puzzle.board().clone();
+        assert!(Puzzle::new(&fsm, board, Rack::with_str("Z"), LetterBag::default()).is_none());
+    }
+
+    #[test]
+    fn deterministic_generate() {
+        let fsm = FastFsm::bundled();
+        let tiles = |puzzle: &Puzzle| Vec::from(puzzle.board().clone());
+        let rack = |puzzle: &Puzzle| puzzle.rack().tiles().collect::<Vec<_>>();
+
+        for seed in 0..10 {
+            let puzzle = Puzzle::generate(fsm, seed).unwrap();
+            let other = Puzzle::generate(fsm, seed).unwrap();
+
+            assert_eq!(tiles(&puzzle), tiles(&other));
+            assert_eq!(rack(&puzzle), rack(&other));
+            assert_eq!(puzzle.best_play(), other.best_play());
+        }
+    }
+}
@@ -3,11 +3,13 @@
 use crate::{
     error::{GameError, GameResult},
     game::{
+        alphabet::Alphabet,
         letter_bag::LetterBag,
         tile::{Letter, Tile},
     },
     util::tile_counts::TileCounts,
 };
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// The maximum number of tiles that can be stored
@@ -17,18 +19,59 @@ pub const RACK_SIZE: usize = 7;
 /// Each player has a rack with up to 7 tiles on it.
 /// The rack is modelled as a vector containing up
 /// to 7 [`tiles`](Tile).
-#[derive(Debug)]
+///
+/// A rack is serialized in a compact form as a string of its tiles in
+/// order, with blanks written as underscores (e.g. `"AEIOSU_"`).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(into = "String", try_from = "String")]
 pub struct Rack {
     counts: TileCounts,
 }
 
+/// Gets the character for a tile on a rack, where blanks are underscores.
+fn tile_char(tile: Tile) -> char {
+    match tile {
+        Tile::Letter(letter) => char::from(letter),
+        Tile::Blank(_) => '_',
+    }
+}
+
 impl fmt::Display for Rack {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "[")?;
-        for tile in self.tiles() {
-            write!(f, "{}", tile)?;
+        let tiles = self
+            .tiles()
+            .map(tile_char)
+            .map(String::from)
+            .collect::<Vec<_>>();
+
+        write!(f, "[{}]", tiles.join(" "))
+    }
+}
+impl From<Rack> for String {
+    fn from(rack: Rack) -> Self {
+        rack.tiles().map(tile_char).collect()
+    }
+}
+impl TryFrom<String> for Rack {
+    type Error = &'static str;
+
+    fn try_from(tiles: String) -> Result<Self, Self::Error> {
+        if tiles.chars().count() > RACK_SIZE {
+            return Err("A rack can contain at most 7 tiles");
         }
-        write!(f, "]")
+
+        let counts = tiles
+            .chars()
+            .map(|ch| match ch {
+                // blanks on a rack never have a letter.
+                '_' => Ok(Tile::Blank(None)),
+                _ => Letter::new(ch)
+                    .map(Tile::Letter)
+                    .ok_or("A rack can only contain letters and blanks"),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { counts })
     }
 }
 impl Rack {
@@ -44,7 +87,7 @@ impl Rack {
     /// Creates a new [`Rack`] with the provided tiles.
     pub fn with_tiles(tiles: &[Tile]) -> Self {
         Self {
-            counts: tiles.iter().take(7).copied().collect(),
+            counts: tiles.iter().take(RACK_SIZE).copied().collect(),
         }
     }
     /// Creates a new [`Rack`] from a string of letters.
@@ -54,7 +97,7 @@ impl Rack {
                 .chars()
                 .filter_map(Letter::new)
                 .map(Tile::from)
-                .take(7)
+                .take(RACK_SIZE)
                 .collect(),
         }
     }
@@ -62,20 +105,36 @@ impl Rack {
     pub fn tile_counts(&self) -> &TileCounts {
         &self.counts
     }
-    /// Gets the sum of the remaining tiles on the rack. This is used
-    /// for scoring at the end of the game.
-    pub fn tile_sum(&self) -> usize {
-        self.counts.tile_sum()
+    /// Gets the sum of the remaining tiles on the rack, valued using
+    /// `alphabet`. This is used for scoring at the end of the game.
+    pub fn tile_sum(&self, alphabet: &Alphabet) -> usize {
+        self.counts.tile_sum(alphabet)
     }
     /// Adds tiles from `letter_bag` to attempt to increase the
     /// number of tiles in the rack to [`RACK_SIZE`].
     pub fn refill(&mut self, letter_bag: &mut LetterBag) {
         self.counts
             .insert(letter_bag.draw_many(self.missing_count()));
+
+        self.debug_assert_capacity();
     }
     /// Gets the number of tiles below [`RACK_SIZE`] in the rack.
     pub fn missing_count(&self) -> usize {
-        RACK_SIZE - self.len()
+        RACK_SIZE.saturating_sub(self.len())
+    }
+    /// Checks whether the rack contains [`RACK_SIZE`] tiles.
+    pub fn is_full(&self) -> bool {
+        self.len() >= RACK_SIZE
+    }
+    /// Panics in debug builds if the rack contains more than
+    /// [`RACK_SIZE`] tiles, which would mean that tiles have been
+    /// created by a caller.
+    fn debug_assert_capacity(&self) {
+        debug_assert!(
+            self.len() <= RACK_SIZE,
+            "a rack can contain at most {RACK_SIZE} tiles, found {}",
+            self.len()
+        );
     }
     /// Gets the number of tiles in the rack.
     pub fn len(&self) -> usize {
@@ -90,7 +149,7 @@ impl Rack {
     /// provided are not all present in the rack, returns [`None`].
     pub fn exchange_tiles(&mut self, tiles: &[Tile], letter_bag: &mut LetterBag) -> GameResult<()> {
         // check number of tiles
-        if !(1..=7).contains(&tiles.len()) {
+        if !(1..=RACK_SIZE).contains(&tiles.len()) {
             return Err(GameError::RedrawCount);
         }
 
@@ -111,6 +170,8 @@ impl Rack {
         // add removed tiles back into bag
         letter_bag.add_tiles(tiles.iter().copied());
 
+        self.debug_assert_capacity();
+
         Ok(())
     }
     /// Checks whether all `tiles` are contained within the rack.
@@ -126,3 +187,113 @@ impl Rack {
         self.counts.iter()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sorts tiles so they can be compared with [`Rack::tiles`].
+    fn sorted<'a>(tiles: impl IntoIterator<Item = &'a Tile>) -> Vec<Tile> {
+        let mut tiles: Vec<_> = tiles.into_iter().copied().collect();
+        tiles.sort();
+        tiles
+    }
+
+    #[test]
+    fn display() {
+        let rack = Rack::with_tiles(&[
+            Tile::from('s'),
+            Tile::Blank(None),
+            Tile::from('e'),
+            Tile::from('a'),
+        ]);
+
+        assert_eq!("[A E S _]", rack.to_string());
+        assert_eq!("[]", Rack::with_str("").to_string());
+    }
+
+    #[test]
+    fn compact_form() {
+        let rack = Rack::with_tiles(&[
+            Tile::from('q'),
+            Tile::Blank(Some(Letter::new('u').unwrap())),
+        ]);
+        assert_eq!("Q_", String::from(rack.clone()));
+
+        // blanks are always undesignated after a round trip.
+        let bytes = bincode::serialize(&rack).unwrap();
+        let rack: Rack = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(
+            vec![Tile::from('q'), Tile::Blank(None)],
+            rack.tiles().collect::<Vec<_>>()
+        );
+
+        // invalid tiles and too many tiles are rejected.
+        assert!(Rack::try_from(String::from("AB?")).is_err());
+        assert!(Rack::try_from(String::from("ABCDEFGH")).is_err());
+    }
+
+    #[test]
+    fn refill() {
+        let mut letter_bag = LetterBag::default().with_seed(0);
+        let expected = letter_bag.peek_remaining();
+
+        let mut rack = Rack::new(&mut letter_bag);
+        let first: Vec<_> = rack.tiles().collect();
+        assert_eq!(sorted(&expected[..7]), first);
+
+        // remove three tiles, then the next three should be drawn.
+        rack.remove(first[..3].iter().copied());
+        rack.refill(&mut letter_bag);
+
+        let tiles = first[3..].iter().chain(&expected[7..10]);
+        assert_eq!(sorted(tiles), rack.tiles().collect::<Vec<_>>());
+        assert_eq!(letter_bag.peek_remaining(), expected[10..]);
+    }
+
+    #[test]
+    fn exchange_tiles() {
+        let mut letter_bag = LetterBag::default().with_seed(0);
+        let mut rack = Rack::new(&mut letter_bag);
+        let first: Vec<_> = rack.tiles().collect();
+        let expected = letter_bag.peek_remaining();
+
+        // the new tiles are drawn before the old ones are returned.
+        rack.exchange_tiles(&first[..2], &mut letter_bag).unwrap();
+
+        let tiles = first[2..].iter().chain(&expected[..2]);
+        assert_eq!(sorted(tiles), rack.tiles().collect::<Vec<_>>());
+        assert_eq!(letter_bag.len(), 93);
+    }
+
+    #[test]
+    fn refill_after_removals() {
+        // a bag with fewer tiles than are needed to keep the rack full.
+        let mut counts = [0; 27];
+        counts[0] = 12;
+        let mut letter_bag = LetterBag::from(counts).with_seed(0);
+
+        let mut rack = Rack::new(&mut letter_bag);
+        assert!(rack.is_full());
+        assert_eq!(0, rack.missing_count());
+
+        for removed in [1, 3, 2, 3] {
+            let tiles: Vec<_> = rack.tiles().take(removed).collect();
+            rack.remove(tiles.into_iter());
+
+            let available = rack.len() + letter_bag.len();
+            rack.refill(&mut letter_bag);
+
+            assert_eq!(RACK_SIZE.min(available), rack.len());
+            assert_eq!(rack.is_full(), available >= RACK_SIZE);
+        }
+        assert!(letter_bag.is_empty());
+
+        // refilling a full rack, or from an empty bag, has no effect.
+        let mut full = Rack::with_str("ABCDEFG");
+        full.refill(&mut LetterBag::default());
+        assert_eq!(RACK_SIZE, full.len());
+        rack.refill(&mut letter_bag);
+        assert_eq!(3, rack.len());
+    }
+}
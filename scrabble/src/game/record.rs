@@ -0,0 +1,386 @@
+//! A compact text encoding of a finished game, which can be shared and
+//! pasted to replay the game.
+//!
+//! The encoding is made of three sections separated by `/`:
+//! * The version, currently `SCR1`.
+//! * The scores, in player order, separated by spaces.
+//! * The plays, in order, separated by spaces:
+//!   * `-` is a pass.
+//!   * `~` followed by the redrawn tiles is a redraw, where `?` is a blank.
+//!   * A placement is written in standard notation: the coordinate of the
+//!     first letter of the main word (`8H` for a horizontal word, `H8` for a
+//!     vertical word), then `:` and the letters of the word. Lowercase
+//!     letters are blanks, and letters in brackets were already on the board.
+//!
+//! For example `SCR1/14 10/8H:CAT J8:(T)o -` is a two player game where
+//! "CAT" was played through the start square, then "TO" was played down
+//! from the "T" using a blank, and then the first player passed.
+
+use crate::{
+    error::{RecordError, RecordResult},
+    game::{
+        board::Board,
+        play::Play,
+        tile::{Letter, Tile},
+    },
+    util::pos::{Direction, Pos},
+};
+use std::{fmt, str::FromStr};
+
+/// The version of the encoding, which starts every record.
+pub const VERSION: &str = "SCR1";
+
+/// The plays and scores of a game.
+#[derive(Debug, Clone)]
+pub struct GameRecord {
+    plays: Vec<Play>,
+    scores: Vec<usize>,
+}
+impl fmt::Display for GameRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{VERSION}/")?;
+
+        let scores: Vec<_> = self.scores.iter().map(usize::to_string).collect();
+        write!(f, "{}/", scores.join(" "))?;
+
+        // placements are written relative to the board before the play.
+        let mut board = Board::default();
+        let mut plays = vec![];
+        for play in &self.plays {
+            plays.push(encode_play(&board, play));
+
+            if let Play::Place(tile_positions) = play {
+                board.place_unchecked(tile_positions);
+            }
+        }
+
+        write!(f, "{}", plays.join(" "))
+    }
+}
+impl FromStr for GameRecord {
+    type Err = RecordError;
+
+    fn from_str(s: &str) -> RecordResult<Self> {
+        let mut sections = s.trim().splitn(3, '/');
+
+        if sections.next() != Some(VERSION) {
+            return Err(RecordError::UnsupportedVersion);
+        }
+
+        let (scores, plays) = match (sections.next(), sections.next()) {
+            (Some(scores), Some(plays)) => (scores, plays),
+            _ => return Err(RecordError::Malformed),
+        };
+        let scores = scores
+            .split_whitespace()
+            .map(|score| score.parse().map_err(|_| RecordError::Malformed))
+            .collect::<RecordResult<Vec<_>>>()?;
+        let plays = plays
+            .split_whitespace()
+            .map(decode_play)
+            .collect::<RecordResult<Vec<_>>>()?;
+
+        match scores.is_empty() {
+            true => Err(RecordError::Malformed),
+            false => Ok(Self { plays, scores }),
+        }
+    }
+}
+impl GameRecord {
+    /// Creates a record from the plays of a game and the score of each
+    /// player. Every play should be legal, as placements are encoded
+    /// as the word that they form.
+    pub fn new(plays: Vec<Play>, scores: Vec<usize>) -> Self {
+        Self { plays, scores }
+    }
+    /// Gets the plays, in the order that they were made.
+    pub fn plays(&self) -> &[Play] {
+        &self.plays
+    }
+    /// Gets the score of each player.
+    pub fn scores(&self) -> &[usize] {
+        &self.scores
+    }
+    /// Gets the number of players.
+    pub fn player_count(&self) -> usize {
+        self.scores.len()
+    }
+    /// Gets the board after the plays have been made.
+    pub fn board(&self) -> Board {
+        let mut board = Board::default();
+
+        for play in &self.plays {
+            if let Play::Place(tile_positions) = play {
+                board.place_unchecked(tile_positions);
+            }
+        }
+
+        board
+    }
+}
+
+/// Encodes a tile, where a blank is lowercase (or `?` without a letter).
+fn encode_tile(tile: Tile) -> char {
+    match tile {
+        Tile::Letter(letter) => char::from(letter),
+        Tile::Blank(Some(letter)) => char::from(letter).to_ascii_lowercase(),
+        Tile::Blank(None) => '?',
+    }
+}
+
+/// Decodes a tile encoded by [`encode_tile`].
+fn decode_tile(ch: char) -> RecordResult<Tile> {
+    match (ch, Letter::new(ch)) {
+        ('?', _) => Ok(Tile::Blank(None)),
+        (_, Some(letter)) if ch.is_ascii_uppercase() => Ok(Tile::Letter(letter)),
+        (_, Some(letter)) => Ok(Tile::Blank(Some(letter))),
+        (_, None) => Err(RecordError::Malformed),
+    }
+}
+
+/// Encodes a play that is made on `board`.
+fn encode_play(board: &Board, play: &Play) -> String {
+    match play {
+        Play::Pass => String::from("-"),
+        Play::Redraw(tiles) => iter_chars('~', tiles.iter().copied().map(encode_tile)),
+        // a placement without tiles is written as a pass.
+        Play::Place(tile_positions) => {
+            encode_placement(board, tile_positions).unwrap_or_else(|| String::from("-"))
+        }
+    }
+}
+
+/// Creates a string from `first` followed by `rest`.
+fn iter_chars(first: char, rest: impl Iterator<Item = char>) -> String {
+    std::iter::once(first).chain(rest).collect()
+}
+
+/// Encodes a placement as the main word that it forms on `board`, or
+/// `None` if no tiles are placed.
+fn encode_placement(board: &Board, tile_positions: &[(Pos, Tile)]) -> Option<String> {
+    let occ = board.grid_h().occ();
+    let is_occ = |pos: Option<Pos>| pos.is_some_and(|pos| occ.is_set(pos));
+    let (first, _) = tile_positions
+        .iter()
+        .min_by_key(|(pos, _)| usize::from(*pos))?;
+    let (last, _) = tile_positions
+        .iter()
+        .max_by_key(|(pos, _)| usize::from(*pos))?;
+
+    // a single tile forms a vertical word unless it has a horizontal
+    // neighbour.
+    let dir = match tile_positions.len() {
+        1 if !is_occ(first.dir(Direction::West)) && !is_occ(first.dir(Direction::East)) => {
+            Direction::South
+        }
+        1 => Direction::East,
+        _ if first.row() == last.row() => Direction::East,
+        _ => Direction::South,
+    };
+
+    // extend the word over the existing tiles at each end.
+    let mut start = *first;
+    while is_occ(start.dir(dir.opposite())) {
+        start = start.dir(dir.opposite()).unwrap();
+    }
+
+    let mut word = format!("{}:", start.coord_string(dir));
+    let mut is_existing = false;
+    for pos in start.project(dir) {
+        let placed = tile_positions.iter().find(|(p, _)| *p == pos);
+        let (tile, existing) = match (placed, board[pos]) {
+            (Some(&(_, tile)), _) => (tile, false),
+            (None, Some(tile)) => (tile, true),
+            (None, None) => break,
+        };
+
+        // existing tiles are bracketed.
+        if existing != is_existing {
+            word.push(if existing { '(' } else { ')' });
+            is_existing = existing;
+        }
+        word.push(encode_tile(tile));
+    }
+    if is_existing {
+        word.push(')');
+    }
+
+    Some(word)
+}
+
+/// Decodes a play encoded by [`encode_play`].
+fn decode_play(s: &str) -> RecordResult<Play> {
+    match s.strip_prefix('~') {
+        _ if s == "-" => Ok(Play::Pass),
+        Some(tiles) => Ok(Play::Redraw(
+            tiles
+                .chars()
+                .map(decode_tile)
+                .collect::<RecordResult<_>>()?,
+        )),
+        None => decode_placement(s),
+    }
+}
+
+/// Decodes a placement, which is written as the main word that it forms.
+fn decode_placement(s: &str) -> RecordResult<Play> {
+    let (coord, word) = s.split_once(':').ok_or(RecordError::Malformed)?;
+    let (start, dir) = decode_coord(coord).ok_or(RecordError::Malformed)?;
+
+    let mut positions = start.project(dir);
+    let mut tile_positions = vec![];
+    let mut is_existing = false;
+    for ch in word.chars() {
+        match ch {
+            '(' => is_existing = true,
+            ')' => is_existing = false,
+            _ => {
+                let tile = decode_tile(ch)?;
+                let pos = positions.next().ok_or(RecordError::Malformed)?;
+
+                if !is_existing {
+                    tile_positions.push((pos, tile));
+                }
+            }
+        }
+    }
+
+    // every placement must place at least one tile.
+    match tile_positions.is_empty() {
+        true => Err(RecordError::Malformed),
+        false => Ok(Play::Place(tile_positions)),
+    }
+}
+
+/// Decodes a coordinate in standard notation, returning the position
+/// and direction of the word.
+fn decode_coord(coord: &str) -> Option<(Pos, Direction)> {
+    let letter_idx = coord.find(|ch: char| ch.is_ascii_alphabetic())?;
+    let (dir, letter, number) = match letter_idx {
+        0 => (Direction::South, &coord[..1], &coord[1..]),
+        _ => (Direction::East, &coord[letter_idx..], &coord[..letter_idx]),
+    };
+
+    let col = usize::from(Letter::new(letter.chars().next()?)?);
+    let row = number.parse::<usize>().ok()?.checked_sub(1)?;
+
+    match letter.len() == 1 && row < 15 && col < 15 {
+        true => Some((Pos::from((row, col)), dir)),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ai::Ai,
+        game::{Game, GameStatus},
+        util::fsm::FastFsm,
+    };
+
+    /// Checks that two plays are the same.
+    fn assert_play_eq(a: &Play, b: &Play) {
+        match (a, b) {
+            (Play::Pass, Play::Pass) => {}
+            (Play::Redraw(a), Play::Redraw(b)) => assert_eq!(a, b),
+            (Play::Place(a), Play::Place(b)) => {
+                let mut a = a.clone();
+                let mut b = b.clone();
+                a.sort_by_key(|&(pos, _)| usize::from(pos));
+                b.sort_by_key(|&(pos, _)| usize::from(pos));
+
+                assert_eq!(a, b);
+            }
+            _ => panic!("{a} != {b}"),
+        }
+    }
+
+    #[test]
+    fn example() {
+        let record: GameRecord = "SCR1/14 10/8H:CAT J8:(T)o -".parse().unwrap();
+
+        assert_eq!(2, record.player_count());
+        assert_eq!(&[14, 10], record.scores());
+        assert_play_eq(
+            &Play::horizontal(Pos::start())
+                .letters("cat")
+                .build(&Board::default()),
+            &record.plays()[0],
+        );
+        assert_play_eq(
+            &Play::Place(vec![(
+                Pos::from((7, 9)).dir(Direction::South).unwrap(),
+                Tile::Blank(Letter::new('o')),
+            )]),
+            &record.plays()[1],
+        );
+        assert_play_eq(&Play::Pass, &record.plays()[2]);
+
+        assert_eq!("SCR1/14 10/8H:CAT J8:(T)o -", record.to_string());
+    }
+
+    #[test]
+    fn redraw() {
+        let record: GameRecord = "SCR1/0 0/~AE? -".parse().unwrap();
+        let tiles = vec![Tile::from('a'), Tile::from('e'), Tile::blank()];
+
+        assert_play_eq(&Play::Redraw(tiles), &record.plays()[0]);
+        assert_eq!("SCR1/0 0/~AE? -", record.to_string());
+    }
+
+    #[test]
+    fn invalid() {
+        for s in [
+            "",
+            "SCR2/0 0/-",
+            "SCR1/0 0",
+            "SCR1//-",
+            "SCR1/a/-",
+            "SCR1/0/16H:AT",
+            "SCR1/0/8H:A1",
+            "SCR1/0 0/8H:(CAT)",
+        ] {
+            assert!(s.parse::<GameRecord>().is_err(), "{s}");
+        }
+        assert!(matches!(
+            "SCR2/0 0/-".parse::<GameRecord>(),
+            Err(RecordError::UnsupportedVersion)
+        ));
+    }
+
+    #[test]
+    fn empty_placement() {
+        let record = GameRecord::new(vec![Play::Place(vec![])], vec![0, 0]);
+        assert_eq!("SCR1/0 0/-", record.to_string());
+    }
+
+    #[test]
+    fn roundtrip_ai_game() {
+        let fsm = FastFsm::bundled();
+        let ai = Ai::default();
+        let mut game = Game::new(2);
+        let mut plays = vec![];
+
+        while game.to_play().is_some() {
+            let play = ai.next_play(fsm, &game).unwrap();
+            game.make_play(&play, fsm).unwrap();
+            plays.push(play);
+        }
+
+        let scores = match game.status() {
+            GameStatus::Over(game_over) => game.player_nums().map(|p| game_over.score(p)).collect(),
+            GameStatus::ToPlay(_) => unreachable!(),
+        };
+        let record = GameRecord::new(plays, scores);
+        let decoded: GameRecord = record.to_string().parse().unwrap();
+
+        assert_eq!(record.scores(), decoded.scores());
+        assert_eq!(record.plays().len(), decoded.plays().len());
+        for (a, b) in record.plays().iter().zip(decoded.plays()) {
+            assert_play_eq(a, b);
+        }
+        assert_eq!(game.board().to_string(), decoded.board().to_string());
+        assert_eq!(record.to_string(), decoded.to_string());
+    }
+}
@@ -0,0 +1,395 @@
+//! Models a [`GameSnapshot`], a stable representation of a [`Game`] for
+//! long term storage.
+
+use crate::{
+    error::{GameError, GameResult},
+    game::{
+        alphabet::Alphabet,
+        board::{Board, CELLS},
+        challenge::ChallengeRule,
+        letter_bag::LetterBag,
+        rack::Rack,
+        tile::{Letter, Tile},
+        EndgameScoring, Game, GameConfig, GameOver, GameOverReason, GameStatus, Player, PlayerNum,
+    },
+    util::tile_counts::TileCounts,
+};
+use serde::{Deserialize, Serialize};
+
+/// A snapshot of the state of a [`Game`], for storing a game that is
+/// reloaded later (such as a correspondence game in the database).
+///
+/// The serialized layout of [`Game`] changes whenever a field is added,
+/// so stored games would fail to deserialize. A snapshot only contains
+/// plain values (tiles are stored as characters), and each layout is a
+/// separate version, so a stored snapshot can always be read back.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum GameSnapshot {
+    /// The first version of the snapshot.
+    V1(SnapshotV1),
+    /// The second version of the snapshot, which adds the alphabet.
+    V2(SnapshotV2),
+}
+
+/// The state of a [`Game`] in version 1 of a [`GameSnapshot`]. The most
+/// recent placement is not stored, so it can no longer be challenged
+/// once the game is restored (and if it ended the game, the game ends).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotV1 {
+    /// The tiles on the board, as `(square index, letter, is blank)`.
+    board: Vec<(usize, char, bool)>,
+    /// The tiles in the letter bag, where blanks are underscores.
+    letter_bag: String,
+    /// The tiles on each rack, where blanks are underscores.
+    racks: Vec<String>,
+    /// The running score of each player.
+    scores: Vec<usize>,
+    /// The number of consecutive passes made by each player.
+    pass_counts: Vec<usize>,
+    /// The index of the player to play next.
+    to_play: usize,
+    /// The number of plays that have been made.
+    play_count: usize,
+    /// The final scores, if the game is over.
+    game_over: Option<GameOverV1>,
+    /// The minimum word length of the [`GameConfig`].
+    min_word_len: usize,
+    /// Whether the first play must cover the start square.
+    require_center_start: bool,
+    /// The rule used for challenges.
+    challenge: ChallengeRule,
+    /// How the final scores are adjusted.
+    endgame: EndgameScoring,
+    /// Whether tiles can be exchanged.
+    allow_exchange: bool,
+    /// Whether premium squares apply.
+    premiums: bool,
+    /// Whether the bingo bonus applies.
+    bingo_bonus: bool,
+}
+
+/// The state of a [`Game`] in version 2 of a [`GameSnapshot`]. Games
+/// restored from version 1 use the English alphabet.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotV2 {
+    /// The state stored by version 1.
+    v1: SnapshotV1,
+    /// The [`Alphabet`] of the [`GameConfig`].
+    alphabet: Alphabet,
+}
+
+/// The final scores of a game in version 1 of a [`GameSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameOverV1 {
+    /// The reason that the game ended.
+    reason: GameOverReason,
+    /// The final score of each player.
+    scores: Vec<usize>,
+    /// The value of the tiles subtracted from each player's score.
+    penalties: Vec<usize>,
+    /// The value of the tiles added to each player's score.
+    bonuses: Vec<usize>,
+}
+
+/// Gets the character for a tile in the letter bag, where blanks
+/// are underscores.
+fn tile_char(tile: Tile) -> char {
+    match tile {
+        Tile::Letter(letter) => char::from(letter),
+        Tile::Blank(_) => '_',
+    }
+}
+
+impl Game {
+    /// Takes a [`GameSnapshot`] of the game, which can be restored with
+    /// [`Game::from_snapshot`].
+    pub fn snapshot(&self) -> GameSnapshot {
+        let board = self
+            .board
+            .iter()
+            .filter_map(|(pos, tile)| {
+                let letter = tile.letter().ok()?;
+                Some((usize::from(pos), char::from(letter), tile.is_blank()))
+            })
+            .collect();
+        let game_over = match &self.status {
+            GameStatus::ToPlay(_) => None,
+            GameStatus::Over(game_over) => Some(GameOverV1 {
+                reason: game_over.reason,
+                scores: game_over.scores.clone(),
+                penalties: game_over.penalties.clone(),
+                bonuses: game_over.bonuses.clone(),
+            }),
+        };
+        let GameConfig {
+            min_word_len,
+            require_center_start,
+            challenge,
+            endgame,
+            allow_exchange,
+            premiums,
+            bingo_bonus,
+            alphabet,
+        } = self.config.clone();
+
+        let v1 = SnapshotV1 {
+            board,
+            letter_bag: self
+                .letter_bag
+                .clone()
+                .counts()
+                .iter()
+                .map(tile_char)
+                .collect(),
+            racks: self
+                .players
+                .iter()
+                .map(|player| String::from(player.rack.clone()))
+                .collect(),
+            scores: self.players.iter().map(|player| player.score).collect(),
+            pass_counts: self
+                .players
+                .iter()
+                .map(|player| player.pass_count)
+                .collect(),
+            to_play: usize::from(self.to_play),
+            play_count: self.play_count,
+            game_over,
+            min_word_len,
+            require_center_start,
+            challenge,
+            endgame,
+            allow_exchange,
+            premiums,
+            bingo_bonus,
+        };
+
+        GameSnapshot::V2(SnapshotV2 { v1, alphabet })
+    }
+    /// Restores a game from a [`GameSnapshot`]. Returns
+    /// [`GameError::InvalidPosition`] if the snapshot is inconsistent.
+    pub fn from_snapshot(snapshot: GameSnapshot) -> GameResult<Self> {
+        match snapshot {
+            GameSnapshot::V1(snapshot) => Self::from_snapshot_v1(snapshot, Alphabet::default()),
+            GameSnapshot::V2(SnapshotV2 { v1, alphabet }) => Self::from_snapshot_v1(v1, alphabet),
+        }
+    }
+
+    /// Restores a game from the version 1 state of a [`GameSnapshot`],
+    /// which is played with `alphabet`.
+    fn from_snapshot_v1(snapshot: SnapshotV1, alphabet: Alphabet) -> GameResult<Self> {
+        let player_count = snapshot.racks.len();
+        let is_valid = player_count > 0
+            && snapshot.scores.len() == player_count
+            && snapshot.pass_counts.len() == player_count
+            && snapshot.to_play < player_count;
+        if !is_valid {
+            return Err(GameError::InvalidPosition);
+        }
+
+        let mut tiles = vec![None; CELLS];
+        for (idx, ch, is_blank) in snapshot.board {
+            let letter = Letter::new(ch).ok_or(GameError::InvalidPosition)?;
+            let tile = match is_blank {
+                true => Tile::Blank(Some(letter)),
+                false => Tile::Letter(letter),
+            };
+            *tiles.get_mut(idx).ok_or(GameError::InvalidPosition)? = Some(tile);
+        }
+        let board = Board::try_from(tiles).map_err(|_| GameError::InvalidPosition)?;
+
+        let letter_bag = snapshot
+            .letter_bag
+            .chars()
+            .map(|ch| match ch {
+                '_' => Ok(Tile::Blank(None)),
+                _ => Letter::new(ch)
+                    .map(Tile::Letter)
+                    .ok_or(GameError::InvalidPosition),
+            })
+            .collect::<GameResult<TileCounts>>()?;
+
+        let players = snapshot
+            .racks
+            .into_iter()
+            .zip(snapshot.scores)
+            .zip(snapshot.pass_counts)
+            .map(|((rack, score), pass_count)| {
+                Ok(Player {
+                    rack: Rack::try_from(rack).map_err(|_| GameError::InvalidPosition)?,
+                    score,
+                    pass_count,
+                })
+            })
+            .collect::<GameResult<Vec<_>>>()?;
+
+        let to_play = PlayerNum(snapshot.to_play);
+        let status = match snapshot.game_over {
+            None => GameStatus::ToPlay(to_play),
+            Some(game_over) => {
+                let is_valid = [&game_over.scores, &game_over.penalties, &game_over.bonuses]
+                    .iter()
+                    .all(|values| values.len() == player_count);
+                if !is_valid {
+                    return Err(GameError::InvalidPosition);
+                }
+
+                GameStatus::Over(GameOver {
+                    max_score: game_over.scores.iter().max().copied().unwrap_or_default(),
+                    running_scores: players.iter().map(|player| player.score).collect(),
+                    scores: game_over.scores,
+                    penalties: game_over.penalties,
+                    bonuses: game_over.bonuses,
+                    reason: game_over.reason,
+                })
+            }
+        };
+
+        let mut game = Self {
+            board,
+            letter_bag: LetterBag::from(<[usize; 27]>::from(letter_bag)),
+            players,
+            to_play,
+            status,
+            config: GameConfig {
+                min_word_len: snapshot.min_word_len,
+                require_center_start: snapshot.require_center_start,
+                challenge: snapshot.challenge,
+                endgame: snapshot.endgame,
+                allow_exchange: snapshot.allow_exchange,
+                premiums: snapshot.premiums,
+                bingo_bonus: snapshot.bingo_bonus,
+                alphabet,
+            },
+            play_count: snapshot.play_count,
+            last_placement: None,
+            initial_tiles: TileCounts::default(),
+        };
+        game.initial_tiles = game.total_tiles_by_kind();
+
+        // a placement that emptied a rack can no longer be challenged once
+        // the game is restored, so the game ends.
+        let emptied = game
+            .players
+            .iter()
+            .position(|player| player.rack.is_empty());
+        if let (Some(previous), false) = (emptied, game.status.is_over()) {
+            game.status = game.next_status(PlayerNum(previous));
+        }
+
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        game::play::Play,
+        util::{
+            fsm::{FastFsm, FsmBuilder},
+            pos::Pos,
+        },
+    };
+
+    #[test]
+    fn round_trip() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["cat"]).unwrap().build();
+        let mut game = Game::with_config(
+            2,
+            LetterBag::default().with_seed(2),
+            GameConfig {
+                min_word_len: 3,
+                ..GameConfig::default()
+            },
+        );
+        game.players[0].rack = Rack::try_from(String::from("CAT_QZZ")).unwrap();
+        game.initial_tiles = game.total_tiles_by_kind();
+        let tile_positions = vec![
+            (Pos::start(), Tile::from('c')),
+            (Pos::from((7, 8)), Tile::Blank(Letter::new('a'))),
+            (Pos::from((7, 9)), Tile::from('t')),
+        ];
+        game.make_play(&Play::Place(tile_positions), &fsm).unwrap();
+        game.make_play(&Play::Pass, &fsm).unwrap();
+
+        let snapshot = game.snapshot();
+        let bytes = bincode::serialize(&snapshot).unwrap();
+        let restored = Game::from_snapshot(bincode::deserialize(&bytes).unwrap()).unwrap();
+        assert_eq!(snapshot, restored.snapshot());
+
+        assert_eq!(game.board().to_string(), restored.board().to_string());
+        assert_eq!(game.letter_bag_len(), restored.letter_bag_len());
+        assert_eq!(game.to_play(), restored.to_play());
+        assert_eq!(2, restored.play_count());
+        assert_eq!(3, restored.config().min_word_len);
+        for player_num in game.player_nums() {
+            let (player, restored_player) = (game.player(player_num), restored.player(player_num));
+            assert_eq!(
+                player.rack().to_string(),
+                restored_player.rack().to_string()
+            );
+            assert_eq!(player.score(), restored_player.score());
+            assert_eq!(player.pass_count(), restored_player.pass_count());
+        }
+
+        // the final scores of a finished game are kept.
+        game.abandon().unwrap();
+        let restored = Game::from_snapshot(game.snapshot()).unwrap();
+        match (game.status(), restored.status()) {
+            (GameStatus::Over(game_over), GameStatus::Over(restored_over)) => {
+                assert_eq!(GameOverReason::Abandoned, restored_over.reason());
+                assert!(game_over.final_scores().eq(restored_over.final_scores()));
+            }
+            _ => panic!("the game should be over"),
+        }
+    }
+
+    #[test]
+    fn alphabet() {
+        let glyphs: Vec<_> = Letter::iter()
+            .map(|letter| char::from(letter).to_string())
+            .collect();
+        let alphabet = Alphabet::new(glyphs.iter().map(|glyph| (glyph.as_str(), 2))).unwrap();
+        let game = Game::with_config(
+            2,
+            LetterBag::default(),
+            GameConfig {
+                alphabet: alphabet.clone(),
+                ..GameConfig::default()
+            },
+        );
+
+        let bytes = bincode::serialize(&game.snapshot()).unwrap();
+        let restored = Game::from_snapshot(bincode::deserialize(&bytes).unwrap()).unwrap();
+        assert_eq!(alphabet, restored.config().alphabet);
+
+        // games saved before alphabets were stored use the English alphabet.
+        let snapshot = match game.snapshot() {
+            GameSnapshot::V2(snapshot) => snapshot.v1,
+            GameSnapshot::V1(_) => panic!("games are saved with the latest version"),
+        };
+        let restored = Game::from_snapshot(GameSnapshot::V1(snapshot)).unwrap();
+        assert_eq!(Alphabet::english(), restored.config().alphabet);
+    }
+
+    #[test]
+    fn invalid_snapshot() {
+        let snapshot = match Game::new(2).snapshot() {
+            GameSnapshot::V2(snapshot) => snapshot.v1,
+            GameSnapshot::V1(_) => panic!("games are saved with the latest version"),
+        };
+
+        let mut invalid = snapshot.clone();
+        invalid.to_play = 2;
+        assert!(Game::from_snapshot(GameSnapshot::V1(invalid)).is_err());
+
+        let mut invalid = snapshot.clone();
+        invalid.racks[0] = String::from("abcdefgh");
+        assert!(Game::from_snapshot(GameSnapshot::V1(invalid)).is_err());
+
+        let mut invalid = snapshot;
+        invalid.board.push((CELLS, 'a', false));
+        assert!(Game::from_snapshot(GameSnapshot::V1(invalid)).is_err());
+    }
+}
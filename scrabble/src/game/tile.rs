@@ -6,6 +6,9 @@ use std::fmt::{self, Display, Formatter};
 
 /// A letter `A..=Z`. Represented as a newtype containing an unsigned
 /// integer from `0..=25` to make game operations easier.
+///
+/// Letters are written using the English alphabet by default, but an
+/// [`Alphabet`](super::alphabet::Alphabet) can map them to other glyphs.
 #[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[repr(transparent)]
 pub struct Letter(#[serde(deserialize_with = "deserialize_letter")] u8);
@@ -18,12 +21,16 @@ where
 {
     match u8::deserialize(deserializer)? {
         // letters are only valid for (0..=25).
-        byte @ 0..=25 => Ok(byte),
+        byte if usize::from(byte) < Letter::COUNT => Ok(byte),
         _ => Err(serde::de::Error::custom("Byte out of letter range")),
     }
 }
 
 impl Letter {
+    /// The number of letters, which is the most that an
+    /// [`Alphabet`](super::alphabet::Alphabet) can have.
+    pub const COUNT: usize = 26;
+
     /// Creates a new letter from a `char`, returns [`None`] if the
     /// `ch` provided is not in the latin alphabet.
     pub fn new(ch: char) -> Option<Self> {
@@ -35,12 +42,12 @@ impl Letter {
     }
     /// Returns an iterator over all 26 letters
     pub fn iter() -> impl Iterator<Item = Letter> {
-        (0..26).map(Letter::from)
+        (0..Letter::COUNT).map(Letter::from)
     }
 }
 impl From<usize> for Letter {
     fn from(v: usize) -> Self {
-        Self((v as u8) % 26)
+        Self((v % Letter::COUNT) as u8)
     }
 }
 impl From<Letter> for usize {
@@ -140,7 +147,9 @@ impl Tile {
     pub fn iter() -> impl Iterator<Item = Tile> {
         (0..27).map(Tile::from)
     }
-    /// Gets the score of the tile
+    /// Gets the score of the tile in the English alphabet. Games use the
+    /// score from the [`Alphabet`](super::alphabet::Alphabet) in their
+    /// config, see [`Alphabet::score`](super::alphabet::Alphabet::score).
     pub fn score(&self) -> usize {
         const TILE_SCORES: [usize; 27] = [
             1,  // A
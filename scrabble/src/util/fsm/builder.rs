@@ -1,8 +1,9 @@
 use crate::{
+    error::{FsmError, FsmResult},
     game::tile::Letter,
     util::fsm::{Fsm, FsmSequence, StateId},
 };
-use std::{collections::HashMap, hash::Hash};
+use std::{cmp::Ordering, collections::HashMap, hash::Hash};
 
 /// Represents a state in a finite state machine. `is_terminal` determines
 /// whether it is an acceptance state.
@@ -58,6 +59,11 @@ impl State {
 pub struct PerfectHash(String);
 
 /// Used to construct a finite state machine.
+///
+/// The builder minimizes the graph as it goes, which relies on sequences being
+/// inserted in alphabetical order. [`FsmBuilder::insert`] rejects any sequence
+/// that is out of order, so for a word list that may not be sorted use
+/// [`FsmBuilder::from_unsorted_iter`] instead.
 #[derive(Debug)]
 pub struct FsmBuilder {
     pub(super) states: HashMap<StateId, State>,
@@ -94,10 +100,46 @@ impl FsmBuilder {
 
         F::from(self)
     }
+    /// Constructs an [`FsmBuilder`] from an iterator over sequences in
+    /// alphabetical order. Fails if any sequence is out of order.
+    pub fn from_sorted_iter<S: FsmSequence>(iter: impl IntoIterator<Item = S>) -> FsmResult<Self> {
+        let mut builder = Self::default();
+
+        for seq in iter {
+            builder.insert(seq)?;
+        }
+
+        Ok(builder)
+    }
+    /// Constructs an [`FsmBuilder`] from an iterator over sequences in any
+    /// order. The sequences are buffered and sorted before insertion.
+    pub fn from_unsorted_iter<S: FsmSequence>(iter: impl IntoIterator<Item = S>) -> Self {
+        let mut seqs: Vec<Vec<Letter>> = iter
+            .into_iter()
+            .map(|seq| seq.into_iter().collect())
+            .collect();
+        seqs.sort_unstable();
+        seqs.dedup();
+
+        // the sequences are now sorted, so insertion cannot fail.
+        Self::from_sorted_iter(seqs.iter().map(Vec::as_slice)).unwrap()
+    }
     /// Inserts a word into the [`FsmBuilder`]. Words must be inserted in
-    /// alphabetical order.
-    pub fn insert(&mut self, seq: impl FsmSequence) {
+    /// alphabetical order, otherwise [`FsmError::OutOfOrder`] is returned.
+    /// Inserting the same word twice in a row has no effect.
+    pub fn insert(&mut self, seq: impl FsmSequence) -> FsmResult<()> {
         let seq: Vec<_> = seq.into_iter().collect();
+
+        // an empty sequence would make the initial state terminal.
+        if seq.is_empty() {
+            return Ok(());
+        }
+        match seq.cmp(&self.previous_seq) {
+            Ordering::Less => return Err(FsmError::OutOfOrder),
+            Ordering::Equal => return Ok(()),
+            Ordering::Greater => {}
+        }
+
         let prefix_len = Self::common_prefix_len(&self.previous_seq, &seq);
 
         // traverse backwards to last node
@@ -108,6 +150,8 @@ impl FsmBuilder {
         self.replace_or_register(last_state_id);
         self.add_suffix(last_state_id, &seq[prefix_len..]);
         self.previous_seq = seq;
+
+        Ok(())
     }
     /// Gets a reference to a [`State`] by id.
     #[inline]
@@ -173,6 +217,7 @@ impl FsmBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::fsm::FastFsm;
 
     #[test]
     fn common_prefix_len() {
@@ -185,4 +230,34 @@ mod tests {
         assert_eq!(0, FsmBuilder::common_prefix_len(&seq_a, &seq_d));
         assert_eq!(0, FsmBuilder::common_prefix_len(&seq_b, &seq_c));
     }
+
+    #[test]
+    fn insert_out_of_order() {
+        let mut builder = FsmBuilder::default();
+
+        assert_eq!(Ok(()), builder.insert("cat"));
+        assert_eq!(Ok(()), builder.insert("cat"));
+        assert_eq!(Err(FsmError::OutOfOrder), builder.insert("bat"));
+        assert_eq!(Ok(()), builder.insert("cats"));
+        assert!(FsmBuilder::from_sorted_iter(["cats", "cat"]).is_err());
+    }
+
+    #[test]
+    fn unsorted_input() {
+        let words = ["cats", "bat", "batman", "cat", "bats", "cat"];
+        let unsorted: FastFsm = FsmBuilder::from_unsorted_iter(words).build();
+
+        let mut sorted_words = words;
+        sorted_words.sort_unstable();
+        let sorted: FastFsm = FsmBuilder::from_sorted_iter(sorted_words).unwrap().build();
+
+        for word in words {
+            assert!(unsorted.accepts(word));
+        }
+        for word in ["ba", "catss", "bet", ""] {
+            assert!(!unsorted.accepts(word));
+        }
+        assert_eq!(sorted.state_count(), unsorted.state_count());
+        assert_eq!(sorted.transition_count(), unsorted.transition_count());
+    }
 }
@@ -1,4 +1,5 @@
 use crate::{
+    error::{FsmError, FsmResult},
     game::tile::Letter,
     util::fsm::{
         small_fsm::{SmallFsm, SmallStateId, Transition},
@@ -6,7 +7,10 @@ use crate::{
     },
 };
 use serde::{Deserialize, Serialize};
-use std::collections::{hash_map, HashMap};
+use std::{
+    collections::{hash_map, HashMap},
+    mem,
+};
 
 /// A state in the [`FastFsm`]. Stores only the transitions to other states,
 /// in a hashmap.
@@ -31,6 +35,40 @@ pub struct FastFsm {
     pub(super) terminal_count: usize,
 }
 
+impl FastFsm {
+    /// Deserializes a [`FastFsm`] from bytes produced by `bincode`, such as
+    /// the `fast_fsm.bin` file written by the `build_fsm` example.
+    pub fn from_bytes(bytes: &[u8]) -> FsmResult<Self> {
+        bincode::deserialize(bytes).map_err(|_| FsmError::Deserialize)
+    }
+    /// Loads the word list that is bundled with the server. The word list is
+    /// only loaded once, and shared between tests.
+    #[cfg(test)]
+    pub(crate) fn bundled() -> &'static Self {
+        static FSM: std::sync::OnceLock<FastFsm> = std::sync::OnceLock::new();
+
+        FSM.get_or_init(|| {
+            let bytes = std::fs::read(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../server/data/fast_fsm.bin"
+            ))
+            .unwrap();
+            Self::from_bytes(&bytes).unwrap()
+        })
+    }
+    /// Gets the approximate number of bytes allocated on the heap by the
+    /// state array and the transition hashmap of each state. Hashmaps use
+    /// an extra control byte per entry.
+    pub fn heap_size(&self) -> usize {
+        let transitions_size: usize = self
+            .states
+            .iter()
+            .map(|state| state.transitions.capacity() * (mem::size_of::<(Letter, StateId)>() + 1))
+            .sum();
+
+        self.states.capacity() * mem::size_of::<State>() + transitions_size
+    }
+}
 impl From<FsmBuilder> for FastFsm {
     fn from(mut builder: FsmBuilder) -> Self {
         // the initial state (at index 0) should be non-terminal
@@ -185,15 +223,9 @@ mod tests {
     use super::*;
 
     fn build() -> FastFsm {
-        let mut builder = FsmBuilder::default();
-
-        builder.insert("bat");
-        builder.insert("batman");
-        builder.insert("bats");
-        builder.insert("cat");
-        builder.insert("cats");
-
-        builder.build()
+        FsmBuilder::from_sorted_iter(["bat", "batman", "bats", "cat", "cats"])
+            .unwrap()
+            .build()
     }
 
     #[test]
@@ -205,6 +237,15 @@ mod tests {
         assert_eq!(fast_fsm_1, fast_fsm_2);
     }
 
+    #[test]
+    fn from_bytes() {
+        let fast_fsm = build();
+        let bytes = bincode::serialize(&fast_fsm).unwrap();
+
+        assert_eq!(Ok(fast_fsm), FastFsm::from_bytes(&bytes));
+        assert_eq!(Err(FsmError::Deserialize), FastFsm::from_bytes(&[1, 2, 3]));
+    }
+
     #[test]
     fn accepts() {
         let fast_fsm = build();
@@ -103,6 +103,13 @@ impl<'a> FsmSequence for &'a [Letter] {
         self.iter().copied()
     }
 }
+impl FsmSequence for Vec<Letter> {
+    type Iter = std::vec::IntoIter<Letter>;
+
+    fn into_iter(self) -> Self::Iter {
+        IntoIterator::into_iter(self)
+    }
+}
 impl FsmSequence for Letter {
     type Iter = iter::Once<Letter>;
 
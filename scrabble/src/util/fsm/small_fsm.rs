@@ -3,6 +3,7 @@ use crate::{
     util::fsm::{FastFsm, Fsm, FsmBuilder, FsmSequence, StateId},
 };
 use serde::{Deserialize, Serialize};
+use std::mem;
 
 /// Used to identify a [`State`]. Uses fewer bits that [`StateId`] so
 /// that it takes up smaller storage space.
@@ -51,6 +52,12 @@ impl SmallFsm {
 
         (start as usize, end)
     }
+    /// Gets the approximate number of bytes allocated on the heap by the
+    /// state and transition arrays.
+    pub fn heap_size(&self) -> usize {
+        self.states.capacity() * mem::size_of::<State>()
+            + self.transitions.capacity() * mem::size_of::<Transition>()
+    }
 }
 impl From<FsmBuilder> for SmallFsm {
     fn from(builder: FsmBuilder) -> Self {
@@ -151,15 +158,9 @@ mod tests {
     use super::*;
 
     fn build() -> SmallFsm {
-        let mut builder = FsmBuilder::default();
-
-        builder.insert("bat");
-        builder.insert("batman");
-        builder.insert("bats");
-        builder.insert("cat");
-        builder.insert("cats");
-
-        builder.build()
+        FsmBuilder::from_sorted_iter(["bat", "batman", "bats", "cat", "cats"])
+            .unwrap()
+            .build()
     }
 
     #[test]
@@ -217,4 +218,15 @@ mod tests {
         assert_eq!(0, transition_count("batman"));
         assert_eq!(2, transition_count(""));
     }
+
+    #[test]
+    fn heap_size() {
+        let fast_fsm = FastFsm::bundled();
+        let small_fsm = SmallFsm::from(fast_fsm.clone());
+
+        println!("FastFsm: {} bytes", fast_fsm.heap_size());
+        println!("SmallFsm: {} bytes", small_fsm.heap_size());
+
+        assert!(small_fsm.heap_size() < fast_fsm.heap_size());
+    }
 }
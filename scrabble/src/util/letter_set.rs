@@ -0,0 +1,175 @@
+//! Contains a set of letters stored as a bitset, used for cross-sets
+//! and other queries over the letters that can be played.
+
+use crate::{
+    game::tile::Letter,
+    util::fsm::{Fsm, StateId},
+};
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign};
+
+/// A set of the 26 letters, where bit `n` is set if the letter
+/// with index `n` is in the set.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct LetterSet(u32);
+
+impl LetterSet {
+    /// The set containing every letter.
+    pub const FULL: LetterSet = LetterSet((1 << Letter::COUNT) - 1);
+    /// The set containing no letters.
+    pub const EMPTY: LetterSet = LetterSet(0);
+
+    /// Finds the letters that have a transition from `state` in `fsm`,
+    /// which are the letters that can follow the prefix leading to `state`.
+    pub fn from_fsm_transitions<'a>(fsm: &'a impl Fsm<'a>, state: StateId) -> Self {
+        fsm.transitions(state).map(|(letter, _)| letter).collect()
+    }
+    /// Adds `letter` to the set.
+    pub fn insert(&mut self, letter: Letter) {
+        self.0 |= Self::bit(letter);
+    }
+    /// Removes `letter` from the set.
+    pub fn remove(&mut self, letter: Letter) {
+        self.0 &= !Self::bit(letter);
+    }
+    /// Checks whether `letter` is in the set.
+    pub fn contains(&self, letter: Letter) -> bool {
+        self.0 & Self::bit(letter) != 0
+    }
+    /// Gets the number of letters in the set.
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+    /// Checks whether the set contains no letters.
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+    /// Gets the letters that are in either set.
+    pub fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+    /// Gets the letters that are in both sets.
+    pub fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+    /// Gets an iterator over the letters in the set, in alphabetical order.
+    pub fn iter(self) -> impl Iterator<Item = Letter> {
+        Letter::iter().filter(move |&letter| self.contains(letter))
+    }
+    /// Gets the bit that represents `letter`.
+    fn bit(letter: Letter) -> u32 {
+        1 << usize::from(letter)
+    }
+}
+impl FromIterator<Letter> for LetterSet {
+    fn from_iter<T: IntoIterator<Item = Letter>>(letters: T) -> Self {
+        let mut set = Self::EMPTY;
+        for letter in letters {
+            set.insert(letter);
+        }
+        set
+    }
+}
+impl BitOr<Self> for LetterSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+impl BitOrAssign<Self> for LetterSet {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = self.union(rhs);
+    }
+}
+impl BitAnd<Self> for LetterSet {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+impl BitAndAssign<Self> for LetterSet {
+    fn bitand_assign(&mut self, rhs: Self) {
+        *self = self.intersection(rhs);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::fsm::{FastFsm, FsmBuilder};
+
+    /// Creates a set from a string of letters.
+    fn set(letters: &str) -> LetterSet {
+        letters.chars().filter_map(Letter::new).collect()
+    }
+
+    #[test]
+    fn full_and_empty() {
+        assert_eq!(26, LetterSet::FULL.len());
+        assert!(Letter::iter().all(|letter| LetterSet::FULL.contains(letter)));
+        assert!(LetterSet::FULL.iter().eq(Letter::iter()));
+
+        assert!(LetterSet::EMPTY.is_empty());
+        assert_eq!(0, LetterSet::EMPTY.iter().count());
+        assert_eq!(LetterSet::EMPTY, LetterSet::default());
+
+        // a set containing every letter is full.
+        assert_eq!(LetterSet::FULL, Letter::iter().collect());
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut letters = LetterSet::EMPTY;
+        let a = Letter::new('a').unwrap();
+        let z = Letter::new('z').unwrap();
+
+        letters.insert(z);
+        letters.insert(a);
+        letters.insert(a);
+        assert_eq!(2, letters.len());
+        assert!(letters.contains(a) && letters.contains(z));
+        assert_eq!(vec![a, z], letters.iter().collect::<Vec<_>>());
+
+        letters.remove(a);
+        assert!(!letters.contains(a));
+        assert_eq!(set("z"), letters);
+    }
+
+    #[test]
+    fn set_operations() {
+        let (abc, bcd) = (set("abc"), set("bcd"));
+
+        assert_eq!(set("abcd"), abc.union(bcd));
+        assert_eq!(set("bc"), abc.intersection(bcd));
+        assert_eq!(abc | bcd, abc.union(bcd));
+        assert_eq!(abc & bcd, abc.intersection(bcd));
+
+        assert_eq!(abc, abc | LetterSet::EMPTY);
+        assert_eq!(LetterSet::EMPTY, abc & LetterSet::EMPTY);
+        assert_eq!(LetterSet::FULL, abc | LetterSet::FULL);
+        assert_eq!(abc, abc & LetterSet::FULL);
+
+        let mut letters = abc;
+        letters &= bcd;
+        letters |= set("x");
+        assert_eq!(set("bcx"), letters);
+    }
+
+    #[test]
+    fn from_fsm_transitions() {
+        let fsm: FastFsm = FsmBuilder::from_sorted_iter(["at", "cat", "cot", "cut", "dog"])
+            .unwrap()
+            .build();
+
+        let initial = fsm.initial_state();
+        assert_eq!(set("acd"), LetterSet::from_fsm_transitions(&fsm, initial));
+
+        let c = fsm.traverse("c").unwrap();
+        assert_eq!(set("aou"), LetterSet::from_fsm_transitions(&fsm, c));
+
+        // there are no transitions from the end of a word.
+        let cat = fsm.traverse("cat").unwrap();
+        assert!(LetterSet::from_fsm_transitions(&fsm, cat).is_empty());
+    }
+}
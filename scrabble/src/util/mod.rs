@@ -3,8 +3,9 @@
 
 use crate::{
     error::{GameError, GameResult},
+    game::GameConfig,
     util::{
-        bitboard::BitBoard,
+        bitboard::{BitBoard, Bits},
         pos::{Col, Pos, Row},
     },
 };
@@ -13,6 +14,7 @@ use std::fmt;
 pub mod bitboard;
 pub mod fsm;
 pub mod grid;
+pub mod letter_set;
 pub mod pos;
 pub mod scoring;
 pub mod tile_counts;
@@ -66,10 +68,12 @@ where
 ///
 /// Ensures that these conditions are met:
 /// * The new tiles cannot intersect the old tiles.
-/// * The set of all tiles must always contain the start tile.
-/// * There must be a path from the start tile to any other tile.
+/// * The set of all tiles must always contain the start tile (unless
+///   `require_center_start` is disabled in the [`GameConfig`]).
+/// * There must be a path from the start tile (or the existing tiles)
+///   to any other tile.
 /// * Every word has at least 2 letters.
-pub fn validate_occ_h(occ_h: BitBoard, mut new_h: BitBoard) -> GameResult<()> {
+pub fn validate_occ_h(occ_h: BitBoard, mut new_h: BitBoard, config: &GameConfig) -> GameResult<()> {
     // Check whether the new tiles intersect the old tiles
     if occ_h.intersects(&new_h) {
         return Err(GameError::CoincedentTiles);
@@ -79,7 +83,7 @@ pub fn validate_occ_h(occ_h: BitBoard, mut new_h: BitBoard) -> GameResult<()> {
     let occ = occ_h | new_h;
 
     // there must be a tile on the start square.
-    if !occ.is_set(Pos::start()) {
+    if config.require_center_start && !occ.is_set(Pos::start()) {
         return Err(GameError::MustIntersectStart);
     }
 
@@ -97,12 +101,18 @@ pub fn validate_occ_h(occ_h: BitBoard, mut new_h: BitBoard) -> GameResult<()> {
     // Start with the current occupancy (assume that `occ_h` is connected).
     let mut connected = occ_h;
 
-    // Set the start bit, required for first move when occupancy
-    // is zero.
-    connected.set(Pos::start());
+    // For the first move the occupancy is zero, so start from the start
+    // square, or from any new tile if the opening can be placed anywhere.
+    if config.require_center_start {
+        connected.set(Pos::start());
+    } else if occ_h.is_zero() {
+        if let Some(first) = Bits::from(new_h).next() {
+            connected.set(first);
+        }
+    }
 
-    // remove the start bit from `new_h`
-    new_h.clear(Pos::start());
+    // remove the starting bits from `new_h`
+    new_h &= !connected;
 
     match is_connected(connected, new_h) {
         // if there are still tiles remaining in `new_h` then
@@ -50,6 +50,21 @@ impl Premium {
         }
     }
 }
+impl fmt::Display for Premium {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Premium::DoubleLetter => "DL",
+                Premium::TripleLetter => "TL",
+                Premium::DoubleWord => "DW",
+                Premium::TripleWord => "TW",
+                Premium::Start => "start",
+            }
+        )
+    }
+}
 
 /// A position on the board. Ranges from `0..`[`CELLS`].
 #[repr(transparent)]
@@ -105,6 +120,15 @@ impl Pos {
 
         Pos::from((row, col))
     }
+    /// Gets the standard scrabble coordinate for a word starting at
+    /// this position. Horizontal words are written row first ("8H"),
+    /// and vertical words are written column first ("H8").
+    pub fn coord_string(&self, dir: Direction) -> String {
+        match dir {
+            Direction::East | Direction::West => format!("{}{}", self.row(), self.col()),
+            Direction::North | Direction::South => format!("{}{}", self.col(), self.row()),
+        }
+    }
     /// Gets the `Pos` for the start square.
     pub fn start() -> Self {
         Self::from((7, 7))
@@ -209,8 +233,9 @@ impl From<Row> for usize {
     }
 }
 impl fmt::Display for Row {
+    /// Rows are numbered from 1 in standard scrabble notation.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{}", self.0 + 1)
     }
 }
 impl Row {
@@ -305,3 +330,119 @@ impl Direction {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn coord_string_start() {
+        let start = Pos::start();
+
+        assert_eq!(start.to_string(), "H8");
+        assert_eq!(start.coord_string(Direction::East), "8H");
+        assert_eq!(start.coord_string(Direction::South), "H8");
+    }
+
+    #[test]
+    fn coord_string_corners() {
+        let corners = [
+            (Pos::from((Row::first(), Col::first())), "1A", "A1"),
+            (Pos::from((Row::first(), Col::last())), "1O", "O1"),
+            (Pos::from((Row::last(), Col::first())), "15A", "A15"),
+            (Pos::from((Row::last(), Col::last())), "15O", "O15"),
+        ];
+
+        for (pos, horizontal, vertical) in corners {
+            assert_eq!(pos.coord_string(Direction::East), horizontal);
+            assert_eq!(pos.coord_string(Direction::South), vertical);
+        }
+    }
+
+    /// The standard premium square layout, where each row is a row of the
+    /// board (row 1 first). `T` and `D` are triple and double word squares,
+    /// `t` and `d` are triple and double letter squares, `*` is the start
+    /// square (which doubles the word) and `.` is a normal square.
+    const LAYOUT: [&str; 15] = [
+        "T..d...T...d..T",
+        ".D...t...t...D.",
+        "..D...d.d...D..",
+        "d..D...d...D..d",
+        "....D.....D....",
+        ".t...t...t...t.",
+        "..d...d.d...d..",
+        "T..d...*...d..T",
+        "..d...d.d...d..",
+        ".t...t...t...t.",
+        "....D.....D....",
+        "d..D...d...D..d",
+        "..D...d.d...D..",
+        ".D...t...t...D.",
+        "T..d...T...d..T",
+    ];
+
+    /// Gets the premium at `(row, col)` from [`LAYOUT`].
+    fn layout_premium(row: usize, col: usize) -> Option<Premium> {
+        match LAYOUT[row].as_bytes()[col] {
+            b'T' => Some(Premium::TripleWord),
+            b'D' => Some(Premium::DoubleWord),
+            b't' => Some(Premium::TripleLetter),
+            b'd' => Some(Premium::DoubleLetter),
+            b'*' => Some(Premium::Start),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn premium_layout() {
+        for row in 0..ROWS {
+            for col in 0..COLS {
+                let premium = Pos::from((row, col)).premium();
+                assert_eq!(layout_premium(row, col), premium, "at ({row}, {col})");
+            }
+        }
+    }
+
+    #[test]
+    fn premium_symmetry() {
+        let last = ROWS - 1;
+
+        // the board is unchanged by each of the 8 rotations and reflections
+        // of a square.
+        for pos in Pos::iter() {
+            let (row, col) = (usize::from(pos.row()), usize::from(pos.col()));
+            let symmetries = [
+                (col, row),
+                (last - row, col),
+                (row, last - col),
+                (last - row, last - col),
+                (last - col, row),
+                (col, last - row),
+                (last - col, last - row),
+            ];
+
+            for rc in symmetries {
+                assert_eq!(pos.premium(), Pos::from(rc).premium(), "at {pos}");
+            }
+        }
+    }
+
+    #[test]
+    fn premium_counts() {
+        let count = |premium| {
+            Pos::iter()
+                .filter(|pos| pos.premium() == Some(premium))
+                .count()
+        };
+
+        assert_eq!(8, count(Premium::TripleWord));
+        assert_eq!(16, count(Premium::DoubleWord));
+        assert_eq!(12, count(Premium::TripleLetter));
+        assert_eq!(24, count(Premium::DoubleLetter));
+        assert_eq!(1, count(Premium::Start));
+        assert_eq!(
+            164,
+            Pos::iter().filter(|pos| pos.premium().is_none()).count()
+        );
+    }
+}
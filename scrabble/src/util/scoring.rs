@@ -2,11 +2,76 @@
 
 use crate::{
     error::{GameError, GameResult},
-    util::{bitboard::BitBoard, fsm::Fsm, words::Word},
+    game::{alphabet::Alphabet, tile::Tile},
+    util::{bitboard::BitBoard, fsm::Fsm, pos::Premium, words::Word},
 };
+use std::fmt;
 
-/// Validates a word and finds its score.
-pub fn score<'a>(word: Word<'_>, new: &BitBoard, fsm: &impl Fsm<'a>) -> GameResult<usize> {
+/// The bonus for placing all 7 tiles from the rack in a single play.
+pub const BINGO_BONUS: usize = 50;
+
+/// The score of a single word formed by a play.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordScore {
+    /// The letters of the word (a blank without a letter is `'?'`).
+    pub word: String,
+    /// The premium squares covered by newly placed tiles, with the tile
+    /// placed on each.
+    pub premiums: Vec<(Tile, Premium)>,
+    /// The score of the word, including the premiums.
+    pub score: usize,
+}
+
+/// The score of a play, broken down into the score of each word.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScoreBreakdown {
+    /// The words formed by the play, starting with the main word.
+    pub words: Vec<WordScore>,
+    /// The bonus for placing all 7 tiles, or zero.
+    pub bingo: usize,
+}
+impl ScoreBreakdown {
+    /// Gets the total score of the play.
+    pub fn total(&self) -> usize {
+        self.words.iter().map(|word| word.score).sum::<usize>() + self.bingo
+    }
+}
+impl fmt::Display for ScoreBreakdown {
+    /// Writes the breakdown as e.g. `HELLO 8 (H on DL) + ZA 11 + 50 bingo = 69`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, word) in self.words.iter().enumerate() {
+            if idx > 0 {
+                write!(f, " + ")?;
+            }
+            write!(f, "{} {}", word.word, word.score)?;
+
+            if !word.premiums.is_empty() {
+                let premiums = word
+                    .premiums
+                    .iter()
+                    .map(|(tile, premium)| match tile.letter() {
+                        Ok(letter) => format!("{letter} on {premium}"),
+                        Err(_) => format!("? on {premium}"),
+                    })
+                    .collect::<Vec<_>>();
+                write!(f, " ({})", premiums.join(", "))?;
+            }
+        }
+        if self.bingo > 0 {
+            write!(f, " + {} bingo", self.bingo)?;
+        }
+
+        write!(f, " = {}", self.total())
+    }
+}
+
+/// Validates a word and finds its score, using the tile scores of `alphabet`.
+pub fn score<'a>(
+    word: Word<'_>,
+    new: &BitBoard,
+    fsm: &impl Fsm<'a>,
+    alphabet: &Alphabet,
+) -> GameResult<usize> {
     let mut score = 0;
     let mut word_multiplier = 1;
     let mut curr_state = fsm.initial_state();
@@ -26,7 +91,7 @@ pub fn score<'a>(word: Word<'_>, new: &BitBoard, fsm: &impl Fsm<'a>) -> GameResu
         };
 
         word_multiplier *= word_m;
-        score += tile_m * tile.score();
+        score += tile_m * alphabet.score(tile);
     }
 
     match fsm.is_terminal(curr_state) {
@@ -36,7 +101,7 @@ pub fn score<'a>(word: Word<'_>, new: &BitBoard, fsm: &impl Fsm<'a>) -> GameResu
 }
 
 /// Finds the score of a word without validating it.
-pub fn score_unchecked(word: Word<'_>, new: &BitBoard) -> usize {
+pub fn score_unchecked(word: Word<'_>, new: &BitBoard, alphabet: &Alphabet) -> usize {
     let mut score = 0;
     let mut word_multiplier = 1;
 
@@ -46,8 +111,44 @@ pub fn score_unchecked(word: Word<'_>, new: &BitBoard) -> usize {
             false => (1, 1),
         };
         word_multiplier *= word_m;
-        score += tile_m * tile.score();
+        score += tile_m * alphabet.score(tile);
     }
 
     score * word_multiplier
 }
+
+/// Finds the score of a word without validating it, along with the
+/// premiums that applied to the newly placed tiles.
+pub fn word_score(word: Word<'_>, new: &BitBoard, alphabet: &Alphabet) -> WordScore {
+    let mut text = String::new();
+    let mut premiums = Vec::new();
+    let mut score = 0;
+    let mut word_multiplier = 1;
+
+    for (pos, tile) in word {
+        text.push(match tile.letter() {
+            Ok(letter) => char::from(letter),
+            Err(_) => '?',
+        });
+
+        // the position premium only applies if a new tile was
+        // placed on the square.
+        let is_new = new.is_set(pos);
+        let (tile_m, word_m) = match is_new {
+            true => pos.premium_multipliers(),
+            false => (1, 1),
+        };
+        if let (true, Some(premium)) = (is_new, pos.premium()) {
+            premiums.push((tile, premium));
+        }
+
+        word_multiplier *= word_m;
+        score += tile_m * alphabet.score(tile);
+    }
+
+    WordScore {
+        word: text,
+        premiums,
+        score: score * word_multiplier,
+    }
+}
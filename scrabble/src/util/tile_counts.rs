@@ -1,22 +1,23 @@
 //! Contains a structure for keeping track of how many of each
 //! of the 27 tiles are in a container.
 
-use crate::game::tile::Tile;
+use crate::game::{alphabet::Alphabet, tile::Tile};
+use serde::{Deserialize, Serialize};
 use std::iter::repeat;
 
 /// Reusable structure used to store a quantity of each tile.
-#[derive(Default, Debug, Clone, Copy)]
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TileCounts {
     counts: [usize; 27],
     len: usize,
 }
 
 impl TileCounts {
-    /// Gets the sum of the tile values.
-    pub fn tile_sum(&self) -> usize {
+    /// Gets the sum of the tile values in `alphabet`.
+    pub fn tile_sum(&self, alphabet: &Alphabet) -> usize {
         Tile::iter()
             .zip(self.counts.iter())
-            .map(|(tile, &count)| tile.score() * count)
+            .map(|(tile, &count)| alphabet.score(tile) * count)
             .sum()
     }
     /// The number of tiles in `self`.
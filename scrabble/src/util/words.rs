@@ -38,7 +38,7 @@ impl IntoIterator for WordBoundary {
 }
 
 /// Used to iterate over the positions in a [`WordBoundary`].
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct WordBoundaryIter {
     curr: Pos,
     end: Pos,
@@ -133,11 +133,54 @@ impl<'a, I: Iterator<Item = WordBoundary>> Iterator for Words<'a, I> {
     }
 }
 
+/// Adapts an iterator over [`WordBoundary`]s to an iterator over the [`Word`]s
+/// on a [`Board`](crate::game::board::Board), paired with the board positions
+/// of their tiles. For the vertical grid, positions are mapped back from the
+/// rotated coordinates.
+#[derive(Debug)]
+pub struct WordsWithPositions<'a, I> {
+    words: Words<'a, I>,
+}
+impl<'a, I: Iterator<Item = WordBoundary>> Iterator for WordsWithPositions<'a, I> {
+    type Item = (Word<'a>, Vec<Pos>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let word = self.words.next()?;
+        let positions = word
+            .boundary
+            .clone()
+            .map(|pos| word.grid.map_pos(pos))
+            .collect();
+
+        Some((word, positions))
+    }
+}
+
 /// An iterator over the ([`Pos`], [`Tile`]) tuples in a word.
+#[derive(Clone)]
 pub struct Word<'a> {
     grid: &'a Grid,
     boundary: WordBoundaryIter,
 }
+impl<'a> Word<'a> {
+    /// Gets the number of remaining tiles in the word.
+    pub fn len(&self) -> usize {
+        let WordBoundaryIter {
+            curr,
+            end,
+            complete,
+        } = self.boundary;
+
+        match complete {
+            true => 0,
+            false => usize::from(end) - usize::from(curr) + 1,
+        }
+    }
+    /// Checks whether there are no remaining tiles in the word.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
 impl<'a> Iterator for Word<'a> {
     type Item = (Pos, Tile);
 
@@ -170,12 +213,15 @@ impl<'a> fmt::Display for Word<'a> {
     }
 }
 
-/// Provides implementations of the `words` and `intersecting` methods
-/// for any iterator over [`WordBoundary`]s.
+/// Provides implementations of the `words`, `words_with_positions` and
+/// `intersecting` methods for any iterator over [`WordBoundary`]s.
 pub trait WordsExt: Sized {
     /// Gets an iterator over the [`Word`]s referred to by each
     /// [`WordBoundary`].
     fn words(self, grid: &'_ Grid) -> Words<'_, Self>;
+    /// Gets an iterator over the [`Word`]s referred to by each
+    /// [`WordBoundary`], along with the board positions of each word.
+    fn words_with_positions(self, grid: &'_ Grid) -> WordsWithPositions<'_, Self>;
     /// Gets the word boundaries that intersect the `new` bitboard.
     fn intersecting(self, occ: BitBoard) -> Intersecting<Self>;
 }
@@ -186,6 +232,11 @@ impl<I: Iterator<Item = WordBoundary>> WordsExt for I {
             grid,
         }
     }
+    fn words_with_positions(self, grid: &'_ Grid) -> WordsWithPositions<'_, I> {
+        WordsWithPositions {
+            words: self.words(grid),
+        }
+    }
     fn intersecting(self, occ: BitBoard) -> Intersecting<Self> {
         let mut new = Bits::from(occ);
         let curr = new.next();
@@ -200,7 +251,10 @@ impl<I: Iterator<Item = WordBoundary>> WordsExt for I {
 
 #[cfg(test)]
 mod tests {
-    use crate::util::pos::Direction;
+    use crate::{
+        game::{board::BoardBuilder, play::Play},
+        util::pos::Direction,
+    };
 
     use super::*;
     use std::iter;
@@ -282,4 +336,45 @@ mod tests {
         );
         assert!(new_words.next().is_none());
     }
+
+    #[test]
+    fn words_with_positions() {
+        let below_start = Pos::start().offset(Direction::South, 1).unwrap();
+        let board = BoardBuilder::default()
+            .place(Play::horizontal(Pos::start()).letters("cat"))
+            .place(Play::vertical(below_start).letters("ow"))
+            .build();
+
+        let (word, positions) = board
+            .grid_h()
+            .word_boundaries()
+            .words_with_positions(board.grid_h())
+            .next()
+            .unwrap();
+        assert_eq!("CAT", word.to_string());
+        assert_eq!(
+            vec![
+                Pos::start(),
+                Pos::start().offset(Direction::East, 1).unwrap(),
+                Pos::start().offset(Direction::East, 2).unwrap(),
+            ],
+            positions
+        );
+
+        let (word, positions) = board
+            .grid_v()
+            .word_boundaries()
+            .words_with_positions(board.grid_v())
+            .next()
+            .unwrap();
+        assert_eq!("COW", word.to_string());
+        assert_eq!(
+            vec![
+                Pos::start(),
+                below_start,
+                below_start.offset(Direction::South, 1).unwrap(),
+            ],
+            positions
+        );
+    }
 }
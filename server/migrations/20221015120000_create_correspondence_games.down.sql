@@ -0,0 +1 @@
+DROP TABLE tbl_correspondence_game;
\ No newline at end of file
@@ -0,0 +1,6 @@
+CREATE TABLE tbl_correspondence_game(
+  id_game SERIAL,
+  state BYTEA NOT NULL,
+  PRIMARY KEY (id_game),
+  FOREIGN KEY (id_game) REFERENCES tbl_game(id_game) ON DELETE CASCADE
+);
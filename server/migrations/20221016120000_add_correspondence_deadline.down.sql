@@ -0,0 +1,2 @@
+ALTER TABLE tbl_correspondence_game
+DROP COLUMN deadline;
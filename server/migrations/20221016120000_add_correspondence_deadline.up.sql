@@ -0,0 +1,2 @@
+ALTER TABLE tbl_correspondence_game
+ADD COLUMN deadline TIMESTAMP NOT NULL DEFAULT NOW();
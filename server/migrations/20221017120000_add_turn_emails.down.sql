@@ -0,0 +1,4 @@
+ALTER TABLE tbl_human_player
+DROP COLUMN is_notified;
+ALTER TABLE tbl_user
+DROP COLUMN turn_emails;
@@ -0,0 +1,4 @@
+ALTER TABLE tbl_user
+ADD COLUMN turn_emails BOOLEAN DEFAULT TRUE NOT NULL;
+ALTER TABLE tbl_human_player
+ADD COLUMN is_notified BOOLEAN DEFAULT FALSE NOT NULL;
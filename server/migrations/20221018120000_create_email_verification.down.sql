@@ -0,0 +1 @@
+DROP TABLE tbl_email_verification;
@@ -0,0 +1,8 @@
+CREATE TABLE tbl_email_verification(
+  id_user SERIAL,
+  new_email TEXT NOT NULL,
+  secret_hex TEXT NOT NULL,
+  valid_until TIMESTAMP NOT NULL,
+  PRIMARY KEY (id_user),
+  FOREIGN KEY (id_user) REFERENCES tbl_user (id_user) ON DELETE CASCADE
+);
@@ -0,0 +1,7 @@
+CREATE TABLE tbl_login_attempt(
+  id_user SERIAL,
+  failed_count INTEGER NOT NULL,
+  locked_until TIMESTAMP,
+  PRIMARY KEY (id_user),
+  FOREIGN KEY (id_user) REFERENCES tbl_user (id_user) ON DELETE CASCADE
+);
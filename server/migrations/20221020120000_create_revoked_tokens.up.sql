@@ -0,0 +1,5 @@
+CREATE TABLE tbl_revoked_token(
+  jti TEXT NOT NULL,
+  valid_until TIMESTAMP NOT NULL,
+  PRIMARY KEY (jti)
+);
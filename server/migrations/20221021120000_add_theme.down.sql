@@ -0,0 +1,2 @@
+ALTER TABLE tbl_user
+DROP COLUMN theme;
@@ -0,0 +1,2 @@
+ALTER TABLE tbl_user
+ADD COLUMN theme VARCHAR(32) DEFAULT 'classic' NOT NULL;
@@ -0,0 +1,8 @@
+CREATE TABLE tbl_puzzle_attempt(
+  id_user SERIAL,
+  puzzle_date DATE NOT NULL,
+  score INTEGER NOT NULL,
+  best_score INTEGER NOT NULL,
+  PRIMARY KEY (id_user, puzzle_date),
+  FOREIGN KEY (id_user) REFERENCES tbl_user (id_user) ON DELETE CASCADE
+);
@@ -0,0 +1,5 @@
+DELETE FROM tbl_ai_player
+WHERE ai_difficulty = 'custom';
+ALTER TABLE tbl_ai_player
+DROP CONSTRAINT valid_difficulty,
+ADD CONSTRAINT valid_difficulty CHECK(ai_difficulty IN ('easy', 'medium', 'hard'));
\ No newline at end of file
@@ -0,0 +1,3 @@
+ALTER TABLE tbl_ai_player
+DROP CONSTRAINT valid_difficulty,
+ADD CONSTRAINT valid_difficulty CHECK(ai_difficulty IN ('easy', 'medium', 'hard', 'custom'));
\ No newline at end of file
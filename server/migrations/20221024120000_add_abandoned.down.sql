@@ -0,0 +1,2 @@
+ALTER TABLE tbl_game
+DROP COLUMN is_abandoned;
@@ -0,0 +1,2 @@
+ALTER TABLE tbl_game
+ADD COLUMN is_abandoned BOOLEAN DEFAULT FALSE NOT NULL;
@@ -0,0 +1,6 @@
+-- $1: id_user
+-- $2: secret_hex
+DELETE FROM tbl_email_verification
+WHERE id_user = $1
+  AND secret_hex = $2
+RETURNING id_user
\ No newline at end of file
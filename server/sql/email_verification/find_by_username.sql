@@ -0,0 +1,5 @@
+SELECT tbl_email_verification.*
+FROM tbl_email_verification,
+  tbl_user
+WHERE tbl_user.username = $1
+  AND tbl_user.id_user = tbl_email_verification.id_user
\ No newline at end of file
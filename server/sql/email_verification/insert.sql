@@ -0,0 +1,6 @@
+INSERT INTO tbl_email_verification
+VALUES ($1, $2, $3, $4) ON CONFLICT (id_user) DO
+UPDATE
+SET new_email = $2,
+    secret_hex = $3,
+    valid_until = $4;
\ No newline at end of file
@@ -0,0 +1,4 @@
+-- $1: id_player
+UPDATE tbl_human_player
+SET is_notified = FALSE
+WHERE id_player = $1;
\ No newline at end of file
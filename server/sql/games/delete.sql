@@ -0,0 +1,3 @@
+-- $1: id_game
+DELETE FROM tbl_game
+WHERE id_game = $1;
\ No newline at end of file
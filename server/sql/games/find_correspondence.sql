@@ -0,0 +1,4 @@
+-- $1: id_game
+SELECT state, deadline
+FROM tbl_correspondence_game
+WHERE id_game = $1;
\ No newline at end of file
@@ -0,0 +1,5 @@
+-- $1: id_game
+SELECT state, deadline
+FROM tbl_correspondence_game
+WHERE id_game = $1
+FOR UPDATE;
\ No newline at end of file
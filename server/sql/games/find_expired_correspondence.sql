@@ -0,0 +1,6 @@
+-- $1: current time
+SELECT tbl_correspondence_game.id_game AS id_game
+FROM tbl_correspondence_game
+    JOIN tbl_game ON tbl_correspondence_game.id_game = tbl_game.id_game
+WHERE NOT tbl_game.is_over
+    AND tbl_correspondence_game.deadline < $1;
\ No newline at end of file
@@ -0,0 +1,5 @@
+-- $1: id_game
+-- $2: state
+-- $3: deadline
+INSERT INTO tbl_correspondence_game (id_game, state, deadline)
+VALUES ($1, $2, $3);
\ No newline at end of file
@@ -0,0 +1,8 @@
+SELECT tbl_player.id_player,
+    tbl_human_player.id_user AS "id_user?",
+    tbl_game.is_over
+FROM tbl_player
+    JOIN tbl_game ON tbl_game.id_game = tbl_player.id_game
+    LEFT JOIN tbl_human_player ON tbl_human_player.id_player = tbl_player.id_player
+WHERE tbl_player.id_game = $1
+ORDER BY tbl_player.id_player;
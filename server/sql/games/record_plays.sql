@@ -0,0 +1,11 @@
+SELECT tbl_play.id_play,
+    tbl_play.id_player,
+    tbl_tile.pos AS "pos?",
+    tbl_tile.letter AS "letter?",
+    tbl_tile.is_blank AS "is_blank?"
+FROM tbl_play
+    JOIN tbl_player ON tbl_player.id_player = tbl_play.id_player
+    LEFT JOIN tbl_tile ON tbl_tile.id_play = tbl_play.id_play
+WHERE tbl_player.id_game = $1
+ORDER BY tbl_play.id_play,
+    tbl_tile.pos;
@@ -0,0 +1,6 @@
+-- $1: id_player
+UPDATE tbl_human_player
+SET is_notified = TRUE
+WHERE id_player = $1
+    AND NOT is_notified
+RETURNING id_player;
\ No newline at end of file
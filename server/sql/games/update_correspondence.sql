@@ -0,0 +1,6 @@
+-- $1: id_game
+-- $2: state
+-- $3: deadline
+UPDATE tbl_correspondence_game
+SET state = $2, deadline = $3
+WHERE id_game = $1;
\ No newline at end of file
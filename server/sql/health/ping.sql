@@ -0,0 +1,2 @@
+-- a trivial query, used to check that the database is reachable.
+SELECT 1 AS "one!";
@@ -71,6 +71,7 @@ FROM game_summary,
   JOIN tbl_player ON tbl_player.id_player = tbl_human_player.id_player
   JOIN tbl_game ON tbl_game.id_game = tbl_player.id_game
 WHERE tbl_game.is_over = TRUE
+  AND tbl_game.is_abandoned = FALSE
   AND tbl_user.is_private = FALSE
   AND game_summary.id_player = tbl_player.id_player
 GROUP BY tbl_user.id_user
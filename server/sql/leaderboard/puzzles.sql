@@ -0,0 +1,65 @@
+-- $1: limit
+-- $2: offset
+-- $3: today
+-- Consecutive puzzle dates have the same streak start.
+WITH streak_query AS (
+  SELECT tbl_puzzle_attempt.id_user,
+    tbl_puzzle_attempt.puzzle_date,
+    tbl_puzzle_attempt.puzzle_date - (
+      ROW_NUMBER() OVER (
+        PARTITION BY tbl_puzzle_attempt.id_user
+        ORDER BY tbl_puzzle_attempt.puzzle_date
+      )
+    )::INTEGER AS streak_start
+  FROM tbl_puzzle_attempt
+),
+streak_summary AS (
+  SELECT streak_query.id_user,
+    COUNT(*) AS streak_len,
+    MAX(streak_query.puzzle_date) AS last_date
+  FROM streak_query
+  GROUP BY streak_query.id_user,
+    streak_query.streak_start
+),
+-- Summary of streaks for each user. A streak is current if the
+-- puzzle was attempted today or yesterday.
+user_streaks AS (
+  SELECT streak_summary.id_user,
+    MAX(streak_summary.streak_len) AS best_streak,
+    COALESCE(
+      MAX(streak_summary.streak_len) FILTER (
+        WHERE streak_summary.last_date >= $3::DATE - 1
+      ),
+      0
+    ) AS current_streak
+  FROM streak_summary
+  GROUP BY streak_summary.id_user
+),
+-- Summary of scores for each user.
+user_scores AS (
+  SELECT tbl_puzzle_attempt.id_user,
+    COUNT(*) AS puzzle_count,
+    AVG(
+      CASE
+        WHEN tbl_puzzle_attempt.best_score = 0 THEN 100.0
+        ELSE LEAST(
+          100.0,
+          100.0 * tbl_puzzle_attempt.score / tbl_puzzle_attempt.best_score
+        )
+      END
+    ) AS avg_percentage
+  FROM tbl_puzzle_attempt
+  GROUP BY tbl_puzzle_attempt.id_user
+)
+SELECT tbl_user.username,
+  user_scores.puzzle_count::INTEGER AS puzzle_count,
+  user_scores.avg_percentage::REAL AS avg_percentage,
+  user_streaks.current_streak::INTEGER AS current_streak,
+  user_streaks.best_streak::INTEGER AS best_streak
+FROM tbl_user
+  JOIN user_scores ON user_scores.id_user = tbl_user.id_user
+  JOIN user_streaks ON user_streaks.id_user = tbl_user.id_user
+WHERE tbl_user.is_private = FALSE
+ORDER BY avg_percentage DESC,
+  puzzle_count DESC
+LIMIT $1 OFFSET $2;
\ No newline at end of file
@@ -0,0 +1,7 @@
+-- $1: id_player
+DELETE FROM tbl_play
+WHERE id_play = (
+  SELECT MAX(id_play)
+  FROM tbl_play
+  WHERE id_player = $1
+);
@@ -1,4 +1,5 @@
 UPDATE tbl_game
 SET is_over = TRUE,
-    end_time = $2
-WHERE id_game = $1;
\ No newline at end of file
+    end_time = $2,
+    is_abandoned = $3
+WHERE id_game = $1;
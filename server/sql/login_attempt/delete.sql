@@ -0,0 +1,2 @@
+DELETE FROM tbl_login_attempt
+WHERE id_user = $1
\ No newline at end of file
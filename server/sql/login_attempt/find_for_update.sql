@@ -0,0 +1,6 @@
+-- $1: id_user
+INSERT INTO tbl_login_attempt (id_user, failed_count)
+VALUES ($1, 0) ON CONFLICT (id_user) DO
+UPDATE
+SET failed_count = tbl_login_attempt.failed_count
+RETURNING *
\ No newline at end of file
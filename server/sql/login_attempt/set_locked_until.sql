@@ -0,0 +1,5 @@
+-- $1: id_user
+-- $2: locked_until
+UPDATE tbl_login_attempt
+SET locked_until = $2
+WHERE id_user = $1
\ No newline at end of file
@@ -1,2 +0,0 @@
-DELETE FROM tbl_password_reset
-WHERE id_user = $1
\ No newline at end of file
@@ -0,0 +1,6 @@
+-- $1: id_user
+-- $2: puzzle_date
+SELECT *
+FROM tbl_puzzle_attempt
+WHERE id_user = $1
+  AND puzzle_date = $2
\ No newline at end of file
@@ -0,0 +1,6 @@
+-- $1: id_user
+-- $2: puzzle_date
+-- $3: score
+-- $4: best_score
+INSERT INTO tbl_puzzle_attempt
+VALUES ($1, $2, $3, $4) ON CONFLICT (id_user, puzzle_date) DO NOTHING
\ No newline at end of file
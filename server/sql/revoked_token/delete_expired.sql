@@ -0,0 +1,3 @@
+-- $1: current time
+DELETE FROM tbl_revoked_token
+WHERE valid_until < $1
\ No newline at end of file
@@ -0,0 +1,2 @@
+SELECT *
+FROM tbl_revoked_token
\ No newline at end of file
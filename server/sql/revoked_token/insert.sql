@@ -0,0 +1,2 @@
+INSERT INTO tbl_revoked_token
+VALUES ($1, $2) ON CONFLICT (jti) DO NOTHING
\ No newline at end of file
@@ -3,5 +3,7 @@ SET username = $1,
   email = $2,
   hashed_pass = $3,
   is_private = $4,
-  date_updated = $5
-WHERE id_user = $6
\ No newline at end of file
+  turn_emails = $5,
+  theme = $6,
+  date_updated = $7
+WHERE id_user = $8
\ No newline at end of file
@@ -1,10 +1,12 @@
 use crate::{
-    auth::hex,
+    auth::{hex, revocation},
+    db::Db,
     error::{Error, Result},
 };
 use api::auth::Token;
 use chrono::{Duration, Utc};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
 use std::{convert::Infallible, env, fmt, str::FromStr};
 
@@ -22,13 +24,35 @@ lazy_static::lazy_static! {
         jwt_secret
     };
     /// Duration for which each token is valid
-    static ref JWT_EXPIRY_DURATION: Duration = Duration::seconds(1800);
+    static ref JWT_EXPIRY_DURATION: Duration = expiry_from_env("JWT_EXPIRY", DEFAULT_JWT_EXPIRY);
+    /// Duration for which each token is valid when the user asks to
+    /// be remembered.
+    static ref JWT_REMEMBER_DURATION: Duration = expiry_from_env("JWT_REMEMBER_EXPIRY", DEFAULT_JWT_REMEMBER_EXPIRY);
     static ref ENCODING_KEY: EncodingKey = EncodingKey::from_secret(&JWT_SECRET);
     static ref DECODING_KEY: DecodingKey = DecodingKey::from_secret(&JWT_SECRET);
     static ref VALIDATION: Validation = Validation::default();
     static ref HEADER: Header = Header::default();
 }
 
+/// The token lifetime in seconds if `JWT_EXPIRY` is not set.
+const DEFAULT_JWT_EXPIRY: i64 = 1800;
+/// The token lifetime in seconds if `JWT_REMEMBER_EXPIRY` is not set
+/// (30 days).
+const DEFAULT_JWT_REMEMBER_EXPIRY: i64 = 2_592_000;
+
+/// Loads a token lifetime in seconds from an env variable, using
+/// `default` if it is not set.
+fn expiry_from_env(key: &str, default: i64) -> Duration {
+    let seconds = match env::var(key) {
+        Ok(expiry) => expiry
+            .parse()
+            .unwrap_or_else(|_| panic!("`{key}` in seconds")),
+        Err(_) => default,
+    };
+
+    Duration::seconds(seconds)
+}
+
 /// User or admin account.
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 pub enum Role {
@@ -75,6 +99,17 @@ struct Claims {
     id_user: i32,
     /// User role.
     role: Role,
+    /// The number of seconds that the token is valid for, which is kept
+    /// when the token is refreshed.
+    #[serde(default = "default_lifetime")]
+    lifetime: i64,
+    /// Unique id of the token, which is used to revoke it.
+    jti: String,
+}
+
+/// Gets the lifetime of a token in seconds, if it was not specified.
+fn default_lifetime() -> i64 {
+    JWT_EXPIRY_DURATION.num_seconds()
 }
 
 /// A (decoded) json web token for a user.
@@ -83,11 +118,25 @@ pub struct Jwt(Claims);
 impl Jwt {
     /// Creates a new json web token from a user id and role.
     pub fn new(id_user: i32, role: Role) -> Self {
+        Self::with_expiry(id_user, role, *JWT_EXPIRY_DURATION)
+    }
+    /// Creates a new json web token from a user id and role, which
+    /// stays valid for a longer time. (Used for "remember me" logins).
+    pub fn remembered(id_user: i32, role: Role) -> Self {
+        Self::with_expiry(id_user, role, *JWT_REMEMBER_DURATION)
+    }
+    /// Creates a new json web token from a user id and role, which is
+    /// valid for `expiry` after each time it is encoded.
+    pub fn with_expiry(id_user: i32, role: Role, expiry: Duration) -> Self {
+        let jti: [u8; 16] = rand::thread_rng().gen();
+
         // exp is set when `to_auth` is called
         Jwt(Claims {
             exp: 0,
             id_user,
             role,
+            lifetime: expiry.num_seconds(),
+            jti: hex::encode(&jti),
         })
     }
     /// Validates and decodes the JWT.
@@ -96,6 +145,9 @@ impl Jwt {
         let jwt = decode::<Claims>(token, &DECODING_KEY, &VALIDATION)
             .map(|token_data| Jwt(token_data.claims))
             .map_err(Error::JwtDecoding)?;
+        if revocation::is_revoked(&jwt.0.jti) {
+            return Err(Error::RevokedToken);
+        }
         let has_role = match required_role {
             Role::Admin => jwt.0.role == Role::Admin,
             Role::User => true,
@@ -110,11 +162,21 @@ impl Jwt {
     pub fn id_user(&self) -> i32 {
         self.0.id_user
     }
-    /// Encodes the JWT, using the secret and expiry time offset
-    /// from the `.env` file.
+    /// Revokes the token, so that it (and any token refreshed from it)
+    /// is rejected.
+    pub async fn revoke(self, db: &Db) -> Result<()> {
+        // the token could have been refreshed by a concurrent request, so
+        // revoke it for the full lifetime, plus the leeway allowed when
+        // validating the expiry.
+        let leeway = Duration::seconds(VALIDATION.leeway as i64);
+        let valid_until = Utc::now() + Duration::seconds(self.0.lifetime) + leeway;
+
+        revocation::revoke(db, self.0.jti, valid_until.naive_utc()).await
+    }
+    /// Encodes the JWT, using the secret and the lifetime of the token.
     pub fn token(mut self) -> Result<Token> {
-        // get current time, and add `JWT_EXPIRY_SECONDS` to get final time
-        let exp_time = Utc::now() + *JWT_EXPIRY_DURATION;
+        // get current time, and add the lifetime to get final time
+        let exp_time = Utc::now() + Duration::seconds(self.0.lifetime);
         self.0.exp = exp_time.timestamp() as usize;
 
         let claims = &self.0;
@@ -124,3 +186,71 @@ impl Jwt {
             .map_err(Error::JwtEncoding)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set_env() {
+        env::set_var("JWT_SECRET", "00".repeat(32));
+        env::set_var("JWT_EXPIRY", "1800");
+        env::set_var("JWT_REMEMBER_EXPIRY", "2592000");
+    }
+
+    #[test]
+    fn valid_token() {
+        set_env();
+
+        let Token(token) = Jwt::remembered(1, Role::User).token().unwrap();
+        let jwt = Jwt::from_auth_token(&token, Role::User).unwrap();
+
+        assert_eq!(jwt.id_user(), 1);
+        assert_eq!(jwt.0.lifetime, JWT_REMEMBER_DURATION.num_seconds());
+    }
+
+    #[test]
+    fn expired_token() {
+        set_env();
+
+        // expired tokens are rejected (allowing for the default leeway).
+        let Token(token) = Jwt::with_expiry(1, Role::User, Duration::seconds(-120))
+            .token()
+            .unwrap();
+
+        assert!(matches!(
+            Jwt::from_auth_token(&token, Role::User),
+            Err(Error::JwtDecoding(_))
+        ));
+    }
+
+    #[test]
+    fn admin_role() {
+        set_env();
+
+        let Token(token) = Jwt::new(1, Role::User).token().unwrap();
+        assert!(matches!(
+            Jwt::from_auth_token(&token, Role::Admin),
+            Err(Error::InsufficientRole)
+        ));
+
+        let Token(token) = Jwt::new(1, Role::Admin).token().unwrap();
+        assert!(Jwt::from_auth_token(&token, Role::Admin).is_ok());
+        assert!(Jwt::from_auth_token(&token, Role::User).is_ok());
+    }
+
+    #[test]
+    fn revoked_token() {
+        set_env();
+
+        let jwt = Jwt::new(1, Role::User);
+        let jti = jwt.0.jti.clone();
+        let Token(token) = jwt.token().unwrap();
+        assert!(Jwt::from_auth_token(&token, Role::User).is_ok());
+
+        revocation::insert(jti, Utc::now().naive_utc() + Duration::seconds(60));
+        assert!(matches!(
+            Jwt::from_auth_token(&token, Role::User),
+            Err(Error::RevokedToken)
+        ));
+    }
+}
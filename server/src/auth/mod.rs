@@ -4,6 +4,7 @@ use crate::error::Error;
 use warp::{hyper::HeaderMap, Filter, Rejection};
 
 pub mod hex;
+pub mod revocation;
 pub mod validation;
 
 mod jwt;
@@ -21,6 +22,12 @@ pub fn authenticated_user() -> impl Filter<Extract = (Jwt,), Error = Rejection>
     auth_validation(Role::User)
 }
 
+/// Filter which checks whether an auth token is present, valid, and
+/// contains the admin role.
+pub fn authenticated_admin() -> impl Filter<Extract = (Jwt,), Error = Rejection> + Copy {
+    auth_validation(Role::Admin)
+}
+
 /// Filter which checks whether an auth token is present, valid, and
 /// contains the specified role.
 fn auth_validation(role: Role) -> impl Filter<Extract = (Jwt,), Error = Rejection> + Copy {
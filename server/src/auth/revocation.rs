@@ -0,0 +1,62 @@
+//! A list of revoked tokens, which is checked whenever a token is decoded.
+//! The list is stored in the database so that it persists when the server
+//! restarts, and is also kept in memory so that checking a token does not
+//! require a query.
+
+use crate::{db::Db, error::Result, models::RevokedToken};
+use chrono::{NaiveDateTime, Utc};
+use std::{collections::HashMap, sync::RwLock, time::Duration};
+
+/// The interval between removing tokens that have expired from the list.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+lazy_static::lazy_static! {
+    /// Maps the id of each revoked token to the time after which it
+    /// would have expired.
+    static ref REVOKED_TOKENS: RwLock<HashMap<String, NaiveDateTime>> = RwLock::default();
+}
+
+/// Loads the revoked tokens from the database.
+pub async fn load(db: &Db) -> Result<()> {
+    for revoked_token in RevokedToken::find_all(db).await? {
+        insert(revoked_token.jti, revoked_token.valid_until);
+    }
+
+    Ok(())
+}
+/// Revokes a token until `valid_until`.
+pub async fn revoke(db: &Db, jti: String, valid_until: NaiveDateTime) -> Result<()> {
+    let revoked_token = RevokedToken { jti, valid_until };
+    revoked_token.insert(db).await?;
+
+    insert(revoked_token.jti, revoked_token.valid_until);
+
+    Ok(())
+}
+/// Periodically removes tokens that have expired from the list.
+pub async fn prune(db: Db) {
+    let mut interval = tokio::time::interval(PRUNE_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let now = Utc::now().naive_utc();
+        if let Err(e) = RevokedToken::delete_expired(&db, now).await {
+            log::error!("failed to prune revoked tokens: {e:?}");
+        }
+
+        REVOKED_TOKENS
+            .write()
+            .unwrap()
+            .retain(|_, valid_until| now <= *valid_until);
+    }
+}
+
+/// Checks whether a token has been revoked.
+pub(super) fn is_revoked(jti: &str) -> bool {
+    REVOKED_TOKENS.read().unwrap().contains_key(jti)
+}
+/// Adds a token to the in memory list.
+pub(super) fn insert(jti: String, valid_until: NaiveDateTime) {
+    REVOKED_TOKENS.write().unwrap().insert(jti, valid_until);
+}
@@ -2,6 +2,10 @@
 //! emails and passwords.
 
 use crate::error::{Error, Result};
+use api::{
+    auth::PasswordStrength,
+    error::{ErrorKey, Field, FieldError},
+};
 use regex::Regex;
 
 /// Checks that the username only contains valid characters.
@@ -34,25 +38,12 @@ pub fn validate_username(username: &str) -> Result<()> {
 ///     * 1 or more of: 0-9
 ///     * 1 or more of: a-z
 ///     * 1 or more of: A-Z
+///
+/// See [`PasswordStrength`] for feedback on which requirements failed.
 pub fn validate_password_complexity(password: &str) -> Result<()> {
-    let mut upper = 0;
-    let mut lower = 0;
-    let mut numbers = 0;
-    let mut symbols = 0;
-
-    for ch in password.chars() {
-        match ch {
-            'a'..='z' => lower += 1,
-            'A'..='Z' => upper += 1,
-            '0'..='9' => numbers += 1,
-            _ => symbols += 1,
-        }
-    }
-
-    if upper == 0 || lower == 0 || numbers == 0 || symbols == 0 {
-        Err(Error::InvalidPassword)
-    } else {
-        Ok(())
+    match PasswordStrength::new(password).is_strong() {
+        true => Ok(()),
+        false => Err(Error::InvalidPassword),
     }
 }
 
@@ -73,3 +64,72 @@ pub fn validate_email(email: &str) -> Result<()> {
         false => Err(Error::InvalidEmail),
     }
 }
+
+/// Validates the username, email and (if provided) password of an account.
+/// Every invalid field is returned in [`Error::Validation`], so that the
+/// user can correct them all at once.
+pub fn validate_account(username: &str, email: &str, password: Option<&str>) -> Result<()> {
+    let is_password_valid =
+        password.is_none_or(|password| validate_password_complexity(password).is_ok());
+    let fields = [
+        (
+            validate_username(username).is_ok(),
+            Field::Username,
+            ErrorKey::InvalidUsername,
+        ),
+        (
+            validate_email(email).is_ok(),
+            Field::Email,
+            ErrorKey::InvalidEmail,
+        ),
+        (
+            is_password_valid,
+            Field::Password,
+            ErrorKey::InvalidPassword,
+        ),
+    ]
+    .into_iter()
+    .filter(|&(is_valid, ..)| !is_valid)
+    .map(|(_, field, key)| FieldError { field, key })
+    .collect::<Vec<_>>();
+
+    match fields.is_empty() {
+        true => Ok(()),
+        false => Err(Error::Validation(fields)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Gets the invalid fields of an account.
+    fn invalid_fields(username: &str, email: &str, password: Option<&str>) -> Vec<Field> {
+        match validate_account(username, email, password) {
+            Ok(()) => vec![],
+            Err(Error::Validation(fields)) => fields.iter().map(|error| error.field).collect(),
+            Err(e) => panic!("unexpected error: {e:?}"),
+        }
+    }
+
+    #[test]
+    fn account_fields() {
+        let (username, email, password) = ("user", "user@example.com", "Passw0rd!");
+
+        assert!(invalid_fields(username, email, Some(password)).is_empty());
+        assert!(invalid_fields(username, email, None).is_empty());
+
+        assert_eq!(
+            vec![Field::Username],
+            invalid_fields("_user", email, Some(password))
+        );
+        assert_eq!(
+            vec![Field::Email, Field::Password],
+            invalid_fields(username, "user", Some("password"))
+        );
+        assert_eq!(
+            vec![Field::Username, Field::Email],
+            invalid_fields("", "", None)
+        );
+    }
+}
@@ -72,6 +72,8 @@ pub enum Error {
     Address(lettre::address::AddressError),
     /// Error serializing or deserializing data.
     Bincode(bincode::Error),
+    /// The word list could not be loaded.
+    Fsm(String),
     /// Error encoding the JWT.
     JwtEncoding(jsonwebtoken::errors::Error),
     /// Error decoding the JWT.
@@ -86,14 +88,22 @@ pub enum Error {
     MissingAuthHeader,
     /// The request has an invalid auth header.
     InvalidAuthHeader,
+    /// The auth token has been revoked (the user has logged out).
+    RevokedToken,
     /// The request has an incorrect password.
     IncorrectPassword,
+    /// Too many failed logins have been made for the account.
+    AccountLocked,
     /// Invalid username provided.
     InvalidUsername,
     /// Password was too weak.
     InvalidPassword,
     /// Email was invalid.
     InvalidEmail,
+    /// One or more fields of an account were invalid.
+    Validation(Vec<api::error::FieldError>),
+    /// The board theme does not exist.
+    InvalidTheme,
     /// Cannot send a reset password request until the previous
     /// request times out.
     ResetTimeout,
@@ -102,8 +112,28 @@ pub enum Error {
     IncorrectResetSecret,
     /// Request to reset password has expired.
     ResetExpired,
+    /// An incorrect secret or username was provided to the verify email route.
+    IncorrectVerifySecret,
+    /// Request to change email has expired.
+    VerifyExpired,
     /// The target authority was missing from the request.
     MissingAuthority,
     /// The client made a request for an account that no longer exists.
     MissingAccount,
+    /// The requested game does not exist, or the user is not a player.
+    MissingGame,
+    /// Must be between 2 and 4 players per game.
+    IllegalPlayerCount,
+    /// The same user was added to a game more than once.
+    DuplicatePlayer,
+    /// A play was made when it was another player's turn.
+    NotYourTurn,
+    /// Too many words were sent to be validated.
+    TooManyWords,
+    /// The requested puzzle is not today's puzzle.
+    MissingPuzzle,
+    /// The user has already attempted the daily puzzle.
+    PuzzleAttempted,
+    /// An illegal play was made.
+    Play(scrabble::error::GameError),
 }
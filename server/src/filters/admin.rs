@@ -0,0 +1,19 @@
+use crate::{
+    auth::authenticated_admin, filters::with, handlers, handlers::live::games::GamesHandle,
+};
+use warp::{filters::BoxedFilter, Filter, Reply};
+
+/// Combined filter for the admin route.
+pub fn all(games: &GamesHandle) -> BoxedFilter<(impl Reply,)> {
+    warp::path("admin").and(game_events(games)).boxed()
+}
+
+/// Get the event log of a live game.
+fn game_events(games: &GamesHandle) -> BoxedFilter<(impl Reply,)> {
+    warp::path!("games" / i32 / "events")
+        .and(warp::get())
+        .and(with(games))
+        .and(authenticated_admin())
+        .and_then(handlers::admin::game_events)
+        .boxed()
+}
@@ -1,10 +1,27 @@
-use crate::{auth::authenticated_user, db::Db, filters::with, handlers};
+use crate::{
+    auth::authenticated_user,
+    db::Db,
+    filters::{json_body, with},
+    fsm::FsmHandle,
+    handlers,
+    mailer::Mailer,
+};
+use serde::{Deserialize, Serialize};
 use warp::{filters::BoxedFilter, Filter, Reply};
 
 /// Combined filter for the games route.
-pub fn all(db: &Db) -> BoxedFilter<(impl Reply,)> {
+pub fn all(db: &Db, mailer: &Mailer, fsm: &FsmHandle) -> BoxedFilter<(impl Reply,)> {
     warp::path("games")
-        .and(list(db).or(stats(db)).or(overall_stats(db)))
+        .and(
+            list(db)
+                .or(stats(db))
+                .or(record(db, fsm))
+                .or(board(db))
+                .or(overall_stats(db))
+                .or(create(db))
+                .or(state(db))
+                .or(play(db, mailer, fsm)),
+        )
         .boxed()
 }
 
@@ -37,3 +54,75 @@ fn overall_stats(db: &Db) -> BoxedFilter<(impl Reply,)> {
         .and_then(handlers::games::overall_stats)
         .boxed()
 }
+
+/// Create a correspondence game.
+fn create(db: &Db) -> BoxedFilter<(impl Reply,)> {
+    warp::path!()
+        .and(warp::post())
+        .and(with(db))
+        .and(authenticated_user())
+        .and(json_body())
+        .and_then(handlers::games::create)
+        .boxed()
+}
+
+/// Export a finished game as a shareable string.
+fn record(db: &Db, fsm: &FsmHandle) -> BoxedFilter<(impl Reply,)> {
+    warp::path!(i32 / "record")
+        .and(warp::get())
+        .and(with(db))
+        .and(with(fsm))
+        .and(authenticated_user())
+        .and_then(handlers::games::record)
+        .boxed()
+}
+
+/// The format that a board is written in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BoardFormat {
+    /// A plain text grid, with row and column labels.
+    Ascii,
+    /// A JSON list of the tile at each position.
+    Json,
+}
+
+/// Query parameter for the board route.
+#[derive(Serialize, Deserialize)]
+pub struct BoardQuery {
+    pub format: Option<BoardFormat>,
+}
+
+/// Get the current board of a live or finished game.
+fn board(db: &Db) -> BoxedFilter<(impl Reply,)> {
+    warp::path!(i32 / "board")
+        .and(warp::get())
+        .and(with(db))
+        .and(authenticated_user())
+        .and(warp::query())
+        .and_then(handlers::games::board)
+        .boxed()
+}
+
+/// Get the state of a correspondence game.
+fn state(db: &Db) -> BoxedFilter<(impl Reply,)> {
+    warp::path!(i32 / "state")
+        .and(warp::get())
+        .and(with(db))
+        .and(authenticated_user())
+        .and_then(handlers::games::state)
+        .boxed()
+}
+
+/// Make a play in a correspondence game.
+fn play(db: &Db, mailer: &Mailer, fsm: &FsmHandle) -> BoxedFilter<(impl Reply,)> {
+    warp::path!(i32 / "play")
+        .and(warp::post())
+        .and(with(db))
+        .and(with(mailer))
+        .and(with(fsm))
+        .and(authenticated_user())
+        .and(json_body())
+        .and_then(handlers::games::play)
+        .boxed()
+}
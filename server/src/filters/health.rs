@@ -0,0 +1,26 @@
+use crate::{db::Db, filters::with, fsm::FsmHandle, handlers};
+use warp::{filters::BoxedFilter, Filter, Reply};
+
+/// Combined filter for the health check routes, which do not
+/// require auth.
+pub fn all(db: &Db, fsm: &FsmHandle) -> BoxedFilter<(impl Reply,)> {
+    health().or(ready(db, fsm)).boxed()
+}
+
+/// Liveness check.
+fn health() -> BoxedFilter<(impl Reply,)> {
+    warp::path!("health")
+        .and(warp::get())
+        .and_then(handlers::health::health)
+        .boxed()
+}
+
+/// Readiness check.
+fn ready(db: &Db, fsm: &FsmHandle) -> BoxedFilter<(impl Reply,)> {
+    warp::path!("ready")
+        .and(warp::get())
+        .and(with(db))
+        .and(with(fsm))
+        .and_then(handlers::health::ready)
+        .boxed()
+}
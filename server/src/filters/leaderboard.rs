@@ -4,7 +4,13 @@ use warp::{filters::BoxedFilter, Filter, Reply};
 
 /// Combined filter for the leaderboard route.
 pub fn all(db: &Db) -> BoxedFilter<(impl Reply,)> {
-    warp::path("leaderboard").and(overall_leaderboard(db).or(friends_leaderboard(db))).boxed()
+    warp::path("leaderboard")
+        .and(
+            overall_leaderboard(db)
+                .or(friends_leaderboard(db))
+                .or(puzzle_leaderboard(db)),
+        )
+        .boxed()
 }
 
 /// Query parameter for the leaderboard route.
@@ -33,3 +39,13 @@ fn friends_leaderboard(db: &Db) -> BoxedFilter<(impl Reply,)> {
         .and_then(handlers::leaderboard::friends_leaderboard)
         .boxed()
 }
+
+/// A leaderboard of daily puzzle results.
+fn puzzle_leaderboard(db: &Db) -> BoxedFilter<(impl Reply,)> {
+    warp::path!("puzzles")
+        .and(warp::get())
+        .and(with(db))
+        .and(warp::query())
+        .and_then(handlers::leaderboard::puzzle_leaderboard)
+        .boxed()
+}
@@ -1,16 +1,16 @@
 use crate::{
-    db::Db,
     filters::with,
-    fsm::FsmHandle,
     handlers::{self, live::games::GamesHandle},
 };
 use warp::{filters::BoxedFilter, ws::Ws, Filter, Reply};
 
-/// Combined filter for the live route.
-pub fn all(db: &Db, fsm: &FsmHandle) -> BoxedFilter<(impl Reply,)> {
-    let games = GamesHandle::new(db, fsm);
+/// The maximum size of a websocket message from the client, in bytes.
+/// Client messages are small, so larger messages are rejected.
+const MAX_MESSAGE_SIZE: usize = 16 * 1024;
 
-    warp::path("live").and(connect(&games)).boxed()
+/// Combined filter for the live route.
+pub fn all(games: &GamesHandle) -> BoxedFilter<(impl Reply,)> {
+    warp::path("live").and(connect(games)).boxed()
 }
 
 /// Connect to the server via websocket.
@@ -20,7 +20,9 @@ fn connect(games: &GamesHandle) -> BoxedFilter<(impl Reply,)> {
         .and(with(games))
         .map(|ws: Ws, games: GamesHandle| {
             log::info!("websocket request received");
-            ws.on_upgrade(move |ws| handlers::live::connected(ws, games))
+            ws.max_message_size(MAX_MESSAGE_SIZE)
+                .max_frame_size(MAX_MESSAGE_SIZE)
+                .on_upgrade(move |ws| handlers::live::connected(ws, games))
         })
         .boxed()
 }
@@ -2,30 +2,50 @@ use crate::{
     db::Db,
     error::{Error, Result},
     fsm::FsmHandle,
-    handlers, Mailer,
+    handlers::{self, live::games::GamesHandle},
+    Mailer,
 };
-use api::error::ErrorResponse;
+use api::error::{ErrorKey, ErrorResponse};
+use serde::de::DeserializeOwned;
 use std::{convert::Infallible, env};
 use warp::{
-    body::BodyDeserializeError, filters::BoxedFilter, hyper::StatusCode, path::Peek, Filter,
-    Rejection, Reply,
+    body::BodyDeserializeError,
+    filters::BoxedFilter,
+    hyper::StatusCode,
+    path::Peek,
+    reject::{LengthRequired, PayloadTooLarge},
+    Filter, Rejection, Reply,
 };
 
+pub mod admin;
 pub mod friends;
 pub mod games;
+pub mod health;
 pub mod leaderboard;
 pub mod live;
+pub mod puzzles;
+pub mod themes;
+pub mod tiles;
 pub mod users;
+pub mod words;
 
 /// Gets a filter that servers the API.
 fn api_filter(db: Db, mailer: Mailer, fsm: FsmHandle) -> BoxedFilter<(impl Reply,)> {
+    let games = GamesHandle::new(&db, &fsm);
+
     warp::path("api")
         .and(
-            friends::all(&db)
-                .or(games::all(&db))
+            admin::all(&games)
+                .or(friends::all(&db))
+                .or(games::all(&db, &mailer, &fsm))
+                .or(health::all(&db, &fsm))
                 .or(leaderboard::all(&db))
-                .or(live::all(&db, &fsm))
-                .or(users::all(&db, &mailer)),
+                .or(live::all(&games))
+                .or(puzzles::all(&db, &fsm))
+                .or(themes::all())
+                .or(tiles::all())
+                .or(users::all(&db, &mailer))
+                .or(words::all(&fsm)),
         )
         .boxed()
 }
@@ -81,65 +101,107 @@ pub fn with<T: Clone + Send>(data: &T) -> impl Filter<Extract = (T,), Error = In
     warp::any().map(move || data.clone())
 }
 
+/// The maximum length of a JSON request body, in bytes.
+const MAX_BODY_LEN: u64 = 16 * 1024;
+
+/// Gets a filter that extracts a JSON body, rejecting bodies longer
+/// than `MAX_BODY_LEN` before they are read.
+pub fn json_body<T: DeserializeOwned + Send>(
+) -> impl Filter<Extract = (T,), Error = Rejection> + Copy {
+    warp::body::content_length_limit(MAX_BODY_LEN).and(warp::body::json())
+}
+
 /// Handles rejections (errors where all filters fail).
 pub async fn handle_rejection(rejection: Rejection) -> Result<impl Reply, Infallible> {
-    let (status, msg) = if let Some(error) = rejection.find::<Error>() {
+    let (status, key) = if let Some(error) = rejection.find::<Error>() {
         log::info!("rejection: {error:?}");
         match error {
-            Error::Http(_) => (StatusCode::BAD_REQUEST, "Bad request"),
-            Error::MissingAuthHeader => (StatusCode::NOT_FOUND, "Missing auth header"),
-            Error::UsernameOrEmailExists => (StatusCode::FORBIDDEN, "Username or email exists"),
-            Error::InvalidUsername => (StatusCode::FORBIDDEN, "Username is invalid"),
-            Error::InvalidPassword => (
+            Error::Http(_) => (StatusCode::BAD_REQUEST, ErrorKey::BadRequest),
+            Error::MissingAuthHeader => (StatusCode::NOT_FOUND, ErrorKey::MissingAuthHeader),
+            Error::UsernameOrEmailExists => {
+                (StatusCode::FORBIDDEN, ErrorKey::UsernameOrEmailExists)
+            }
+            Error::InvalidUsername => (StatusCode::FORBIDDEN, ErrorKey::InvalidUsername),
+            Error::InvalidPassword => (StatusCode::FORBIDDEN, ErrorKey::InvalidPassword),
+            Error::InvalidEmail => (StatusCode::FORBIDDEN, ErrorKey::InvalidEmail),
+            Error::Validation(fields) => (
                 StatusCode::FORBIDDEN,
-                "Password is too weak.\n\
-                Must contain at least one uppercase and\n\
-                lowercase letter, number and symbol.",
+                fields
+                    .first()
+                    .map_or(ErrorKey::BadRequest, |error| error.key),
             ),
-            Error::InvalidEmail => (StatusCode::FORBIDDEN, "Email is invalid"),
+            Error::InvalidTheme => (StatusCode::BAD_REQUEST, ErrorKey::InvalidTheme),
             Error::Lettre(_)
             | Error::Address(_)
             | Error::JwtEncoding(_)
             | Error::Bincode(_)
+            | Error::Fsm(_)
             | Error::Io(_)
             | Error::Smtp(_)
             | Error::Env(_)
             | Error::SocketAddr(_)
             | Error::Sqlx(_)
-            | Error::Argon2(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error"),
+            | Error::Argon2(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                ErrorKey::InternalServerError,
+            ),
             Error::InvalidAuthHeader | Error::MissingAuthority => {
-                (StatusCode::BAD_REQUEST, "Invalid auth header")
+                (StatusCode::BAD_REQUEST, ErrorKey::InvalidAuthHeader)
             }
-            Error::ResetTimeout => (
-                StatusCode::FORBIDDEN,
-                "A recent request was made to reset the password",
-            ),
-            Error::ResetExpired => (
-                StatusCode::FORBIDDEN,
-                "The request to reset your password has expired",
-            ),
+            Error::ResetTimeout => (StatusCode::FORBIDDEN, ErrorKey::ResetTimeout),
+            Error::ResetExpired => (StatusCode::FORBIDDEN, ErrorKey::ResetExpired),
+            Error::VerifyExpired => (StatusCode::FORBIDDEN, ErrorKey::VerifyExpired),
             Error::MissingAccount | Error::IncorrectPassword => {
-                (StatusCode::UNAUTHORIZED, "Incorrect username or password")
+                (StatusCode::UNAUTHORIZED, ErrorKey::IncorrectCredentials)
             }
-            Error::JwtDecoding(_) | Error::IncorrectResetSecret | Error::InsufficientRole => {
-                (StatusCode::UNAUTHORIZED, "Unauthorized")
+            Error::AccountLocked => (StatusCode::TOO_MANY_REQUESTS, ErrorKey::AccountLocked),
+            Error::JwtDecoding(_)
+            | Error::RevokedToken
+            | Error::IncorrectResetSecret
+            | Error::IncorrectVerifySecret
+            | Error::InsufficientRole => (StatusCode::UNAUTHORIZED, ErrorKey::Unauthorized),
+            Error::MissingGame | Error::MissingPuzzle => {
+                (StatusCode::NOT_FOUND, ErrorKey::NotFound)
             }
+            Error::IllegalPlayerCount => (StatusCode::BAD_REQUEST, ErrorKey::IllegalPlayerCount),
+            Error::DuplicatePlayer => (StatusCode::BAD_REQUEST, ErrorKey::DuplicatePlayer),
+            Error::TooManyWords => (StatusCode::PAYLOAD_TOO_LARGE, ErrorKey::PayloadTooLarge),
+            Error::NotYourTurn => (StatusCode::FORBIDDEN, ErrorKey::NotYourTurn),
+            Error::PuzzleAttempted => (StatusCode::CONFLICT, ErrorKey::PuzzleAttempted),
+            Error::Play(_) => (StatusCode::BAD_REQUEST, ErrorKey::IllegalPlay),
         }
     } else if rejection.is_not_found() {
         log::info!("not found");
-        (StatusCode::NOT_FOUND, "Not found")
+        (StatusCode::NOT_FOUND, ErrorKey::NotFound)
+    } else if rejection.find::<PayloadTooLarge>().is_some() {
+        log::info!("payload too large");
+        (StatusCode::PAYLOAD_TOO_LARGE, ErrorKey::PayloadTooLarge)
+    } else if rejection.find::<LengthRequired>().is_some() {
+        log::info!("length required");
+        (StatusCode::LENGTH_REQUIRED, ErrorKey::BadRequest)
     } else if rejection.find::<BodyDeserializeError>().is_some() {
         log::info!("body deserialize error");
-        (StatusCode::BAD_REQUEST, "Invalid request body")
+        (StatusCode::BAD_REQUEST, ErrorKey::InvalidRequestBody)
     } else {
         log::error!("unmatched error: {rejection:?}");
-        (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error")
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            ErrorKey::InternalServerError,
+        )
+    };
+
+    // the fields that failed validation are sent so that the client
+    // can highlight them.
+    let fields = match rejection.find::<Error>() {
+        Some(Error::Validation(fields)) => fields.clone(),
+        _ => vec![],
     };
 
     Ok(warp::reply::with_status(
         warp::reply::json(&ErrorResponse {
             status: status.to_string(),
-            msg: msg.to_string(),
+            key,
+            fields,
         }),
         status,
     ))
@@ -0,0 +1,38 @@
+use crate::{
+    auth::authenticated_user,
+    db::Db,
+    filters::{json_body, with},
+    fsm::FsmHandle,
+    handlers,
+};
+use warp::{filters::BoxedFilter, Filter, Reply};
+
+/// Combined filter for the puzzles route.
+pub fn all(db: &Db, fsm: &FsmHandle) -> BoxedFilter<(impl Reply,)> {
+    warp::path("puzzles")
+        .and(daily(db, fsm).or(attempt(db, fsm)))
+        .boxed()
+}
+
+/// Get today's puzzle.
+fn daily(db: &Db, fsm: &FsmHandle) -> BoxedFilter<(impl Reply,)> {
+    warp::path!("daily")
+        .and(warp::get())
+        .and(with(db))
+        .and(with(fsm))
+        .and(authenticated_user())
+        .and_then(handlers::puzzles::daily)
+        .boxed()
+}
+
+/// Make an attempt at today's puzzle.
+fn attempt(db: &Db, fsm: &FsmHandle) -> BoxedFilter<(impl Reply,)> {
+    warp::path!("daily")
+        .and(warp::post())
+        .and(with(db))
+        .and(with(fsm))
+        .and(authenticated_user())
+        .and(json_body())
+        .and_then(handlers::puzzles::attempt)
+        .boxed()
+}
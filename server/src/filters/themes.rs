@@ -0,0 +1,15 @@
+use crate::handlers;
+use warp::{filters::BoxedFilter, Filter, Reply};
+
+/// Combined filter for the themes route.
+pub fn all() -> BoxedFilter<(impl Reply,)> {
+    warp::path("themes").and(list()).boxed()
+}
+
+/// The custom board themes.
+fn list() -> BoxedFilter<(impl Reply,)> {
+    warp::path!()
+        .and(warp::get())
+        .and_then(handlers::themes::list)
+        .boxed()
+}
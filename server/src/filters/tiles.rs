@@ -0,0 +1,15 @@
+use crate::handlers;
+use warp::{filters::BoxedFilter, Filter, Reply};
+
+/// Combined filter for the tiles route.
+pub fn all() -> BoxedFilter<(impl Reply,)> {
+    warp::path("tiles").and(distribution()).boxed()
+}
+
+/// The tiles in the letter bag at the start of a game.
+fn distribution() -> BoxedFilter<(impl Reply,)> {
+    warp::path!()
+        .and(warp::get())
+        .and_then(handlers::tiles::distribution)
+        .boxed()
+}
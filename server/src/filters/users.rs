@@ -1,4 +1,10 @@
-use crate::{auth::authenticated_user, db::Db, filters::with, handlers, mailer::Mailer};
+use crate::{
+    auth::authenticated_user,
+    db::Db,
+    filters::{json_body, with},
+    handlers,
+    mailer::Mailer,
+};
 use warp::{filters::BoxedFilter, Filter, Reply};
 
 /// Combined filter for the users route.
@@ -6,12 +12,14 @@ pub fn all(db: &Db, mailer: &Mailer) -> BoxedFilter<(impl Reply,)> {
     warp::path("users")
         .and(
             log_in(db)
+                .or(log_out(db))
                 .or(sign_up(db))
                 .or(profile(db))
                 .or(delete(db))
-                .or(update(db))
+                .or(update(db, mailer))
                 .or(reset_password(db, mailer))
-                .or(reset_with_secret(db)),
+                .or(reset_with_secret(db))
+                .or(verify_email(db)),
         )
         .boxed()
 }
@@ -21,17 +29,27 @@ fn log_in(db: &Db) -> BoxedFilter<(impl Reply,)> {
     warp::path!("login")
         .and(warp::post())
         .and(with(db))
-        .and(warp::body::json())
+        .and(json_body())
         .and_then(handlers::users::log_in)
         .boxed()
 }
 
+/// Log out of an account (revokes the token).
+fn log_out(db: &Db) -> BoxedFilter<(impl Reply,)> {
+    warp::path!("logout")
+        .and(warp::post())
+        .and(with(db))
+        .and(authenticated_user())
+        .and_then(handlers::users::log_out)
+        .boxed()
+}
+
 /// Sign up for an account.
 fn sign_up(db: &Db) -> BoxedFilter<(impl Reply,)> {
     warp::path!()
         .and(warp::post())
         .and(with(db))
-        .and(warp::body::json())
+        .and(json_body())
         .and_then(handlers::users::sign_up)
         .boxed()
 }
@@ -52,18 +70,19 @@ fn delete(db: &Db) -> BoxedFilter<(impl Reply,)> {
         .and(warp::delete())
         .and(with(db))
         .and(authenticated_user())
-        .and(warp::body::json())
+        .and(json_body())
         .and_then(handlers::users::delete)
         .boxed()
 }
 
 /// Update an account.
-fn update(db: &Db) -> BoxedFilter<(impl Reply,)> {
+fn update(db: &Db, mailer: &Mailer) -> BoxedFilter<(impl Reply,)> {
     warp::path!()
         .and(warp::put())
         .and(with(db))
+        .and(with(mailer))
         .and(authenticated_user())
-        .and(warp::body::json())
+        .and(json_body())
         .and_then(handlers::users::update)
         .boxed()
 }
@@ -74,7 +93,7 @@ fn reset_password(db: &Db, mailer: &Mailer) -> BoxedFilter<(impl Reply,)> {
         .and(warp::post())
         .and(with(db))
         .and(with(mailer))
-        .and(warp::body::json())
+        .and(json_body())
         .and_then(handlers::users::reset_password)
         .boxed()
 }
@@ -84,7 +103,42 @@ fn reset_with_secret(db: &Db) -> BoxedFilter<(impl Reply,)> {
     warp::path!("reset-password")
         .and(warp::put())
         .and(with(db))
-        .and(warp::body::json())
+        .and(json_body())
         .and_then(handlers::users::reset_with_secret)
         .boxed()
 }
+
+/// Verify a new email address from secret sent in email link.
+fn verify_email(db: &Db) -> BoxedFilter<(impl Reply,)> {
+    warp::path!("verify-email")
+        .and(warp::put())
+        .and(with(db))
+        .and(json_body())
+        .and_then(handlers::users::verify_email)
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db, filters::handle_rejection};
+    use warp::{hyper::StatusCode, test::request};
+
+    #[tokio::test]
+    async fn oversized_body() {
+        let db = db::connect().await.unwrap();
+        let filter = log_in(&db).recover(handle_rejection);
+
+        // a body that would be a valid login, if it were not padded.
+        let mut body = br#"{"username":"user","password":"password","remember":false}"#.to_vec();
+        body.resize(64 * 1024, b' ');
+
+        let response = request()
+            .method("POST")
+            .path("/login")
+            .body(body)
+            .reply(&filter)
+            .await;
+        assert_eq!(StatusCode::PAYLOAD_TOO_LARGE, response.status());
+    }
+}
@@ -0,0 +1,23 @@
+use crate::{
+    auth::authenticated_admin,
+    filters::{json_body, with},
+    fsm::FsmHandle,
+    handlers,
+};
+use warp::{filters::BoxedFilter, Filter, Reply};
+
+/// Combined filter for the words route.
+pub fn all(fsm: &FsmHandle) -> BoxedFilter<(impl Reply,)> {
+    warp::path("words").and(validate(fsm)).boxed()
+}
+
+/// Check which words are in the word list.
+fn validate(fsm: &FsmHandle) -> BoxedFilter<(impl Reply,)> {
+    warp::path!("validate")
+        .and(warp::post())
+        .and(with(fsm))
+        .and(authenticated_admin())
+        .and(json_body())
+        .and_then(handlers::words::validate)
+        .boxed()
+}
@@ -1,30 +1,158 @@
-//! Stores a `FastFsm` that can be shared across threads.
+//! Stores the `FastFsm`s that can be shared across threads.
 
-use crate::error::Result;
-use scrabble::util::fsm::FastFsm;
-use std::{env, ops::Deref, sync::Arc};
+use crate::error::{Error, Result};
+use scrabble::{
+    ai::Ai,
+    util::fsm::{FastFsm, Fsm},
+};
+use std::{env, fs::File, io::BufReader, ops::Deref, path::Path, sync::Arc};
 
-/// A structure that contains a thread safe immutable reference
-/// to a `scrabble::util::fsm::Fsm` impl.
+/// The minimum number of states in a plausible word list. The word list
+/// bundled with the server has far more states than this, so a smaller
+/// fsm was most likely built from the wrong file.
+const MIN_STATE_COUNT: usize = 1000;
+
+/// A structure that contains thread safe immutable references to
+/// `scrabble::util::fsm::Fsm` impls. Dereferences to the full word
+/// list, which is used to validate plays.
 #[derive(Clone, Debug)]
-pub struct FsmHandle(Arc<FastFsm>);
+pub struct FsmHandle {
+    /// The full word list.
+    fsm: Arc<FastFsm>,
+    /// The common words (a subset of `fsm`), that some AI players
+    /// are restricted to.
+    common_fsm: Arc<FastFsm>,
+}
 impl FsmHandle {
-    /// Loads the Fsm from env variables.
+    /// Loads the Fsms from env variables.
     pub fn new_from_env() -> Result<Self> {
-        let fsm_path = env::var("FAST_FSM_BIN")?;
+        let fsm_path = env::var("FAST_FSM_BIN")
+            .map_err(|_| Error::Fsm(String::from("FAST_FSM_BIN is not set")))?;
+        let common_fsm_path = env::var("COMMON_FSM_BIN")
+            .map_err(|_| Error::Fsm(String::from("COMMON_FSM_BIN is not set")))?;
 
-        log::info!("loading fast fsm: {fsm_path}");
-        let file = std::fs::File::open(&fsm_path)?;
-        let rdr = std::io::BufReader::new(file);
-        let fast_fsm: FastFsm = bincode::deserialize_from(rdr)?;
+        Self::load(fsm_path, common_fsm_path)
+    }
+    /// Loads the full and common word lists from files.
+    pub fn load(fsm_path: impl AsRef<Path>, common_fsm_path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self {
+            fsm: Arc::new(load_fsm("FAST_FSM_BIN", fsm_path.as_ref())?),
+            common_fsm: Arc::new(load_fsm("COMMON_FSM_BIN", common_fsm_path.as_ref())?),
+        })
+    }
+    /// Loads the word lists that are bundled with the server.
+    #[cfg(test)]
+    pub fn bundled() -> Self {
+        Self::load("data/fast_fsm.bin", "data/common_fsm.bin").unwrap()
+    }
 
-        Ok(Self(Arc::new(fast_fsm)))
+    /// Checks whether both word lists are loaded, and plausible.
+    pub fn is_loaded(&self) -> bool {
+        self.fsm.state_count() >= MIN_STATE_COUNT && self.common_fsm.state_count() > 0
+    }
+    /// Gets the fsm that an Ai should generate plays from.
+    pub fn for_ai(&self, ai: &Ai) -> &FastFsm {
+        ai.generation_fsm(&self.fsm, &self.common_fsm)
     }
 }
 impl Deref for FsmHandle {
     type Target = FastFsm;
 
     fn deref(&self) -> &Self::Target {
-        self.0.deref()
+        self.fsm.deref()
+    }
+}
+
+/// Loads an Fsm from a file, checking that it is a plausible word list.
+/// `var` is the env variable that the path was read from.
+fn load_fsm(var: &str, fsm_path: &Path) -> Result<FastFsm> {
+    let file = File::open(fsm_path);
+    let fsm_path = fsm_path.display();
+
+    log::info!("loading fast fsm: {fsm_path}");
+    let file = file.map_err(|e| Error::Fsm(format!("failed to open {var} ({fsm_path}): {e}")))?;
+    let fast_fsm: FastFsm = bincode::deserialize_from(BufReader::new(file))
+        .map_err(|e| Error::Fsm(format!("{var} ({fsm_path}) is not a valid fsm: {e}")))?;
+
+    let state_count = fast_fsm.state_count();
+    if state_count < MIN_STATE_COUNT {
+        return Err(Error::Fsm(format!(
+            "{var} ({fsm_path}) only has {state_count} states"
+        )));
+    }
+
+    Ok(fast_fsm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scrabble::{ai::Vocabulary, util::fsm::FsmBuilder};
+
+    /// Writes `bytes` to a temporary file, returning its path.
+    fn temp_file(name: &str, bytes: &[u8]) -> std::path::PathBuf {
+        let path = env::temp_dir().join(format!("{name}-{}", std::process::id()));
+        std::fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn load_bundled() {
+        let fsm = FsmHandle::bundled();
+        assert!(fsm.state_count() >= MIN_STATE_COUNT);
+        assert!(fsm.common_fsm.state_count() < fsm.state_count());
+        assert!(fsm.is_loaded());
+    }
+
+    #[test]
+    fn common_words_are_valid() {
+        let fsm = FsmHandle::bundled();
+        let common_words = std::fs::read_to_string("data/common_words.txt").unwrap();
+
+        for word in common_words.lines() {
+            assert!(fsm.accepts(word), "{word} is not in the full word list");
+            assert!(
+                fsm.common_fsm.accepts(word),
+                "{word} is not in the common fsm"
+            );
+        }
+
+        // obscure words are only in the full word list.
+        assert!(fsm.accepts("qat"));
+        assert!(!fsm.common_fsm.accepts("qat"));
+    }
+
+    #[test]
+    fn ai_vocabulary() {
+        let fsm = FsmHandle::bundled();
+
+        assert_eq!(Vocabulary::Common, Ai::easy().vocabulary());
+        assert!(std::ptr::eq(fsm.for_ai(&Ai::easy()), &*fsm.common_fsm));
+        assert!(std::ptr::eq(fsm.for_ai(&Ai::hard()), &*fsm));
+    }
+
+    #[test]
+    fn load_missing() {
+        let result = FsmHandle::load("data/missing_fsm.bin", "data/common_fsm.bin");
+        assert!(matches!(result, Err(Error::Fsm(_))));
+    }
+
+    #[test]
+    fn load_corrupt() {
+        let path = temp_file("corrupt_fsm.bin", b"not an fsm");
+        let result = FsmHandle::load("data/fast_fsm.bin", &path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(result, Err(Error::Fsm(_))));
+    }
+
+    #[test]
+    fn load_implausible() {
+        let fast_fsm: FastFsm = FsmBuilder::from_sorted_iter(["cat"]).unwrap().build();
+        let path = temp_file("small_fsm.bin", &bincode::serialize(&fast_fsm).unwrap());
+        let result = FsmHandle::load(&path, "data/common_fsm.bin");
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(result, Err(Error::Fsm(_))));
     }
 }
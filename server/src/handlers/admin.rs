@@ -0,0 +1,22 @@
+use crate::{
+    auth::Jwt,
+    error::{Error, Result},
+    handlers::live::games::GamesHandle,
+};
+use api::auth::AuthWrapper;
+use warp::{Rejection, Reply};
+
+/// GET /api/admin/games/{id_game}/events [+Auth(Admin)]
+pub async fn game_events(
+    id_game: i32,
+    games: GamesHandle,
+    jwt: Jwt,
+) -> Result<impl Reply, Rejection> {
+    let game = games.read().await.get(id_game).ok_or(Error::MissingGame)?;
+    let response = game.lock().await.events();
+
+    Ok(warp::reply::json(&AuthWrapper {
+        token: Some(jwt.token()?),
+        response,
+    }))
+}
@@ -1,13 +1,59 @@
-use crate::{auth::Jwt, db::Db, error::Error};
+use crate::{
+    auth::Jwt,
+    db::Db,
+    error::{Error, Result},
+    filters::games::{BoardFormat, BoardQuery},
+    fsm::FsmHandle,
+    handlers::live::game::{insert_play, set_game_over},
+    mailer::Mailer,
+    models::{self, CorrespondenceGame, CorrespondencePlayer},
+};
 use api::{
     auth::AuthWrapper,
     routes::{
-        games::{GameMetadata, GameStatsResponse, ListGamesResponse, OverallStatsResponse},
+        games::{
+            CreateGameRequest, GameBoardResponse, GameMetadata, GameRecordResponse,
+            GameStateResponse, GameStatsResponse, ListGamesResponse, OverallStatsResponse,
+        },
         leaderboard::LeaderboardRow,
+        live::Player,
+    },
+};
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use scrabble::{
+    error::GameError,
+    game::{
+        board::{Board, CELLS},
+        play::Play,
+        record::GameRecord,
+        tile::{Letter, Tile},
+        GameConfig, GameStatus, PlayerNum,
     },
+    util::{fsm::FastFsm, pos::Pos},
 };
+use std::env;
 use warp::{Rejection, Reply};
 
+lazy_static::lazy_static! {
+    /// The time that a player has to make a play in a correspondence
+    /// game. If the deadline passes, the player passes their turn.
+    static ref CORRESPONDENCE_MOVE_TIMEOUT: Duration = {
+        let move_timeout = env::var("CORRESPONDENCE_MOVE_TIMEOUT")
+            .expect("`CORRESPONDENCE_MOVE_TIMEOUT` env variable");
+        let days: usize = move_timeout
+            .parse()
+            .expect("`CORRESPONDENCE_MOVE_TIMEOUT` in days");
+
+        Duration::days(days as i64)
+    };
+    /// The domain that the client is served from, used for links in emails.
+    static ref DOMAIN: String = env::var("DOMAIN").expect("`DOMAIN` env variable");
+}
+
+/// The interval between checks for correspondence games where the
+/// deadline has passed.
+const EXPIRY_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
 /// GET /api/games [+Auth]
 pub async fn list(db: Db, jwt: Jwt) -> Result<impl Reply, Rejection> {
     let games = sqlx::query_file!("sql/games/list.sql", jwt.id_user())
@@ -58,6 +104,137 @@ pub async fn stats(id_game: i32, db: Db, jwt: Jwt) -> Result<impl Reply, Rejecti
     }))
 }
 
+/// Finds the player id and tiles placed for each play of a game, in the
+/// order that they were made. Plays without tiles are passes or redraws.
+async fn stored_plays(db: &Db, id_game: i32) -> Result<Vec<(i32, Vec<(Pos, Tile)>)>> {
+    let rows = sqlx::query_file!("sql/games/record_plays.sql", id_game)
+        .fetch_all(db)
+        .await?;
+
+    // group the tiles of each play, in the order that they were made.
+    let mut last_id_play = None;
+    let mut plays: Vec<(i32, Vec<(Pos, Tile)>)> = Vec::new();
+    for row in rows {
+        if last_id_play != Some(row.id_play) {
+            last_id_play = Some(row.id_play);
+            plays.push((row.id_player, Vec::new()));
+        }
+
+        if let (Some(pos), Some(letter), Some(is_blank)) = (row.pos, row.letter, row.is_blank) {
+            let letter = letter
+                .chars()
+                .next()
+                .and_then(Letter::new)
+                .ok_or(Error::MissingGame)?;
+            let tile = if is_blank {
+                Tile::Blank(Some(letter))
+            } else {
+                Tile::Letter(letter)
+            };
+
+            let (_, tile_positions) = plays.last_mut().unwrap();
+            tile_positions.push((Pos::from(pos as usize), tile));
+        }
+    }
+
+    Ok(plays)
+}
+
+/// GET /api/games/{game id}/record [+Auth]
+pub async fn record(
+    id_game: i32,
+    db: Db,
+    fsm: FsmHandle,
+    jwt: Jwt,
+) -> Result<impl Reply, Rejection> {
+    let players = sqlx::query_file!("sql/games/record_players.sql", id_game)
+        .fetch_all(&db)
+        .await
+        .map_err(Error::Sqlx)?;
+
+    // only players of a finished game can export it.
+    let is_player = players.iter().any(|row| row.id_user == Some(jwt.id_user()));
+    if !is_player || !players.iter().all(|row| row.is_over) {
+        return Err(Error::MissingGame.into());
+    }
+
+    let plays = stored_plays(&db, id_game).await?;
+
+    // redraws are not stored, so plays without tiles are exported as passes.
+    // the scores are found by replaying each placement. The racks at the end
+    // of the game are not stored, so these are the totals of the placements
+    // rather than the final scores.
+    let fsm: &FastFsm = &fsm;
+    let mut board = Board::default();
+    let mut scores = vec![0; players.len()];
+    let plays = plays
+        .into_iter()
+        .map(|(id_player, tile_positions)| {
+            if tile_positions.is_empty() {
+                return Ok(Play::Pass);
+            }
+
+            let score = board
+                .make_placement(&tile_positions, fsm, &GameConfig::default())
+                .map_err(Error::Play)?;
+            let player = players
+                .iter()
+                .position(|row| row.id_player == id_player)
+                .ok_or(Error::MissingGame)?;
+            scores[player] += score;
+
+            Ok(Play::Place(tile_positions))
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(warp::reply::json(&AuthWrapper {
+        token: Some(jwt.token()?),
+        response: GameRecordResponse {
+            record: GameRecord::new(plays, scores).to_string(),
+        },
+    }))
+}
+
+/// GET /api/games/{game id}/board?format=ascii|json [+Auth]
+pub async fn board(
+    id_game: i32,
+    db: Db,
+    jwt: Jwt,
+    query: BoardQuery,
+) -> Result<impl Reply, Rejection> {
+    let players = sqlx::query_file!("sql/games/record_players.sql", id_game)
+        .fetch_all(&db)
+        .await
+        .map_err(Error::Sqlx)?;
+
+    // only players can view the board, whether or not the game is over.
+    if !players.iter().any(|row| row.id_user == Some(jwt.id_user())) {
+        return Err(Error::MissingGame.into());
+    }
+
+    // the stored plays were validated when they were made, so are placed
+    // without checking them against the word list.
+    let mut tiles = vec![None; CELLS];
+    for (_, tile_positions) in stored_plays(&db, id_game).await? {
+        for (pos, tile) in tile_positions {
+            tiles[usize::from(pos)] = Some(tile);
+        }
+    }
+
+    Ok(match query.format.unwrap_or(BoardFormat::Json) {
+        BoardFormat::Ascii => {
+            let board = Board::try_from(tiles).map_err(|_| Error::MissingGame)?;
+            warp::reply::with_header(board.to_string(), "content-type", "text/plain")
+                .into_response()
+        }
+        BoardFormat::Json => warp::reply::json(&AuthWrapper {
+            token: Some(jwt.token()?),
+            response: GameBoardResponse { tiles },
+        })
+        .into_response(),
+    })
+}
+
 /// GET /api/games/stats [+Auth]
 pub async fn overall_stats(db: Db, jwt: Jwt) -> Result<impl Reply, Rejection> {
     // Just query a single leaderboard row for this user.
@@ -84,3 +261,345 @@ pub async fn overall_stats(db: Db, jwt: Jwt) -> Result<impl Reply, Rejection> {
         response: OverallStatsResponse { row },
     }))
 }
+
+/// POST /api/games [+Auth]
+pub async fn create(db: Db, jwt: Jwt, create: CreateGameRequest) -> Result<impl Reply, Rejection> {
+    let player_count = create.usernames.len() + 1;
+    if !(2..=4).contains(&player_count) {
+        return Err(Error::IllegalPlayerCount.into());
+    }
+
+    // find the ids of the other players before creating any records. Each
+    // user can only play once, since players are found by user id.
+    let mut id_users = vec![jwt.id_user()];
+    for username in &create.usernames {
+        let id_user = models::User::find_by_username(&db, username).await?.id_user;
+        if id_users.contains(&id_user) {
+            return Err(Error::DuplicatePlayer.into());
+        }
+
+        id_users.push(id_user);
+    }
+
+    // the game is only created if every player can be added.
+    let mut tx = db.begin().await.map_err(Error::Sqlx)?;
+    let id_game = models::Game::insert(&mut tx).await?;
+
+    // the user that created the game plays first, and the other
+    // players must be their friends.
+    let mut players = Vec::with_capacity(player_count);
+    for (idx, &id_user) in id_users.iter().enumerate() {
+        let id_owner = match idx {
+            0 => None,
+            _ => Some(jwt.id_user()),
+        };
+        let (id_player, username) =
+            models::Player::insert_user(&mut tx, id_game, id_user, id_owner).await?;
+
+        players.push(CorrespondencePlayer {
+            id_player,
+            id_user,
+            username,
+        });
+    }
+
+    let correspondence_game = CorrespondenceGame {
+        game: scrabble::game::Game::new(player_count),
+        players,
+        deadline: next_deadline(),
+    };
+    correspondence_game.insert(&mut tx, id_game).await?;
+    tx.commit().await.map_err(Error::Sqlx)?;
+
+    Ok(warp::reply::json(&AuthWrapper {
+        token: Some(jwt.token()?),
+        response: state_response(id_game, &correspondence_game, PlayerNum::first()),
+    }))
+}
+
+/// GET /api/games/{game id}/state [+Auth]
+pub async fn state(id_game: i32, db: Db, jwt: Jwt) -> Result<impl Reply, Rejection> {
+    let correspondence_game = CorrespondenceGame::find(&db, id_game)
+        .await?
+        .ok_or(Error::MissingGame)?;
+    let player_num = correspondence_game
+        .player_num(jwt.id_user())
+        .ok_or(Error::MissingGame)?;
+
+    Ok(warp::reply::json(&AuthWrapper {
+        token: Some(jwt.token()?),
+        response: state_response(id_game, &correspondence_game, player_num),
+    }))
+}
+
+/// POST /api/games/{game id}/play [+Auth]
+pub async fn play(
+    id_game: i32,
+    db: Db,
+    mailer: Mailer,
+    fsm: FsmHandle,
+    jwt: Jwt,
+    play: Play,
+) -> Result<impl Reply, Rejection> {
+    // lock the game state until the play has been stored.
+    let mut tx = db.begin().await.map_err(Error::Sqlx)?;
+    let mut correspondence_game = CorrespondenceGame::find_for_update(&mut tx, id_game)
+        .await?
+        .ok_or(Error::MissingGame)?;
+    let player_num = correspondence_game
+        .player_num(jwt.id_user())
+        .ok_or(Error::MissingGame)?;
+
+    // check that it is the player's turn.
+    match correspondence_game.game.to_play() {
+        None => return Err(Error::Play(GameError::Over).into()),
+        Some(to_play) if to_play != player_num => return Err(Error::NotYourTurn.into()),
+        Some(_) => (),
+    }
+
+    // attempt to make the play.
+    let fsm: &FastFsm = &fsm;
+    correspondence_game
+        .game
+        .make_play(&play, fsm)
+        .map_err(Error::Play)?;
+    correspondence_game.deadline = next_deadline();
+
+    correspondence_game.update(&mut tx, id_game).await?;
+    tx.commit().await.map_err(Error::Sqlx)?;
+
+    // the player has made a play, so can be sent an email on their next turn.
+    let id_player = correspondence_game.player(player_num).id_player;
+    models::Player::clear_notified(&db, id_player).await?;
+
+    on_play(
+        &db,
+        &mailer,
+        id_game,
+        &correspondence_game,
+        player_num,
+        &play,
+    )
+    .await;
+
+    Ok(warp::reply::json(&AuthWrapper {
+        token: Some(jwt.token()?),
+        response: state_response(id_game, &correspondence_game, player_num),
+    }))
+}
+
+/// Periodically checks for correspondence games where the deadline has
+/// passed, and passes the turn of the player that is next to play.
+pub async fn expire_correspondence_games(db: Db, mailer: Mailer, fsm: FsmHandle) {
+    let mut interval = tokio::time::interval(EXPIRY_CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let now = Utc::now().naive_utc();
+        let expired = match CorrespondenceGame::find_expired(&db, now).await {
+            Ok(expired) => expired,
+            Err(e) => {
+                log::error!("failed to find expired games: {e:?}");
+                continue;
+            }
+        };
+
+        for id_game in expired {
+            if let Err(e) = expire_correspondence_game(&db, &mailer, &fsm, id_game, now).await {
+                log::error!("failed to expire game {id_game}: {e:?}");
+            }
+        }
+    }
+}
+/// Passes the turn of the next player in a correspondence game, if the
+/// deadline has passed.
+async fn expire_correspondence_game(
+    db: &Db,
+    mailer: &Mailer,
+    fsm: &FastFsm,
+    id_game: i32,
+    now: NaiveDateTime,
+) -> Result<()> {
+    let mut tx = db.begin().await?;
+    let mut correspondence_game =
+        match CorrespondenceGame::find_for_update(&mut tx, id_game).await? {
+            Some(correspondence_game) => correspondence_game,
+            None => return Ok(()),
+        };
+
+    // a play may have been made since the expired games were found.
+    let player_num = match correspondence_game.game.to_play() {
+        Some(player_num) if correspondence_game.deadline < now => player_num,
+        _ => return Ok(()),
+    };
+
+    let play = Play::Pass;
+    correspondence_game
+        .game
+        .make_play(&play, fsm)
+        .map_err(Error::Play)?;
+    correspondence_game.deadline = next_deadline();
+
+    correspondence_game.update(&mut tx, id_game).await?;
+    tx.commit().await?;
+
+    on_play(db, mailer, id_game, &correspondence_game, player_num, &play).await;
+
+    Ok(())
+}
+/// Records a play that has been made in a correspondence game, and
+/// notifies the next player.
+async fn on_play(
+    db: &Db,
+    mailer: &Mailer,
+    id_game: i32,
+    correspondence_game: &CorrespondenceGame,
+    player_num: PlayerNum,
+    play: &Play,
+) {
+    // record the play for the stats routes.
+    let id_player = correspondence_game.player(player_num).id_player;
+    let game = &correspondence_game.game;
+    insert_play(db, id_player, play, game.board(), &game.config().alphabet).await;
+
+    match correspondence_game.game.status() {
+        GameStatus::Over(game_over) => {
+            set_game_over(db, id_game, game_over, |player_num| {
+                correspondence_game.player(player_num).id_player
+            })
+            .await
+        }
+        GameStatus::ToPlay(next) => {
+            let &CorrespondencePlayer {
+                id_player, id_user, ..
+            } = correspondence_game.player(*next);
+            let (db, mailer) = (db.clone(), mailer.clone());
+
+            // send the email on a seperate task as it may take a while.
+            tokio::spawn(async move {
+                let result = send_turn_email(&db, mailer, id_game, id_player, id_user).await;
+                if let Err(e) = result {
+                    log::error!("failed to send email: {e:?}");
+                }
+            });
+        }
+    }
+}
+/// Sends an email to tell a user that it is their turn in a correspondence game.
+/// Only one email is sent per game until the user makes a play, and no email is
+/// sent if the user has disabled turn emails.
+async fn send_turn_email(
+    db: &Db,
+    mailer: Mailer,
+    id_game: i32,
+    id_player: i32,
+    id_user: i32,
+) -> Result<()> {
+    let user = models::User::find_by_id(db, id_user).await?;
+    if !user.turn_emails || !models::Player::set_notified(db, id_player).await? {
+        return Ok(());
+    }
+
+    let games_link = games_link(&DOMAIN);
+    let body_html = format!(
+        r#"
+    <style>
+        html {{
+            font-family: monospace;
+        }}
+    </style>
+    <h1>It's your turn</h1>
+    <p>
+        Hi {username}, it's your turn in game {id_game}.
+
+        <br />
+
+        <a href="{games_link}">
+            Click here to view your games.
+        </a>
+    </p>
+    "#,
+        username = user.username,
+    );
+    let body_plain = format!(
+        "Hi {username}, it's your turn in game {id_game}.
+
+        Click to view your games: {games_link}
+        ",
+        username = user.username,
+    );
+
+    mailer
+        .send(&user.email, "Scrabble AI: Your Turn", body_html, body_plain)
+        .await
+}
+/// Builds the link sent in a turn email, which matches the client route
+/// `/games` that lists the user's games.
+fn games_link(domain: &str) -> String {
+    format!("https://{domain}/games")
+}
+
+/// Finds the time by which the next player in a correspondence game must
+/// make a play.
+fn next_deadline() -> NaiveDateTime {
+    Utc::now().naive_utc() + *CORRESPONDENCE_MOVE_TIMEOUT
+}
+
+/// Gets the state of a correspondence game for a player.
+fn state_response(
+    id_game: i32,
+    correspondence_game: &CorrespondenceGame,
+    player_num: PlayerNum,
+) -> GameStateResponse {
+    let game = &correspondence_game.game;
+    let api_player = |player_num| {
+        let CorrespondencePlayer {
+            id_player,
+            username,
+            ..
+        } = correspondence_game.player(player_num);
+
+        Player {
+            id_player: *id_player,
+            username: username.clone(),
+            is_connected: true,
+            difficulty: None,
+        }
+    };
+
+    // the scores are adjusted for the remaining tiles once the game is over.
+    let scores = game
+        .player_nums()
+        .map(|player_num| {
+            let score = match game.status() {
+                GameStatus::ToPlay(_) => game.player(player_num).score(),
+                GameStatus::Over(game_over) => game_over.score(player_num),
+            };
+
+            (api_player(player_num), score)
+        })
+        .collect();
+
+    GameStateResponse {
+        id_game,
+        tiles: Vec::from(game.board().grid_h().tiles()),
+        rack: game.player(player_num).rack().tiles().collect(),
+        scores,
+        next: game.to_play().map(api_player),
+        letter_bag_len: game.letter_bag_len(),
+        deadline: game
+            .to_play()
+            .map(|_| DateTime::from_utc(correspondence_game.deadline, Utc)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::games_link;
+
+    #[test]
+    fn games_link_format() {
+        assert_eq!(games_link("thrgd.uk"), "https://thrgd.uk/games");
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{db::Db, fsm::FsmHandle};
+use api::{auth::AuthWrapper, routes::health::ReadyResponse};
+use warp::{hyper::StatusCode, Rejection, Reply};
+
+/// GET /api/health
+pub async fn health() -> Result<impl Reply, Rejection> {
+    Ok(StatusCode::OK)
+}
+
+/// GET /api/ready
+pub async fn ready(db: Db, fsm: FsmHandle) -> Result<impl Reply, Rejection> {
+    let database = match sqlx::query_file!("sql/health/ping.sql")
+        .fetch_one(&db)
+        .await
+    {
+        Ok(_) => true,
+        Err(e) => {
+            log::warn!("readiness check failed to query the database: {e:?}");
+            false
+        }
+    };
+    let response = ReadyResponse {
+        database,
+        word_list: fsm.is_loaded(),
+    };
+
+    // a load balancer only checks the status code.
+    let status = match response.is_ready() {
+        true => StatusCode::OK,
+        false => StatusCode::SERVICE_UNAVAILABLE,
+    };
+
+    Ok(warp::reply::with_status(
+        warp::reply::json(&AuthWrapper {
+            token: None,
+            response,
+        }),
+        status,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{db, filters, fsm::FsmHandle};
+    use api::{auth::AuthWrapper, routes::health::ReadyResponse};
+    use sqlx::postgres::PgPoolOptions;
+    use std::time::Duration;
+    use warp::{hyper::StatusCode, test::request};
+
+    #[tokio::test]
+    async fn health() {
+        let db = db::connect().await.unwrap();
+        let filter = filters::health::all(&db, &FsmHandle::bundled());
+
+        let response = request().path("/health").reply(&filter).await;
+        assert_eq!(StatusCode::OK, response.status());
+    }
+
+    #[tokio::test]
+    async fn ready() {
+        let db = db::connect().await.unwrap();
+        let filter = filters::health::all(&db, &FsmHandle::bundled());
+
+        let response = request().path("/ready").reply(&filter).await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let body: AuthWrapper<ReadyResponse> = serde_json::from_slice(response.body()).unwrap();
+        assert!(body.response.database);
+        assert!(body.response.word_list);
+    }
+
+    #[tokio::test]
+    async fn ready_without_database() {
+        // nothing listens on port 1, so connecting fails.
+        let db = PgPoolOptions::new()
+            .connect_timeout(Duration::from_secs(1))
+            .connect_lazy("postgres://postgres@localhost:1/scrabble")
+            .unwrap();
+        let filter = filters::health::all(&db, &FsmHandle::bundled());
+
+        let response = request().path("/ready").reply(&filter).await;
+        assert_eq!(StatusCode::SERVICE_UNAVAILABLE, response.status());
+
+        let body: AuthWrapper<ReadyResponse> = serde_json::from_slice(response.body()).unwrap();
+        assert!(!body.response.database);
+        assert!(body.response.word_list);
+
+        // the server is still alive.
+        let response = request().path("/health").reply(&filter).await;
+        assert_eq!(StatusCode::OK, response.status());
+    }
+}
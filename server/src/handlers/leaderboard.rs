@@ -1,8 +1,11 @@
 use crate::{auth::Jwt, db::Db, error::Error, filters::leaderboard::LeaderboardQuery};
 use api::{
     auth::AuthWrapper,
-    routes::leaderboard::{LeaderboardResponse, LeaderboardRow},
+    routes::leaderboard::{
+        LeaderboardResponse, LeaderboardRow, PuzzleLeaderboardResponse, PuzzleLeaderboardRow,
+    },
 };
+use chrono::Utc;
 use warp::{Rejection, Reply};
 
 /// GET /api/leaderboard
@@ -61,3 +64,81 @@ pub async fn friends_leaderboard(db: Db, jwt: Jwt) -> Result<impl Reply, Rejecti
         response: LeaderboardResponse { rows },
     }))
 }
+
+/// GET /api/leaderboard/puzzles
+pub async fn puzzle_leaderboard(db: Db, query: LeaderboardQuery) -> Result<impl Reply, Rejection> {
+    let limit = query.limit.unwrap_or(20).clamp(10, 50) as i64;
+    let offset = query.offset.unwrap_or(0) as i64;
+    let today = Utc::today().naive_utc();
+
+    let rows = sqlx::query_file!("sql/leaderboard/puzzles.sql", limit, offset, today)
+        .fetch_all(&db)
+        .await
+        .map_err(Error::Sqlx)?
+        .into_iter()
+        .map(|row| PuzzleLeaderboardRow {
+            username: row.username,
+            puzzle_count: row.puzzle_count.unwrap_or(0) as usize,
+            avg_percentage: row.avg_percentage.unwrap_or(0.0),
+            current_streak: row.current_streak.unwrap_or(0) as usize,
+            best_streak: row.best_streak.unwrap_or(0) as usize,
+        })
+        .collect::<Vec<_>>();
+
+    Ok(warp::reply::json(&AuthWrapper {
+        token: None,
+        response: PuzzleLeaderboardResponse { rows },
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        db,
+        models::{PuzzleAttempt, User},
+    };
+    use chrono::{Duration, Utc};
+
+    #[tokio::test]
+    async fn puzzle_streaks() {
+        let db = db::connect().await.unwrap();
+        let user = User::insert_for_test(&db, "streak").await;
+        let id_user = user.id_user;
+
+        // a streak of 3 up to yesterday (25%, 50% and 75%), and an older
+        // streak of 4 (100%).
+        let today = Utc::today().naive_utc();
+        for (days_ago, score) in [
+            (1, 10),
+            (2, 20),
+            (3, 30),
+            (6, 40),
+            (7, 40),
+            (8, 40),
+            (9, 40),
+        ] {
+            let attempt = PuzzleAttempt {
+                id_user,
+                puzzle_date: today - Duration::days(days_ago),
+                score,
+                best_score: 40,
+            };
+            assert!(attempt.insert(&db).await.unwrap());
+        }
+
+        let row = sqlx::query_file!("sql/leaderboard/puzzles.sql", i64::MAX, 0, today)
+            .fetch_all(&db)
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|row| row.username == user.username)
+            .unwrap();
+        assert_eq!(Some(7), row.puzzle_count);
+        assert_eq!(Some(3), row.current_streak);
+        assert_eq!(Some(4), row.best_streak);
+        let avg_percentage = row.avg_percentage.unwrap();
+        assert!((avg_percentage - 550.0 / 7.0).abs() < 0.01);
+
+        user.delete(&db).await.unwrap();
+    }
+}
@@ -1,34 +1,35 @@
-use crate::{
-    db::Db,
-    fsm::FsmHandle,
-    models::{self, AiDifficulty},
+use crate::{db::Db, fsm::FsmHandle, models};
+use api::routes::{
+    admin::{EventSource, GameEvent, GameEventsResponse, MAX_GAME_EVENTS},
+    live::{
+        board_hash, ClientMsg, FinalScore, LiveError, Player, SequencedMsg, ServerMsg,
+        CHALLENGE_WINDOW, REPLAY_LIMIT,
+    },
 };
-use api::routes::live::{ClientMsg, LiveError, Player, ServerMsg};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use scrabble::{
-    ai::Ai,
+    ai::{Ai, AiDifficulty, Vocabulary},
     error::GameError,
-    game::{play::Play, tile::Tile, GameOver, GameStatus, PlayerNum},
-    util::{bitboard::BitBoard, fsm::FastFsm, scoring, words::WordsExt},
+    game::{
+        alphabet::Alphabet,
+        board::Board,
+        challenge::{ChallengeOutcome, ChallengeRule},
+        letter_bag::{BagSeed, LetterBag},
+        play::Play,
+        tile::Tile,
+        GameConfig, GameOver, GameOverReason, GameStatus, PlayerNum, PASS_LIMIT,
+    },
+    util::{bitboard::BitBoard, fsm::FastFsm, pos::Pos, scoring, words::WordsExt},
 };
 use std::{
-    collections::{hash_map::Entry, HashMap},
-    env,
+    collections::{hash_map::Entry, HashMap, VecDeque},
+    fmt::Debug,
     ops::Deref,
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tokio::sync::{mpsc, Mutex};
 
-lazy_static::lazy_static! {
-    static ref USER_TIMEOUT: Duration = {
-        let seconds = env::var("USER_TIMEOUT").expect("`USER_TIMEOUT` env var");
-        let seconds = seconds.parse().expect("`USER_TIMEOUT` should be an integer");
-
-        Duration::from_secs(seconds)
-    };
-}
-
 /// A thread-safe handle to a game.
 #[derive(Clone, Debug)]
 pub struct GameHandle(Arc<Mutex<Game>>);
@@ -46,24 +47,20 @@ impl GameHandle {
         db: Db,
         fsm: FsmHandle,
         ai_count: usize,
-        ai_difficulty: api::routes::live::AiDifficulty,
+        difficulty: AiDifficulty,
         player_count: usize,
         id_owner: Option<i32>,
+        move_timeout: Duration,
     ) -> Option<(i32, GameHandle)> {
-        // convert the API type to a type that the server can use.
-        let difficulty = match ai_difficulty {
-            api::routes::live::AiDifficulty::Easy => AiDifficulty::Easy,
-            api::routes::live::AiDifficulty::Medium => AiDifficulty::Medium,
-            api::routes::live::AiDifficulty::Hard => AiDifficulty::Hard,
-        };
-
         let total_count = ai_count + player_count;
         // create a queue that allows connected clients to send messages
         // to the game (multiple producers) and the game to receive the
         // message (single consumer).
         let (sender, mut receiver) = mpsc::unbounded_channel::<GameMsg>();
         // create a database record for the game.
-        let id_game = models::Game::insert(&db).await.ok()?;
+        let id_game = models::Game::insert(&mut *db.acquire().await.ok()?)
+            .await
+            .ok()?;
         // create database records for the ai players.
         let mut slots = HashMap::default();
         // add the ai players at the end of the slots, to allow the human players to go first.
@@ -88,15 +85,30 @@ impl GameHandle {
             );
         }
 
+        // public games count towards the leaderboard, so an ai must not
+        // play for a user that has disconnected.
+        let disconnect_policy = match id_owner {
+            Some(_) => DisconnectPolicy::Ai,
+            None => DisconnectPolicy::Pass,
+        };
+
         // create the game.
         let game = Game {
             game: scrabble::game::Game::new(total_count),
-            play_count: 0,
             slots,
             db,
             fsm,
             id_game,
             id_owner,
+            move_timeout,
+            deadline: None,
+            challenge_deadline: None,
+            open_hands: false,
+            ai_vocabulary: None,
+            seed: None,
+            disconnect_policy,
+            last_activity: Instant::now(),
+            events: std::sync::Mutex::default(),
             sender,
         };
         let game_handle = GameHandle(Arc::new(Mutex::new(game)));
@@ -116,6 +128,15 @@ impl GameHandle {
                             let mut game_lock = game_handle.lock().await;
                             game_lock.on_msg(id_user, msg, game_handle.clone()).await;
                         }
+                        GameMsg::Closed {
+                            id_user,
+                            connection,
+                        } => {
+                            let mut game_lock = game_handle.lock().await;
+                            game_lock
+                                .on_closed(id_user, connection, game_handle.clone())
+                                .await;
+                        }
                     }
                 }
 
@@ -131,7 +152,6 @@ impl GameHandle {
 #[derive(Debug)]
 pub struct Game {
     game: scrabble::game::Game,
-    play_count: usize,
     slots: HashMap<PlayerNum, Slot>,
 
     db: Db,
@@ -139,6 +159,22 @@ pub struct Game {
 
     id_game: i32,
     id_owner: Option<i32>,
+    move_timeout: Duration,
+    deadline: Option<DateTime<Utc>>,
+    /// The time until which the most recent placement can be challenged.
+    challenge_deadline: Option<DateTime<Utc>>,
+    open_hands: bool,
+    /// The words that the AI players can play, if they are not chosen by
+    /// the difficulty of each player.
+    ai_vocabulary: Option<Vocabulary>,
+    /// The seed of the letter bag, which is committed to when the game
+    /// starts and revealed when it ends, so players can verify the draws.
+    seed: Option<BagSeed>,
+    disconnect_policy: DisconnectPolicy,
+    /// The time that a user last joined or sent a message to the game.
+    last_activity: Instant,
+    /// The messages received and broadcast by the game, for admins.
+    events: std::sync::Mutex<EventLog>,
 
     sender: mpsc::UnboundedSender<GameMsg>,
 }
@@ -148,6 +184,17 @@ impl Game {
         self.slots.values().all(Slot::is_empty)
     }
 
+    /// Checks whether the game has finished.
+    pub fn is_over(&self) -> bool {
+        self.game.status().is_over()
+    }
+
+    /// Checks whether no user has joined or sent a message to the game
+    /// for at least `timeout`.
+    pub fn is_inactive(&self, timeout: Duration) -> bool {
+        self.last_activity.elapsed() >= timeout
+    }
+
     /// Gets the game id.
     pub fn id_game(&self) -> i32 {
         self.id_game
@@ -156,11 +203,62 @@ impl Game {
     pub fn sender(&self) -> mpsc::UnboundedSender<GameMsg> {
         self.sender.clone()
     }
+    /// Gets the most recent messages received and broadcast by the game.
+    pub fn events(&self) -> GameEventsResponse {
+        self.events.lock().unwrap().response()
+    }
+    /// Sets whether all players can see each other's racks.
+    pub fn set_open_hands(&mut self, open_hands: bool) {
+        self.open_hands = open_hands;
+    }
+    /// Sets the words that the AI players can play, or `None` for the
+    /// words to be chosen by the difficulty of each player. Plays are
+    /// always validated against the full word list.
+    pub fn set_ai_vocabulary(&mut self, ai_vocabulary: Option<Vocabulary>) {
+        self.ai_vocabulary = ai_vocabulary;
+    }
+    /// Sets whether words are checked when played or when challenged, and
+    /// whether tiles can be exchanged. The racks are redrawn, so this must
+    /// be called before anyone joins.
+    pub fn set_rules(&mut self, challenge: ChallengeRule, allow_exchange: bool) {
+        let config = GameConfig {
+            challenge,
+            allow_exchange,
+            ..GameConfig::default()
+        };
+
+        self.game =
+            scrabble::game::Game::with_config(self.game.player_count(), self.letter_bag(), config);
+    }
+    /// Sets whether the letter bag is seeded with a random seed that is
+    /// revealed at the end of the game. The racks are redrawn, so this must
+    /// be called before anyone joins.
+    pub fn set_verifiable_bag(&mut self, verifiable_bag: bool) {
+        self.seed = verifiable_bag.then(BagSeed::random);
+
+        self.game = scrabble::game::Game::with_config(
+            self.game.player_count(),
+            self.letter_bag(),
+            self.game.config().clone(),
+        );
+    }
+    /// Creates a full letter bag, using the seed if there is one.
+    fn letter_bag(&self) -> LetterBag {
+        match self.seed {
+            Some(bag_seed) => LetterBag::default().with_seed(bag_seed.seed),
+            None => LetterBag::default(),
+        }
+    }
 
     /// Attempts to add a player to the game. Return value indicates
     /// success.
-    pub async fn add_player(&mut self, id_user: i32, tx: mpsc::UnboundedSender<ServerMsg>) -> bool {
+    pub async fn add_player(
+        &mut self,
+        id_user: i32,
+        tx: mpsc::UnboundedSender<SequencedMsg>,
+    ) -> bool {
         let id_game = self.id_game();
+        self.last_activity = Instant::now();
 
         // first check whether the user is in the game but
         // has disconnected.
@@ -192,7 +290,13 @@ impl Game {
                 // add a player record in the database if the user is
                 // a friend of `self.id_owner` (or `self.id_owner` is
                 // None).
-                match models::Player::insert_user(&self.db, id_game, id_user, id_owner).await {
+                let inserted = match self.db.acquire().await {
+                    Ok(mut conn) => {
+                        models::Player::insert_user(&mut conn, id_game, id_user, id_owner).await
+                    }
+                    Err(e) => Err(e.into()),
+                };
+                match inserted {
                     Ok((id_player, username)) => {
                         // Insert the player.
                         e.insert(Slot {
@@ -201,6 +305,8 @@ impl Game {
                                 id_user,
                                 username,
                                 sender: Some(tx),
+                                connection: 0,
+                                outbox: std::sync::Mutex::default(),
                             },
                         });
 
@@ -220,21 +326,58 @@ impl Game {
 
         false
     }
+    /// Reconnects a user whose connection dropped, resending the messages
+    /// after `last_seq` (or the full state if too many were missed). Returns
+    /// false if the user is not in the game.
+    pub fn rejoin(
+        &mut self,
+        id_user: i32,
+        tx: mpsc::UnboundedSender<SequencedMsg>,
+        last_seq: u64,
+    ) -> bool {
+        self.last_activity = Instant::now();
+
+        let player_num = match self.id_user_to_player_num(id_user) {
+            Some(player_num) => player_num,
+            None => return false,
+        };
+        let slot = self.slots.get_mut(&player_num).unwrap();
+        slot.set_sender(tx);
+
+        if !slot.resend_since(last_seq) {
+            log::info!("too many messages missed, resyncing: {id_user}");
+            self.send_full_state(player_num);
+        }
+
+        // notify the players.
+        let player = self.slots[&player_num].player();
+        self.send_all(ServerMsg::Players(self.api_scores()));
+        self.send_all(ServerMsg::UserConnected(player));
+
+        true
+    }
     /// Sends a join message to the player that joined and notifies
     /// all other players in the game.
     fn send_join_msg(&self, player_num: PlayerNum) {
         // send a join game message.
         let slot = &self.slots[&player_num];
-        slot.send_msg(ServerMsg::Joined {
-            id_game: self.id_game(),
-            id_player: slot.id_player(),
-            capacity: self.game.player_count(),
-            tiles: self.api_tiles(),
-            rack: self.api_rack(player_num),
-            scores: self.api_scores(),
-            next: self.api_next(),
-            letter_bag_len: self.game.letter_bag_len(),
-        });
+        self.send_to(
+            player_num,
+            ServerMsg::Joined {
+                id_game: self.id_game(),
+                id_player: slot.id_player(),
+                capacity: self.game.player_count(),
+                tiles: self.api_tiles(),
+                rack: self.api_rack(player_num),
+                scores: self.api_scores(),
+                next: self.api_next(),
+                letter_bag_len: self.game.letter_bag_len(),
+                move_timeout: self.move_timeout.as_secs(),
+                deadline: self.deadline,
+                challenge_rule: self.game.config().challenge,
+                allow_exchange: self.game.config().allow_exchange,
+            },
+        );
 
         // send a message to update the players.
         self.send_all(ServerMsg::Players(self.api_scores()));
@@ -242,23 +385,42 @@ impl Game {
 
         // check whether there are enough players to start and
         // no plays have been made yet.
-        if self.play_count == 0 && self.is_full() {
-            self.send_all(ServerMsg::Starting);
+        if self.game.play_count() == 0 && self.is_full() {
+            self.send_all(ServerMsg::Starting {
+                seed_commitment: self.seed.as_ref().map(BagSeed::commitment),
+            });
         }
+
+        self.send_all_racks();
     }
 
     /// Called when a message is received from a user.
     async fn on_msg(&mut self, id_user: i32, msg: ClientMsg, game_handle: GameHandle) {
+        self.record(EventSource::Client(id_user), &msg);
+        self.last_activity = Instant::now();
+
         match msg {
             ClientMsg::Disconnect => self.on_disconnect(id_user).await,
             ClientMsg::Chat(chat) => self.on_chat(id_user, chat),
+            ClientMsg::Resync => self.on_resync(id_user),
             // Require the game to be full before a play can be made.
             ClientMsg::Play(play) if self.is_full() => {
                 self.on_play(id_user, play, game_handle).await
             }
+            ClientMsg::Challenge if self.is_full() => self.on_challenge(id_user, game_handle).await,
             _ => log::error!("unexpected message: {msg:?}"),
         }
     }
+    /// Called when the connection of a user closes. The user is only
+    /// disconnected if they have not since rejoined on a new connection.
+    async fn on_closed(&mut self, id_user: i32, connection: u64, game_handle: GameHandle) {
+        if self.connection(id_user) == Some(connection) {
+            self.on_msg(id_user, ClientMsg::Disconnect, game_handle)
+                .await;
+        } else {
+            log::debug!("ignoring closed connection: {id_user}");
+        }
+    }
     /// Called when a chat message is received.
     fn on_chat(&self, id_user: i32, chat: String) {
         if let Some(player_num) = self.id_user_to_player_num(id_user) {
@@ -268,6 +430,16 @@ impl Game {
             self.send_all(ServerMsg::Chat(player, chat));
         }
     }
+    /// Called when a resync message is received, sends the full game
+    /// state to the user.
+    fn on_resync(&self, id_user: i32) {
+        if let Some(player_num) = self.id_user_to_player_num(id_user) {
+            log::warn!("resyncing user: {id_user}");
+
+            self.send_full_state(player_num);
+            self.send_all_racks();
+        }
+    }
     /// Called when a disconnect message is received.
     async fn on_disconnect(&mut self, id_user: i32) {
         if let Some(player_num) = self.id_user_to_player_num(id_user) {
@@ -277,30 +449,38 @@ impl Game {
 
             // disconnect the user.
             slot.disconnect();
+            let was_over = self.is_over();
 
             // send a message containing the new players.
             self.send_all(ServerMsg::Players(self.api_scores()));
             self.send_all(ServerMsg::UserDisconnected(player));
 
-            // make ai plays for the user.
+            // make ai plays (or passes) for the user.
             self.make_ai_plays().await;
+
+            // the user may have passed until the game ended.
+            if let (false, GameStatus::Over(game_over)) = (was_over, self.game.status()) {
+                self.on_game_over(game_over).await;
+            }
         }
     }
     /// Called when a play message is received.
     async fn on_play(&mut self, id_user: i32, play: Play, game_handle: GameHandle) {
         let to_play = self.game.to_play();
         let player_num = self.id_user_to_player_num(id_user).unwrap();
-        let slot = &self.slots[&player_num];
 
         // check whether the game is over.
         if to_play.is_none() {
-            slot.send_msg(ServerMsg::Error(LiveError::Play(GameError::Over)));
+            self.send_to(
+                player_num,
+                ServerMsg::Error(LiveError::Play(GameError::Over)),
+            );
             return;
         }
 
         // check whether it is the player's turn.
         if to_play != Some(player_num) {
-            slot.send_msg(ServerMsg::Error(LiveError::NotYourTurn));
+            self.send_to(player_num, ServerMsg::Error(LiveError::NotYourTurn));
             return;
         }
 
@@ -319,50 +499,163 @@ impl Game {
             }
         }
     }
-    /// Handles database updates for the end of the game.
-    async fn on_game_over(&self, game_over: &GameOver) {
-        // set the `is_over` column to true and the `end_time` column
-        // to the current time on `tbl_game`.
-        let end_time = Utc::now().naive_utc();
-        sqlx::query_file!("sql/live/set_game_over.sql", self.id_game(), end_time)
-            .execute(&self.db)
-            .await
-            .unwrap();
+    /// Called when a challenge message is received. Only the player to play
+    /// can challenge the most recent placement, within [`CHALLENGE_WINDOW`].
+    async fn on_challenge(&mut self, id_user: i32, game_handle: GameHandle) {
+        let player_num = self.id_user_to_player_num(id_user).unwrap();
 
-        // set `is_winner` to true for all winners.
-        for (player_num, _) in game_over.winners() {
-            let id_player = self.slots[&player_num].id_player();
-            sqlx::query_file!("sql/live/set_winner.sql", id_player)
-                .execute(&self.db)
-                .await
-                .unwrap();
+        // check whether it is the player's turn.
+        if self.game.to_play() != Some(player_num) {
+            self.send_to(player_num, ServerMsg::Error(LiveError::NotYourTurn));
+            return;
         }
 
-        // set `is_winner` to false for all losers.
-        for (player_num, _) in game_over.losers() {
-            let id_player = self.slots[&player_num].id_player();
-            sqlx::query_file!("sql/live/set_loser.sql", id_player)
-                .execute(&self.db)
-                .await
-                .unwrap();
+        // check whether the placement can still be challenged.
+        if self.challenge_deadline.is_none_or(|d| Utc::now() > d) {
+            self.send_to(player_num, ServerMsg::Error(LiveError::ChallengeExpired));
+            return;
         }
 
-        self.send_all(ServerMsg::Over(game_over.reason()));
+        let outcome = match self.apply_challenge(player_num).await {
+            Some(outcome) => outcome,
+            None => return,
+        };
+
+        // the turn only moves on if the challenger lost their turn, otherwise
+        // the challenger's move timer is still running.
+        if outcome.lost_turn {
+            self.make_ai_plays().await;
+        }
+        match self.game.status() {
+            &GameStatus::ToPlay(to_play) if outcome.lost_turn => {
+                self.start_timer(to_play, game_handle)
+            }
+            GameStatus::Over(game_over) => self.on_game_over(game_over).await,
+            GameStatus::ToPlay(_) => (),
+        }
     }
-    /// Continues to make plays for Ai players until a connected
-    /// player is encountered or the game ends.
+    /// Challenges the most recent placement for `player_num`, then updates
+    /// the database and sends the result to all players. Returns `None` if
+    /// the placement can not be challenged.
+    async fn apply_challenge(&mut self, player_num: PlayerNum) -> Option<ChallengeOutcome> {
+        let fsm: &FastFsm = &self.fsm;
+        let outcome = match self.game.challenge(fsm) {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                self.send_to(player_num, ServerMsg::Error(LiveError::Play(e)));
+                return None;
+            }
+        };
+        self.challenge_deadline = None;
+
+        // a withdrawn placement is replaced by a pass, and a lost turn is
+        // recorded as a pass for the challenger.
+        if outcome.is_successful() {
+            let id_player = self.slots[&outcome.player_num].id_player;
+            if let Err(e) = models::Play::delete_last(&self.db, id_player).await {
+                log::error!("failed to delete challenged play: {e:?}");
+            }
+            self.insert_play(&Play::Pass, outcome.player_num).await;
+        } else if outcome.lost_turn {
+            self.insert_play(&Play::Pass, player_num).await;
+            self.deadline = self.next_deadline();
+        }
+
+        self.send_all(ServerMsg::ChallengeResult {
+            challenger: self.api_player(player_num).unwrap(),
+            player: self.api_player(outcome.player_num).unwrap(),
+            invalid_words: outcome.invalid_words.clone(),
+            lost_turn: outcome.lost_turn,
+        });
+        for &player_num in self.slots.keys() {
+            self.send_full_state(player_num);
+        }
+        self.send_all_racks();
+
+        Some(outcome)
+    }
+    /// Ends a game that the players have stopped playing, recording it
+    /// as abandoned.
+    pub async fn abandon(&mut self) {
+        if self.game.abandon().is_ok() {
+            self.deadline = None;
+            self.challenge_deadline = None;
+
+            if let GameStatus::Over(game_over) = self.game.status() {
+                self.on_game_over(game_over).await;
+            }
+        }
+    }
+    /// Handles database updates for the end of the game.
+    async fn on_game_over(&self, game_over: &GameOver) {
+        set_game_over(&self.db, self.id_game(), game_over, |player_num| {
+            self.slots[&player_num].id_player()
+        })
+        .await;
+
+        self.send_all(ServerMsg::Over {
+            reason: game_over.reason(),
+            final_scores: self.api_final_scores(game_over),
+            seed: self.seed,
+        });
+    }
+    /// Continues to make plays for Ai players (and disconnected users)
+    /// until a connected player is encountered or the game ends.
     async fn make_ai_plays(&mut self) {
         // loop until game is over.
         while let Some(to_play) = self.game.to_play() {
-            if let Some(ai) = &self.slots.get(&to_play).and_then(Slot::ai) {
+            let slot = self.slots.get(&to_play);
+
+            if self.disconnect_policy == DisconnectPolicy::Pass
+                && slot.is_some_and(Slot::is_disconnected)
+            {
+                // the turn counts as a pass, so a user that stays disconnected
+                // ends the game once they reach the pass limit.
+                if !self.try_play(Play::Pass, to_play).await {
+                    log::error!("failed to pass for disconnected user");
+                    break;
+                }
+
+                log::trace!("passed for disconnected user");
+            } else if let Some(ai) = slot.and_then(Slot::ai) {
+                // an ai challenges the most recent placement if it formed
+                // an invalid word, before it can be accepted by a play.
                 let fsm: &FastFsm = &self.fsm;
+                if !self.game.invalid_words(fsm).is_empty() {
+                    log::trace!("ai challenging invalid placement");
+
+                    if self.apply_challenge(to_play).await.is_none() {
+                        break;
+                    }
+                    continue;
+                }
+
+                let ai = match self.ai_vocabulary {
+                    Some(vocabulary) => ai.with_vocabulary(vocabulary),
+                    None => ai,
+                };
+                // generate plays from the words that the ai knows, and validate
+                // them against the full word list in `try_play`.
+                let fsm = self.fsm.for_ai(&ai);
                 log::trace!("finding next play");
-                let play = ai.next_play(fsm, &self.game);
-                let is_success = self.try_play(play, to_play).await;
+                let play = match ai.next_play(fsm, &self.game) {
+                    Ok(play) => play,
+                    Err(e) => {
+                        log::error!("failed to find ai play: {e:?}");
+                        break;
+                    }
+                };
+                // the ai should only choose valid plays, but if it does not
+                // it passes instead of stopping the game.
+                if !self.try_play(play, to_play).await {
+                    log::error!("invalid ai play, passing instead");
 
-                log::trace!("made ai play");
+                    if !self.try_play(Play::Pass, to_play).await {
+                        break;
+                    }
+                }
 
-                assert!(is_success, "Ai move should always be valid");
+                log::trace!("made ai play");
             } else {
                 // No more ai plays need to be made.
                 break;
@@ -373,6 +666,12 @@ impl Game {
     async fn try_play(&mut self, play: Play, player_num: PlayerNum) -> bool {
         log::info!("attempting to make play: {play:?}");
 
+        // a play accepts a placement that ended the game, and is not made.
+        if self.game.is_ending() {
+            self.challenge_deadline = None;
+            return self.game.accept_placement().is_ok();
+        }
+
         // store the current tile positions.
         let prev_tiles = self.api_tiles();
         let fsm: &FastFsm = &self.fsm;
@@ -380,22 +679,31 @@ impl Game {
             Ok(()) => {
                 log::trace!("legal play");
 
-                self.play_count += 1;
+                self.deadline = self.next_deadline();
+                self.challenge_deadline = match self.game.can_challenge() {
+                    true => Some(Utc::now() + chrono::Duration::seconds(CHALLENGE_WINDOW as i64)),
+                    false => None,
+                };
 
                 // add the play to the database.
                 self.insert_play(&play, player_num).await;
 
                 // send a rack message.
-                self.slots[&player_num].send_msg(ServerMsg::Rack(self.api_rack(player_num)));
+                self.send_to(player_num, ServerMsg::Rack(self.api_rack(player_num)));
                 // send a play message to all players.
                 self.send_all(ServerMsg::Play {
                     player: self.api_player(player_num).unwrap(),
                     prev_tiles,
-                    play,
+                    play: hide_redraw(play),
                     letter_bag_len: self.game.letter_bag_len(),
                     next: self.api_next(),
                     scores: self.api_scores(),
+                    pass_count: self.game.player(player_num).pass_count(),
+                    pass_limit: PASS_LIMIT,
+                    deadline: self.deadline,
+                    board_hash: board_hash(&self.api_tiles()),
                 });
+                self.send_all_racks();
 
                 true
             }
@@ -403,75 +711,41 @@ impl Game {
                 log::debug!("illegal play");
 
                 // send a message for an illegal play.
-                self.slots[&player_num].send_msg(ServerMsg::Error(LiveError::Play(e)));
+                self.send_to(player_num, ServerMsg::Error(LiveError::Play(e)));
                 false
             }
         }
     }
     /// Adds a play to the database.
     async fn insert_play(&mut self, play: &Play, player_num: PlayerNum) {
-        let slot = &self.slots[&player_num];
-        let id_player = slot.id_player;
-
-        // insert a play record.
-        log::trace!("inserting play");
-        let id_play = models::Play::insert(&self.db, id_player).await.unwrap();
-
-        // insert records for each of the placed tiles.
-        let mut new_h = BitBoard::default();
-        let mut new_v = BitBoard::default();
-        if let Play::Place(tile_positions) = play {
-            for (pos, tile) in tile_positions {
-                models::Tile::insert(&self.db, id_play, pos, tile)
-                    .await
-                    .unwrap();
-
-                new_h.set(*pos);
-                new_v.set(pos.swap_rc());
+        let id_player = self.slots[&player_num].id_player;
+
+        insert_play(
+            &self.db,
+            id_player,
+            play,
+            self.game.board(),
+            &self.game.config().alphabet,
+        )
+        .await;
+    }
+    /// Finds the time by which the next player must make a play. Only
+    /// connected users are timed.
+    fn next_deadline(&self) -> Option<DateTime<Utc>> {
+        let to_play = self.game.to_play()?;
+
+        match self.slots.get(&to_play)?.ai() {
+            Some(_) => None,
+            None => {
+                let move_timeout = chrono::Duration::from_std(self.move_timeout).ok()?;
+                Some(Utc::now() + move_timeout)
             }
         }
-
-        // insert records for each of the vertical and horizontal words.
-        for (letters, score) in self.words(new_h, new_v) {
-            models::Word::insert(&self.db, id_play, letters, score)
-                .await
-                .unwrap();
-        }
-    }
-
-    /// Iterates over the new (word, score) tuples on the board.
-    fn words(
-        &self,
-        new_h: BitBoard,
-        new_v: BitBoard,
-    ) -> impl Iterator<Item = (String, usize)> + '_ {
-        let board = self.game.board();
-        let horizontal = board
-            .grid_h()
-            .word_boundaries()
-            .intersecting(new_h)
-            .words(board.grid_h())
-            .map(move |word| {
-                let word_str = word.to_string();
-                let score = scoring::score_unchecked(word, &new_h);
-                (word_str, score)
-            });
-        let vertical = board
-            .grid_v()
-            .word_boundaries()
-            .intersecting(new_v)
-            .words(board.grid_v())
-            .map(move |word| {
-                let word_str = word.to_string();
-                let score = scoring::score_unchecked(word, &new_v);
-                (word_str, score)
-            });
-
-        horizontal.chain(vertical)
     }
     /// Starts a move timer for the specified player.
     fn start_timer(&self, player_num: PlayerNum, game_handle: GameHandle) {
-        let curr_count = self.play_count;
+        let curr_count = self.game.play_count();
+        let move_timeout = self.move_timeout;
         let slot = &self.slots[&player_num];
 
         // only start a timer if the slot is occupied by a player.
@@ -479,12 +753,13 @@ impl Game {
             let id_user = slot.id_user().unwrap();
 
             tokio::spawn(async move {
-                // wait `USER_TIMEOUT` seconds for the next player to make a play.
-                tokio::time::sleep(*USER_TIMEOUT).await;
+                // wait `move_timeout` for the next player to make a play.
+                tokio::time::sleep(move_timeout).await;
 
                 let mut game = game_handle.lock().await;
-                // if the play count has not advanced, disconnect the user.
-                if game.play_count == curr_count {
+                // if the play count has not advanced (and the game has not
+                // been abandoned), disconnect the user.
+                if !game.is_over() && game.game.play_count() == curr_count {
                     // send a timeout message to all users.
                     let player = game
                         .id_user_to_player_num(id_user)
@@ -499,14 +774,68 @@ impl Game {
         }
     }
 
-    /// Sends a message to all users.
+    /// Sends the full game state to a player.
+    fn send_full_state(&self, player_num: PlayerNum) {
+        self.send_to(
+            player_num,
+            ServerMsg::FullState {
+                tiles: self.api_tiles(),
+                rack: self.api_rack(player_num),
+                scores: self.api_scores(),
+                next: self.api_next(),
+                letter_bag_len: self.game.letter_bag_len(),
+                deadline: self.deadline,
+                players: self.api_players(),
+                last_play: self.api_last_play(),
+                can_challenge: self.can_challenge(player_num),
+            },
+        );
+    }
+    /// Sends a message to all users. Messages containing a rack must be
+    /// sent to the owning player with [`Game::send_to`] instead.
     fn send_all(&self, msg: ServerMsg) {
+        debug_assert!(!msg.contains_rack(), "rack broadcast to all: {msg:?}");
+
         log::trace!("sending message to all: {msg:?}");
+        self.record(EventSource::Server, &msg);
         for slot in self.slots.values() {
             slot.send_msg(msg.clone());
         }
     }
 
+    /// Sends a message to a single player, recording it in the event log
+    /// if the slot belongs to a user.
+    fn send_to(&self, player_num: PlayerNum, msg: ServerMsg) {
+        let slot = &self.slots[&player_num];
+        if let Some(id_user) = slot.id_user() {
+            self.record(EventSource::ToClient(id_user), &msg);
+        }
+        slot.send_msg(msg);
+    }
+    /// Appends a message to the event log of the game.
+    fn record(&self, source: EventSource, msg: &impl Debug) {
+        self.events.lock().unwrap().push(source, format!("{msg:?}"));
+    }
+
+    /// Sends the racks of every player to all players, if the game
+    /// has open hands.
+    fn send_all_racks(&self) {
+        if !self.open_hands {
+            return;
+        }
+
+        let racks: HashMap<_, _> = self
+            .slots
+            .iter()
+            .map(|(&player_num, slot)| (slot.player(), self.api_rack(player_num)))
+            .collect();
+
+        log::trace!("sending all racks");
+        for &player_num in self.slots.keys() {
+            self.send_to(player_num, ServerMsg::AllRacks(racks.clone()));
+        }
+    }
+
     /// Gets the number of slots.
     fn slot_count(&self) -> usize {
         self.game.player_count()
@@ -527,6 +856,12 @@ impl Game {
             .map(|(player_num, _)| player_num)
             .copied()
     }
+    /// Gets the current connection of a user, which changes each time the
+    /// user joins or rejoins the game.
+    pub fn connection(&self, id_user: i32) -> Option<u64> {
+        let player_num = self.id_user_to_player_num(id_user)?;
+        self.slots[&player_num].connection()
+    }
     /// Gets the score for a player.
     fn score(&self, player_num: PlayerNum) -> usize {
         // If the game is over a bonus may be applied, so check
@@ -536,6 +871,16 @@ impl Game {
             GameStatus::Over(game_over) => game_over.score(player_num),
         }
     }
+    /// Checks whether a player can challenge the most recent placement,
+    /// which must have been made by another player within
+    /// [`CHALLENGE_WINDOW`].
+    fn can_challenge(&self, player_num: PlayerNum) -> bool {
+        self.challenge_deadline.is_some_and(|d| Utc::now() <= d)
+            && self
+                .game
+                .challengeable_placement()
+                .is_some_and(|(placed_by, _)| placed_by != player_num)
+    }
 
     /// Gets the rack tiles API type for a player.
     fn api_rack(&self, player_num: PlayerNum) -> Vec<Tile> {
@@ -551,6 +896,13 @@ impl Game {
     fn api_player(&self, player_num: PlayerNum) -> Option<Player> {
         self.slots.get(&player_num).map(|slot| slot.player())
     }
+    /// Gets the players that have joined, in the order that they play.
+    fn api_players(&self) -> Vec<Player> {
+        self.game
+            .player_nums()
+            .flat_map(|player_num| self.api_player(player_num))
+            .collect()
+    }
     /// Gets a HashMap storing scores for the API.
     fn api_scores(&self) -> HashMap<Player, usize> {
         self.game
@@ -561,10 +913,36 @@ impl Game {
             })
             .collect()
     }
+    /// Gets a HashMap storing the final score breakdowns for the API.
+    fn api_final_scores(&self, game_over: &GameOver) -> HashMap<Player, FinalScore> {
+        self.game
+            .player_nums()
+            .flat_map(|player_num| {
+                self.api_player(player_num).map(|api_player| {
+                    let final_score = FinalScore {
+                        running_score: game_over.running_score(player_num),
+                        penalty: game_over.penalty(player_num),
+                        bonus: game_over.bonus(player_num),
+                        score: game_over.score(player_num),
+                    };
+
+                    (api_player, final_score)
+                })
+            })
+            .collect()
+    }
     /// Gets the board tiles for the API.
     fn api_tiles(&self) -> Vec<Option<Tile>> {
         Vec::from(self.game.board().grid_h().tiles())
     }
+    /// Gets the positions of the most recent placement for the API, if it
+    /// can still be challenged.
+    fn api_last_play(&self) -> Vec<Pos> {
+        self.game
+            .challengeable_placement()
+            .map(|(_, tile_positions)| tile_positions.iter().map(|&(pos, _)| pos).collect())
+            .unwrap_or_default()
+    }
 }
 impl Drop for Game {
     fn drop(&mut self) {
@@ -572,6 +950,130 @@ impl Drop for Game {
     }
 }
 
+/// Adds a play to the database, along with the tiles that were placed and
+/// the words that were formed. `board` should be the board after the play,
+/// and `alphabet` is used to score the words.
+pub async fn insert_play(db: &Db, id_player: i32, play: &Play, board: &Board, alphabet: &Alphabet) {
+    // insert a play record.
+    log::trace!("inserting play");
+    let id_play = models::Play::insert(db, id_player).await.unwrap();
+
+    // insert records for each of the placed tiles.
+    let mut new_h = BitBoard::default();
+    let mut new_v = BitBoard::default();
+    if let Play::Place(tile_positions) = play {
+        for (pos, tile) in tile_positions {
+            models::Tile::insert(db, id_play, pos, tile).await.unwrap();
+
+            new_h.set(*pos);
+            new_v.set(pos.swap_rc());
+        }
+    }
+
+    // insert records for each of the vertical and horizontal words.
+    for (letters, score) in words(board, alphabet, new_h, new_v) {
+        models::Word::insert(db, id_play, letters, score)
+            .await
+            .unwrap();
+    }
+}
+
+/// Hides the tiles of a redraw so that it can be sent to other players,
+/// replacing each tile with a blank so that only the count is known.
+fn hide_redraw(play: Play) -> Play {
+    match play {
+        Play::Redraw(tiles) => Play::Redraw(vec![Tile::blank(); tiles.len()]),
+        play => play,
+    }
+}
+
+/// Iterates over the new (word, score) tuples on the board, scored
+/// using `alphabet`.
+fn words<'a>(
+    board: &'a Board,
+    alphabet: &'a Alphabet,
+    new_h: BitBoard,
+    new_v: BitBoard,
+) -> impl Iterator<Item = (String, usize)> + 'a {
+    let horizontal = board
+        .grid_h()
+        .word_boundaries()
+        .intersecting(new_h)
+        .words(board.grid_h())
+        .map(move |word| {
+            let word_str = word.to_string();
+            let score = scoring::score_unchecked(word, &new_h, alphabet);
+            (word_str, score)
+        });
+    let vertical = board
+        .grid_v()
+        .word_boundaries()
+        .intersecting(new_v)
+        .words(board.grid_v())
+        .map(move |word| {
+            let word_str = word.to_string();
+            let score = scoring::score_unchecked(word, &new_v, alphabet);
+            (word_str, score)
+        });
+
+    horizontal.chain(vertical)
+}
+
+/// Updates the database records for the end of a game. `id_player` maps each
+/// [`PlayerNum`] to the id of its record in `tbl_player`. Abandoned games
+/// are marked as such, and do not count as a win or loss for any player.
+pub async fn set_game_over(
+    db: &Db,
+    id_game: i32,
+    game_over: &GameOver,
+    id_player: impl Fn(PlayerNum) -> i32,
+) {
+    // set the `is_over` column to true and the `end_time` column
+    // to the current time on `tbl_game`.
+    let end_time = Utc::now().naive_utc();
+    let is_abandoned = game_over.reason() == GameOverReason::Abandoned;
+    sqlx::query_file!(
+        "sql/live/set_game_over.sql",
+        id_game,
+        end_time,
+        is_abandoned
+    )
+    .execute(db)
+    .await
+    .unwrap();
+
+    // an abandoned game has no winners or losers.
+    if is_abandoned {
+        return;
+    }
+
+    // set `is_winner` to true for all winners.
+    for (player_num, _) in game_over.winners() {
+        sqlx::query_file!("sql/live/set_winner.sql", id_player(player_num))
+            .execute(db)
+            .await
+            .unwrap();
+    }
+
+    // set `is_winner` to false for all losers.
+    for (player_num, _) in game_over.losers() {
+        sqlx::query_file!("sql/live/set_loser.sql", id_player(player_num))
+            .execute(db)
+            .await
+            .unwrap();
+    }
+}
+
+/// What happens on the turn of a user that has disconnected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DisconnectPolicy {
+    /// An easy Ai plays for the user until they reconnect.
+    Ai,
+    /// The user passes until they reconnect. Used in games that count
+    /// towards the leaderboard, where Ai plays would distort the stats.
+    Pass,
+}
+
 /// A message sent to a game. (ClientMsg + user id).
 #[derive(Debug)]
 pub enum GameMsg {
@@ -579,6 +1081,9 @@ pub enum GameMsg {
     Close,
     /// A message from a user.
     ClientMsg { id_user: i32, msg: ClientMsg },
+    /// A connection of a user closed. `connection` is the value of
+    /// [`Game::connection`] when the user joined on the connection.
+    Closed { id_user: i32, connection: u64 },
 }
 impl GameMsg {
     /// Creates a new [`GameMsg::ClientMsg`].
@@ -616,38 +1121,78 @@ impl Slot {
         }
     }
 
+    /// Checks whether the slot contains a user that has disconnected.
+    pub fn is_disconnected(&self) -> bool {
+        matches!(self.game_player, GamePlayer::User { sender: None, .. })
+    }
+
     /// Gets the the Ai for the player if the player is an Ai or
     /// a disconnected user.
     pub fn ai(&self) -> Option<Ai> {
         match self.game_player {
-            GamePlayer::Ai { difficulty } => Some(match difficulty {
-                AiDifficulty::Easy => Ai::easy(),
-                AiDifficulty::Medium => Ai::medium(),
-                AiDifficulty::Hard => Ai::hard(),
-            }),
+            GamePlayer::Ai { difficulty } => Some(Ai::from(difficulty)),
             GamePlayer::User { sender: None, .. } => Some(Ai::easy()),
             _ => None,
         }
     }
 
-    /// Updates the `sender` of the contained user.
-    pub fn set_sender(&mut self, tx: mpsc::UnboundedSender<ServerMsg>) {
-        if let GamePlayer::User { sender, .. } = &mut self.game_player {
+    /// Updates the `sender` of the contained user, starting a new
+    /// connection.
+    pub fn set_sender(&mut self, tx: mpsc::UnboundedSender<SequencedMsg>) {
+        if let GamePlayer::User {
+            sender, connection, ..
+        } = &mut self.game_player
+        {
             *sender = Some(tx);
+            *connection += 1;
+        }
+    }
+    /// Gets the current connection of the contained user.
+    pub fn connection(&self) -> Option<u64> {
+        match &self.game_player {
+            GamePlayer::User { connection, .. } => Some(*connection),
+            GamePlayer::Ai { .. } => None,
         }
     }
 
-    /// Sends a message to the user if they are connected.
+    /// Sends a message to the user if they are connected. The message is
+    /// stored so that it can be resent if the user rejoins.
     pub fn send_msg(&self, msg: ServerMsg) {
+        if let GamePlayer::User { sender, outbox, .. } = &self.game_player {
+            let msg = outbox.lock().unwrap().push(msg);
+
+            if let Some(sender) = sender {
+                log::trace!("send message: {msg:?}");
+                if let Err(e) = sender.send(msg) {
+                    log::error!("failed to send message: {e:?}");
+                }
+            }
+        }
+    }
+    /// Resends the messages after `last_seq` to the user. Returns false if
+    /// some of the messages are no longer stored.
+    pub fn resend_since(&self, last_seq: u64) -> bool {
         if let GamePlayer::User {
             sender: Some(sender),
+            outbox,
             ..
         } = &self.game_player
         {
-            log::trace!("send message: {msg:?}");
-            if let Err(e) = sender.send(msg) {
-                log::error!("failed to send message: {e:?}");
+            match outbox.lock().unwrap().since(last_seq) {
+                Some(missed) => {
+                    log::trace!("resending {} messages", missed.len());
+                    for msg in missed {
+                        if let Err(e) = sender.send(msg) {
+                            log::error!("failed to send message: {e:?}");
+                        }
+                    }
+
+                    true
+                }
+                None => false,
             }
+        } else {
+            false
         }
     }
     /// Disconnects the user.
@@ -663,17 +1208,16 @@ impl Slot {
         Player {
             id_player: self.id_player,
             username: match &self.game_player {
-                GamePlayer::Ai { difficulty, .. } => String::from(match difficulty {
-                    AiDifficulty::Easy => "AI (easy)",
-                    AiDifficulty::Medium => "AI (medium)",
-                    AiDifficulty::Hard => "AI (hard)",
-                }),
-                GamePlayer::User {
-                    username, sender, ..
-                } => match sender.is_some() {
-                    true => username.clone(),
-                    false => format!("Disconnected ({username})"),
-                },
+                GamePlayer::Ai { difficulty, .. } => format!("AI ({difficulty})"),
+                GamePlayer::User { username, .. } => username.clone(),
+            },
+            is_connected: match &self.game_player {
+                GamePlayer::Ai { .. } => true,
+                GamePlayer::User { sender, .. } => sender.is_some(),
+            },
+            difficulty: match &self.game_player {
+                GamePlayer::Ai { difficulty, .. } => Some(*difficulty),
+                GamePlayer::User { .. } => None,
             },
         }
     }
@@ -694,8 +1238,524 @@ pub enum GamePlayer {
         /// Username of the player.
         username: String,
         /// Sender half of an mpsc queue that sends `ServerMsg`s to
-        /// a connected user. If `None` the player has disconnected,
-        /// and an easy AI will make their moves until they reconnect.
-        sender: Option<mpsc::UnboundedSender<ServerMsg>>,
+        /// a connected user. If `None` the player has disconnected, and
+        /// their turns are played according to the [`DisconnectPolicy`].
+        sender: Option<mpsc::UnboundedSender<SequencedMsg>>,
+        /// Incremented each time the user joins on a new connection, so
+        /// that a replaced connection closing does not disconnect the user.
+        connection: u64,
+        /// The most recent messages sent to the user.
+        outbox: std::sync::Mutex<Outbox>,
     },
 }
+
+/// Numbers the messages sent to a user, storing the most recent
+/// [`REPLAY_LIMIT`] so that they can be resent when the user rejoins.
+#[derive(Debug, Default)]
+pub struct Outbox {
+    last_seq: u64,
+    messages: VecDeque<SequencedMsg>,
+}
+impl Outbox {
+    /// Numbers and stores a message, returning the numbered message.
+    pub fn push(&mut self, msg: ServerMsg) -> SequencedMsg {
+        self.last_seq += 1;
+        let msg = SequencedMsg {
+            seq: self.last_seq,
+            msg,
+        };
+
+        if self.messages.len() == REPLAY_LIMIT {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(msg.clone());
+
+        msg
+    }
+    /// Gets the messages after `last_seq`, or `None` if some of them are no
+    /// longer stored (or `last_seq` has not been sent).
+    pub fn since(&self, last_seq: u64) -> Option<Vec<SequencedMsg>> {
+        let missed_count = usize::try_from(self.last_seq.checked_sub(last_seq)?).ok()?;
+        let skip_count = self.messages.len().checked_sub(missed_count)?;
+
+        Some(self.messages.iter().skip(skip_count).cloned().collect())
+    }
+}
+
+/// A bounded log of the messages received and broadcast by a game. Once
+/// [`MAX_GAME_EVENTS`] events are stored, the oldest events are dropped.
+#[derive(Debug, Default)]
+pub struct EventLog {
+    events: VecDeque<GameEvent>,
+    dropped_count: usize,
+}
+impl EventLog {
+    /// Appends an event with the current time.
+    pub fn push(&mut self, source: EventSource, message: String) {
+        if self.events.len() == MAX_GAME_EVENTS {
+            self.events.pop_front();
+            self.dropped_count += 1;
+        }
+
+        self.events.push_back(GameEvent {
+            time: Utc::now(),
+            source,
+            message,
+        });
+    }
+    /// Gets the stored events as an API response.
+    pub fn response(&self) -> GameEventsResponse {
+        GameEventsResponse {
+            events: self.events.iter().cloned().collect(),
+            dropped_count: self.dropped_count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db, models::User};
+    use scrabble::{
+        game::tile::Letter,
+        util::{fsm::Fsm, pos::Pos},
+    };
+
+    /// Gets the racks from the messages received by a player.
+    fn received_racks(rx: &mut mpsc::UnboundedReceiver<SequencedMsg>) -> Vec<Vec<Tile>> {
+        let mut racks = vec![];
+        while let Ok(SequencedMsg { msg, .. }) = rx.try_recv() {
+            match msg {
+                ServerMsg::Joined { rack, .. }
+                | ServerMsg::FullState { rack, .. }
+                | ServerMsg::Rack(rack) => racks.push(rack),
+                _ => (),
+            }
+        }
+
+        racks
+    }
+
+    #[test]
+    fn redraw_is_hidden() {
+        let play = hide_redraw(Play::Redraw(vec![Tile::from('a'), Tile::from('z')]));
+        assert!(matches!(play, Play::Redraw(tiles) if tiles == [Tile::blank(); 2]));
+
+        let play = hide_redraw(Play::Place(vec![(Pos::start(), Tile::from('a'))]));
+        assert!(matches!(play, Play::Place(tile_positions) if tile_positions.len() == 1));
+    }
+
+    #[test]
+    fn outbox_since() {
+        let mut outbox = Outbox::default();
+        assert_eq!(Some(0), outbox.since(0).map(|missed| missed.len()));
+
+        for _ in 0..REPLAY_LIMIT + 10 {
+            outbox.push(ServerMsg::Starting {
+                seed_commitment: None,
+            });
+        }
+        let last_seq = (REPLAY_LIMIT + 10) as u64;
+        let seqs = |last_seq| {
+            outbox
+                .since(last_seq)
+                .map(|missed| missed.iter().map(|msg| msg.seq).collect::<Vec<_>>())
+        };
+
+        // only the messages after `last_seq` are resent.
+        assert_eq!(Some(vec![]), seqs(last_seq));
+        assert_eq!(Some(vec![last_seq - 1, last_seq]), seqs(last_seq - 2));
+        assert_eq!(
+            REPLAY_LIMIT,
+            seqs(last_seq - REPLAY_LIMIT as u64).unwrap().len()
+        );
+
+        // the older messages are no longer stored, and future messages
+        // have not been sent.
+        assert_eq!(None, seqs(last_seq - REPLAY_LIMIT as u64 - 1));
+        assert_eq!(None, seqs(last_seq + 1));
+    }
+
+    #[test]
+    fn event_log_is_bounded() {
+        let mut log = EventLog::default();
+        for i in 0..MAX_GAME_EVENTS + 5 {
+            log.push(EventSource::Client(1), i.to_string());
+        }
+        log.push(EventSource::Server, String::from("last"));
+
+        let response = log.response();
+        assert_eq!(MAX_GAME_EVENTS, response.events.len());
+        assert_eq!(6, response.dropped_count);
+        assert_eq!("6", response.events[0].message);
+        assert_eq!(
+            EventSource::Server,
+            response.events[MAX_GAME_EVENTS - 1].source
+        );
+    }
+
+    #[tokio::test]
+    async fn racks_only_sent_to_owner() {
+        let db = db::connect().await.unwrap();
+        let fsm = FsmHandle::bundled();
+
+        let mut users = vec![];
+        for i in 0..2 {
+            users.push(User::insert_for_test(&db, &format!("rack{i}_")).await);
+        }
+        let id_users: Vec<_> = users.iter().map(User::id_user).collect();
+
+        // create a game between the two users.
+        let move_timeout = Duration::from_secs(60);
+        let (id_game, game_handle) = GameHandle::create(
+            db.clone(),
+            fsm,
+            0,
+            AiDifficulty::Easy,
+            2,
+            None,
+            move_timeout,
+        )
+        .await
+        .unwrap();
+        let mut game = game_handle.lock().await;
+
+        let mut receivers = vec![];
+        for &id_user in &id_users {
+            let (tx, rx) = mpsc::unbounded_channel();
+            assert!(game.add_player(id_user, tx).await);
+            receivers.push(rx);
+        }
+
+        // the first user passes, then both users request a resync.
+        game.on_msg(
+            id_users[0],
+            ClientMsg::Play(Play::Pass),
+            game_handle.clone(),
+        )
+        .await;
+        for &id_user in &id_users {
+            game.on_msg(id_user, ClientMsg::Resync, game_handle.clone())
+                .await;
+        }
+
+        // each user only receives their own rack: on joining, after the
+        // pass (only for the first user) and after resyncing.
+        for (idx, (&id_user, rx)) in id_users.iter().zip(&mut receivers).enumerate() {
+            let player_num = game.id_user_to_player_num(id_user).unwrap();
+            let rack = game.api_rack(player_num);
+            let racks = received_racks(rx);
+
+            assert_eq!(if idx == 0 { 3 } else { 2 }, racks.len());
+            assert!(racks.iter().all(|r| r == &rack));
+        }
+
+        // the messages sent to a single user are recorded for that user.
+        let events = game.events.lock().unwrap().response().events;
+        for (idx, &id_user) in id_users.iter().enumerate() {
+            let rack_count = events
+                .iter()
+                .filter(|event| event.source == EventSource::ToClient(id_user))
+                .filter(|event| event.message.starts_with("Rack"))
+                .count();
+            assert_eq!(if idx == 0 { 1 } else { 0 }, rack_count);
+        }
+        assert!(
+            events
+                .iter()
+                .all(|event| event.source != EventSource::Server
+                    || !event.message.starts_with("Rack"))
+        );
+
+        game.sender().send(GameMsg::Close).unwrap();
+        drop(game);
+        sqlx::query_file!("sql/games/delete.sql", id_game)
+            .execute(&db)
+            .await
+            .unwrap();
+        for user in users {
+            user.delete(&db).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn disconnected_user_passes() {
+        let db = db::connect().await.unwrap();
+        let fsm = FsmHandle::bundled();
+
+        let mut users = vec![];
+        for i in 0..2 {
+            users.push(User::insert_for_test(&db, &format!("pass{i}_")).await);
+        }
+        let id_users: Vec<_> = users.iter().map(User::id_user).collect();
+
+        // create a public game between the two users.
+        let move_timeout = Duration::from_secs(60);
+        let (id_game, game_handle) = GameHandle::create(
+            db.clone(),
+            fsm,
+            0,
+            AiDifficulty::Easy,
+            2,
+            None,
+            move_timeout,
+        )
+        .await
+        .unwrap();
+        let mut game = game_handle.lock().await;
+        assert_eq!(DisconnectPolicy::Pass, game.disconnect_policy);
+
+        let mut receivers = vec![];
+        for &id_user in &id_users {
+            let (tx, rx) = mpsc::unbounded_channel();
+            assert!(game.add_player(id_user, tx).await);
+            receivers.push(rx);
+        }
+        let first = game.id_user_to_player_num(id_users[0]).unwrap();
+        let second = game.id_user_to_player_num(id_users[1]).unwrap();
+
+        // the first user disconnects on their turn, so passes.
+        game.on_msg(id_users[0], ClientMsg::Disconnect, game_handle.clone())
+            .await;
+        assert_eq!(1, game.game.player(first).pass_count());
+        assert_eq!(Some(second), game.game.to_play());
+
+        // after the second user plays, the first user passes again, which
+        // ends the game.
+        game.on_msg(
+            id_users[1],
+            ClientMsg::Play(Play::Pass),
+            game_handle.clone(),
+        )
+        .await;
+        assert_eq!(PASS_LIMIT, game.game.player(first).pass_count());
+        assert!(game.is_over());
+
+        let mut is_over_sent = false;
+        while let Ok(SequencedMsg { msg, .. }) = receivers[1].try_recv() {
+            is_over_sent |= matches!(msg, ServerMsg::Over { .. });
+        }
+        assert!(is_over_sent);
+
+        game.sender().send(GameMsg::Close).unwrap();
+        drop(game);
+        sqlx::query_file!("sql/games/delete.sql", id_game)
+            .execute(&db)
+            .await
+            .unwrap();
+        for user in users {
+            user.delete(&db).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn ai_challenges_invalid_placement() {
+        let db = db::connect().await.unwrap();
+        let fsm = FsmHandle::bundled();
+
+        let user = User::insert_for_test(&db, "challenge_").await;
+        let id_user = user.id_user;
+
+        // create a game between a user and an ai, where words are only
+        // checked when challenged.
+        let move_timeout = Duration::from_secs(60);
+        let (id_game, game_handle) = GameHandle::create(
+            db.clone(),
+            fsm.clone(),
+            1,
+            AiDifficulty::Easy,
+            1,
+            None,
+            move_timeout,
+        )
+        .await
+        .unwrap();
+        let mut game = game_handle.lock().await;
+        game.set_rules(ChallengeRule::Single, true);
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        assert!(game.add_player(id_user, tx).await);
+        let first = game.id_user_to_player_num(id_user).unwrap();
+
+        // find two tiles from the rack that do not form a word.
+        let rack: Vec<_> = game
+            .game
+            .player(first)
+            .rack()
+            .tiles()
+            .map(|tile| match tile {
+                Tile::Blank(_) => Tile::Blank(Letter::new('q')),
+                tile => tile,
+            })
+            .collect();
+        let tile_positions = (0..rack.len())
+            .flat_map(|i| {
+                (0..rack.len())
+                    .filter(move |&j| j != i)
+                    .map(move |j| (i, j))
+            })
+            .map(|(i, j)| vec![(Pos::start(), rack[i]), (Pos::from((7, 8)), rack[j])])
+            .find(|tile_positions| {
+                let word: String = tile_positions
+                    .iter()
+                    .map(|(_, tile)| char::from(tile.letter().unwrap()).to_ascii_lowercase())
+                    .collect();
+                !fsm.accepts(word.as_str())
+            })
+            .unwrap();
+
+        // the ai challenges the placement, so it is withdrawn.
+        game.on_msg(
+            id_user,
+            ClientMsg::Play(Play::Place(tile_positions)),
+            game_handle.clone(),
+        )
+        .await;
+        assert_eq!(0, game.game.player(first).score());
+        assert_eq!(1, game.game.player(first).pass_count());
+
+        let mut is_challenged = false;
+        while let Ok(SequencedMsg { msg, .. }) = rx.try_recv() {
+            if let ServerMsg::ChallengeResult { invalid_words, .. } = msg {
+                is_challenged |= !invalid_words.is_empty();
+            }
+        }
+        assert!(is_challenged);
+
+        game.sender().send(GameMsg::Close).unwrap();
+        drop(game);
+        sqlx::query_file!("sql/games/delete.sql", id_game)
+            .execute(&db)
+            .await
+            .unwrap();
+        user.delete(&db).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn verifiable_bag() {
+        let db = db::connect().await.unwrap();
+        let fsm = FsmHandle::bundled();
+
+        let mut users = vec![];
+        for i in 0..2 {
+            users.push(User::insert_for_test(&db, &format!("seed{i}_")).await);
+        }
+        let id_users: Vec<_> = users.iter().map(User::id_user).collect();
+
+        // create a game between the two users with a seeded bag.
+        let move_timeout = Duration::from_secs(60);
+        let (id_game, game_handle) = GameHandle::create(
+            db.clone(),
+            fsm,
+            0,
+            AiDifficulty::Easy,
+            2,
+            None,
+            move_timeout,
+        )
+        .await
+        .unwrap();
+        let mut game = game_handle.lock().await;
+        game.set_verifiable_bag(true);
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        assert!(game.add_player(id_users[0], tx).await);
+        let (tx, _rx) = mpsc::unbounded_channel();
+        assert!(game.add_player(id_users[1], tx).await);
+        let first = game.id_user_to_player_num(id_users[0]).unwrap();
+        let rack = game.api_rack(first);
+
+        // both users pass until the game ends.
+        for id_user in [id_users[0], id_users[1], id_users[0]] {
+            game.on_msg(id_user, ClientMsg::Play(Play::Pass), game_handle.clone())
+                .await;
+        }
+        assert!(game.is_over());
+
+        let mut commitment = None;
+        let mut seed = None;
+        while let Ok(SequencedMsg { msg, .. }) = rx.try_recv() {
+            match msg {
+                ServerMsg::Starting { seed_commitment } => commitment = seed_commitment,
+                ServerMsg::Over { seed: revealed, .. } => seed = revealed,
+                _ => (),
+            }
+        }
+
+        // the revealed seed matches the commitment, and reproduces the rack.
+        let seed = seed.unwrap();
+        assert_eq!(Some(seed.commitment()), commitment);
+        let replay = scrabble::game::Game::with_config(
+            2,
+            LetterBag::default().with_seed(seed.seed),
+            game.game.config().clone(),
+        );
+        assert_eq!(
+            rack,
+            replay.player(first).rack().tiles().collect::<Vec<_>>()
+        );
+
+        game.sender().send(GameMsg::Close).unwrap();
+        drop(game);
+        sqlx::query_file!("sql/games/delete.sql", id_game)
+            .execute(&db)
+            .await
+            .unwrap();
+        for user in users {
+            user.delete(&db).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn replaced_connection_closes() {
+        let db = db::connect().await.unwrap();
+        let fsm = FsmHandle::bundled();
+
+        let user = User::insert_for_test(&db, "rejoin_").await;
+        let id_user = user.id_user;
+
+        // create a game between a user and an ai.
+        let move_timeout = Duration::from_secs(60);
+        let (id_game, game_handle) = GameHandle::create(
+            db.clone(),
+            fsm,
+            1,
+            AiDifficulty::Easy,
+            1,
+            None,
+            move_timeout,
+        )
+        .await
+        .unwrap();
+        let mut game = game_handle.lock().await;
+
+        let (tx, _rx) = mpsc::unbounded_channel();
+        assert!(game.add_player(id_user, tx).await);
+        let old_connection = game.connection(id_user).unwrap();
+
+        // the user rejoins before the old connection has closed.
+        let (tx, _rx) = mpsc::unbounded_channel();
+        assert!(game.rejoin(id_user, tx, 0));
+        let new_connection = game.connection(id_user).unwrap();
+        assert_ne!(old_connection, new_connection);
+
+        // closing the old connection does not disconnect the user.
+        let player_num = game.id_user_to_player_num(id_user).unwrap();
+        game.on_closed(id_user, old_connection, game_handle.clone())
+            .await;
+        assert!(!game.slots[&player_num].is_disconnected());
+
+        // closing the new connection does.
+        game.on_closed(id_user, new_connection, game_handle.clone())
+            .await;
+        assert!(game.slots[&player_num].is_disconnected());
+
+        game.sender().send(GameMsg::Close).unwrap();
+        drop(game);
+        sqlx::query_file!("sql/games/delete.sql", id_game)
+            .execute(&db)
+            .await
+            .unwrap();
+        user.delete(&db).await.unwrap();
+    }
+}
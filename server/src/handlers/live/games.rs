@@ -3,12 +3,35 @@ use crate::{
     fsm::FsmHandle,
     handlers::live::game::{GameHandle, GameMsg},
 };
-use api::routes::live::AiDifficulty;
-use std::{collections::HashMap, ops::Deref, sync::Arc, time::Duration};
+use api::routes::live::LiveError;
+use scrabble::ai::AiDifficulty;
+use std::{collections::HashMap, env, ops::Deref, sync::Arc, time::Duration};
 use tokio::{sync::RwLock, time::interval};
 
 /// The number of milliseconds waited between polls to close a game room.
 const GAME_CLOSE_PERIOD: u64 = 10_000;
+/// The number of minutes used for [`ABANDON_TIMEOUT`] when the
+/// `ABANDON_TIMEOUT` env variable is not set.
+const DEFAULT_ABANDON_TIMEOUT: u64 = 60;
+
+lazy_static::lazy_static! {
+    /// The maximum number of live games that a user can have created
+    /// at once.
+    static ref MAX_GAMES_PER_USER: usize = env::var("MAX_GAMES_PER_USER")
+        .expect("`MAX_GAMES_PER_USER` env variable")
+        .parse()
+        .expect("`MAX_GAMES_PER_USER` to be a number");
+    /// The time without any user joining or sending a message, after which
+    /// an unfinished game is ended and recorded as abandoned.
+    static ref ABANDON_TIMEOUT: Duration = {
+        let abandon_timeout = env::var("ABANDON_TIMEOUT")
+            .ok()
+            .and_then(|minutes| minutes.parse().ok())
+            .unwrap_or(DEFAULT_ABANDON_TIMEOUT);
+
+        Duration::from_secs(60 * abandon_timeout)
+    };
+}
 
 /// Type containing a thread-safe handle to all the games.
 #[derive(Clone, Debug)]
@@ -18,6 +41,9 @@ impl GamesHandle {
     pub fn new(db: &Db, fsm: &FsmHandle) -> Self {
         let games_handle = GamesHandle(Arc::new(RwLock::new(Games {
             games: HashMap::default(),
+            creators: HashMap::default(),
+            max_games_per_user: *MAX_GAMES_PER_USER,
+            abandon_timeout: *ABANDON_TIMEOUT,
             fsm: fsm.clone(),
             db: db.clone(),
         })));
@@ -32,26 +58,7 @@ impl GamesHandle {
                 // games at a fixed interval.
                 loop {
                     interval.tick().await;
-
-                    let mut games = games_handle.write().await;
-                    let mut to_remove = vec![];
-
-                    // iterate over the games and find any that are empty.
-                    for (&id_game, game_handle) in games.games.iter() {
-                        let game = game_handle.lock().await;
-                        if game.is_empty() {
-                            // send a close message to the game, which stops the async task
-                            // that is listening for messages.
-                            game.sender().send(GameMsg::Close).unwrap();
-                            to_remove.push(id_game);
-                        }
-                    }
-
-                    // remove the games from the hashmap.
-                    for id_game in to_remove {
-                        log::info!("removing empty game: {id_game}");
-                        games.games.remove(&id_game);
-                    }
+                    games_handle.write().await.cleanup().await;
                 }
             }
         });
@@ -70,6 +77,11 @@ impl Deref for GamesHandle {
 #[derive(Debug)]
 pub struct Games {
     games: HashMap<i32, GameHandle>,
+    /// Maps the id of each game to the id of the user that created it.
+    creators: HashMap<i32, i32>,
+    max_games_per_user: usize,
+    /// The time after which an inactive game is abandoned.
+    abandon_timeout: Duration,
     fsm: FsmHandle,
     db: Db,
 }
@@ -86,26 +98,255 @@ impl Games {
     pub fn get(&self, id_game: i32) -> Option<GameHandle> {
         self.games.get(&id_game).cloned()
     }
-    /// Inserts a game into the list of games.
+    /// Removes a game from the list of games, stopping the task that
+    /// handles messages for the game.
+    pub async fn remove(&mut self, id_game: i32) -> Option<GameHandle> {
+        let game_handle = self.games.remove(&id_game)?;
+        self.creators.remove(&id_game);
+
+        // send a close message to the game, which stops the async task
+        // that is listening for messages (if it has not already stopped).
+        let _ = game_handle.lock().await.sender().send(GameMsg::Close);
+
+        Some(game_handle)
+    }
+    /// Removes every game that has no connected players, including finished
+    /// games once all the players have left. Unfinished games that have been
+    /// inactive for the abandon timeout are ended and removed. Returns the ids
+    /// of the removed games.
+    pub async fn cleanup(&mut self) -> Vec<i32> {
+        let mut to_remove = vec![];
+
+        // iterate over the games and find any that are empty or abandoned.
+        for (&id_game, game_handle) in self.games.iter() {
+            let mut game = game_handle.lock().await;
+            if !game.is_over() && game.is_inactive(self.abandon_timeout) {
+                log::info!("removing abandoned game: {id_game}");
+                game.abandon().await;
+                to_remove.push(id_game);
+            } else if game.is_empty() {
+                match game.is_over() {
+                    true => log::info!("removing finished game: {id_game}"),
+                    false => log::info!("removing empty game: {id_game}"),
+                }
+                to_remove.push(id_game);
+            }
+        }
+
+        // remove the games from the hashmap.
+        for &id_game in &to_remove {
+            self.remove(id_game).await;
+        }
+
+        to_remove
+    }
+    /// Counts the games created by a user that have not been removed.
+    pub fn created_count(&self, id_user: i32) -> usize {
+        self.creators
+            .values()
+            .filter(|&&id_creator| id_creator == id_user)
+            .count()
+    }
+    /// Inserts a game created by `id_creator` into the list of games.
     pub async fn insert(
         &mut self,
+        id_creator: i32,
         ai_count: usize,
         ai_difficulty: AiDifficulty,
         player_count: usize,
         id_owner: Option<i32>,
-    ) -> Option<GameHandle> {
+        move_timeout: Duration,
+    ) -> Result<GameHandle, LiveError> {
+        // limit the number of games that each user can create.
+        if self.created_count(id_creator) >= self.max_games_per_user {
+            return Err(LiveError::TooManyGames);
+        }
+
         log::info!("inserting game");
 
         let db = self.db();
         let fsm = self.fsm();
-        let created =
-            GameHandle::create(db, fsm, ai_count, ai_difficulty, player_count, id_owner).await;
-
-        if let Some((id_game, game_handle)) = created {
-            self.games.insert(id_game, game_handle.clone());
-            Some(game_handle)
-        } else {
-            None
+        let created = GameHandle::create(
+            db,
+            fsm,
+            ai_count,
+            ai_difficulty,
+            player_count,
+            id_owner,
+            move_timeout,
+        )
+        .await;
+
+        match created {
+            Some((id_game, game_handle)) => {
+                self.games.insert(id_game, game_handle.clone());
+                self.creators.insert(id_game, id_creator);
+                Ok(game_handle)
+            }
+            None => Err(LiveError::FailedToJoin),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db, models::User};
+    use api::routes::live::{ClientMsg, SequencedMsg, ServerMsg};
+    use scrabble::game::GameOverReason;
+    use tokio::{
+        sync::mpsc,
+        time::{sleep, timeout},
+    };
+
+    /// Creates an empty list of games.
+    fn games(db: &Db, max_games_per_user: usize) -> Games {
+        Games {
+            games: HashMap::default(),
+            creators: HashMap::default(),
+            max_games_per_user,
+            abandon_timeout: Duration::from_secs(60 * 60),
+            fsm: FsmHandle::bundled(),
+            db: db.clone(),
+        }
+    }
+
+    #[tokio::test]
+    async fn cleanup_finished_game() {
+        let db = db::connect().await.unwrap();
+        let mut games = games(&db, 1);
+
+        let user = User::insert_for_test(&db, "cleanup").await;
+        let id_user = user.id_user;
+
+        // create a game against an ai, and join it.
+        let move_timeout = Duration::from_secs(60);
+        let game_handle = games
+            .insert(id_user, 1, AiDifficulty::Easy, 1, None, move_timeout)
+            .await
+            .unwrap();
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let (id_game, sender) = {
+            let mut game = game_handle.lock().await;
+            assert!(game.add_player(id_user, tx).await);
+            (game.id_game(), game.sender())
+        };
+
+        // the game is not removed while the user is connected.
+        assert!(games.cleanup().await.is_empty());
+
+        // when the user disconnects, they pass until the game ends.
+        sender
+            .send(GameMsg::client_msg(id_user, ClientMsg::Disconnect))
+            .unwrap();
+        timeout(Duration::from_secs(120), async {
+            while !game_handle.lock().await.is_over() {
+                sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        // the finished, empty game is removed.
+        assert_eq!(vec![id_game], games.cleanup().await);
+        assert!(games.get(id_game).is_none());
+
+        // the task handling messages for the game stops.
+        timeout(Duration::from_secs(10), sender.closed())
+            .await
+            .unwrap();
+
+        sqlx::query_file!("sql/games/delete.sql", id_game)
+            .execute(&db)
+            .await
+            .unwrap();
+        user.delete(&db).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn cleanup_abandoned_game() {
+        let db = db::connect().await.unwrap();
+        let mut games = games(&db, 1);
+
+        let user = User::insert_for_test(&db, "abandon").await;
+        let id_user = user.id_user;
+
+        // create a game against an ai, and join it.
+        let move_timeout = Duration::from_secs(60);
+        let game_handle = games
+            .insert(id_user, 1, AiDifficulty::Easy, 1, None, move_timeout)
+            .await
+            .unwrap();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let id_game = {
+            let mut game = game_handle.lock().await;
+            assert!(game.add_player(id_user, tx).await);
+            game.id_game()
+        };
+
+        // the game has recently been joined, so it is not removed.
+        assert!(games.cleanup().await.is_empty());
+
+        // once the game has been inactive for longer than the timeout, it
+        // is ended even though the user is still connected.
+        games.abandon_timeout = Duration::ZERO;
+        assert_eq!(vec![id_game], games.cleanup().await);
+        assert!(games.get(id_game).is_none());
+        assert!(game_handle.lock().await.is_over());
+
+        let mut reasons = vec![];
+        while let Ok(SequencedMsg { msg, .. }) = rx.try_recv() {
+            if let ServerMsg::Over { reason, .. } = msg {
+                reasons.push(reason);
+            }
+        }
+        assert_eq!(vec![GameOverReason::Abandoned], reasons);
+
+        sqlx::query_file!("sql/games/delete.sql", id_game)
+            .execute(&db)
+            .await
+            .unwrap();
+        user.delete(&db).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn max_games_per_user() {
+        let db = db::connect().await.unwrap();
+        let mut games = games(&db, 2);
+        let move_timeout = Duration::from_secs(60);
+
+        // the creator is not stored in the database.
+        let (id_user, id_other) = (-1, -2);
+        let mut id_games = vec![];
+        for id_creator in [id_user, id_user, id_other] {
+            let game_handle = games
+                .insert(id_creator, 1, AiDifficulty::Easy, 1, None, move_timeout)
+                .await
+                .unwrap();
+            id_games.push(game_handle.lock().await.id_game());
+        }
+
+        // the next game is rejected, even though other users have games.
+        let result = games
+            .insert(id_user, 1, AiDifficulty::Easy, 1, None, move_timeout)
+            .await;
+        assert!(matches!(result, Err(LiveError::TooManyGames)));
+
+        // once a game is removed, the user can create another.
+        games.remove(id_games[0]).await.unwrap();
+        assert_eq!(1, games.created_count(id_user));
+        let game_handle = games
+            .insert(id_user, 1, AiDifficulty::Easy, 1, None, move_timeout)
+            .await
+            .unwrap();
+        id_games.push(game_handle.lock().await.id_game());
+
+        for id_game in id_games {
+            games.remove(id_game).await;
+            sqlx::query_file!("sql/games/delete.sql", id_game)
+                .execute(&db)
+                .await
+                .unwrap();
         }
     }
 }
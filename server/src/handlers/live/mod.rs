@@ -5,10 +5,17 @@ use self::{
 use crate::auth::{Jwt, Role};
 use api::{
     auth::Token,
-    routes::live::{AiDifficulty, ClientMsg, LiveError, ServerMsg},
+    routes::live::{
+        AiVocabulary, ClientMsg, LiveError, SequencedMsg, ServerMsg, MAX_MOVE_TIMEOUT,
+        MIN_MOVE_TIMEOUT, PROTOCOL_VERSION,
+    },
 };
 use futures::{Sink, SinkExt, StreamExt};
-use std::fmt::Debug;
+use scrabble::{
+    ai::{AiDifficulty, Vocabulary},
+    game::challenge::ChallengeRule,
+};
+use std::{fmt::Debug, time::Duration};
 use tokio::sync::mpsc;
 use warp::ws::{Message, WebSocket};
 
@@ -19,21 +26,19 @@ pub mod games;
 pub async fn connected(mut ws: WebSocket, games: GamesHandle) {
     // listen to `receiver` for an `AuthMsg`.
     if let Some(Ok(msg)) = ws.next().await {
-        // deserialize the message.
-        let bytes = msg.as_bytes();
-
-        if let Ok(ClientMsg::Auth(Token(token))) = bincode::deserialize(bytes) {
-            if let Ok(jwt) = Jwt::from_auth_token(&token, Role::User) {
-                log::info!("authenticated: {}", jwt.id_user());
+        match auth_token(msg.as_bytes()) {
+            Ok(token) => {
+                if let Ok(jwt) = Jwt::from_auth_token(&token, Role::User) {
+                    log::info!("authenticated: {}", jwt.id_user());
 
-                authenticated(ws, jwt, games).await;
-            } else {
-                send_msg(&mut ws, &ServerMsg::Error(LiveError::InvalidToken)).await;
+                    authenticated(ws, jwt, games).await;
+                } else {
+                    send_msg(&mut ws, ServerMsg::Error(LiveError::InvalidToken)).await;
 
-                log::error!("invalid token: {token}");
+                    log::error!("invalid token: {token}");
+                }
             }
-        } else {
-            log::error!("failed to deserialize as auth message");
+            Err(e) => send_msg(&mut ws, ServerMsg::Error(e)).await,
         }
     } else {
         log::error!("auth message not received");
@@ -42,6 +47,31 @@ pub async fn connected(mut ws: WebSocket, games: GamesHandle) {
     log::info!("disconnecting user");
 }
 
+/// Gets the token from the first message sent by a client, checking that the
+/// client uses the same [`PROTOCOL_VERSION`]. Any other message is assumed to
+/// be from a client with a different version, as the first message is always
+/// a [`ClientMsg::Auth`].
+fn auth_token(bytes: &[u8]) -> Result<String, LiveError> {
+    match bincode::deserialize(bytes) {
+        Ok(ClientMsg::Auth {
+            version,
+            token: Token(token),
+        }) if version == PROTOCOL_VERSION => Ok(token),
+        Ok(ClientMsg::Auth { version, .. }) => {
+            log::error!("client protocol version {version}, expected {PROTOCOL_VERSION}");
+            Err(LiveError::VersionMismatch)
+        }
+        Ok(msg) => {
+            log::error!("expected an auth message: {msg:?}");
+            Err(LiveError::VersionMismatch)
+        }
+        Err(e) => {
+            log::error!("failed to deserialize as auth message: {e:?}");
+            Err(LiveError::VersionMismatch)
+        }
+    }
+}
+
 /// Called when a user has authenticated.
 async fn authenticated(mut ws: WebSocket, jwt: Jwt, games: GamesHandle) {
     let id_user = jwt.id_user();
@@ -50,23 +80,36 @@ async fn authenticated(mut ws: WebSocket, jwt: Jwt, games: GamesHandle) {
         match bincode::deserialize(msg.as_bytes()) {
             Ok(client_msg) => {
                 match client_msg {
-                    ClientMsg::Join(id_game) => join_game(id_game, ws, jwt, games).await,
+                    ClientMsg::Join(id_game) => join_game(id_game, None, ws, jwt, games).await,
+                    ClientMsg::Rejoin { id_game, last_seq } => {
+                        join_game(id_game, Some(last_seq), ws, jwt, games).await
+                    }
                     ClientMsg::Create {
                         ai_count,
                         ai_difficulty,
                         player_count,
                         friends_only,
+                        move_timeout,
+                        open_hands,
+                        challenge_rule,
+                        allow_exchange,
+                        verifiable_bag,
+                        ai_vocabulary,
                     } => {
-                        create_game(
+                        let options = CreateOptions {
                             ai_count,
                             ai_difficulty,
                             player_count,
                             friends_only,
-                            ws,
-                            jwt,
-                            games,
-                        )
-                        .await
+                            move_timeout,
+                            open_hands,
+                            challenge_rule,
+                            allow_exchange,
+                            verifiable_bag,
+                            ai_vocabulary,
+                        };
+
+                        create_game(options, ws, jwt, games).await
                     }
                     msg => {
                         log::error!("unexpected message: {msg:?}");
@@ -82,8 +125,14 @@ async fn authenticated(mut ws: WebSocket, jwt: Jwt, games: GamesHandle) {
     log::info!("disconnecting client: id_user={id_user}");
 }
 
-/// Joins a game.
-async fn join_game(id_game: i32, mut ws: WebSocket, jwt: Jwt, games: GamesHandle) {
+/// Joins a game, or rejoins it if `last_seq` is set.
+async fn join_game(
+    id_game: i32,
+    last_seq: Option<u64>,
+    mut ws: WebSocket,
+    jwt: Jwt,
+    games: GamesHandle,
+) {
     log::info!("user ({}) is joining game ({id_game})", jwt.id_user());
 
     // attempt to get the game by id.
@@ -93,33 +142,59 @@ async fn join_game(id_game: i32, mut ws: WebSocket, jwt: Jwt, games: GamesHandle
 
     match game {
         // if the game exists, call `playing`.
-        Some(game_handle) => playing(ws, jwt, game_handle).await,
+        Some(game_handle) => playing(ws, jwt, game_handle, last_seq).await,
         None => {
             log::error!("game not found: {id_game}");
-            send_msg(&mut ws, &ServerMsg::Error(LiveError::FailedToJoin)).await;
+            send_msg(&mut ws, ServerMsg::Error(LiveError::FailedToJoin)).await;
         }
     }
 }
 
-/// Creates a game.
-async fn create_game(
+/// The options from a [`ClientMsg::Create`] message.
+struct CreateOptions {
     ai_count: usize,
     ai_difficulty: AiDifficulty,
     player_count: usize,
     friends_only: bool,
-    mut ws: WebSocket,
-    jwt: Jwt,
-    games: GamesHandle,
-) {
+    move_timeout: u64,
+    open_hands: bool,
+    challenge_rule: ChallengeRule,
+    allow_exchange: bool,
+    verifiable_bag: bool,
+    ai_vocabulary: AiVocabulary,
+}
+
+/// Creates a game.
+async fn create_game(options: CreateOptions, mut ws: WebSocket, jwt: Jwt, games: GamesHandle) {
+    let CreateOptions {
+        ai_count,
+        ai_difficulty,
+        player_count,
+        friends_only,
+        move_timeout,
+        open_hands,
+        challenge_rule,
+        allow_exchange,
+        verifiable_bag,
+        ai_vocabulary,
+    } = options;
     let count = player_count + ai_count;
 
     // send an error if there are no players.
     if player_count == 0 {
-        send_msg(&mut ws, &ServerMsg::Error(LiveError::ZeroPlayers)).await;
+        send_msg(&mut ws, ServerMsg::Error(LiveError::ZeroPlayers)).await;
     }
     // send an error for too few or too many players.
     else if !(2..=4).contains(&count) {
-        send_msg(&mut ws, &ServerMsg::Error(LiveError::IllegalPlayerCount)).await;
+        send_msg(&mut ws, ServerMsg::Error(LiveError::IllegalPlayerCount)).await;
+    }
+    // send an error if the move timeout is out of range.
+    else if !(MIN_MOVE_TIMEOUT..=MAX_MOVE_TIMEOUT).contains(&move_timeout) {
+        send_msg(&mut ws, ServerMsg::Error(LiveError::IllegalMoveTimeout)).await;
+    }
+    // open hands are only allowed in practice games between friends.
+    else if open_hands && !friends_only {
+        send_msg(&mut ws, ServerMsg::Error(LiveError::IllegalOpenHands)).await;
     }
     // otherwise create the game.
     else {
@@ -132,20 +207,42 @@ async fn create_game(
             false => None,
         };
         // create the game.
+        let move_timeout = Duration::from_secs(move_timeout);
         let game_handle = games_write
-            .insert(ai_count, ai_difficulty, player_count, id_user)
+            .insert(
+                jwt.id_user(),
+                ai_count,
+                ai_difficulty,
+                player_count,
+                id_user,
+                move_timeout,
+            )
             .await;
+        // set the rules before other players can find the game.
+        if let Ok(game_handle) = &game_handle {
+            let mut game = game_handle.lock().await;
+            game.set_open_hands(open_hands);
+            game.set_rules(challenge_rule, allow_exchange);
+            game.set_verifiable_bag(verifiable_bag);
+            game.set_ai_vocabulary(match ai_vocabulary {
+                AiVocabulary::Difficulty => None,
+                AiVocabulary::Full => Some(Vocabulary::Full),
+                AiVocabulary::Common => Some(Vocabulary::Common),
+            });
+        }
         drop(games_write);
 
-        if let Some(game_handle) = game_handle {
-            playing(ws, jwt, game_handle).await;
+        match game_handle {
+            Ok(game_handle) => playing(ws, jwt, game_handle, None).await,
+            Err(e) => send_msg(&mut ws, ServerMsg::Error(e)).await,
         }
     }
 }
 
 /// Forwards messages from the user to the game, and from the
-/// game to the user, until the user disconnects.
-async fn playing(ws: WebSocket, jwt: Jwt, game: GameHandle) {
+/// game to the user, until the user disconnects. If `last_seq` is set,
+/// the user is rejoining the game.
+async fn playing(ws: WebSocket, jwt: Jwt, game: GameHandle, last_seq: Option<u64>) {
     log::info!("playing game: id_user={}", jwt.id_user());
 
     let (mut sender, mut receiver) = ws.split();
@@ -155,11 +252,17 @@ async fn playing(ws: WebSocket, jwt: Jwt, game: GameHandle) {
     // Add the player to the game.
     let mut game = game.lock().await;
     let game_sender = game.sender();
-    if !game.add_player(id_user, tx).await {
+    let is_added = match last_seq {
+        Some(last_seq) => game.rejoin(id_user, tx, last_seq),
+        None => game.add_player(id_user, tx).await,
+    };
+    if !is_added {
         // stop execution if adding the player failed.
-        send_msg(&mut sender, &ServerMsg::Error(LiveError::FailedToJoin)).await;
+        send_msg(&mut sender, ServerMsg::Error(LiveError::FailedToJoin)).await;
         return;
     }
+    // the user was added, so has a connection.
+    let connection = game.connection(id_user).unwrap();
     drop(game);
 
     // Forward messages from `receiver` -> `game_sender`
@@ -174,21 +277,28 @@ async fn playing(ws: WebSocket, jwt: Jwt, game: GameHandle) {
                     }
                     Err(e) => log::error!("failed to deserialize: {e:?}"),
                 },
-                Err(e) => log::error!("error receiving message: {e:?}"),
+                // oversized messages close the connection.
+                Err(e) => {
+                    log::error!("error receiving message: {e:?}");
+                    break;
+                }
             }
         }
 
-        // Ensure the user is disconnected by this point by sending a disconnect
-        // message to the game room.
+        // Ensure the user is disconnected by this point (unless they have
+        // rejoined on another connection) by notifying the game room.
         game_sender
-            .send(GameMsg::client_msg(id_user, ClientMsg::Disconnect))
+            .send(GameMsg::Closed {
+                id_user,
+                connection,
+            })
             .unwrap();
     });
 
     // Forward messages from `rx` -> `sender`
     // (Messages from game to the client)
     while let Some(msg) = rx.recv().await {
-        send_msg(&mut sender, &msg).await;
+        send_msg(&mut sender, msg).await;
     }
 
     // Ensure that both async tasks complete.
@@ -200,17 +310,51 @@ async fn playing(ws: WebSocket, jwt: Jwt, game: GameHandle) {
 }
 
 /// Attempts to sends a message to the client.
-async fn send_msg<T>(ws: &mut T, msg: &ServerMsg)
+async fn send_msg<T>(ws: &mut T, msg: impl Into<SequencedMsg>)
 where
     T: SinkExt<Message> + Unpin,
     <T as Sink<Message>>::Error: Debug,
 {
+    let msg = msg.into();
     log::trace!("sending message: {msg:?}");
 
-    let bytes = bincode::serialize(msg).unwrap();
+    let bytes = bincode::serialize(&msg).unwrap();
     let msg = Message::binary(bytes);
 
     if let Err(e) = ws.send(msg).await {
         log::error!("failed to send message: {e:?}");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn version_mismatch() {
+        let auth = |version| {
+            bincode::serialize(&ClientMsg::Auth {
+                version,
+                token: Token(String::from("token")),
+            })
+            .unwrap()
+        };
+
+        let token = auth_token(&auth(PROTOCOL_VERSION));
+        assert!(matches!(token, Ok(token) if token == "token"));
+        let token = auth_token(&auth(PROTOCOL_VERSION + 1));
+        assert!(matches!(token, Err(LiveError::VersionMismatch)));
+
+        // a message that is not an auth message, or cannot be deserialized,
+        // is from an incompatible client.
+        let resync = bincode::serialize(&ClientMsg::Resync).unwrap();
+        assert!(matches!(
+            auth_token(&resync),
+            Err(LiveError::VersionMismatch)
+        ));
+        assert!(matches!(
+            auth_token(&[0xff; 3]),
+            Err(LiveError::VersionMismatch)
+        ));
+    }
+}
@@ -2,11 +2,17 @@ use warp::{host::Authority, hyper::Uri, path::FullPath, Rejection, Reply};
 
 use crate::error::Error;
 
+pub mod admin;
 pub mod friends;
 pub mod games;
+pub mod health;
 pub mod leaderboard;
 pub mod live;
+pub mod puzzles;
+pub mod themes;
+pub mod tiles;
 pub mod users;
+pub mod words;
 
 /// Handler that redirects HTTP to HTTPS.
 pub fn http_redirect(
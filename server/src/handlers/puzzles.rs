@@ -0,0 +1,121 @@
+use crate::{
+    auth::Jwt,
+    db::Db,
+    error::{Error, Result},
+    fsm::FsmHandle,
+    models,
+};
+use api::{
+    auth::AuthWrapper,
+    routes::puzzles::{
+        DailyPuzzleResponse, PuzzleAttempt, PuzzleAttemptRequest, PuzzleAttemptResponse,
+    },
+};
+use chrono::{Datelike, NaiveDate, Utc};
+use scrabble::game::puzzle::Puzzle;
+use std::sync::Mutex;
+use warp::{Rejection, Reply};
+
+lazy_static::lazy_static! {
+    /// The most recently generated puzzle, and its date.
+    static ref DAILY_PUZZLE: Mutex<Option<(NaiveDate, Puzzle)>> = Mutex::new(None);
+}
+
+/// Gets the puzzle for a date, which is generated from a seed derived from
+/// the date so that every user gets the same puzzle.
+fn daily_puzzle(fsm: &FsmHandle, date: NaiveDate) -> Result<Puzzle> {
+    let mut daily = DAILY_PUZZLE.lock().unwrap();
+
+    match &*daily {
+        Some((puzzle_date, puzzle)) if *puzzle_date == date => Ok(puzzle.clone()),
+        _ => {
+            let seed = date.num_days_from_ce() as u64;
+            let puzzle = Puzzle::generate(&**fsm, seed).ok_or(Error::MissingPuzzle)?;
+            *daily = Some((date, puzzle.clone()));
+
+            Ok(puzzle)
+        }
+    }
+}
+
+/// GET /api/puzzles/daily [+Auth]
+pub async fn daily(db: Db, fsm: FsmHandle, jwt: Jwt) -> Result<impl Reply, Rejection> {
+    let date = Utc::today().naive_utc();
+    let puzzle = daily_puzzle(&fsm, date)?;
+    let attempt = models::PuzzleAttempt::find(&db, jwt.id_user(), date).await?;
+
+    Ok(warp::reply::json(&AuthWrapper {
+        token: Some(jwt.token()?),
+        response: DailyPuzzleResponse {
+            date,
+            tiles: puzzle.board().clone().into(),
+            rack: puzzle.rack().tiles().collect(),
+            attempt: attempt.map(|attempt| {
+                PuzzleAttempt::new(attempt.score as usize, attempt.best_score as usize)
+            }),
+        },
+    }))
+}
+
+/// POST /api/puzzles/daily [+Auth]
+pub async fn attempt(
+    db: Db,
+    fsm: FsmHandle,
+    jwt: Jwt,
+    req: PuzzleAttemptRequest,
+) -> Result<impl Reply, Rejection> {
+    // an attempt can only be made at today's puzzle.
+    let date = Utc::today().naive_utc();
+    if req.date != date {
+        return Err(Error::MissingPuzzle.into());
+    }
+
+    let puzzle = daily_puzzle(&fsm, date)?;
+    let score = puzzle
+        .score(&*fsm, req.tile_positions)
+        .map_err(Error::Play)?;
+
+    let attempt = models::PuzzleAttempt {
+        id_user: jwt.id_user(),
+        puzzle_date: date,
+        score: score as i32,
+        best_score: puzzle.best_score() as i32,
+    };
+    if !attempt.insert(&db).await? {
+        return Err(Error::PuzzleAttempted.into());
+    }
+
+    Ok(warp::reply::json(&AuthWrapper {
+        token: Some(jwt.token()?),
+        response: PuzzleAttemptResponse {
+            attempt: PuzzleAttempt::new(score, puzzle.best_score()),
+            best_play: puzzle.best_play().to_vec(),
+        },
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deterministic_puzzle() {
+        let fsm = FsmHandle::bundled();
+        let date = NaiveDate::from_ymd(2022, 10, 22);
+
+        // the cached puzzle is the same as a newly generated puzzle.
+        let puzzle = daily_puzzle(&fsm, date).unwrap();
+        let cached = daily_puzzle(&fsm, date).unwrap();
+        let seed = date.num_days_from_ce() as u64;
+        let generated = Puzzle::generate(&*fsm, seed).unwrap();
+        let tiles = |puzzle: &Puzzle| Vec::from(puzzle.board().clone());
+        for other in [&cached, &generated] {
+            assert_eq!(tiles(&puzzle), tiles(other));
+            assert_eq!(puzzle.best_play(), other.best_play());
+        }
+
+        // the best play can be made from the rack.
+        let best_play = puzzle.best_play().to_vec();
+        assert_eq!(puzzle.best_score(), puzzle.score(&*fsm, best_play).unwrap());
+    }
+}
@@ -0,0 +1,68 @@
+use crate::error::{Error, Result};
+use api::{
+    auth::AuthWrapper,
+    routes::themes::{Palette, ThemesResponse, BUILTIN_THEMES},
+};
+use std::{
+    env,
+    fs::File,
+    io::{self, BufReader},
+    path::Path,
+};
+use warp::{Rejection, Reply};
+
+lazy_static::lazy_static! {
+    /// The custom board themes, loaded from the file at `THEMES_JSON`.
+    static ref PALETTES: Vec<Palette> = {
+        let path = env::var("THEMES_JSON").expect("`THEMES_JSON` env variable");
+
+        load_palettes(path).expect("`THEMES_JSON` to be a list of palettes")
+    };
+}
+
+/// Loads a list of palettes from a JSON file.
+fn load_palettes(path: impl AsRef<Path>) -> Result<Vec<Palette>> {
+    let file = File::open(path)?;
+
+    // json errors are converted to io errors.
+    let palettes = serde_json::from_reader(BufReader::new(file)).map_err(io::Error::from)?;
+
+    Ok(palettes)
+}
+
+/// Checks that `theme` is the name of a built in or custom theme.
+pub fn validate(theme: &str) -> Result<()> {
+    let is_builtin = BUILTIN_THEMES.contains(&theme);
+
+    match is_builtin || PALETTES.iter().any(|palette| palette.name == theme) {
+        true => Ok(()),
+        false => Err(Error::InvalidTheme),
+    }
+}
+
+/// GET /api/themes
+pub async fn list() -> Result<impl Reply, Rejection> {
+    Ok(warp::reply::json(&AuthWrapper {
+        token: None,
+        response: ThemesResponse {
+            palettes: PALETTES.clone(),
+        },
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_palettes() {
+        let palettes = load_palettes("data/themes.json").unwrap();
+        assert!(!palettes.is_empty());
+
+        // custom themes must not replace a built in theme, or each other.
+        for (idx, palette) in palettes.iter().enumerate() {
+            assert!(!BUILTIN_THEMES.contains(&palette.name.as_str()));
+            assert!(palettes[..idx].iter().all(|p| p.name != palette.name));
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use api::{auth::AuthWrapper, routes::tiles::TilesResponse};
+use scrabble::game::letter_bag::LetterBag;
+use warp::{Rejection, Reply};
+
+/// GET /api/tiles
+pub async fn distribution() -> Result<impl Reply, Rejection> {
+    // live games use the standard letter bag.
+    Ok(warp::reply::json(&AuthWrapper {
+        token: None,
+        response: TilesResponse::from(&LetterBag::default()),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::filters;
+    use api::{auth::AuthWrapper, routes::tiles::TilesResponse};
+    use warp::{hyper::StatusCode, test::request};
+
+    #[tokio::test]
+    async fn distribution() {
+        let filter = filters::tiles::all();
+
+        let response = request().path("/tiles").reply(&filter).await;
+        assert_eq!(StatusCode::OK, response.status());
+
+        let body: AuthWrapper<TilesResponse> = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(27, body.response.tiles.len());
+        assert_eq!(100, body.response.total());
+        assert_eq!(2, body.response.blank_count);
+    }
+}
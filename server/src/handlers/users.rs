@@ -2,13 +2,18 @@ use crate::{
     auth::{self, hex, validation, Jwt, Role},
     db::Db,
     error::{Error, Result},
+    handlers::themes,
     mailer::Mailer,
     models,
 };
 use api::{
     auth::AuthWrapper,
-    routes::users::{
-        DeleteAccount, Login, ResetPassword, ResetWithSecret, SignUp, UpdateAccount, UserDetails,
+    routes::{
+        themes::DEFAULT_THEME,
+        users::{
+            DeleteAccount, Login, ResetPassword, ResetWithSecret, SignUp, UpdateAccount,
+            UserDetails, VerifyEmail,
+        },
     },
 };
 use chrono::{Duration, Utc};
@@ -26,6 +31,15 @@ lazy_static::lazy_static! {
 
         Duration::seconds(seconds as i64)
     };
+    /// Duration for which the link to verify a new email address is valid.
+    static ref VERIFY_EMAIL_TIMEOUT: Duration = {
+        let verify_timeout = env::var("VERIFY_EMAIL_TIMEOUT").expect("`VERIFY_EMAIL_TIMEOUT` env variable");
+        let seconds: usize = verify_timeout.parse().expect("`VERIFY_EMAIL_TIMEOUT` in seconds");
+
+        Duration::seconds(seconds as i64)
+    };
+    /// The domain that the client is served from, used for links in emails.
+    static ref DOMAIN: String = env::var("DOMAIN").expect("`DOMAIN` env variable");
 }
 
 /// POST /api/users/reset-password
@@ -79,11 +93,7 @@ async fn send_reset_email(
     secret_hex: String,
 ) -> Result<()> {
     // send a reset password email.
-    let reset_link = format!(
-        "https://thrgd.uk/users/reset-password/{username}/{hex}",
-        username = username,
-        hex = secret_hex
-    );
+    let reset_link = reset_link(&DOMAIN, &username, &secret_hex);
     let body_html = format!(
         r#"
     <style>
@@ -118,14 +128,22 @@ async fn send_reset_email(
         .send(&email, "Scrabble AI: Password Reset", body_html, body_plain)
         .await
 }
+/// Builds the link sent in a reset password email, which matches the
+/// client route `/users/reset-password/<username>/<secret>`.
+fn reset_link(domain: &str, username: &str, secret_hex: &str) -> String {
+    format!("https://{domain}/users/reset-password/{username}/{secret_hex}")
+}
 
 /// PUT /api/users/reset-password
 pub async fn reset_with_secret(
     db: Db,
     with_secret: ResetWithSecret,
 ) -> Result<impl Reply, Rejection> {
-    // lookup the secret in the database.
-    let pwd_reset = models::PasswordReset::find_by_username(&db, &with_secret.username).await?;
+    // lookup the secret in the database. (the record is deleted once the
+    // secret has been used).
+    let pwd_reset = models::PasswordReset::find_by_username(&db, &with_secret.username)
+        .await?
+        .ok_or(Error::IncorrectResetSecret)?;
 
     // check that the record has not expired
     if pwd_reset.is_expired() {
@@ -141,6 +159,12 @@ pub async fn reset_with_secret(
     validation::validate_password_complexity(&with_secret.new_password)?;
     let new_hashed_pass = auth::hash(&with_secret.new_password);
 
+    // delete the reset password record, so that the secret cannot be
+    // used again.
+    if !pwd_reset.consume(&db).await? {
+        return Err(Error::IncorrectResetSecret.into());
+    }
+
     // update the user's password.
     let user = models::User::find_by_id(&db, pwd_reset.id_user).await?;
     let new_user = models::User {
@@ -149,9 +173,6 @@ pub async fn reset_with_secret(
     };
     new_user.update(&db).await?;
 
-    // delete the reset password record
-    pwd_reset.delete(&db).await?;
-
     // generate a jwt.
     let jwt = Jwt::new(new_user.id_user(), new_user.role());
 
@@ -161,6 +182,8 @@ pub async fn reset_with_secret(
             username: new_user.username,
             email: new_user.email,
             is_private: new_user.is_private,
+            turn_emails: new_user.turn_emails,
+            theme: new_user.theme,
         },
     }))
 }
@@ -168,9 +191,27 @@ pub async fn reset_with_secret(
 /// POST /api/users/login
 pub async fn log_in(db: Db, login: Login) -> Result<impl Reply, Rejection> {
     let user = models::User::find_by_username(&db, login.username.trim()).await?;
-    let jwt = Jwt::new(user.id_user(), user.role());
+    let jwt = match login.remember {
+        true => Jwt::remembered(user.id_user(), user.role()),
+        false => Jwt::new(user.id_user(), user.role()),
+    };
 
-    auth::verify(&user.hashed_pass, &login.password)?;
+    // reject the login if there have been too many failed attempts. The
+    // record stays locked until the attempt is recorded, so parallel
+    // logins cannot all pass the check before any failure is counted.
+    let mut tx = db.begin().await.map_err(Error::Sqlx)?;
+    let login_attempt = models::LoginAttempt::find_for_update(&mut tx, user.id_user()).await?;
+    if login_attempt.is_locked() {
+        return Err(Error::AccountLocked.into());
+    }
+
+    if let Err(e) = auth::verify(&user.hashed_pass, &login.password) {
+        models::LoginAttempt::record_failure(&mut tx, user.id_user()).await?;
+        tx.commit().await.map_err(Error::Sqlx)?;
+        return Err(e.into());
+    }
+    models::LoginAttempt::clear(&mut tx, user.id_user()).await?;
+    tx.commit().await.map_err(Error::Sqlx)?;
 
     Ok(warp::reply::json(&AuthWrapper {
         token: Some(jwt.token()?),
@@ -178,11 +219,20 @@ pub async fn log_in(db: Db, login: Login) -> Result<impl Reply, Rejection> {
     }))
 }
 
+/// POST /api/users/logout [+Auth]
+pub async fn log_out(db: Db, jwt: Jwt) -> Result<impl Reply, Rejection> {
+    jwt.revoke(&db).await?;
+
+    // no token is sent, as the current token has been revoked.
+    Ok(warp::reply::json(&AuthWrapper {
+        token: None,
+        response: (),
+    }))
+}
+
 /// POST /api/users
 pub async fn sign_up(db: Db, sign_up: SignUp) -> Result<impl Reply, Rejection> {
-    validation::validate_password_complexity(&sign_up.password)?;
-    validation::validate_username(&sign_up.username)?;
-    validation::validate_email(&sign_up.email)?;
+    validation::validate_account(&sign_up.username, &sign_up.email, Some(&sign_up.password))?;
     models::User::check_username_and_email_free(&db, &sign_up.username, &sign_up.email, -1).await?;
 
     let hashed_pass = auth::hash(&sign_up.password);
@@ -204,6 +254,8 @@ pub async fn sign_up(db: Db, sign_up: SignUp) -> Result<impl Reply, Rejection> {
             username: sign_up.username,
             email: sign_up.email,
             is_private: sign_up.is_private,
+            turn_emails: true,
+            theme: String::from(DEFAULT_THEME),
         },
     }))
 }
@@ -219,7 +271,12 @@ pub async fn profile(db: Db, jwt: Jwt) -> Result<impl Reply, Rejection> {
 }
 
 /// PUT /api/users [+Auth]
-pub async fn update(db: Db, jwt: Jwt, update: UpdateAccount) -> Result<impl Reply, Rejection> {
+pub async fn update(
+    db: Db,
+    mailer: Mailer,
+    jwt: Jwt,
+    update: UpdateAccount,
+) -> Result<impl Reply, Rejection> {
     let user = models::User::find_by_id(&db, jwt.id_user()).await?;
     auth::verify(&user.hashed_pass, &update.old_password)?;
 
@@ -232,16 +289,19 @@ pub async fn update(db: Db, jwt: Jwt, update: UpdateAccount) -> Result<impl Repl
             .map(auth::hash)
             .unwrap_or_else(|| user.hashed_pass.clone()),
         is_private: update.is_private.unwrap_or(user.is_private),
+        turn_emails: update.turn_emails.unwrap_or(user.turn_emails),
+        theme: update.theme.unwrap_or_else(|| user.theme.clone()),
         date_updated: Utc::now().naive_utc(),
         ..user.clone()
     };
 
     // ensure that the new username, email and password are still valid.
-    if let Some(pwd) = update.password.as_deref() {
-        validation::validate_password_complexity(pwd)?;
-    }
-    validation::validate_username(&updated_user.username)?;
-    validation::validate_email(&updated_user.email)?;
+    validation::validate_account(
+        &updated_user.username,
+        &updated_user.email,
+        update.password.as_deref(),
+    )?;
+    themes::validate(&updated_user.theme)?;
     models::User::check_username_and_email_free(
         &db,
         &updated_user.username,
@@ -250,6 +310,33 @@ pub async fn update(db: Db, jwt: Jwt, update: UpdateAccount) -> Result<impl Repl
     )
     .await?;
 
+    // a new email address must be verified before it is used, so keep the
+    // previous address until the link is followed.
+    if updated_user.email != user.email {
+        let secret: [u8; 32] = rand::thread_rng().gen();
+        let secret_hex = hex::encode(&secret);
+        let email_verification = models::EmailVerification {
+            id_user: user.id_user(),
+            new_email: updated_user.email.clone(),
+            secret_hex: secret_hex.clone(),
+            valid_until: Utc::now().naive_utc() + *VERIFY_EMAIL_TIMEOUT,
+        };
+        email_verification.insert(&db).await?;
+
+        // send the email on a seperate task as it may take a while.
+        let (email, username) = (updated_user.email.clone(), updated_user.username.clone());
+        tokio::spawn(async move {
+            let result = send_verify_email(mailer, email, username, secret_hex).await;
+            if let Err(e) = result {
+                log::error!("failed to send email: {e:?}");
+            }
+        });
+    }
+    let updated_user = models::User {
+        email: user.email,
+        ..updated_user
+    };
+
     updated_user.update(&db).await?;
 
     Ok(warp::reply::json(&AuthWrapper {
@@ -257,6 +344,100 @@ pub async fn update(db: Db, jwt: Jwt, update: UpdateAccount) -> Result<impl Repl
         response: (),
     }))
 }
+/// Sends an email to verify a new email address.
+async fn send_verify_email(
+    mailer: Mailer,
+    email: String,
+    username: String,
+    secret_hex: String,
+) -> Result<()> {
+    let verify_link = verify_link(&DOMAIN, &username, &secret_hex);
+    let body_html = format!(
+        r#"
+    <style>
+        html {{
+            font-family: monospace;
+        }}
+    </style>
+    <h1>Verify email</h1>
+    <p>
+        You are receiving this email because a request was made to
+        change the email address for an account with username: {username}.
+
+        <br />
+
+        <a href="{verify_link}">
+            Click here to verify your new email address.
+        </a>
+    </p>
+    "#,
+    );
+    let body_plain = format!(
+        "You are receiving this email because a request was made to \
+        change the email address for an account with username: {username}.
+
+        Click to verify your new email address: {verify_link}
+        ",
+    );
+
+    mailer
+        .send(&email, "Scrabble AI: Verify Email", body_html, body_plain)
+        .await
+}
+/// Builds the link sent in a verify email message, which matches the
+/// client route `/users/verify-email/<username>/<secret>`.
+fn verify_link(domain: &str, username: &str, secret_hex: &str) -> String {
+    format!("https://{domain}/users/verify-email/{username}/{secret_hex}")
+}
+
+/// PUT /api/users/verify-email
+pub async fn verify_email(db: Db, verify: VerifyEmail) -> Result<impl Reply, Rejection> {
+    // lookup the secret in the database. (the record is deleted once the
+    // secret has been used).
+    let email_verification = models::EmailVerification::find_by_username(&db, &verify.username)
+        .await?
+        .ok_or(Error::IncorrectVerifySecret)?;
+
+    // check that the record has not expired
+    if email_verification.is_expired() {
+        return Err(Error::VerifyExpired.into());
+    }
+
+    // compare the secret with the stored value.
+    if !email_verification.secret_matches(&verify.secret_hex) {
+        return Err(Error::IncorrectVerifySecret.into());
+    }
+
+    // check that the email has not been taken since the request was made.
+    let user = models::User::find_by_id(&db, email_verification.id_user).await?;
+    models::User::check_username_and_email_free(
+        &db,
+        &user.username,
+        &email_verification.new_email,
+        user.id_user(),
+    )
+    .await?;
+
+    // delete the record, so that the secret cannot be used again.
+    if !email_verification.consume(&db).await? {
+        return Err(Error::IncorrectVerifySecret.into());
+    }
+
+    // update the user's email.
+    let new_user = models::User {
+        email: email_verification.new_email,
+        date_updated: Utc::now().naive_utc(),
+        ..user
+    };
+    new_user.update(&db).await?;
+
+    let jwt = Jwt::new(new_user.id_user(), new_user.role());
+
+    Ok(warp::reply::json(&AuthWrapper {
+        token: Some(jwt.token()?),
+        response: new_user.into_user_details(),
+    }))
+}
 
 /// DELETE /api/users [+Auth]
 pub async fn delete(db: Db, jwt: Jwt, delete: DeleteAccount) -> Result<impl Reply, Rejection> {
@@ -269,3 +450,53 @@ pub async fn delete(db: Db, jwt: Jwt, delete: DeleteAccount) -> Result<impl Repl
         response: (),
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db, error::Error};
+
+    #[test]
+    fn reset_link_format() {
+        assert_eq!(
+            reset_link("thrgd.uk", "user", "00010a3264c8ff"),
+            "https://thrgd.uk/users/reset-password/user/00010a3264c8ff"
+        );
+    }
+
+    #[test]
+    fn verify_link_format() {
+        assert_eq!(
+            verify_link("thrgd.uk", "user", "00010a3264c8ff"),
+            "https://thrgd.uk/users/verify-email/user/00010a3264c8ff"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn parallel_failed_logins() {
+        let db = db::connect().await.unwrap();
+        let mut user = models::User::insert_for_test(&db, "parallel").await;
+        user.hashed_pass = auth::hash("password");
+        user.update(&db).await.unwrap();
+
+        let handles = (0..20).map(|_| {
+            let login = Login {
+                username: user.username.clone(),
+                password: String::from("wrong"),
+                remember: false,
+            };
+            tokio::spawn(log_in(db.clone(), login))
+        });
+        let results = futures::future::join_all(handles).await;
+
+        // only the attempts before the account is locked check the password.
+        let incorrect = results
+            .into_iter()
+            .map(|result| result.unwrap().err().unwrap())
+            .filter(|rejection| matches!(rejection.find(), Some(Error::IncorrectPassword)))
+            .count();
+        assert_eq!(5, incorrect);
+
+        user.delete(&db).await.unwrap();
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{
+    auth::Jwt,
+    error::{Error, Result},
+    fsm::FsmHandle,
+};
+use api::{
+    auth::AuthWrapper,
+    routes::words::{ValidateWordsRequest, ValidateWordsResponse, MAX_VALIDATE_WORDS},
+};
+use scrabble::{
+    game::tile::Letter,
+    util::fsm::{FastFsm, Fsm},
+};
+use warp::{Rejection, Reply};
+
+/// Splits `words` into those that are accepted and rejected by `fsm`.
+fn check_words(fsm: &FastFsm, words: Vec<String>) -> ValidateWordsResponse {
+    // a word is traversed up to its first character that is not a letter,
+    // so the characters are checked first.
+    let (accepted, rejected) = words.into_iter().partition(|word| {
+        word.chars().all(|ch| Letter::new(ch).is_some()) && fsm.accepts(word.as_str())
+    });
+
+    ValidateWordsResponse { accepted, rejected }
+}
+
+/// POST /api/words/validate [+Auth(Admin)]
+pub async fn validate(
+    fsm: FsmHandle,
+    jwt: Jwt,
+    req: ValidateWordsRequest,
+) -> Result<impl Reply, Rejection> {
+    if req.words.len() > MAX_VALIDATE_WORDS {
+        return Err(Error::TooManyWords.into());
+    }
+
+    Ok(warp::reply::json(&AuthWrapper {
+        token: Some(jwt.token()?),
+        response: check_words(&fsm, req.words),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_bundled_words() {
+        let fsm = FsmHandle::bundled();
+        let words = ["qat", "CAT", "zzzz", "cat1", "", "ca t"]
+            .map(String::from)
+            .to_vec();
+
+        assert_eq!(
+            ValidateWordsResponse {
+                accepted: vec![String::from("qat"), String::from("CAT")],
+                rejected: ["zzzz", "cat1", "", "ca t"].map(String::from).to_vec(),
+            },
+            check_words(&fsm, words)
+        );
+    }
+}
@@ -6,17 +6,37 @@ use lettre::{
     transport::smtp::authentication::Credentials,
     AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
 };
-use std::{env, sync::Arc};
+use std::{env, fs::OpenOptions, io::Write, path::PathBuf, sync::Arc};
 
 /// Used to send emails asynchronously.
 #[derive(Clone)]
-pub struct Mailer {
-    mailer: AsyncSmtpTransport<Tokio1Executor>,
-    from_mailbox: Arc<Mailbox>,
+pub enum Mailer {
+    /// Sends emails through an SMTP server.
+    Smtp {
+        mailer: AsyncSmtpTransport<Tokio1Executor>,
+        from_mailbox: Arc<Mailbox>,
+    },
+    /// Prints emails to stdout (for local development).
+    Console,
+    /// Appends emails to a file (for local development).
+    File(Arc<PathBuf>),
 }
 impl Mailer {
-    /// Creates a [`Mailer`] using env variables.
+    /// Creates a [`Mailer`] using env variables. The `MAILER` variable selects
+    /// where emails are sent: "console", "file" (to `MAILER_FILE`) or "smtp"
+    /// (the default).
     pub fn new_from_env() -> Result<Self> {
+        match env::var("MAILER").as_deref() {
+            Ok("console") => Ok(Mailer::Console),
+            Ok("file") => {
+                let path = env::var("MAILER_FILE")?;
+                Ok(Mailer::File(Arc::new(PathBuf::from(path))))
+            }
+            _ => Self::smtp_from_env(),
+        }
+    }
+    /// Creates a [`Mailer`] that sends emails through an SMTP server.
+    fn smtp_from_env() -> Result<Self> {
         // load env variables
         let smtp_server = env::var("EMAIL_SMTP_SERVER")?;
         let email_addr = env::var("EMAIL_ADDRESS")?;
@@ -28,7 +48,7 @@ impl Mailer {
             .credentials(credentials)
             .build();
 
-        Ok(Mailer {
+        Ok(Mailer::Smtp {
             mailer,
             from_mailbox: Arc::new(from_mailbox),
         })
@@ -41,7 +61,26 @@ impl Mailer {
         body_html: String,
         body_plain: String,
     ) -> Result<()> {
-        let from = (*self.from_mailbox).clone();
+        let (mailer, from_mailbox) = match self {
+            Mailer::Smtp {
+                mailer,
+                from_mailbox,
+            } => (mailer, from_mailbox),
+            Mailer::Console => {
+                println!("{}", Self::format_email(to, subject, &body_plain));
+                return Ok(());
+            }
+            Mailer::File(path) => {
+                let mut file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path.as_ref())?;
+                writeln!(file, "{}", Self::format_email(to, subject, &body_plain))?;
+                return Ok(());
+            }
+        };
+
+        let from = (**from_mailbox).clone();
         let msg = Message::builder()
             .from(from)
             .to(to.parse()?)
@@ -60,8 +99,12 @@ impl Mailer {
                     ),
             )?;
 
-        self.mailer.send(msg).await?;
+        mailer.send(msg).await?;
 
         Ok(())
     }
+    /// Formats an email as plain text, for the development mailers.
+    fn format_email(to: &str, subject: &str, body_plain: &str) -> String {
+        format!("To: {to}\nSubject: {subject}\n\n{body_plain}\n")
+    }
 }
@@ -10,7 +10,11 @@ use error::Result;
 use fsm::FsmHandle;
 use mailer::Mailer;
 use std::env;
-use warp::{cors::Cors, http::Method, Filter};
+use warp::{
+    cors::{Builder, Cors},
+    http::Method,
+    Filter,
+};
 
 mod auth;
 mod db;
@@ -29,10 +33,22 @@ async fn main() -> Result<()> {
     // load `.env` file.
     dotenv::dotenv().expect("`.env` file to be present");
 
+    // load the word list before starting the servers, so that the server
+    // stops immediately if it is missing or corrupt.
+    let fsm = match FsmHandle::new_from_env() {
+        Ok(fsm) => fsm,
+        Err(e) => {
+            log::error!(
+                "failed to load the word lists (check FAST_FSM_BIN and COMMON_FSM_BIN): {e:?}"
+            );
+            return Err(e);
+        }
+    };
+
     // run a HTTP redirect server.
     let http = tokio::spawn(serve_http());
     // run an HTTPS server.
-    let https = tokio::spawn(serve_https());
+    let https = tokio::spawn(serve_https(fsm));
 
     let result;
     // wait for either of the servers to run in to an error.
@@ -44,32 +60,56 @@ async fn main() -> Result<()> {
     result
 }
 
+/// Finds the origins that are allowed to make cross origin requests. These
+/// are the comma separated origins in `cors_origins` ($CORS_ORIGINS), or
+/// the app fqdn if none are given.
+fn allowed_origins(cors_origins: Option<&str>, domain: &str, is_https: bool) -> Vec<String> {
+    let origins = cors_origins
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|origin| !origin.is_empty())
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    match origins.is_empty() {
+        true if is_https => vec![format!("https://{domain}")],
+        true => vec![format!("http://{domain}")],
+        false => origins,
+    }
+}
+
 /// Builds the CORS request wrapper.
 fn cors(is_https: bool) -> Result<Cors> {
-    // load the domain name.
+    // load the domain name and any other allowed origins.
     let domain = env::var("DOMAIN")?;
+    let cors_origins = env::var("CORS_ORIGINS").ok();
+    let origins = allowed_origins(cors_origins.as_deref(), &domain, is_https);
 
-    // Only allow origins from the app fqdn.
-    let origin = if is_https {
-        format!("https://{domain}")
-    } else {
-        format!("http://{domain}")
-    };
+    Ok(cors_builder(&origins).build())
+}
 
-    // CORS settings, which set allowed origins, headers and methods.
-    let cors = warp::cors()
-        .allow_origin(origin.as_str())
-        .allow_methods(&[
-            Method::GET,
-            Method::OPTIONS,
-            Method::POST,
-            Method::DELETE,
-            Method::PUT,
-        ])
-        .allow_headers(vec!["authorization", "content-type"])
-        .build();
-
-    Ok(cors)
+/// Sets the CORS allowed origins, headers and methods. With the `dev`
+/// feature, any origin is allowed.
+fn cors_builder(origins: &[String]) -> Builder {
+    let cors = warp::cors();
+
+    #[cfg(feature = "dev")]
+    let cors = {
+        log::warn!("allowing requests from any origin (ignoring {origins:?})");
+        cors.allow_any_origin()
+    };
+    #[cfg(not(feature = "dev"))]
+    let cors = cors.allow_origins(origins.iter().map(String::as_str));
+
+    cors.allow_methods(&[
+        Method::GET,
+        Method::OPTIONS,
+        Method::POST,
+        Method::DELETE,
+        Method::PUT,
+    ])
+    .allow_headers(vec!["authorization", "content-type"])
 }
 
 /// Starts a server that redirects requests from :80 to :443.
@@ -83,15 +123,26 @@ async fn serve_http() -> Result<()> {
 }
 
 /// Starts a HTTPS server on localhost:443.
-async fn serve_https() -> Result<()> {
+async fn serve_https(fsm: FsmHandle) -> Result<()> {
     // load TLS certificate and private key.
     let cert_path = env::var("CERT_PATH")?;
     let key_path = env::var("KEY_PATH")?;
 
-    // set up database connection, mail connection, and load the fsm.
+    // set up database connection and mail connection.
     let db = db::connect().await?;
     let mailer = Mailer::new_from_env()?;
-    let fsm = FsmHandle::new_from_env()?;
+
+    // load the revoked auth tokens, and remove expired tokens periodically.
+    auth::revocation::load(&db).await?;
+    tokio::spawn(auth::revocation::prune(db.clone()));
+
+    // pass the turn of players in correspondence games that have missed
+    // their deadline.
+    tokio::spawn(handlers::games::expire_correspondence_games(
+        db.clone(),
+        mailer.clone(),
+        fsm.clone(),
+    ));
 
     // handlers for the endpoints.
     let routes = filters::all(db, mailer, fsm)?.recover(filters::handle_rejection);
@@ -107,3 +158,47 @@ async fn serve_https() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn origins() {
+        assert_eq!(
+            vec!["https://example.com"],
+            allowed_origins(None, "example.com", true)
+        );
+        assert_eq!(
+            vec!["http://example.com"],
+            allowed_origins(Some(" "), "example.com", false)
+        );
+        assert_eq!(
+            vec!["https://example.com", "http://localhost:8080"],
+            allowed_origins(
+                Some("https://example.com, http://localhost:8080,"),
+                "example.org",
+                true
+            )
+        );
+    }
+
+    #[cfg(not(feature = "dev"))]
+    #[tokio::test]
+    async fn disallowed_origin() {
+        use warp::{http::StatusCode, test::request};
+
+        let origins = allowed_origins(Some("https://a.com,https://b.com"), "c.com", true);
+        let filter = warp::any().map(warp::reply).with(cors_builder(&origins));
+
+        for (origin, status) in [
+            ("https://a.com", StatusCode::OK),
+            ("https://b.com", StatusCode::OK),
+            ("https://c.com", StatusCode::FORBIDDEN),
+            ("http://a.com", StatusCode::FORBIDDEN),
+        ] {
+            let response = request().header("origin", origin).reply(&filter).await;
+            assert_eq!(status, response.status(), "{origin}");
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use crate::{
+    db::Db,
+    error::{Error, Result},
+};
+use chrono::NaiveDateTime;
+use scrabble::game::{snapshot::GameSnapshot, PlayerNum};
+use serde::{Deserialize, Serialize};
+use sqlx::{Postgres, Transaction};
+
+/// A record in `tbl_correspondence_game`. Correspondence games are played
+/// through REST requests rather than a live websocket, so the entire game
+/// state is stored and reloaded for each request.
+#[derive(Debug)]
+pub struct CorrespondenceGame {
+    /// The state of the game.
+    pub game: scrabble::game::Game,
+    /// The players of the game, ordered by `PlayerNum`.
+    pub players: Vec<CorrespondencePlayer>,
+    /// The time by which the next player must make a play.
+    pub deadline: NaiveDateTime,
+}
+
+/// A player in a [`CorrespondenceGame`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorrespondencePlayer {
+    /// References the record in `tbl_player`.
+    pub id_player: i32,
+    /// The id of the user.
+    pub id_user: i32,
+    /// The username of the user.
+    pub username: String,
+}
+
+impl CorrespondenceGame {
+    /// Inserts the game state into the database.
+    pub async fn insert(&self, tx: &mut Transaction<'_, Postgres>, id_game: i32) -> Result<()> {
+        let state = self.serialize_state()?;
+
+        sqlx::query_file!(
+            "sql/games/insert_correspondence.sql",
+            id_game,
+            state,
+            self.deadline
+        )
+        .execute(tx)
+        .await?;
+
+        Ok(())
+    }
+    /// Finds the state of a game by id.
+    pub async fn find(db: &Db, id_game: i32) -> Result<Option<Self>> {
+        let record = sqlx::query_file!("sql/games/find_correspondence.sql", id_game)
+            .fetch_optional(db)
+            .await?;
+
+        record
+            .map(|record| Self::deserialize_state(&record.state, record.deadline))
+            .transpose()
+    }
+    /// Finds the state of a game by id, locking the record until the
+    /// transaction ends so that concurrent plays cannot overwrite each other.
+    pub async fn find_for_update(
+        tx: &mut Transaction<'_, Postgres>,
+        id_game: i32,
+    ) -> Result<Option<Self>> {
+        let record = sqlx::query_file!("sql/games/find_correspondence_for_update.sql", id_game)
+            .fetch_optional(tx)
+            .await?;
+
+        record
+            .map(|record| Self::deserialize_state(&record.state, record.deadline))
+            .transpose()
+    }
+    /// Finds the ids of ongoing games where the deadline has passed.
+    pub async fn find_expired(db: &Db, now: NaiveDateTime) -> Result<Vec<i32>> {
+        let ids = sqlx::query_file_scalar!("sql/games/find_expired_correspondence.sql", now)
+            .fetch_all(db)
+            .await?;
+
+        Ok(ids)
+    }
+    /// Updates the stored game state.
+    pub async fn update(&self, tx: &mut Transaction<'_, Postgres>, id_game: i32) -> Result<()> {
+        let state = self.serialize_state()?;
+
+        sqlx::query_file!(
+            "sql/games/update_correspondence.sql",
+            id_game,
+            state,
+            self.deadline
+        )
+        .execute(tx)
+        .await?;
+
+        Ok(())
+    }
+    /// Finds the [`PlayerNum`] of a user.
+    pub fn player_num(&self, id_user: i32) -> Option<PlayerNum> {
+        PlayerNum::iter(self.players.len())
+            .zip(&self.players)
+            .find(|(_, player)| player.id_user == id_user)
+            .map(|(player_num, _)| player_num)
+    }
+    /// Gets a player by [`PlayerNum`].
+    pub fn player(&self, player_num: PlayerNum) -> &CorrespondencePlayer {
+        &self.players[usize::from(player_num)]
+    }
+
+    /// Serializes the game and players for the `state` column. The game is
+    /// stored as a versioned [`GameSnapshot`], so that stored games can still
+    /// be loaded after the layout of [`scrabble::game::Game`] changes.
+    fn serialize_state(&self) -> Result<Vec<u8>> {
+        Ok(bincode::serialize(&(self.game.snapshot(), &self.players))?)
+    }
+    /// Deserializes the `state` column.
+    fn deserialize_state(state: &[u8], deadline: NaiveDateTime) -> Result<Self> {
+        let (snapshot, players): (GameSnapshot, _) = bincode::deserialize(state)?;
+
+        Ok(Self {
+            game: scrabble::game::Game::from_snapshot(snapshot).map_err(Error::Play)?,
+            players,
+            deadline,
+        })
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{error::Result, Db};
+use chrono::{NaiveDateTime, Utc};
+
+/// A record in `tbl_email_verification`. Stores a new email address
+/// until the user follows the link sent to it.
+#[derive(Debug)]
+pub struct EmailVerification {
+    /// The id of the user who requested the change.
+    pub id_user: i32,
+    /// The new email address, which is not used until it is verified.
+    pub new_email: String,
+    /// A secret which is sent to the new email address.
+    pub secret_hex: String,
+    /// The time at which the `email_verification` is no longer valid.
+    pub valid_until: NaiveDateTime,
+}
+
+impl EmailVerification {
+    /// Checks whether the email verification record has expired.
+    pub fn is_expired(&self) -> bool {
+        let current_time = Utc::now().naive_utc();
+        self.valid_until < current_time
+    }
+    /// Finds an `EmailVerification` record by username.
+    pub async fn find_by_username(db: &Db, username: &str) -> Result<Option<Self>> {
+        Ok(sqlx::query_file_as!(
+            EmailVerification,
+            "sql/email_verification/find_by_username.sql",
+            username
+        )
+        .fetch_optional(db)
+        .await?)
+    }
+    /// Inserts the record into the database, replacing any previous
+    /// request for the user.
+    pub async fn insert(&self, db: &Db) -> Result<()> {
+        sqlx::query_file!(
+            "sql/email_verification/insert.sql",
+            self.id_user,
+            self.new_email,
+            self.secret_hex,
+            self.valid_until,
+        )
+        .execute(db)
+        .await?;
+
+        Ok(())
+    }
+    /// Deletes the record if the secret has not changed, returning false if
+    /// the record has already been used (or replaced).
+    pub async fn consume(&self, db: &Db) -> Result<bool> {
+        let id_user = sqlx::query_file_scalar!(
+            "sql/email_verification/delete_with_secret.sql",
+            self.id_user,
+            self.secret_hex,
+        )
+        .fetch_optional(db)
+        .await?;
+
+        Ok(id_user.is_some())
+    }
+    /// Check that the secret matches another value.
+    pub fn secret_matches(&self, hex: &str) -> bool {
+        self.secret_hex == hex
+    }
+}
@@ -1,5 +1,6 @@
-use crate::{error::Result, Db};
+use crate::error::Result;
 use chrono::{NaiveDateTime, Utc};
+use sqlx::PgConnection;
 
 /// A record in `tbl_game`.
 #[derive(Debug)]
@@ -15,8 +16,9 @@ pub struct Game {
 }
 
 impl Game {
-    /// Inserts the record into the database, returning the id.
-    pub async fn insert(db: &Db) -> Result<i32> {
+    /// Inserts the record into the database, returning the id. The connection
+    /// can be a transaction.
+    pub async fn insert(conn: &mut PgConnection) -> Result<i32> {
         let start_time = Some(Utc::now().naive_utc());
 
         let id_game = sqlx::query_file_scalar!(
@@ -25,7 +27,7 @@ impl Game {
             Option::<NaiveDateTime>::None, // end_time
             false,                         // is_over
         )
-        .fetch_one(db)
+        .fetch_one(&mut *conn)
         .await?;
 
         Ok(id_game)
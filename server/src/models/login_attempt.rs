@@ -0,0 +1,156 @@
+use crate::error::Result;
+use chrono::{Duration, NaiveDateTime, Utc};
+use sqlx::{Postgres, Transaction};
+
+/// The number of failed login attempts before an account is locked.
+const MAX_FAILED_LOGINS: i32 = 5;
+/// The time that an account is locked for after `MAX_FAILED_LOGINS` failed
+/// attempts. This doubles for every further failed attempt.
+const BASE_LOCKOUT_SECS: i64 = 30;
+/// The maximum time that an account can be locked for.
+const MAX_LOCKOUT_SECS: i64 = 60 * 60;
+
+/// A record in `tbl_login_attempt`. Tracks consecutive failed logins
+/// for an account, so that passwords cannot be brute forced.
+#[derive(Debug)]
+pub struct LoginAttempt {
+    /// The id of the user.
+    pub id_user: i32,
+    /// The number of failed logins since the last successful login.
+    pub failed_count: i32,
+    /// The time until which logins are rejected.
+    pub locked_until: Option<NaiveDateTime>,
+}
+
+impl LoginAttempt {
+    /// Checks whether the account is currently locked.
+    pub fn is_locked(&self) -> bool {
+        let current_time = Utc::now().naive_utc();
+        self.locked_until
+            .is_some_and(|locked_until| current_time < locked_until)
+    }
+    /// Finds the `LoginAttempt` record for a user, inserting one with no
+    /// failures if it does not exist. The record is locked until the
+    /// transaction ends, so that concurrent logins are checked one at a time.
+    pub async fn find_for_update(tx: &mut Transaction<'_, Postgres>, id_user: i32) -> Result<Self> {
+        Ok(sqlx::query_file_as!(
+            LoginAttempt,
+            "sql/login_attempt/find_for_update.sql",
+            id_user
+        )
+        .fetch_one(tx)
+        .await?)
+    }
+    /// Records a failed login, locking the account if there have been
+    /// too many consecutive failures.
+    pub async fn record_failure(tx: &mut Transaction<'_, Postgres>, id_user: i32) -> Result<Self> {
+        let mut login_attempt = sqlx::query_file_as!(
+            LoginAttempt,
+            "sql/login_attempt/insert_failure.sql",
+            id_user
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if let Some(lockout) = lockout_duration(login_attempt.failed_count) {
+            let locked_until = Utc::now().naive_utc() + lockout;
+            sqlx::query_file!(
+                "sql/login_attempt/set_locked_until.sql",
+                id_user,
+                locked_until
+            )
+            .execute(tx)
+            .await?;
+
+            login_attempt.locked_until = Some(locked_until);
+        }
+
+        Ok(login_attempt)
+    }
+    /// Clears the failed logins for a user (after a successful login).
+    pub async fn clear(tx: &mut Transaction<'_, Postgres>, id_user: i32) -> Result<()> {
+        sqlx::query_file!("sql/login_attempt/delete.sql", id_user)
+            .execute(tx)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Finds the time that an account is locked for after `failed_count`
+/// consecutive failed logins. (None if the account should not be locked).
+fn lockout_duration(failed_count: i32) -> Option<Duration> {
+    if failed_count < MAX_FAILED_LOGINS {
+        return None;
+    }
+
+    let doublings = (failed_count - MAX_FAILED_LOGINS).min(16);
+    let secs = BASE_LOCKOUT_SECS << doublings;
+
+    Some(Duration::seconds(secs.min(MAX_LOCKOUT_SECS)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db, models::User};
+
+    #[test]
+    fn exponential_backoff() {
+        for failed_count in 0..MAX_FAILED_LOGINS {
+            assert_eq!(lockout_duration(failed_count), None);
+        }
+
+        assert_eq!(lockout_duration(5), Some(Duration::seconds(30)));
+        assert_eq!(lockout_duration(6), Some(Duration::seconds(60)));
+        assert_eq!(lockout_duration(7), Some(Duration::seconds(120)));
+        assert_eq!(
+            lockout_duration(100),
+            Some(Duration::seconds(MAX_LOCKOUT_SECS))
+        );
+    }
+
+    #[tokio::test]
+    async fn repeated_failures() {
+        let db = db::connect().await.unwrap();
+        let user = User::insert_for_test(&db, "lockout").await;
+        let id_user = user.id_user;
+
+        // the account is not locked until the limit is reached.
+        let mut tx = db.begin().await.unwrap();
+        let login_attempt = LoginAttempt::find_for_update(&mut tx, id_user)
+            .await
+            .unwrap();
+        assert_eq!(login_attempt.failed_count, 0);
+        for _ in 1..MAX_FAILED_LOGINS {
+            let login_attempt = LoginAttempt::record_failure(&mut tx, id_user)
+                .await
+                .unwrap();
+            assert!(!login_attempt.is_locked());
+        }
+        let login_attempt = LoginAttempt::record_failure(&mut tx, id_user)
+            .await
+            .unwrap();
+        assert_eq!(login_attempt.failed_count, MAX_FAILED_LOGINS);
+        assert!(login_attempt.is_locked());
+        tx.commit().await.unwrap();
+
+        // the stored record is also locked.
+        let mut tx = db.begin().await.unwrap();
+        let login_attempt = LoginAttempt::find_for_update(&mut tx, id_user)
+            .await
+            .unwrap();
+        assert!(login_attempt.is_locked());
+
+        // a successful login clears the record.
+        LoginAttempt::clear(&mut tx, id_user).await.unwrap();
+        let login_attempt = LoginAttempt::find_for_update(&mut tx, id_user)
+            .await
+            .unwrap();
+        assert_eq!(login_attempt.failed_count, 0);
+        assert!(!login_attempt.is_locked());
+        tx.commit().await.unwrap();
+
+        user.delete(&db).await.unwrap();
+    }
+}
@@ -1,19 +1,29 @@
 //! Module that models database records.
 
+mod correspondence_game;
+mod email_verification;
 mod friend_request;
 mod game;
+mod login_attempt;
 mod password_reset;
 mod play;
 mod player;
+mod puzzle_attempt;
+mod revoked_token;
 mod tile;
 mod user;
 mod word;
 
+pub use correspondence_game::{CorrespondenceGame, CorrespondencePlayer};
+pub use email_verification::EmailVerification;
 pub use friend_request::FriendRequest;
 pub use game::Game;
+pub use login_attempt::LoginAttempt;
 pub use password_reset::PasswordReset;
 pub use play::Play;
-pub use player::{AiDifficulty, Player};
+pub use player::Player;
+pub use puzzle_attempt::PuzzleAttempt;
+pub use revoked_token::RevokedToken;
 pub use tile::Tile;
 pub use user::User;
 pub use word::Word;
@@ -30,13 +30,13 @@ impl PasswordReset {
         .await?)
     }
     /// Finds a `PasswordReset` record by username.
-    pub async fn find_by_username(db: &Db, username: &str) -> Result<Self> {
+    pub async fn find_by_username(db: &Db, username: &str) -> Result<Option<Self>> {
         Ok(sqlx::query_file_as!(
             PasswordReset,
             "sql/password_reset/find_by_username.sql",
             username
         )
-        .fetch_one(db)
+        .fetch_optional(db)
         .await?)
     }
     /// Inserts the record into the database.
@@ -52,16 +52,53 @@ impl PasswordReset {
 
         Ok(())
     }
-    /// Deletes the record.
-    pub async fn delete(&self, db: &Db) -> Result<()> {
-        sqlx::query_file!("sql/password_reset/delete.sql", self.id_user,)
-            .execute(db)
-            .await?;
+    /// Deletes the record if the secret has not changed, returning false if
+    /// the record has already been used (or replaced). This ensures that a
+    /// secret can only be used once, even by concurrent requests.
+    pub async fn consume(&self, db: &Db) -> Result<bool> {
+        let id_user = sqlx::query_file_scalar!(
+            "sql/password_reset/delete_with_secret.sql",
+            self.id_user,
+            self.secret_hex,
+        )
+        .fetch_optional(db)
+        .await?;
 
-        Ok(())
+        Ok(id_user.is_some())
     }
     /// Check that the secret matches another value.
     pub fn secret_matches(&self, hex: &str) -> bool {
         self.secret_hex == hex
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::PasswordReset;
+    use crate::{db, models::User};
+    use chrono::{Duration, Utc};
+
+    #[tokio::test]
+    async fn single_use() {
+        let db = db::connect().await.unwrap();
+        let user = User::insert_for_test(&db, "reset").await;
+        let id_user = user.id_user;
+
+        let password_reset = PasswordReset {
+            id_user,
+            secret_hex: String::from("00010a3264c8ff"),
+            valid_until: Utc::now().naive_utc() + Duration::minutes(1),
+        };
+        password_reset.insert(&db).await.unwrap();
+
+        // the secret can only be used once.
+        assert!(password_reset.consume(&db).await.unwrap());
+        assert!(!password_reset.consume(&db).await.unwrap());
+        assert!(PasswordReset::find_by_username(&db, &user.username)
+            .await
+            .unwrap()
+            .is_none());
+
+        user.delete(&db).await.unwrap();
+    }
+}
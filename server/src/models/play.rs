@@ -17,4 +17,12 @@ impl Play {
             .await?;
         Ok(id_play)
     }
+    /// Deletes the most recent play made by a player, along with its
+    /// tiles and words.
+    pub async fn delete_last(db: &Db, id_player: i32) -> Result<()> {
+        sqlx::query_file!("sql/live/delete_last_play.sql", id_player)
+            .execute(db)
+            .await?;
+        Ok(())
+    }
 }
@@ -1,5 +1,6 @@
 use crate::{db::Db, error::Result};
-use std::{convert::Infallible, fmt, str::FromStr};
+use scrabble::ai::AiDifficulty;
+use sqlx::PgConnection;
 
 /// A record in `tbl_friend_request`.
 #[derive(Debug, Clone)]
@@ -33,16 +34,17 @@ impl Player {
 
         Ok(id_player)
     }
-    /// Inserts a user player, returning (id, username).
+    /// Inserts a user player, returning (id, username). The connection
+    /// can be a transaction.
     pub async fn insert_user(
-        db: &Db,
+        conn: &mut PgConnection,
         id_game: i32,
         id_user: i32,
         id_owner: Option<i32>,
     ) -> Result<(i32, String)> {
         // find the username for the user.
         let username = sqlx::query_file!("sql/live/find_username.sql", id_user)
-            .fetch_one(db)
+            .fetch_one(&mut *conn)
             .await?
             .username;
 
@@ -57,51 +59,40 @@ impl Player {
                     id_user,
                     id_owner
                 )
-                .fetch_one(db)
+                .fetch_one(&mut *conn)
                 .await?
             }
             // Don't check that the user is the owner.
             None => {
                 sqlx::query_file_scalar!("sql/live/insert_player.sql", id_game)
-                    .fetch_one(db)
+                    .fetch_one(&mut *conn)
                     .await?
             }
         };
 
         // insert a record into `tbl_human_player`
         sqlx::query_file!("sql/live/insert_human_player.sql", id_player, id_user)
-            .execute(db)
+            .execute(&mut *conn)
             .await?;
 
         Ok((id_player, username))
     }
-}
-
-/// Gets the difficult setting of the ai player.
-#[derive(Debug, Clone, Copy)]
-pub enum AiDifficulty {
-    Easy,
-    Medium,
-    Hard,
-}
+    /// Marks a user player as having been sent an email for their turn. Returns
+    /// false if an email has already been sent since their last play.
+    pub async fn set_notified(db: &Db, id_player: i32) -> Result<bool> {
+        let id_player = sqlx::query_file_scalar!("sql/games/set_notified.sql", id_player)
+            .fetch_optional(db)
+            .await?;
 
-impl fmt::Display for AiDifficulty {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            AiDifficulty::Easy => write!(f, "easy"),
-            AiDifficulty::Medium => write!(f, "medium"),
-            AiDifficulty::Hard => write!(f, "hard"),
-        }
+        Ok(id_player.is_some())
     }
-}
-impl FromStr for AiDifficulty {
-    type Err = Infallible;
+    /// Clears the notification flag for a user player once they have made
+    /// a play, so that they are notified on their next turn.
+    pub async fn clear_notified(db: &Db, id_player: i32) -> Result<()> {
+        sqlx::query_file!("sql/games/clear_notified.sql", id_player)
+            .execute(db)
+            .await?;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(match s {
-            "hard" => Self::Hard,
-            "medium" => Self::Medium,
-            _ => Self::Easy,
-        })
+        Ok(())
     }
 }
@@ -0,0 +1,87 @@
+use crate::{error::Result, Db};
+use chrono::NaiveDate;
+
+/// A record in `tbl_puzzle_attempt`. Each user can make a single attempt
+/// at each daily puzzle.
+#[derive(Debug)]
+pub struct PuzzleAttempt {
+    /// The id of the user.
+    pub id_user: i32,
+    /// The date of the puzzle.
+    pub puzzle_date: NaiveDate,
+    /// The score of the user's placement.
+    pub score: i32,
+    /// The score of the highest scoring placement.
+    pub best_score: i32,
+}
+
+impl PuzzleAttempt {
+    /// Finds a user's attempt at the puzzle for a date.
+    pub async fn find(db: &Db, id_user: i32, puzzle_date: NaiveDate) -> Result<Option<Self>> {
+        Ok(sqlx::query_file_as!(
+            PuzzleAttempt,
+            "sql/puzzle_attempt/find.sql",
+            id_user,
+            puzzle_date
+        )
+        .fetch_optional(db)
+        .await?)
+    }
+    /// Inserts the record into the database, returning `false` if the user
+    /// has already attempted the puzzle.
+    pub async fn insert(&self, db: &Db) -> Result<bool> {
+        let result = sqlx::query_file!(
+            "sql/puzzle_attempt/insert.sql",
+            self.id_user,
+            self.puzzle_date,
+            self.score,
+            self.best_score
+        )
+        .execute(db)
+        .await?;
+
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db, models::User};
+    use chrono::Utc;
+
+    #[tokio::test]
+    async fn single_attempt() {
+        let db = db::connect().await.unwrap();
+        let user = User::insert_for_test(&db, "puzzle").await;
+        let id_user = user.id_user;
+        let puzzle_date = Utc::today().naive_utc();
+
+        assert!(PuzzleAttempt::find(&db, id_user, puzzle_date)
+            .await
+            .unwrap()
+            .is_none());
+
+        let attempt = PuzzleAttempt {
+            id_user,
+            puzzle_date,
+            score: 12,
+            best_score: 24,
+        };
+        assert!(attempt.insert(&db).await.unwrap());
+
+        // a second attempt is not recorded.
+        let retry = PuzzleAttempt {
+            score: 24,
+            ..attempt
+        };
+        assert!(!retry.insert(&db).await.unwrap());
+        let attempt = PuzzleAttempt::find(&db, id_user, puzzle_date)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(12, attempt.score);
+
+        user.delete(&db).await.unwrap();
+    }
+}
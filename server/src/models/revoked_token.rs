@@ -0,0 +1,39 @@
+use crate::{error::Result, Db};
+use chrono::NaiveDateTime;
+
+/// A record in `tbl_revoked_token`. Tokens are revoked when a user
+/// logs out, and are rejected until they would have expired.
+#[derive(Debug)]
+pub struct RevokedToken {
+    /// The unique id of the token (the `jti` claim).
+    pub jti: String,
+    /// The time after which the token would have expired anyway.
+    pub valid_until: NaiveDateTime,
+}
+
+impl RevokedToken {
+    /// Inserts the record into the database.
+    pub async fn insert(&self, db: &Db) -> Result<()> {
+        sqlx::query_file!("sql/revoked_token/insert.sql", self.jti, self.valid_until)
+            .execute(db)
+            .await?;
+
+        Ok(())
+    }
+    /// Finds all revoked tokens.
+    pub async fn find_all(db: &Db) -> Result<Vec<Self>> {
+        Ok(
+            sqlx::query_file_as!(RevokedToken, "sql/revoked_token/find_all.sql")
+                .fetch_all(db)
+                .await?,
+        )
+    }
+    /// Deletes the records for tokens that have expired.
+    pub async fn delete_expired(db: &Db, now: NaiveDateTime) -> Result<()> {
+        sqlx::query_file!("sql/revoked_token/delete_expired.sql", now)
+            .execute(db)
+            .await?;
+
+        Ok(())
+    }
+}
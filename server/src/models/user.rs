@@ -21,6 +21,10 @@ pub struct User {
     pub role: String,
     /// Whether the user stats are private.
     pub is_private: bool,
+    /// Whether to email the user when it is their turn in a correspondence game.
+    pub turn_emails: bool,
+    /// The name of the board theme chosen by the user.
+    pub theme: String,
     /// The date that the user created their account.
     pub date_joined: NaiveDateTime,
     /// The most recent update to the user's account.
@@ -42,6 +46,8 @@ impl User {
             username: self.username,
             email: self.email,
             is_private: self.is_private,
+            turn_emails: self.turn_emails,
+            theme: self.theme,
         }
     }
     /// Returns Ok(()) if `username` and `email` are not taken (for any user
@@ -103,6 +109,18 @@ impl User {
 
         Ok(id_user)
     }
+    /// Inserts a user with a unique username starting with `prefix`, and
+    /// no password. The user should be deleted at the end of the test.
+    #[cfg(test)]
+    pub async fn insert_for_test(db: &Db, prefix: &str) -> Self {
+        let username = format!("{prefix}{}", Utc::now().timestamp_nanos());
+        let email = format!("{username}@example.com");
+        let id_user = Self::insert(db, &username, &email, "", Role::User, false)
+            .await
+            .unwrap();
+
+        Self::find_by_id(db, id_user).await.unwrap()
+    }
     /// Deletes the record by id.
     pub async fn delete(&self, db: &Db) -> Result<()> {
         sqlx::query_file!("sql/users/delete.sql", self.id_user,)
@@ -111,8 +129,8 @@ impl User {
 
         Ok(())
     }
-    /// Updates the (email, hashed_pass, username, date_updated, is_private) fields
-    /// of the user record (keeping the same id).
+    /// Updates the (email, hashed_pass, username, date_updated, is_private, turn_emails,
+    /// theme) fields of the user record (keeping the same id).
     pub async fn update(&self, db: &Db) -> Result<()> {
         let date_updated = Utc::now().naive_utc();
 
@@ -122,6 +140,8 @@ impl User {
             self.email,
             self.hashed_pass,
             self.is_private,
+            self.turn_emails,
+            self.theme,
             date_updated,
             self.id_user,
         )